@@ -3,6 +3,7 @@ import { View, Text, StyleSheet } from 'react-native';
 import { AllocationPie } from '../portfolio/AllocationPie';
 import { theme } from '../../theme/colors';
 import { TempAllocation } from '../../context/PortfolioContext';
+import { GoalProgress, PnlReport } from '../../utils/contractInteractions';
 
 interface PortfolioDisplayProps {
   portfolioData?: any;
@@ -13,14 +14,27 @@ interface PortfolioDisplayProps {
     lastUpdated: Date;
     isSaved?: boolean;
   } | null;
+  goalProgress?: GoalProgress | null;
+  pnl?: PnlReport | null;
+}
+
+interface DisplayAllocation {
+  symbol: string;
+  currentPct: number;
+  targetPct: number;
+  // Unrealized (priced mints only) and realized gains (base asset units), when the P&L view is loaded
+  gain?: number;
+  realized?: number;
 }
 
 export const PortfolioDisplay: React.FC<PortfolioDisplayProps> = ({
   portfolioData,
-  tempPortfolio
+  tempPortfolio,
+  goalProgress,
+  pnl
 }) => {
   // Determine what data to display - prioritize temporary portfolio if available
-  const getDisplayData = () => {
+  const getDisplayData = (): { allocations: DisplayAllocation[]; isTemporary: boolean } => {
     // First check for temporary portfolio data (session changes)
     if (tempPortfolio && tempPortfolio.allocations.length > 0) {
       // Temporary portfolio data (unsaved changes from edit screen)
@@ -40,10 +54,14 @@ export const PortfolioDisplay: React.FC<PortfolioDisplayProps> = ({
           ? (allocation.currentAmount.toNumber() / portfolioData.totalValue.toNumber()) * 100
           : 0;
 
+        const allocationPnl = pnl?.allocations.find((entry) => entry.mint.equals(allocation.mint));
+
         return {
           symbol: allocation.symbol,
           currentPct: currentPercentage,
           targetPct: allocation.targetPercentage / 100, // Convert from basis points
+          gain: allocationPnl?.unrealizedPnl?.toNumber(),
+          realized: allocationPnl ? allocationPnl.realizedPnl.toNumber() : undefined,
         };
       });
       return { allocations, isTemporary: false };
@@ -61,6 +79,10 @@ export const PortfolioDisplay: React.FC<PortfolioDisplayProps> = ({
 
   const { allocations, isTemporary } = getDisplayData();
 
+  // Signed amount in base asset units (e.g. +$1,250 / -$300)
+  const formatGain = (amount: number) =>
+    `${amount < 0 ? '-' : '+'}$${Math.abs(amount).toLocaleString('en-US')}`;
+
   const getStatusMessage = () => {
     if (portfolioData) {
       return {
@@ -111,6 +133,22 @@ export const PortfolioDisplay: React.FC<PortfolioDisplayProps> = ({
         )}
       </View>
       
+      {portfolioData?.goal && goalProgress && (
+        <View style={styles.goal}>
+          <Text style={styles.allocationsTitle}>🎯 {portfolioData.goal.label}</Text>
+          <Text style={styles.goalProgress}>
+            {(goalProgress.progressBps / 100).toFixed(1)}% of $
+            {goalProgress.targetAmount.toNumber().toLocaleString('en-US')} by{' '}
+            {new Date(goalProgress.targetDate.toNumber() * 1000).toLocaleDateString()}
+          </Text>
+          <Text style={styles.goalContribution}>
+            {goalProgress.requiredContribution.isZero()
+              ? 'On track without further deposits'
+              : `Deposit $${goalProgress.requiredContribution.toNumber().toLocaleString('en-US')} per month to reach it`}
+          </Text>
+        </View>
+      )}
+
       <AllocationPie data={allocations} animate={true} />
       
       <View style={styles.allocationsList}>
@@ -125,6 +163,17 @@ export const PortfolioDisplay: React.FC<PortfolioDisplayProps> = ({
                   (Current: {allocation.currentPct.toFixed(1)}%)
                 </Text>
               )}
+              {allocation.gain !== undefined && (
+                <Text
+                  style={[
+                    styles.allocationGain,
+                    { color: allocation.gain < 0 ? theme.colors.error : theme.colors.success },
+                  ]}
+                >
+                  {formatGain(allocation.gain)}
+                  {allocation.realized ? ` (realized ${formatGain(allocation.realized)})` : ''}
+                </Text>
+              )}
             </View>
           </View>
         ))}
@@ -168,6 +217,18 @@ const styles = StyleSheet.create({
     fontStyle: 'italic',
     marginTop: theme.spacing.xs,
   },
+  goal: {
+    marginBottom: theme.spacing.lg,
+  },
+  goalProgress: {
+    fontSize: 14,
+    color: theme.colors.text,
+  },
+  goalContribution: {
+    fontSize: 12,
+    color: theme.colors.textSecondary,
+    marginTop: theme.spacing.xs,
+  },
   allocationsList: {
     marginTop: theme.spacing.lg,
   },
@@ -202,4 +263,8 @@ const styles = StyleSheet.create({
     fontSize: 12,
     color: theme.colors.textSecondary,
   },
+  allocationGain: {
+    fontSize: 12,
+    fontWeight: '500',
+  },
 });
\ No newline at end of file
@@ -172,7 +172,18 @@ export const EditPortfolioScreen: React.FC = () => {
             console.log(`Updating ${allocation.symbol} from ${originalTargetBasisPoints} to ${newTargetBasisPoints} basis points`);
             
             const mint = new PublicKey(originalAllocation.mint.toString());
-            await contract.updateAllocation(allocation.symbol, mint, newTargetBasisPoints);
+            // Keep the allocation's band while it still contains the new target
+            const band = originalAllocation.band;
+            const keepBand =
+              band &&
+              band.minPercentage <= newTargetBasisPoints &&
+              newTargetBasisPoints <= band.maxPercentage;
+            await contract.updateAllocation(
+              allocation.symbol,
+              mint,
+              newTargetBasisPoints,
+              keepBand ? band : null
+            );
           }
         }
       }
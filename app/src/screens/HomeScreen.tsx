@@ -12,6 +12,7 @@ import { ActionButtons } from "../components/home/ActionButtons";
 import { PortfolioDisplay } from "../components/home/PortfolioDisplay";
 import { mockPortfolio, mockChartData } from "../utils/mock";
 import { useContract } from "../hooks/useContract";
+import { GoalProgress, PnlReport } from "../utils/contractInteractions";
 import { usePortfolioContext } from "../context/PortfolioContext";
 import { theme } from "../theme/colors";
 import { useMobileWallet } from "../utils/useMobileWallet";
@@ -28,6 +29,8 @@ export function HomeScreen() {
     const contract = useContract();
     const { tempPortfolio } = usePortfolioContext();
     const [portfolioData, setPortfolioData] = useState<any>(null);
+    const [goalProgress, setGoalProgress] = useState<GoalProgress | null>(null);
+    const [pnl, setPnl] = useState<PnlReport | null>(null);
     const [isLoading, setIsLoading] = useState(false);
     const [hasInitializeAttempted, setHasInitializeAttempted] = useState(false);
     const { signAndSendTransaction } = useMobileWallet();
@@ -56,6 +59,8 @@ export function HomeScreen() {
         console.log('📊 Portfolio data result:', data);
         console.log('📊 Setting portfolioData state to:', data);
         setPortfolioData(data);
+        setGoalProgress(data?.goal ? await contract.getGoalProgress() : null);
+        setPnl(data ? await contract.getPnl() : null);
         
         if (data) {
           console.log('✅ Portfolio found - should show Rebalance button');
@@ -264,6 +269,8 @@ export function HomeScreen() {
               <PortfolioDisplay
                 portfolioData={portfolioData}
                 tempPortfolio={tempPortfolio}
+                goalProgress={goalProgress}
+                pnl={pnl}
               />
               
               <ActionButtons 
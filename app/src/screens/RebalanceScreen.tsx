@@ -4,6 +4,8 @@ import { Text, Button, Card, ActivityIndicator, TextInput } from 'react-native-p
 import { useNavigation } from '@react-navigation/native';
 import { useContract } from '../hooks/useContract';
 import { theme } from '../theme/colors';
+import { currentPercentages, needsRebalancing as coreNeedsRebalancing } from '../utils/portfolioMath';
+import { RebalancePreview } from '../utils/contractInteractions';
 
 interface AllocationData {
   symbol: string;
@@ -19,6 +21,7 @@ export function RebalanceScreen() {
   const [slippage, setSlippage] = useState('50'); // 0.5% default
   const [isLoading, setIsLoading] = useState(true);
   const [isRebalancing, setIsRebalancing] = useState(false);
+  const [preview, setPreview] = useState<RebalancePreview | null>(null);
   const navigation = useNavigation();
   const contract = useContract();
 
@@ -39,19 +42,14 @@ export function RebalanceScreen() {
 
       setPortfolioData(data);
       
-      const processedAllocations = data.allocations.map((allocation: any) => {
-        const currentPercentage = data.totalValue.toNumber() > 0
-          ? (allocation.currentAmount.toNumber() / data.totalValue.toNumber()) * 100
-          : 0;
-
-        return {
-          symbol: allocation.symbol,
-          currentAmount: allocation.currentAmount.toNumber(),
-          targetPercentage: allocation.targetPercentage / 100,
-          currentPercentage,
-          mint: allocation.mint.toString(),
-        };
-      });
+      const percentages = currentPercentages(data.allocations);
+      const processedAllocations = data.allocations.map((allocation: any, index: number) => ({
+        symbol: allocation.symbol,
+        currentAmount: allocation.currentAmount.toNumber(),
+        targetPercentage: allocation.targetPercentage / 100,
+        currentPercentage: percentages[index],
+        mint: allocation.mint.toString(),
+      }));
 
       setAllocations(processedAllocations);
     } catch (error) {
@@ -62,12 +60,14 @@ export function RebalanceScreen() {
     }
   };
 
-  const needsRebalancing = () => {
-    const threshold = 5; // 5% threshold
-    return allocations.some(allocation => 
-      Math.abs(allocation.currentPercentage - allocation.targetPercentage) > threshold
-    );
-  };
+  const needsRebalancing = () =>
+    portfolioData ? coreNeedsRebalancing(portfolioData.allocations) : false;
+
+  const toSol = (amount: { toNumber(): number }) => (amount.toNumber() / 1_000_000_000).toFixed(4);
+
+  const symbolFor = (mint: { toString(): string }) =>
+    allocations.find((allocation) => allocation.mint === mint.toString())?.symbol ??
+    (mint.toString() === portfolioData?.baseMint.toString() ? 'BASE' : mint.toString().slice(0, 8));
 
   const handleRebalance = async () => {
     if (!contract) {
@@ -81,14 +81,32 @@ export function RebalanceScreen() {
       return;
     }
 
-    if (!needsRebalancing()) {
-      Alert.alert('Info', 'Portfolio is already balanced within the 5% threshold');
+    // Dry run on-chain first so the user signs for the exact legs
+    let dryRun: RebalancePreview;
+    try {
+      dryRun = await contract.previewRebalance(slippageBps);
+      setPreview(dryRun);
+    } catch (error) {
+      console.error('Preview error:', error);
+      Alert.alert('Error', `Failed to preview rebalance: ${error instanceof Error ? error.message : 'Unknown error'}`);
+      return;
+    }
+
+    if (!dryRun.needsRebalancing) {
+      Alert.alert('Info', 'Portfolio is already balanced within its bands');
+      return;
+    }
+
+    if (!dryRun.canExecute) {
+      const nextAt = new Date(dryRun.nextRebalanceAt.toNumber() * 1000);
+      Alert.alert('Info', `Next rebalance is possible after ${nextAt.toLocaleString()}`);
       return;
     }
 
     Alert.alert(
       'Confirm Rebalance',
-      `This will rebalance your portfolio with ${slippageBps / 100}% slippage. Continue?`,
+      `This will execute ${dryRun.legs.length} swap(s) with ${slippageBps / 100}% slippage ` +
+        `(estimated cost ${toSol(dryRun.estimatedCost)} SOL). Continue?`,
       [
         { text: 'Cancel', style: 'cancel' },
         { text: 'Confirm', onPress: executeRebalance }
@@ -117,6 +135,7 @@ export function RebalanceScreen() {
           {
             text: 'OK',
             onPress: () => {
+              setPreview(null);
               loadPortfolioData(); // Reload data
             }
           }
@@ -185,12 +204,37 @@ export function RebalanceScreen() {
             </Card>
           ))}
 
+          {preview && (
+            <View style={styles.summaryContainer}>
+              <Text style={styles.summaryTitle}>Rebalance Preview</Text>
+              {preview.legs.length === 0 ? (
+                <Text style={styles.summaryText}>No swaps needed</Text>
+              ) : (
+                preview.legs.map((leg, index) => (
+                  <Text key={index} style={styles.summaryText}>
+                    {symbolFor(leg.fromMint)} → {symbolFor(leg.toMint)}: {toSol(leg.amount)} SOL
+                    (fee {toSol(leg.estimatedFee)}, cost {toSol(leg.estimatedCost)})
+                  </Text>
+                ))
+              )}
+              <Text style={styles.summaryText}>
+                Max Drift: {(preview.maxDriftBps.toNumber() / 100).toFixed(2)}% → {(preview.maxResidualDriftBps.toNumber() / 100).toFixed(2)}%
+              </Text>
+              <Text style={styles.summaryText}>
+                Estimated Fees: {toSol(preview.estimatedFees)} SOL / Slippage: {toSol(preview.estimatedSlippage)} SOL
+              </Text>
+            </View>
+          )}
+
           <Text style={styles.sectionTitle}>Slippage Tolerance</Text>
           <TextInput
             style={styles.slippageInput}
             mode="outlined"
             value={slippage}
-            onChangeText={setSlippage}
+            onChangeText={(value) => {
+              setSlippage(value);
+              setPreview(null); // Estimates depend on the slippage
+            }}
             placeholder="Slippage in basis points"
             keyboardType="numeric"
             right={<TextInput.Affix text="bps" />}
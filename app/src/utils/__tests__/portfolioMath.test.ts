@@ -0,0 +1,80 @@
+import { PublicKey } from '@solana/web3.js';
+import BN from 'bn.js';
+import {
+  amountToBps,
+  currentPercentages,
+  driftPercentages,
+  effectiveTarget,
+  glideTargets,
+  needsRebalancing,
+  AllocationInput,
+} from '../portfolioMath';
+
+// 期待値は sloomo-core（Rust）のテストと同じ入力・出力を使い、
+// オンチェーンと同じ数値になることを確認する
+const mint = (index: number) => new PublicKey(new Uint8Array(32).fill(index + 1));
+
+const allocation = (
+  index: number,
+  currentAmount: number,
+  targetPercentage: number,
+  band: AllocationInput['band'] = null
+): AllocationInput => ({ mint: mint(index), currentAmount: new BN(currentAmount), targetPercentage, band });
+
+describe('portfolioMath', () => {
+  it('割合は切り捨てのベーシスポイントで計算する', () => {
+    expect(amountToBps(new BN(250), new BN(1000))).toBe(2500);
+    expect(amountToBps(new BN(1), new BN(0))).toBe(0);
+    expect(amountToBps(new BN(1), new BN(3))).toBe(3333);
+
+    // u64 の上限付近でも精度を失わない
+    const max = new BN('18446744073709551615');
+    expect(amountToBps(max.subn(1), max)).toBe(9999);
+  });
+
+  it('現在の配分率と乖離をパーセントで返す', () => {
+    const allocations = [allocation(0, 400, 3000), allocation(1, 200, 3000), allocation(2, 400, 4000)];
+    expect(currentPercentages(allocations)).toEqual([40, 20, 40]);
+    expect(driftPercentages(allocations)).toEqual([10, 10, 0]);
+  });
+
+  it('閾値またはバンドを外れた配分でリバランスが必要になる', () => {
+    // 34/66: 30% 目標から 4% ずれているが 25-35% のバンド内
+    const banded = [
+      allocation(0, 340, 3000, { minPercentage: 2500, maxPercentage: 3500 }),
+      allocation(1, 660, 7000, { minPercentage: 6500, maxPercentage: 7500 }),
+    ];
+    expect(needsRebalancing(banded, 0)).toBe(false);
+
+    // バンドがなければ閾値で判定する
+    const unbanded = [allocation(0, 340, 3000), allocation(1, 660, 7000)];
+    expect(needsRebalancing(unbanded)).toBe(false);
+    expect(needsRebalancing(unbanded, 300)).toBe(true);
+
+    // 残高がなければリバランス不要
+    expect(needsRebalancing([allocation(0, 0, 10000)], 0)).toBe(false);
+  });
+
+  it('資産クラスの実効目標はクラス目標とウェイトの積を切り捨てる', () => {
+    expect(effectiveTarget(6000, 5000)).toBe(3000);
+    expect(effectiveTarget(3333, 3333)).toBe(1110);
+  });
+
+  it('グライドパスの目標を線形補間する', () => {
+    const legs = [
+      { startPercentage: 8000, endPercentage: 3000 },
+      { startPercentage: 2000, endPercentage: 7000 },
+    ];
+    expect(glideTargets(legs, 1_000, 11_000, 0)).toEqual([8000, 2000]);
+    expect(glideTargets(legs, 1_000, 11_000, 6_000)).toEqual([5500, 4500]);
+    expect(glideTargets(legs, 1_000, 11_000, 8_500)).toEqual([4250, 5750]);
+    expect(glideTargets(legs, 1_000, 11_000, 11_000)).toEqual([3000, 7000]);
+
+    // 減少方向も切り捨て（Rust の div_euclid と同じ）
+    const thirds = [
+      { startPercentage: 3000, endPercentage: 4000 },
+      { startPercentage: 4000, endPercentage: 3000 },
+    ];
+    expect(glideTargets(thirds, 0, 3, 1)).toEqual([3333, 3666]);
+  });
+});
@@ -13,6 +13,7 @@ import {
   SystemProgram, 
   LAMPORTS_PER_SOL, 
   Transaction,
+  TransactionInstruction,
   Connection,
   Keypair
 } from "@solana/web3.js";
@@ -22,12 +23,183 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountInstruction,
   createSyncNativeInstruction,
-  NATIVE_MINT
+  createApproveInstruction,
+  createRevokeInstruction,
+  getMint,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID
 } from "@solana/spl-token";
 import idl from "./sloomo_portfolio.json";
+import { glideTargets } from "./portfolioMath";
 
 const DEVNET_USDC_MINT = new PublicKey("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU");
 const PROGRAM_ID = new PublicKey("EAkD1pREBvpRtoAY88hmwKYr2qhdbU1rLYQ9sxTAzxhC"); // devnet program ID
+const JUPITER_V6_PROGRAM_ID = new PublicKey("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+// Instruction groups a session key may sign (mirrors SESSION_SCOPE_* in the program)
+export const SESSION_SCOPE_DEPOSIT = 1 << 0;
+export const SESSION_SCOPE_ALLOCATION = 1 << 1;
+export const SESSION_SCOPE_REBALANCE = 1 << 2;
+
+export type LiquidationOrder = "proportional" | "overweightFirst";
+
+export type BandRestore = "edge" | "target";
+
+export interface AllocationBand {
+  minPercentage: number; // basis points
+  maxPercentage: number; // basis points
+}
+
+export interface AssetClassInput {
+  name: string; // max 16 chars
+  targetPercentage: number; // basis points
+  band: AllocationBand | null;
+}
+
+export interface ClassMembershipInput {
+  mint: PublicKey;
+  assetClass: number; // index into the classes passed alongside
+  weight: number; // basis points of the class; members of a class sum to 10000
+}
+
+export interface EffectiveTarget {
+  mint: PublicKey;
+  symbol: string;
+  targetPercentage: number; // basis points of the portfolio
+  assetClass: string | null; // class name, null for unclassified allocations
+}
+
+export interface SavingsGoalInput {
+  targetAmount: number; // base asset units
+  targetDate: number; // unix seconds
+  label: string; // max 32 chars
+}
+
+// Result of the get_goal_progress view (amounts in base asset units)
+export interface GoalProgress {
+  totalValue: anchor.BN;
+  targetAmount: anchor.BN;
+  targetDate: anchor.BN;
+  progressBps: number;
+  remainingAmount: anchor.BN;
+  periodsRemaining: anchor.BN;
+  expectedGrowthBps: number;
+  projectedValue: anchor.BN;
+  requiredContribution: anchor.BN;
+}
+
+export const MONTHLY_CONTRIBUTION_INTERVAL = 30 * 24 * 60 * 60;
+
+// Results of the get_summary / get_drift / preview_rebalance views (percentages in basis points)
+export interface PortfolioSummary {
+  owner: PublicKey;
+  baseMint: PublicKey;
+  totalValue: anchor.BN;
+  allocatedValue: anchor.BN;
+  lastRebalance: anchor.BN;
+  isRebalancing: boolean;
+  needsRebalancing: boolean;
+  latestGrowthBps: number;
+  allocations: {
+    mint: PublicKey;
+    symbol: string;
+    currentAmount: anchor.BN;
+    currentPercentage: anchor.BN;
+    targetPercentage: number;
+  }[];
+}
+
+export interface DriftReport {
+  totalValue: anchor.BN;
+  needsRebalancing: boolean;
+  maxDriftBps: anchor.BN;
+  allocations: {
+    mint: PublicKey;
+    currentPercentage: anchor.BN;
+    targetPercentage: number;
+    driftBps: anchor.BN;
+    withinBand: boolean;
+  }[];
+  assetClasses: {
+    name: string;
+    currentPercentage: anchor.BN;
+    targetPercentage: number;
+    withinBand: boolean;
+  }[];
+}
+
+// Dry run of rebalancePortfolio (fees, slippage and amounts in base asset units)
+export interface RebalancePreview {
+  totalValue: anchor.BN;
+  targetAllocations: { mint: PublicKey; targetPercentage: number }[];
+  needsRebalancing: boolean;
+  maxDriftBps: anchor.BN;
+  legs: {
+    fromMint: PublicKey;
+    toMint: PublicKey;
+    amount: anchor.BN;
+    estimatedCost: anchor.BN;
+    estimatedFee: anchor.BN;
+  }[];
+  estimatedCost: anchor.BN;
+  estimatedFees: anchor.BN;
+  estimatedSlippage: anchor.BN;
+  maxResidualDriftBps: anchor.BN;
+  withinBand: boolean;
+  nextRebalanceAt: anchor.BN;
+  canExecute: boolean;
+}
+
+// Receipt written by commit_rebalance (percentages and slippage in basis points)
+export interface RebalanceRecord {
+  portfolio: PublicKey;
+  index: anchor.BN;
+  startedAt: anchor.BN;
+  committedAt: anchor.BN;
+  slippageBps: number;
+  totalValueBefore: anchor.BN;
+  totalValueAfter: anchor.BN;
+  legs: {
+    fromMint: PublicKey;
+    toMint: PublicKey;
+    amountIn: anchor.BN;
+    amountOut: anchor.BN;
+    effectivePrice: anchor.BN; // amountOut per amountIn, scaled by 1e9
+    realizedSlippageBps: number; // negative when more was received than spent
+  }[];
+  allocations: { mint: PublicKey; percentageBefore: number; percentageAfter: number }[];
+}
+
+// Price passed to get_pnl: base units per unit, scaled by 1e9 (PRICE_SCALE)
+export interface AssetPriceInput {
+  mint: PublicKey;
+  price: anchor.BN;
+}
+
+// Result of the get_pnl view (amounts in base asset units, averageCost and price scaled by 1e9);
+// null values are unknown because the mint was not priced
+export interface PnlReport {
+  costBasis: anchor.BN;
+  value: anchor.BN | null;
+  unrealizedPnl: anchor.BN | null;
+  realizedPnl: anchor.BN;
+  allocations: {
+    mint: PublicKey;
+    currentAmount: anchor.BN;
+    costBasis: anchor.BN;
+    averageCost: anchor.BN;
+    price: anchor.BN | null;
+    value: anchor.BN | null;
+    unrealizedPnl: anchor.BN | null;
+    realizedPnl: anchor.BN;
+  }[];
+}
+
+export interface LiquidationSwapInput {
+  fromMint: PublicKey;
+  amountIn: number; // base units of fromMint
+  instruction: TransactionInstruction; // swap instruction with the portfolio PDA as authority
+}
 
 export interface ContractInteractionConfig {
   connection: Connection;
@@ -42,6 +214,8 @@ export class ContractInteractions {
   private connection: Connection;
   private userWallet: any;
   private program: anchor.Program<any> | null = null;
+  // Device-held session key; scoped instructions are signed with it instead of the wallet
+  private sessionKeypair: Keypair | null = null;
 
   constructor(config: ContractInteractionConfig) {
     this.connection = config.connection;
@@ -63,10 +237,406 @@ export class ContractInteractions {
   }
 
   private async getPortfolioPda(): Promise<[PublicKey, number]> {
-    return await PublicKey.findProgramAddress(
+    // Portfolios created before portfolio IDs are seeded by their creating key
+    const legacyPda = await PublicKey.findProgramAddress(
       [Buffer.from("portfolio"), this.userWallet.publicKey.toBuffer()],
       PROGRAM_ID
     );
+
+    // The PDA is derived from the portfolio ID chosen at initialization, so the
+    // portfolio is found by its owner field
+    const program = await this.getProgram();
+    if (!program) return legacyPda;
+    try {
+      const owned = await (program.account as any).portfolio.all([
+        { memcmp: { offset: 8, bytes: this.userWallet.publicKey.toBase58() } },
+      ]);
+      if (owned.length > 0) return [owned[0].publicKey, owned[0].account.bump];
+    } catch (error) {
+      // Accounts not migrated to the current layout cannot be decoded
+      console.log("Portfolio lookup by owner failed:", error);
+    }
+    return legacyPda;
+  }
+
+  async proposeOwnerTransfer(newOwner: PublicKey): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const proposeIx = await program.methods
+      .proposeOwnerTransfer(newOwner)
+      .accounts({
+        portfolio: portfolioPda,
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(proposeIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async cancelOwnerTransfer(): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const cancelIx = await program.methods
+      .cancelOwnerTransfer()
+      .accounts({
+        portfolio: portfolioPda,
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(cancelIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  // Called from the new owner's wallet with the address of the portfolio being handed over
+  async acceptOwnerTransfer(portfolio: PublicKey): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const acceptIx = await program.methods
+      .acceptOwnerTransfer()
+      .accounts({
+        portfolio,
+        guardianSet: this.getGuardianSetPda(portfolio),
+        newOwner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(acceptIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  private getGuardianSetPda(portfolio: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("guardians"), portfolio.toBuffer()],
+      PROGRAM_ID
+    )[0];
+  }
+
+  // An empty guardian list with a zero threshold disables recovery
+  async setGuardians(guardians: PublicKey[], threshold: number, timelockSeconds: number): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const setIx = await program.methods
+      .setGuardians(guardians, threshold, new anchor.BN(timelockSeconds))
+      .accounts({
+        portfolio: portfolioPda,
+        guardianSet: this.getGuardianSetPda(portfolioPda),
+        owner: this.userWallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(setIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  // Called from a guardian's wallet with the address of the portfolio being recovered
+  async initiateRecovery(portfolio: PublicKey, newOwner: PublicKey): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const initiateIx = await program.methods
+      .initiateRecovery(newOwner)
+      .accounts({
+        portfolio,
+        guardianSet: this.getGuardianSetPda(portfolio),
+        guardian: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(initiateIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async approveRecovery(portfolio: PublicKey): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const approveIx = await program.methods
+      .approveRecovery()
+      .accounts({
+        portfolio,
+        guardianSet: this.getGuardianSetPda(portfolio),
+        guardian: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(approveIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async vetoRecovery(): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const vetoIx = await program.methods
+      .vetoRecovery()
+      .accounts({
+        portfolio: portfolioPda,
+        guardianSet: this.getGuardianSetPda(portfolioPda),
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(vetoIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async completeRecovery(portfolio: PublicKey): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const completeIx = await program.methods
+      .completeRecovery()
+      .accounts({
+        portfolio,
+        guardianSet: this.getGuardianSetPda(portfolio),
+        executor: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(completeIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  private getSessionKeyPda(portfolio: PublicKey, authority: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("session_key"), portfolio.toBuffer(), authority.toBuffer()],
+      PROGRAM_ID
+    )[0];
+  }
+
+  // Signer accounts for a scoped instruction: the session key when one is active, else the owner
+  private scopedSigner(portfolio: PublicKey): { authority: PublicKey; sessionKey: PublicKey | null } {
+    if (!this.sessionKeypair) {
+      return { authority: this.userWallet.publicKey, sessionKey: null };
+    }
+    const authority = this.sessionKeypair.publicKey;
+    return { authority, sessionKey: this.getSessionKeyPda(portfolio, authority) };
+  }
+
+  // Sends without a wallet prompt when a session key is active (it also pays the fee)
+  private async sendScopedTransaction(transaction: Transaction): Promise<string> {
+    if (!this.sessionKeypair) return await this.sendTransaction(transaction);
+
+    const { blockhash } = await this.connection.getLatestBlockhash();
+    transaction.recentBlockhash = blockhash;
+    transaction.feePayer = this.sessionKeypair.publicKey;
+    transaction.sign(this.sessionKeypair);
+    const signature = await this.connection.sendRawTransaction(transaction.serialize());
+    console.log("✅ Transaction sent with session key:", signature);
+    return signature;
+  }
+
+  // One wallet approval issues the key, funds its fees and, for deposits, approves it as
+  // delegate of the owner's base asset account (up to the spend limit)
+  async createSessionKey(
+    scopes: number,
+    durationSeconds: number,
+    spendLimit: number | null = null, // base units of the base asset
+    feeLamports: number = 0.01 * LAMPORTS_PER_SOL
+  ): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const sessionKeypair = Keypair.generate();
+    const expiresAt = Math.floor(Date.now() / 1000) + durationSeconds;
+
+    const createIx = await program.methods
+      .createSessionKey(
+        sessionKeypair.publicKey,
+        scopes,
+        new anchor.BN(expiresAt),
+        spendLimit === null ? null : new anchor.BN(spendLimit)
+      )
+      .accounts({
+        portfolio: portfolioPda,
+        sessionKey: this.getSessionKeyPda(portfolioPda, sessionKeypair.publicKey),
+        owner: this.userWallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(
+      createIx,
+      SystemProgram.transfer({
+        fromPubkey: this.userWallet.publicKey,
+        toPubkey: sessionKeypair.publicKey,
+        lamports: feeLamports,
+      })
+    );
+
+    if (scopes & SESSION_SCOPE_DEPOSIT) {
+      const baseAccount = await this.getBaseAssetAccount(portfolioPda);
+      transaction.add(
+        createApproveInstruction(
+          baseAccount.address,
+          sessionKeypair.publicKey,
+          this.userWallet.publicKey,
+          spendLimit === null ? BigInt("18446744073709551615") : BigInt(spendLimit),
+          [],
+          baseAccount.tokenProgram
+        )
+      );
+    }
+
+    const signature = await this.sendTransaction(transaction);
+    this.sessionKeypair = sessionKeypair;
+    return signature;
+  }
+
+  async revokeSessionKey(): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+    if (!this.sessionKeypair) throw new Error("No active session key");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const baseAccount = await this.getBaseAssetAccount(portfolioPda);
+
+    const revokeIx = await program.methods
+      .revokeSessionKey()
+      .accounts({
+        portfolio: portfolioPda,
+        sessionKey: this.getSessionKeyPda(portfolioPda, this.sessionKeypair.publicKey),
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(
+      revokeIx,
+      createRevokeInstruction(
+        baseAccount.address,
+        this.userWallet.publicKey,
+        [],
+        baseAccount.tokenProgram
+      )
+    );
+    const signature = await this.sendTransaction(transaction);
+    this.sessionKeypair = null;
+    return signature;
+  }
+
+  private getRecurringDepositPda(portfolio: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("recurring_deposit"), portfolio.toBuffer()],
+      PROGRAM_ID
+    )[0];
+  }
+
+  // Schedules the deposit and approves the portfolio PDA to pull it, in one wallet approval.
+  // Anyone (e.g. a keeper) cranks each due occurrence afterwards.
+  async createRecurringDeposit(
+    amount: number, // UI units of the base asset
+    intervalSeconds: number,
+    maxOccurrences: number | null = null
+  ): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
+    const baseAccount = await this.getBaseAssetAccount(portfolioPda);
+    const { decimals } = await getMint(
+      this.connection,
+      portfolioData.baseMint,
+      undefined,
+      baseAccount.tokenProgram
+    );
+    const baseUnits = BigInt(Math.floor(amount * 10 ** decimals));
+
+    const createIx = await program.methods
+      .createRecurringDeposit(
+        new anchor.BN(baseUnits.toString()),
+        new anchor.BN(intervalSeconds),
+        null,
+        maxOccurrences
+      )
+      .accounts({
+        portfolio: portfolioPda,
+        recurringDeposit: this.getRecurringDepositPda(portfolioPda),
+        source: baseAccount.address,
+        baseMint: portfolioData.baseMint,
+        owner: this.userWallet.publicKey,
+        tokenProgram: baseAccount.tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(
+      createIx,
+      createApproveInstruction(
+        baseAccount.address,
+        portfolioPda,
+        this.userWallet.publicKey,
+        maxOccurrences === null
+          ? BigInt("18446744073709551615")
+          : baseUnits * BigInt(maxOccurrences),
+        [],
+        baseAccount.tokenProgram
+      )
+    );
+    return await this.sendTransaction(transaction);
+  }
+
+  async cancelRecurringDeposit(): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const baseAccount = await this.getBaseAssetAccount(portfolioPda);
+
+    const cancelIx = await program.methods
+      .cancelRecurringDeposit()
+      .accounts({
+        portfolio: portfolioPda,
+        recurringDeposit: this.getRecurringDepositPda(portfolioPda),
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(
+      cancelIx,
+      createRevokeInstruction(
+        baseAccount.address,
+        this.userWallet.publicKey,
+        [],
+        baseAccount.tokenProgram
+      )
+    );
+    return await this.sendTransaction(transaction);
+  }
+
+  // Owner's associated account for the portfolio base asset, with the mint's token program
+  private async getBaseAssetAccount(
+    portfolio: PublicKey
+  ): Promise<{ address: PublicKey; tokenProgram: PublicKey }> {
+    const program = await this.getProgram();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolio);
+    const baseMint: PublicKey = portfolioData.baseMint;
+    const mintInfo = await this.connection.getAccountInfo(baseMint);
+    if (!mintInfo) throw new Error("Mint not found");
+
+    const address = await getAssociatedTokenAddress(
+      baseMint,
+      this.userWallet.publicKey,
+      false,
+      mintInfo.owner
+    );
+    return { address, tokenProgram: mintInfo.owner };
   }
 
   private async sendTransaction(transaction: Transaction): Promise<string> {
@@ -136,7 +706,12 @@ export class ContractInteractions {
     if (!program) throw new Error("Program not initialized");
     console.log("✅ Program initialized:", program.programId.toString());
 
-    const [portfolioPda] = await this.getPortfolioPda();
+    // A fresh portfolio ID seeds the PDA, so it survives owner transfers
+    const portfolioId = Keypair.generate().publicKey;
+    const [portfolioPda] = await PublicKey.findProgramAddress(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      PROGRAM_ID
+    );
     console.log("✅ Portfolio PDA:", portfolioPda.toString());
 
     const initialAllocations = [
@@ -144,11 +719,13 @@ export class ContractInteractions {
         mint: NATIVE_MINT, // wSOL (GOOGLx representation)
         symbol: "GOOGLx",
         targetPercentage: 6000, // 60%
+        band: null,
       },
       {
         mint: DEVNET_USDC_MINT, // USDC (COINx representation)
         symbol: "COINx", 
         targetPercentage: 4000, // 40%
+        band: null,
       },
     ];
     console.log("✅ Initial allocations:", initialAllocations);
@@ -200,7 +777,7 @@ export class ContractInteractions {
 
     console.log("🔧 Creating initialization instruction...");
     const initIx = await program.methods
-      .initializePortfolio({ 
+      .initializePortfolio(portfolioId, {
         initialAllocations,
         initialSolAmount: new anchor.BN(initialSolAmountLamports),
         enableJupiterSwap: true
@@ -208,9 +785,9 @@ export class ContractInteractions {
       .accounts({
         portfolio: portfolioPda,
         owner: this.userWallet.publicKey,
-        userWsolAccount: userWsolAccount,
-        portfolioWsolVault: portfolioWsolVault,
-        wsolMint: NATIVE_MINT,
+        userBaseAccount: userWsolAccount,
+        portfolioBaseVault: portfolioWsolVault,
+        baseMint: NATIVE_MINT,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       } as any)
@@ -286,11 +863,41 @@ export class ContractInteractions {
     return signature;
   }
 
+  // Portfolios created before versioning keep their owner-seeded PDA and wSOL vault
+  async migratePortfolio(): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await PublicKey.findProgramAddress(
+      [Buffer.from("portfolio"), this.userWallet.publicKey.toBuffer()],
+      PROGRAM_ID
+    );
+    const [baseVault] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), NATIVE_MINT.toBuffer()],
+      PROGRAM_ID
+    );
+
+    const migrateIx = await program.methods
+      .migratePortfolio()
+      .accounts({
+        portfolio: portfolioPda,
+        baseVault,
+        baseMint: NATIVE_MINT,
+        owner: this.userWallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .instruction();
+
+    const transaction = new Transaction().add(migrateIx);
+    return await this.sendTransaction(transaction);
+  }
+
   async depositUsdc(amount: number): Promise<string> {
     const program = await this.getProgram();
     if (!program) throw new Error("Program not initialized");
 
     const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
     
     const userUsdcAccount = await getAssociatedTokenAddress(
       DEVNET_USDC_MINT,
@@ -311,6 +918,7 @@ export class ContractInteractions {
         userUsdcAccount: userUsdcAccount,
         portfolioUsdcVault: portfolioUsdcVault,
         usdcMint: DEVNET_USDC_MINT,
+        baseMint: portfolioData.baseMint,
         owner: this.userWallet.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -322,6 +930,256 @@ export class ContractInteractions {
     return await this.sendTransaction(transaction);
   }
 
+  async depositToken(mint: PublicKey, amount: number): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
+    const baseMint: PublicKey = portfolioData.baseMint;
+
+    // SPL Token or Token-2022, whichever owns the mint
+    const mintInfo = await this.connection.getAccountInfo(mint);
+    if (!mintInfo) throw new Error("Mint not found");
+    const tokenProgram = mintInfo.owner;
+
+    const userTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      this.userWallet.publicKey,
+      false,
+      tokenProgram
+    );
+
+    const [portfolioVault] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      PROGRAM_ID
+    );
+
+    // Amount is given in UI units; the program checks the mint's decimals
+    const { decimals } = await getMint(this.connection, mint, undefined, tokenProgram);
+    const depositAmount = Math.floor(amount * 10 ** decimals);
+
+    const depositIx = await program.methods
+      .depositToken(new anchor.BN(depositAmount))
+      .accounts({
+        portfolio: portfolioPda,
+        userTokenAccount: userTokenAccount,
+        portfolioVault: portfolioVault,
+        mint: mint,
+        baseMint: baseMint,
+        ...this.scopedSigner(portfolioPda),
+        tokenProgram: tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(depositIx);
+    return await this.sendScopedTransaction(transaction);
+  }
+
+  async setDepositRouting(enabled: boolean): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const routingIx = await program.methods
+      .setDepositRouting(enabled)
+      .accounts({
+        portfolio: portfolioPda,
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(routingIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async setBandRestore(restore: BandRestore): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const restoreIx = await program.methods
+      .setBandRestore({ [restore]: {} })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(restoreIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async setSavingsGoal(goal: SavingsGoalInput | null): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const goalIx = await program.methods
+      .setSavingsGoal(
+        goal && {
+          targetAmount: new anchor.BN(goal.targetAmount),
+          targetDate: new anchor.BN(goal.targetDate),
+          label: goal.label,
+        }
+      )
+      .accounts({
+        portfolio: portfolioPda,
+        owner: this.userWallet.publicKey,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(goalIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  // Read-only: simulated, the program returns the progress through return data
+  async getGoalProgress(
+    contributionInterval: number = MONTHLY_CONTRIBUTION_INTERVAL
+  ): Promise<GoalProgress> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    return await program.methods
+      .getGoalProgress(new anchor.BN(contributionInterval))
+      .accounts({ portfolio: portfolioPda })
+      .view();
+  }
+
+  async getSummary(): Promise<PortfolioSummary> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    return await program.methods
+      .getSummary()
+      .accounts({ portfolio: portfolioPda })
+      .view();
+  }
+
+  async getDrift(): Promise<DriftReport> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    return await program.methods
+      .getDrift()
+      .accounts({ portfolio: portfolioPda })
+      .view();
+  }
+
+  // Legs rebalancePortfolio would trade now, planned from the same vault balances
+  async previewRebalance(slippageBps: number = 50): Promise<RebalancePreview> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
+
+    const vaultFor = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+        PROGRAM_ID
+      )[0];
+
+    const targetAllocations = portfolioData.allocations.map((allocation: any) => ({
+      mint: allocation.mint,
+      targetPercentage: allocation.targetPercentage,
+    }));
+
+    return await program.methods
+      .previewRebalance(targetAllocations, slippageBps)
+      .accounts({
+        portfolio: portfolioPda,
+        baseVault: vaultFor(portfolioData.baseMint),
+        baseMint: portfolioData.baseMint,
+      } as any)
+      .remainingAccounts(this.snapshotAccounts(portfolioPda, portfolioData, false))
+      .view();
+  }
+
+  // Gains per allocation; mints other than the base asset without a price have unknown value and gains
+  async getPnl(prices: AssetPriceInput[] = []): Promise<PnlReport> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    return await program.methods
+      .getPnl(prices)
+      .accounts({ portfolio: portfolioPda })
+      .view();
+  }
+
+  // Vault of every non-base allocation (allocation order), then the same mints,
+  // so the program can value each vault in base asset decimals
+  private snapshotAccounts(portfolioPda: PublicKey, portfolioData: any, isWritable: boolean) {
+    const mints: PublicKey[] = portfolioData.allocations
+      .map((allocation: any) => allocation.mint)
+      .filter((mint: PublicKey) => !mint.equals(portfolioData.baseMint));
+    return [
+      ...mints.map((mint) => ({
+        pubkey: PublicKey.findProgramAddressSync(
+          [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+          PROGRAM_ID
+        )[0],
+        isSigner: false,
+        isWritable,
+      })),
+      ...mints.map((mint) => ({ pubkey: mint, isSigner: false, isWritable: false })),
+    ];
+  }
+
+  private getRebalanceRecordPda(portfolio: PublicKey, index: anchor.BN): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_record"), portfolio.toBuffer(), index.toArrayLike(Buffer, "le", 8)],
+      PROGRAM_ID
+    )[0];
+  }
+
+  // Receipts of committed rebalances still open, newest first
+  async getRebalanceRecords(): Promise<RebalanceRecord[]> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const records = await (program.account as any).rebalanceRecord.all([
+      { memcmp: { offset: 8, bytes: portfolioPda.toBase58() } },
+    ]);
+    return records
+      .map((record: any) => record.account as RebalanceRecord)
+      .sort((a: RebalanceRecord, b: RebalanceRecord) => b.index.cmp(a.index));
+  }
+
+  // Reclaims the record's rent; the index is not reused
+  async closeRebalanceRecord(index: anchor.BN): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const closeIx = await program.methods
+      .closeRebalanceRecord()
+      .accounts({
+        portfolio: portfolioPda,
+        record: this.getRebalanceRecordPda(portfolioPda, index),
+        owner: this.userWallet.publicKey,
+      } as any)
+      .instruction();
+
+    const transaction = new Transaction().add(closeIx);
+    return await this.sendTransaction(transaction);
+  }
+
   async depositSol(amount: number): Promise<string> {
     console.log("🚀 Starting SOL deposit...");
     console.log("💰 Deposit amount:", amount, "SOL");
@@ -346,69 +1204,221 @@ export class ContractInteractions {
     }
 
     const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
     const depositAmountLamports = Math.floor(amount * LAMPORTS_PER_SOL);
     console.log("📋 Deposit amount (lamports):", depositAmountLamports);
     console.log("🏛️ Portfolio PDA:", portfolioPda.toString());
-    
-    const userWsolAccount = await getAssociatedTokenAddress(
-      NATIVE_MINT,
-      this.userWallet.publicKey
+
+    const [portfolioWsolVault] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), NATIVE_MINT.toBuffer()],
+      PROGRAM_ID
     );
-    console.log("🎯 wSOL account:", userWsolAccount.toString());
+
+    // The program wraps plain SOL into the portfolio's wSOL vault
+    const depositIx = await program.methods
+      .depositSol(new anchor.BN(depositAmountLamports))
+      .accounts({
+        portfolio: portfolioPda,
+        portfolioWsolVault: portfolioWsolVault,
+        wsolMint: NATIVE_MINT,
+        baseMint: portfolioData.baseMint,
+        owner: this.userWallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(depositIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async withdrawSol(amount: number): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
+    const withdrawAmountLamports = Math.floor(amount * LAMPORTS_PER_SOL);
 
     const [portfolioWsolVault] = await PublicKey.findProgramAddress(
       [Buffer.from("vault"), portfolioPda.toBuffer(), NATIVE_MINT.toBuffer()],
       PROGRAM_ID
     );
+    const [unwrapAccount] = await PublicKey.findProgramAddress(
+      [Buffer.from("unwrap"), portfolioPda.toBuffer()],
+      PROGRAM_ID
+    );
 
-    // Check if wSOL account exists
-    const wsolAccountInfo = await this.connection.getAccountInfo(userWsolAccount);
-    const needsWsolAccount = !wsolAccountInfo;
+    // The program unwraps wSOL from the vault and pays out plain SOL
+    const withdrawIx = await program.methods
+      .withdrawSol(new anchor.BN(withdrawAmountLamports))
+      .accounts({
+        portfolio: portfolioPda,
+        portfolioWsolVault: portfolioWsolVault,
+        unwrapAccount: unwrapAccount,
+        wsolMint: NATIVE_MINT,
+        baseMint: portfolioData.baseMint,
+        owner: this.userWallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
 
-    const instructions = [];
+    const transaction = new Transaction().add(withdrawIx);
+    return await this.sendTransaction(transaction);
+  }
 
-    if (needsWsolAccount) {
-      // Create wSOL account
-      instructions.push(
+  async withdrawProportional(bps: number): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
+    const baseMint: PublicKey = portfolioData.baseMint;
+
+    // Base asset first, then every other allocation in order
+    const heldMints: PublicKey[] = [
+      baseMint,
+      ...portfolioData.allocations
+        .map((allocation: any) => allocation.mint)
+        .filter((mint: PublicKey) => !mint.equals(baseMint)),
+    ];
+
+    const transaction = new Transaction();
+    const remainingAccounts = [];
+    for (const mint of heldMints) {
+      const mintInfo = await this.connection.getAccountInfo(mint);
+      if (!mintInfo) throw new Error(`Mint not found: ${mint.toString()}`);
+
+      const [vault] = await PublicKey.findProgramAddress(
+        [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+        PROGRAM_ID
+      );
+      const ownerAccount = await getAssociatedTokenAddress(
+        mint,
+        this.userWallet.publicKey,
+        false,
+        mintInfo.owner
+      );
+
+      // Create missing ATAs so every share has somewhere to land
+      if (!(await this.connection.getAccountInfo(ownerAccount))) {
+        transaction.add(
+          createAssociatedTokenAccountInstruction(
+            this.userWallet.publicKey,
+            ownerAccount,
+            this.userWallet.publicKey,
+            mint,
+            mintInfo.owner
+          )
+        );
+      }
+
+      remainingAccounts.push(
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: ownerAccount, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false }
+      );
+    }
+
+    const withdrawIx = await program.methods
+      .withdrawProportional(bps)
+      .accounts({
+        portfolio: portfolioPda,
+        owner: this.userWallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .instruction();
+
+    transaction.add(withdrawIx);
+    return await this.sendTransaction(transaction);
+  }
+
+  async withdrawToBase(
+    amount: number,
+    swaps: LiquidationSwapInput[] = [],
+    order: LiquidationOrder = "proportional",
+    maxSlippageBps: number = 50
+  ): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+    const portfolioData = await (program.account as any).portfolio.fetch(portfolioPda);
+    const baseMint: PublicKey = portfolioData.baseMint;
+
+    const mintInfo = await this.connection.getAccountInfo(baseMint);
+    if (!mintInfo) throw new Error("Base mint not found");
+    const tokenProgram = mintInfo.owner;
+
+    const vaultFor = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+        PROGRAM_ID
+      )[0];
+
+    // Amount is given in UI units of the base asset
+    const { decimals } = await getMint(this.connection, baseMint, undefined, tokenProgram);
+    const withdrawAmount = Math.floor(amount * 10 ** decimals);
+
+    const userBaseAccount = await getAssociatedTokenAddress(
+      baseMint,
+      this.userWallet.publicKey,
+      false,
+      tokenProgram
+    );
+
+    const transaction = new Transaction();
+    if (!(await this.connection.getAccountInfo(userBaseAccount))) {
+      transaction.add(
         createAssociatedTokenAccountInstruction(
           this.userWallet.publicKey,
-          userWsolAccount,
+          userBaseAccount,
           this.userWallet.publicKey,
-          NATIVE_MINT
+          baseMint,
+          tokenProgram
         )
       );
     }
 
-    // Transfer SOL to wSOL account
-    instructions.push(
-      SystemProgram.transfer({
-        fromPubkey: this.userWallet.publicKey,
-        toPubkey: userWsolAccount,
-        lamports: depositAmountLamports,
-      })
+    // Every non-base allocation's vault and mint, then each swap's accounts
+    const vaultAccounts = this.snapshotAccounts(portfolioPda, portfolioData, true);
+    const swapAccounts = swaps.flatMap((swap) =>
+      swap.instruction.keys.map((key) => ({
+        pubkey: key.pubkey,
+        // The portfolio PDA signs inside the program
+        isSigner: key.isSigner && !key.pubkey.equals(portfolioPda),
+        isWritable: key.isWritable,
+      }))
     );
 
-    // Sync wSOL account
-    instructions.push(createSyncNativeInstruction(userWsolAccount));
-
-    // Deposit instruction
-    const depositIx = await program.methods
-      .depositUsdc(new anchor.BN(depositAmountLamports))
+    const withdrawIx = await program.methods
+      .withdrawToBase(
+        new anchor.BN(withdrawAmount),
+        order === "overweightFirst" ? { overweightFirst: {} } : { proportional: {} },
+        maxSlippageBps,
+        swaps.map((swap) => ({
+          fromMint: swap.fromMint,
+          amountIn: new anchor.BN(swap.amountIn),
+          accountCount: swap.instruction.keys.length,
+          data: swap.instruction.data,
+        }))
+      )
       .accounts({
         portfolio: portfolioPda,
-        userUsdcAccount: userWsolAccount,
-        portfolioUsdcVault: portfolioWsolVault,
-        usdcMint: NATIVE_MINT,
         owner: this.userWallet.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        baseVault: vaultFor(baseMint),
+        userBaseAccount,
+        baseMint,
+        swapProgram: swaps[0]?.instruction.programId ?? JUPITER_V6_PROGRAM_ID,
+        tokenProgram,
       })
+      .remainingAccounts([...vaultAccounts, ...swapAccounts])
       .instruction();
 
-    instructions.push(depositIx);
-
-    const transaction = new Transaction().add(...instructions);
+    transaction.add(withdrawIx);
     return await this.sendTransaction(transaction);
   }
 
@@ -424,10 +1434,11 @@ export class ContractInteractions {
       throw new Error("Rebalancing is already in progress");
     }
 
-    const wsolTokenAccount = await getAssociatedTokenAddress(
-      NATIVE_MINT,
-      this.userWallet.publicKey
-    );
+    const vaultFor = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+        PROGRAM_ID
+      )[0];
 
     const targetAllocations = portfolioData.allocations.map((allocation: any) => ({
       mint: allocation.mint,
@@ -435,19 +1446,20 @@ export class ContractInteractions {
     }));
 
     const rebalanceIx = await program.methods
-      .solJupiterRebalance(targetAllocations, slippageBps)
+      .rebalance(targetAllocations, slippageBps)
       .accounts({
         portfolio: portfolioPda,
-        owner: this.userWallet.publicKey,
-        wsolTokenAccount: wsolTokenAccount,
-        wsolMint: NATIVE_MINT,
+        ...this.scopedSigner(portfolioPda),
+        baseVault: vaultFor(portfolioData.baseMint),
+        baseMint: portfolioData.baseMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
-      })
+      } as any)
+      .remainingAccounts(this.snapshotAccounts(portfolioPda, portfolioData, true))
       .instruction();
 
     const transaction = new Transaction().add(rebalanceIx);
-    return await this.sendTransaction(transaction);
+    return await this.sendScopedTransaction(transaction);
   }
 
   async getPortfolioData(): Promise<any> {
@@ -483,7 +1495,12 @@ export class ContractInteractions {
     }
   }
 
-  async updateAllocation(symbol: string, mint: PublicKey, targetPercentage: number): Promise<string> {
+  async updateAllocation(
+    symbol: string,
+    mint: PublicKey,
+    targetPercentage: number,
+    band: AllocationBand | null = null
+  ): Promise<string> {
     console.log("🔧 Updating allocation:", symbol, "to", targetPercentage / 100, "%");
     
     const program = await this.getProgram();
@@ -494,26 +1511,87 @@ export class ContractInteractions {
     console.log("✅ Portfolio PDA:", portfolioPda.toString());
 
     const updateIx = await program.methods
-      .addOrUpdateAllocation({
-        mint,
-        symbol,
-        targetPercentage,
-      })
+      .addOrUpdateAllocation(mint, symbol, targetPercentage, band)
       .accounts({
         portfolio: portfolioPda,
-        owner: this.userWallet.publicKey,
-        systemProgram: SystemProgram.programId,
+        ...this.scopedSigner(portfolioPda),
       })
       .instruction();
 
     const transaction = new Transaction().add(updateIx);
     console.log("📤 Sending allocation update transaction...");
-    const signature = await this.sendTransaction(transaction);
+    const signature = await this.sendScopedTransaction(transaction);
     console.log("✅ Allocation updated with signature:", signature);
     
     return signature;
   }
 
+  async setAssetClasses(
+    classes: AssetClassInput[],
+    memberships: ClassMembershipInput[]
+  ): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const classesIx = await program.methods
+      .setAssetClasses(classes, memberships)
+      .accounts({
+        portfolio: portfolioPda,
+        ...this.scopedSigner(portfolioPda),
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(classesIx);
+    return await this.sendScopedTransaction(transaction);
+  }
+
+  async setGlidePath(
+    startAllocations: { mint: PublicKey; targetPercentage: number }[],
+    endAllocations: { mint: PublicKey; targetPercentage: number }[],
+    targetDate: number, // unix seconds
+    stepBps: number
+  ): Promise<string> {
+    const program = await this.getProgram();
+    if (!program) throw new Error("Program not initialized");
+
+    const [portfolioPda] = await this.getPortfolioPda();
+
+    const glideIx = await program.methods
+      .setGlidePath(startAllocations, endAllocations, new anchor.BN(targetDate), stepBps)
+      .accounts({
+        portfolio: portfolioPda,
+        ...this.scopedSigner(portfolioPda),
+      })
+      .instruction();
+
+    const transaction = new Transaction().add(glideIx);
+    return await this.sendScopedTransaction(transaction);
+  }
+
+  // Flattened per-mint targets (class target x intra-class weight for classified allocations,
+  // interpolated now for glide path mints, whose stored target only moves in steps)
+  async getEffectiveTargets(): Promise<EffectiveTarget[]> {
+    const portfolioData = await this.getPortfolioData();
+    if (!portfolioData) return [];
+
+    const classes = portfolioData.assetClasses ?? [];
+    const glidePath = portfolioData.glidePath;
+    const glided = glidePath
+      ? glideTargets(glidePath.legs, glidePath.startTime.toNumber(), glidePath.targetDate.toNumber())
+      : [];
+    return portfolioData.allocations.map((a: any) => {
+      const leg = glidePath ? glidePath.legs.findIndex((l: any) => l.mint.equals(a.mint)) : -1;
+      return {
+        mint: a.mint,
+        symbol: a.symbol,
+        targetPercentage: leg >= 0 ? glided[leg] : a.targetPercentage,
+        assetClass: a.assetClass ? classes[a.assetClass.assetClass]?.name ?? null : null,
+      };
+    });
+  }
+
   async checkTransactionStatus(signature: string): Promise<boolean> {
     console.log("🔍 Checking transaction status for:", signature);
     
@@ -0,0 +1,90 @@
+import { PublicKey } from '@solana/web3.js';
+import BN from 'bn.js';
+
+// sloomo-core（Rust）の配分計算の TypeScript 移植。オンチェーンプログラムと同じ
+// 整数演算（切り捨て）で同じ数値を算出する
+// 対応: math::amount_to_bps / valuation::current_percentages / drift /
+//       hierarchy::effective_target / glide::interpolate_targets
+
+/** ベーシスポイントの分母（10000 = 100%） */
+const BPS_DENOMINATOR = 10000;
+
+/** デフォルトのリバランス閾値（ベーシスポイント、5%） */
+export const DEFAULT_DRIFT_THRESHOLD_BPS = 500;
+
+export interface AllocationInput {
+  mint: PublicKey;
+  currentAmount: BN;
+  targetPercentage: number; // basis points
+  band?: { minPercentage: number; maxPercentage: number } | null; // basis points
+}
+
+const totalValue = (allocations: AllocationInput[]) =>
+  allocations.reduce((total, a) => total.add(a.currentAmount), new BN(0));
+
+/** totalValue に占める amount の割合（ベーシスポイント、100%を超えうる） */
+export function amountToBps(amount: BN, total: BN): number {
+  if (total.isZero()) {
+    return 0;
+  }
+  return amount.muln(BPS_DENOMINATOR).div(total).toNumber();
+}
+
+/** 現在の配分率（パーセント） */
+export function currentPercentages(allocations: AllocationInput[]): number[] {
+  const total = totalValue(allocations);
+  return allocations.map((a) => amountToBps(a.currentAmount, total) / 100);
+}
+
+/** 目標配分からの乖離（パーセント） */
+export function driftPercentages(allocations: AllocationInput[]): number[] {
+  const total = totalValue(allocations);
+  return allocations.map(
+    (a) => Math.abs(amountToBps(a.currentAmount, total) - a.targetPercentage) / 100
+  );
+}
+
+/** リバランスが必要か（オンチェーンの needs_rebalancing と同一ロジック）
+ *  バンドを持つ配分はそのバンド、持たない配分は目標の±thresholdBps で判定する */
+export function needsRebalancing(
+  allocations: AllocationInput[],
+  thresholdBps = DEFAULT_DRIFT_THRESHOLD_BPS
+): boolean {
+  const total = totalValue(allocations);
+  if (total.isZero()) {
+    return false;
+  }
+  return allocations.some((target) => {
+    // 同じミントが重複する場合はオンチェーンと同じく最初の保有額を使う
+    const held = allocations.find((a) => a.mint.equals(target.mint))!;
+    const share = amountToBps(held.currentAmount, total);
+    return target.band
+      ? share < target.band.minPercentage || share > target.band.maxPercentage
+      : Math.abs(share - target.targetPercentage) > thresholdBps;
+  });
+}
+
+/** 資産クラスに属する配分の実効目標（クラス目標 × クラス内ウェイト、ベーシスポイント） */
+export function effectiveTarget(classTargetBps: number, weightBps: number): number {
+  return Math.floor((classTargetBps * weightBps) / BPS_DENOMINATOR);
+}
+
+/** グライドパス上の各ミントの現在の目標（オンチェーンの補間と同一、ベーシスポイント）
+ *  開始前は開始時の目標、目標日以降は最終目標。補間値は切り捨てる */
+export function glideTargets(
+  legs: { startPercentage: number; endPercentage: number }[],
+  startTime: number,
+  targetDate: number,
+  now: number = Math.floor(Date.now() / 1000)
+): number[] {
+  return legs.map(({ startPercentage, endPercentage }) => {
+    if (now >= targetDate) {
+      return endPercentage;
+    }
+    if (now <= startTime) {
+      return startPercentage;
+    }
+    const delta = (endPercentage - startPercentage) * (now - startTime);
+    return startPercentage + Math.floor(delta / (targetDate - startTime));
+  });
+}
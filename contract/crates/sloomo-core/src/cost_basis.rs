@@ -0,0 +1,32 @@
+//! Average cost basis and profit and loss of holdings
+
+use crate::math::PRICE_SCALE;
+
+/// Cost given up when `amount` of `held` units carrying `cost_basis` leave
+///
+/// Average cost, rounded down so the remainder stays with the units left;
+/// all of it leaves with the last unit.
+pub fn cost_of(cost_basis: u64, held: u64, amount: u64) -> u64 {
+    if amount >= held {
+        return cost_basis;
+    }
+    (cost_basis as u128 * amount as u128 / held as u128) as u64
+}
+
+/// Gain of `value` over `cost` (saturating at the i64 range)
+pub fn gain(value: u64, cost: u64) -> i64 {
+    (value as i128 - cost as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
+/// Value of `amount` at `price` (base units per unit, scaled by `PRICE_SCALE`)
+pub fn value_at(amount: u64, price: u64) -> u64 {
+    (amount as u128 * price as u128 / PRICE_SCALE as u128).min(u64::MAX as u128) as u64
+}
+
+/// Cost per unit held, scaled by `PRICE_SCALE` (0 when nothing is held)
+pub fn average_cost(cost_basis: u64, held: u64) -> u64 {
+    if held == 0 {
+        return 0;
+    }
+    (cost_basis as u128 * PRICE_SCALE as u128 / held as u128).min(u64::MAX as u128) as u64
+}
@@ -0,0 +1,84 @@
+use crate::math::{amount_to_bps, BPS_DENOMINATOR};
+use crate::valuation::Target;
+use crate::Mint;
+
+/// Default drift threshold (basis points)
+pub const DEFAULT_DRIFT_THRESHOLD_BPS: u16 = 500;
+
+/// Allowed range for one holding's share of the portfolio (basis points)
+///
+/// Holdings inside their band are left alone; a holding only trades once its
+/// share leaves the band.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Band {
+    /// Lowest share before the holding is bought back up (basis points)
+    pub min_percentage: u16,
+    /// Highest share before the holding is sold down (basis points)
+    pub max_percentage: u16,
+}
+
+impl Band {
+    /// Whether the band contains `target_percentage` and stays within 100%
+    pub fn is_valid_for(&self, target_percentage: u16) -> bool {
+        self.min_percentage <= target_percentage
+            && target_percentage <= self.max_percentage
+            && self.max_percentage as u64 <= BPS_DENOMINATOR
+    }
+
+    /// Whether a share of `percentage` lies within the band
+    pub fn contains(&self, percentage: u64) -> bool {
+        (self.min_percentage as u64..=self.max_percentage as u64).contains(&percentage)
+    }
+}
+
+/// Absolute drift of `amount` from `target_percentage` (basis points)
+pub fn drift_bps(amount: u64, total_value: u64, target_percentage: u16) -> u64 {
+    amount_to_bps(amount, total_value).abs_diff(target_percentage as u64)
+}
+
+/// Whether a share of `percentage` is inside `band`, or within
+/// `threshold_bps` either side of `target_percentage` without one
+pub fn within_band(percentage: u64, target_percentage: u16, band: Option<Band>, threshold_bps: u16) -> bool {
+    match band {
+        Some(band) => band.contains(percentage),
+        None => percentage.abs_diff(target_percentage as u64) <= threshold_bps as u64,
+    }
+}
+
+/// Determine if any target has left its band
+///
+/// Targets with their own band are checked against it; the others may drift
+/// up to `threshold_bps` either side of their target percentage.
+pub fn needs_rebalancing(
+    holdings: &[(Mint, u64)],
+    targets: &[Target],
+    total_value: u64,
+    threshold_bps: u16,
+) -> bool {
+    total_value > 0
+        && targets.iter().any(|target| {
+            let share = amount_to_bps(held_amount(holdings, &target.mint), total_value);
+            !within_band(share, target.target_percentage, target.band, threshold_bps)
+        })
+}
+
+/// Largest drift across all targets (basis points)
+///
+/// Targets for mints that are not held count their full percentage as drift.
+pub fn max_drift_bps(holdings: &[(Mint, u64)], targets: &[Target], total_value: u64) -> u64 {
+    targets
+        .iter()
+        .map(|target| {
+            drift_bps(held_amount(holdings, &target.mint), total_value, target.target_percentage)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Amount held of `mint`, 0 if it is not held
+fn held_amount(holdings: &[(Mint, u64)], mint: &Mint) -> u64 {
+    holdings
+        .iter()
+        .find(|(held, _)| held == mint)
+        .map_or(0, |(_, amount)| *amount)
+}
@@ -0,0 +1,28 @@
+use core::fmt;
+
+/// Errors raised by the portfolio math
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoreError {
+    /// Arithmetic overflow
+    MathOverflow,
+    /// Target percentages exceed 100%
+    AllocationOverflow,
+    /// Holdings cannot cover the requested amount
+    InsufficientLiquidity,
+    /// Asset class weights or memberships do not add up
+    InvalidHierarchy,
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreError::MathOverflow => f.write_str("Math overflow"),
+            CoreError::AllocationOverflow => f.write_str("Total allocation exceeds 100%"),
+            CoreError::InsufficientLiquidity => f.write_str("Holdings cannot cover the requested amount"),
+            CoreError::InvalidHierarchy => f.write_str("Asset class weights or memberships do not add up"),
+        }
+    }
+}
+
+/// Result type for the portfolio math
+pub type Result<T> = core::result::Result<T, CoreError>;
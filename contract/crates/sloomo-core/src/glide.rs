@@ -0,0 +1,48 @@
+//! Glide paths: targets that move linearly from a start allocation to an end
+//! allocation between two timestamps
+
+use alloc::vec::Vec;
+
+/// Target of one holding at `now` on the way from `start` (at `start_time`)
+/// to `end` (at `end_time`), rounded down (basis points)
+///
+/// Before `start_time` the start target applies and from `end_time` on the
+/// end target. Rounding down keeps interpolated targets from summing above
+/// their endpoints.
+pub fn interpolate_target(start: u16, end: u16, start_time: i64, end_time: i64, now: i64) -> u16 {
+    if now >= end_time {
+        return end;
+    }
+    if now <= start_time {
+        return start;
+    }
+    let elapsed = (now - start_time) as i128;
+    let duration = (end_time - start_time) as i128;
+    let delta = (end as i128 - start as i128) * elapsed;
+    (start as i128 + delta.div_euclid(duration)) as u16
+}
+
+/// Targets of every holding at `now` (see `interpolate_target`)
+pub fn interpolate_targets(
+    start: &[u16],
+    end: &[u16],
+    start_time: i64,
+    end_time: i64,
+    now: i64,
+) -> Vec<u16> {
+    start
+        .iter()
+        .zip(end)
+        .map(|(start, end)| interpolate_target(*start, *end, start_time, end_time, now))
+        .collect()
+}
+
+/// Largest move of any target between two sets of targets (basis points)
+pub fn max_target_move(previous: &[u16], current: &[u16]) -> u16 {
+    previous
+        .iter()
+        .zip(current)
+        .map(|(previous, current)| previous.abs_diff(*current))
+        .max()
+        .unwrap_or(0)
+}
@@ -0,0 +1,121 @@
+//! Savings goal progress and the periodic contribution needed to reach it
+
+use crate::math::{amount_to_bps, BPS_DENOMINATOR};
+
+/// Fixed-point scale for compounded growth factors
+const FACTOR_SCALE: u128 = 1_000_000_000_000;
+
+/// Growth recorded at a point in time (see `PerformanceSnapshot`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GrowthSample {
+    /// Record timestamp
+    pub timestamp: i64,
+    /// Growth since the previous sample (basis points)
+    pub growth_bps: i16,
+}
+
+/// Progress towards a goal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GoalProgress {
+    /// Current value as a share of the goal (basis points, at most 10000)
+    pub progress_bps: u16,
+    /// Amount still missing today (base units)
+    pub remaining_amount: u64,
+    /// Contribution intervals left until the target date
+    pub periods_remaining: u64,
+    /// Growth per interval expected from the history (basis points)
+    pub expected_growth_bps: i16,
+    /// Current value grown until the target date without contributions (base units)
+    pub projected_value: u64,
+    /// Contribution per interval that reaches the goal (base units; the whole
+    /// shortfall once the target date has passed)
+    pub required_contribution: u64,
+}
+
+/// Average growth per `interval` seconds across the history (basis points)
+///
+/// Growth is averaged over the time the samples span, not compounded, and
+/// clamped to -99.99%..+100%. Fewer than two samples give no growth.
+pub fn expected_growth_bps(history: &[GrowthSample], interval: i64) -> i16 {
+    let (Some(first), Some(last)) = (history.first(), history.last()) else {
+        return 0;
+    };
+    let elapsed = last.timestamp as i128 - first.timestamp as i128;
+    if elapsed <= 0 || interval <= 0 {
+        return 0;
+    }
+    // The first sample's growth predates the window
+    let growth: i128 = history[1..].iter().map(|s| s.growth_bps as i128).sum();
+    let per_interval = growth * interval as i128 / elapsed;
+    per_interval.clamp(1 - BPS_DENOMINATOR as i128, BPS_DENOMINATOR as i128) as i16
+}
+
+/// `(1 + growth)^periods` in `FACTOR_SCALE` fixed point (saturating)
+fn growth_factor(growth_bps: i16, periods: u64) -> u128 {
+    let mut base = FACTOR_SCALE * (BPS_DENOMINATOR as i128 + growth_bps as i128) as u128
+        / BPS_DENOMINATOR as u128;
+    let mut factor = FACTOR_SCALE;
+    let mut exponent = periods;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            factor = factor.saturating_mul(base) / FACTOR_SCALE;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base.saturating_mul(base) / FACTOR_SCALE;
+        }
+    }
+    factor
+}
+
+/// Progress of `current_value` towards `target_amount` by `target_date`, with
+/// contributions made at the end of every `interval` seconds and the value
+/// growing by `expected_growth_bps` per interval
+pub fn goal_progress(
+    current_value: u64,
+    target_amount: u64,
+    now: i64,
+    target_date: i64,
+    interval: i64,
+    expected_growth_bps: i16,
+) -> GoalProgress {
+    let progress_bps = if target_amount == 0 {
+        BPS_DENOMINATOR
+    } else {
+        amount_to_bps(current_value, target_amount).min(BPS_DENOMINATOR)
+    } as u16;
+
+    let periods_remaining = if interval <= 0 || now >= target_date {
+        0
+    } else {
+        (target_date.abs_diff(now)).div_ceil(interval as u64)
+    };
+
+    let factor = growth_factor(expected_growth_bps, periods_remaining);
+    let projected_value = (current_value as u128).saturating_mul(factor) / FACTOR_SCALE;
+    let projected_value = projected_value.min(u64::MAX as u128) as u64;
+    let shortfall = target_amount.saturating_sub(projected_value);
+
+    // Value at the target date of contributing 1 per interval (FACTOR_SCALE fixed point)
+    let annuity = match expected_growth_bps {
+        0 => periods_remaining as u128 * FACTOR_SCALE,
+        growth => {
+            factor.abs_diff(FACTOR_SCALE).saturating_mul(BPS_DENOMINATOR as u128)
+                / growth.unsigned_abs() as u128
+        }
+    };
+    let required_contribution = if shortfall == 0 || periods_remaining == 0 || annuity == 0 {
+        shortfall
+    } else {
+        (shortfall as u128 * FACTOR_SCALE).div_ceil(annuity).min(u64::MAX as u128) as u64
+    };
+
+    GoalProgress {
+        progress_bps,
+        remaining_amount: target_amount.saturating_sub(current_value),
+        periods_remaining,
+        expected_growth_bps,
+        projected_value,
+        required_contribution,
+    }
+}
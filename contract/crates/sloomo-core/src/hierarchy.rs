@@ -0,0 +1,135 @@
+//! Two-level targets: asset classes hold a share of the portfolio, split
+//! across their member mints by intra-class weights
+
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::drift::{within_band, Band};
+use crate::error::{CoreError, Result};
+use crate::math::{amount_to_bps, sum_target_percentages, BPS_DENOMINATOR};
+use crate::planner::PlannerHolding;
+
+/// Asset class (e.g. equities, stablecoins)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssetClass {
+    /// Share of the portfolio (basis points)
+    pub target_percentage: u16,
+    /// Allowed range of the class share, `None` for the drift threshold either side
+    pub band: Option<Band>,
+}
+
+/// Membership of one holding in an asset class
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClassMember {
+    /// Index of the asset class
+    pub class: u8,
+    /// Share of the class (basis points; members of a class sum to 10000)
+    pub weight: u16,
+}
+
+/// Flattened target of a member: its weight of the class target (basis points)
+pub fn effective_target(class_target: u16, weight: u16) -> u16 {
+    (class_target as u64 * weight as u64 / BPS_DENOMINATOR) as u16
+}
+
+/// Check that both levels sum correctly
+///
+/// Class targets may not exceed 100% in total, every class needs members,
+/// each class's member weights must sum to exactly 100%, and class bands
+/// must contain their target.
+pub fn validate_hierarchy(classes: &[AssetClass], members: &[Option<ClassMember>]) -> Result<()> {
+    sum_target_percentages(classes.iter().map(|c| c.target_percentage))
+        .ok()
+        .filter(|total| *total as u64 <= BPS_DENOMINATOR)
+        .ok_or(CoreError::AllocationOverflow)?;
+
+    let mut weights = vec![0u64; classes.len()];
+    for member in members.iter().flatten() {
+        let weight = weights
+            .get_mut(member.class as usize)
+            .ok_or(CoreError::InvalidHierarchy)?;
+        *weight += member.weight as u64;
+    }
+
+    for (class, weight) in classes.iter().zip(&weights) {
+        if *weight != BPS_DENOMINATOR {
+            return Err(CoreError::InvalidHierarchy);
+        }
+        if class.band.is_some_and(|band| !band.is_valid_for(class.target_percentage)) {
+            return Err(CoreError::InvalidHierarchy);
+        }
+    }
+    Ok(())
+}
+
+/// Value held in each class
+pub fn class_values(values: &[u64], members: &[Option<ClassMember>], class_count: usize) -> Vec<u64> {
+    let mut totals = vec![0u64; class_count];
+    for (value, member) in values.iter().zip(members) {
+        if let Some(total) = member.and_then(|m| totals.get_mut(m.class as usize)) {
+            *total = total.saturating_add(*value);
+        }
+    }
+    totals
+}
+
+/// Whether each class has left its band (`threshold_bps` either side of its
+/// target for classes without one)
+pub fn out_of_band_classes(
+    values: &[u64],
+    members: &[Option<ClassMember>],
+    classes: &[AssetClass],
+    total_value: u64,
+    threshold_bps: u16,
+) -> Vec<bool> {
+    if total_value == 0 {
+        return vec![false; classes.len()];
+    }
+    classes
+        .iter()
+        .zip(class_values(values, members, classes.len()))
+        .map(|(class, value)| {
+            let share = amount_to_bps(value, total_value);
+            !within_band(share, class.target_percentage, class.band, threshold_bps)
+        })
+        .collect()
+}
+
+/// Determine if any asset class has left its band
+pub fn needs_class_rebalancing(
+    values: &[u64],
+    members: &[Option<ClassMember>],
+    classes: &[AssetClass],
+    total_value: u64,
+    threshold_bps: u16,
+) -> bool {
+    out_of_band_classes(values, members, classes, total_value, threshold_bps)
+        .into_iter()
+        .any(|out| out)
+}
+
+/// Pin every member of an out-of-band class to its target
+///
+/// Members may each sit inside their own band while the class as a whole
+/// has drifted (every equity slightly overweight). Narrowing their bands to
+/// the target makes the planner trade the class back. Returns the number of
+/// classes pinned.
+pub fn pin_out_of_band_classes(
+    holdings: &mut [PlannerHolding],
+    members: &[Option<ClassMember>],
+    classes: &[AssetClass],
+    threshold_bps: u16,
+) -> Result<usize> {
+    let values: Vec<u64> = holdings.iter().map(|h| h.value).collect();
+    let total_value = crate::valuation::total_value(values.iter().copied())?;
+    let out = out_of_band_classes(&values, members, classes, total_value, threshold_bps);
+
+    for (holding, member) in holdings.iter_mut().zip(members) {
+        if member.is_some_and(|m| out.get(m.class as usize).copied().unwrap_or(false)) {
+            holding.band = Some(Band {
+                min_percentage: holding.target_percentage,
+                max_percentage: holding.target_percentage,
+            });
+        }
+    }
+    Ok(out.iter().filter(|out| **out).count())
+}
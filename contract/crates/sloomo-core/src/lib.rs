@@ -0,0 +1,25 @@
+//! Portfolio math shared by the Sloomo program and off-chain tools
+//!
+//! Everything here works on plain structs with mints as raw 32-byte keys, so
+//! the crate builds without Anchor and in `no_std`. The app mirrors the drift,
+//! band and glide-path functions in `app/src/utils/portfolioMath.ts`.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod error;
+pub mod math;
+pub mod valuation;
+pub mod drift;
+pub mod planner;
+pub mod hierarchy;
+pub mod glide;
+pub mod goal;
+pub mod cost_basis;
+pub mod performance;
+
+pub use error::*;
+
+/// Token mint address (raw public key bytes)
+pub type Mint = [u8; 32];
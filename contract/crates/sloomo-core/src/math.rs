@@ -0,0 +1,74 @@
+use crate::error::{CoreError, Result};
+
+/// Basis points denominator (10000 = 100%)
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+/// Share of `total_value` represented by `amount` (basis points)
+///
+/// Not truncated to u16: an allocation larger than `total_value` yields a
+/// value above 10000 instead of wrapping around.
+pub fn amount_to_bps(amount: u64, total_value: u64) -> u64 {
+    if total_value == 0 {
+        return 0;
+    }
+    // amount * 10000 < 2^78 always fits in u128; only the quotient is clamped
+    (amount as u128 * BPS_DENOMINATOR as u128 / total_value as u128).min(u64::MAX as u128) as u64
+}
+
+/// Fixed-point scale of swap prices (`PRICE_SCALE` = one unit out per unit in)
+pub const PRICE_SCALE: u64 = 1_000_000_000;
+
+/// Units received per unit spent, scaled by `PRICE_SCALE` (0 when nothing was spent)
+pub fn effective_price(amount_in: u64, amount_out: u64) -> u64 {
+    if amount_in == 0 {
+        return 0;
+    }
+    (amount_out as u128 * PRICE_SCALE as u128 / amount_in as u128).min(u64::MAX as u128) as u64
+}
+
+/// Shortfall of `amount_out` against `amount_in` (basis points, clamped to -100%..+100%)
+///
+/// Legs are planned in base units, so a swap without slippage returns what it
+/// spends. Negative values mean more was received than spent.
+pub fn realized_slippage_bps(amount_in: u64, amount_out: u64) -> i16 {
+    if amount_in == 0 {
+        return 0;
+    }
+    let slippage = (amount_in as i128 - amount_out as i128) * BPS_DENOMINATOR as i128 / amount_in as i128;
+    slippage.clamp(-(BPS_DENOMINATOR as i128), BPS_DENOMINATOR as i128) as i16
+}
+
+/// Amount corresponding to `bps` basis points of `total_value`
+pub fn bps_of(total_value: u64, bps: u16) -> Result<u64> {
+    let amount = total_value as u128 * bps as u128 / BPS_DENOMINATOR as u128;
+    u64::try_from(amount).map_err(|_| CoreError::MathOverflow)
+}
+
+/// Sum target percentages without wrapping
+pub fn sum_target_percentages(percentages: impl IntoIterator<Item = u16>) -> Result<u16> {
+    percentages
+        .into_iter()
+        .try_fold(0u16, |acc, val| acc.checked_add(val))
+        .ok_or(CoreError::AllocationOverflow)
+}
+
+/// Re-express `amount` of a token with `from_decimals` in units of `to_decimals`
+///
+/// Scaling down truncates toward zero.
+pub fn rescale_decimals(amount: u64, from_decimals: u8, to_decimals: u8) -> Result<u64> {
+    let scaled = if to_decimals >= from_decimals {
+        10u128
+            .checked_pow((to_decimals - from_decimals) as u32)
+            .and_then(|factor| (amount as u128).checked_mul(factor))
+    } else {
+        // A divisor beyond u128 means the amount rounds to zero
+        Some(
+            10u128
+                .checked_pow((from_decimals - to_decimals) as u32)
+                .map_or(0, |factor| amount as u128 / factor),
+        )
+    };
+    scaled
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or(CoreError::MathOverflow)
+}
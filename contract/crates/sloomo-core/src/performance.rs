@@ -0,0 +1,11 @@
+use crate::math::BPS_DENOMINATOR;
+
+/// Growth between two values (basis points, clamped to -100%..+100%)
+pub fn growth_bps(previous: u64, current: u64) -> i16 {
+    if previous == 0 {
+        return 0;
+    }
+    let growth = (current as i128 - previous as i128) * BPS_DENOMINATOR as i128 / previous as i128;
+    // Clamp before narrowing so large moves saturate instead of wrapping
+    growth.clamp(-(BPS_DENOMINATOR as i128), BPS_DENOMINATOR as i128) as i16
+}
@@ -0,0 +1,584 @@
+use alloc::vec::Vec;
+use crate::drift::{Band, DEFAULT_DRIFT_THRESHOLD_BPS};
+use crate::error::{CoreError, Result};
+use crate::math::{amount_to_bps, bps_of, BPS_DENOMINATOR};
+use crate::valuation::Target;
+use crate::Mint;
+
+/// Default minimum leg size (base units, 0.01 USDC)
+pub const DEFAULT_MIN_TRADE_VALUE: u64 = 10_000;
+/// Default maximum number of swap legs per transaction
+pub const DEFAULT_MAX_LEGS: u8 = 3;
+/// Default estimated swap fee (basis points)
+pub const DEFAULT_FEE_BPS: u16 = 25;
+/// Default slippage tolerance (basis points)
+pub const DEFAULT_SLIPPAGE_BPS: u16 = 50;
+
+/// Holding as seen by the planner
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannerHolding {
+    /// Token mint address
+    pub mint: Mint,
+    /// Current value (base units)
+    pub value: u64,
+    /// Target allocation percentage (basis points)
+    pub target_percentage: u16,
+    /// Allowed range around the target, `None` for `drift_threshold_bps` either side
+    pub band: Option<Band>,
+}
+
+/// How far an asset that left its band is traded back
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BandRestore {
+    /// Back to the nearest edge of its band (smallest trade)
+    #[default]
+    Edge,
+    /// All the way back to its target percentage
+    Target,
+}
+
+/// Planner configuration
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannerConfig {
+    /// Allowed drift from target for holdings without their own band (basis points)
+    pub drift_threshold_bps: u16,
+    /// How far assets outside their band are traded back
+    pub restore: BandRestore,
+    /// Legs below this value are skipped (base units)
+    pub min_trade_value: u64,
+    /// Maximum number of legs in one plan
+    pub max_legs: u8,
+    /// Estimated swap fee per leg (basis points)
+    pub fee_bps: u16,
+    /// Estimated slippage per leg (basis points)
+    pub slippage_bps: u16,
+}
+
+impl Default for PlannerConfig {
+    fn default() -> Self {
+        Self {
+            drift_threshold_bps: DEFAULT_DRIFT_THRESHOLD_BPS,
+            restore: BandRestore::default(),
+            min_trade_value: DEFAULT_MIN_TRADE_VALUE,
+            max_legs: DEFAULT_MAX_LEGS,
+            fee_bps: DEFAULT_FEE_BPS,
+            slippage_bps: DEFAULT_SLIPPAGE_BPS,
+        }
+    }
+}
+
+/// Single direct swap leg
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedLeg {
+    /// Mint sold
+    pub from_mint: Mint,
+    /// Mint bought
+    pub to_mint: Mint,
+    /// Value moved (base units)
+    pub amount: u64,
+    /// Estimated fee + slippage (base units)
+    pub estimated_cost: u64,
+}
+
+/// Rebalance plan
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct RebalancePlan {
+    /// Legs to execute, largest first
+    pub legs: Vec<PlannedLeg>,
+    /// Total estimated fee + slippage (base units)
+    pub estimated_cost: u64,
+    /// Largest drift from target after the plan (basis points)
+    pub max_residual_drift_bps: u64,
+    /// Whether every asset lands within its band
+    pub within_band: bool,
+}
+
+/// Plan the cheapest set of direct swaps that brings every asset within its band
+///
+/// Only assets outside their band must trade; each is moved back to the band
+/// edge or to its target depending on `config.restore`. Overweight assets fund
+/// underweight ones directly instead of routing through the base asset, and
+/// value that has no counterparty (targets summing below 100%) is swapped to
+/// or from `base_mint`.
+pub fn plan_rebalance(
+    holdings: &[PlannerHolding],
+    base_mint: Mint,
+    config: &PlannerConfig,
+) -> Result<RebalancePlan> {
+    let total_value = holdings
+        .iter()
+        .try_fold(0u64, |acc, h| acc.checked_add(h.value))
+        .ok_or(CoreError::MathOverflow)?;
+    if total_value == 0 {
+        return Ok(RebalancePlan { within_band: true, ..Default::default() });
+    }
+
+    let bands = band_bounds(holdings, total_value, config)?;
+
+    // Signed deviation from target for each holding
+    let mut deviations = Vec::with_capacity(holdings.len());
+    for holding in holdings {
+        let target = bps_of(total_value, holding.target_percentage)?;
+        deviations.push(holding.value as i128 - target as i128);
+    }
+
+    // Mandatory flows: assets outside their band go back to the edge or target.
+    // What is left of a deviation after its mandatory flow is the room that
+    // asset has to act as a counterparty without crossing its target.
+    let mut sells: Vec<(Mint, u64)> = Vec::new();
+    let mut buys: Vec<(Mint, u64)> = Vec::new();
+    let mut room = Vec::with_capacity(holdings.len());
+    for ((holding, deviation), (lower, upper)) in holdings.iter().zip(&deviations).zip(&bands) {
+        let restore_to = match config.restore {
+            BandRestore::Edge if *deviation > *upper => *upper,
+            BandRestore::Edge if *deviation < *lower => *lower,
+            _ => 0,
+        };
+        let excess = if *deviation > *upper || *deviation < *lower {
+            deviation.abs_diff(restore_to).min(u64::MAX as u128) as u64
+        } else {
+            0
+        };
+        room.push(deviation.unsigned_abs().saturating_sub(excess as u128).min(u64::MAX as u128) as u64);
+        if excess == 0 {
+            continue;
+        }
+        if *deviation > 0 {
+            sells.push((holding.mint, excess));
+        } else {
+            buys.push((holding.mint, excess));
+        }
+    }
+
+    // Balance the two sides with assets that are on the right side of their
+    // target, so netting never pushes another asset past its target
+    let required_sells: u128 = sells.iter().map(|(_, a)| *a as u128).sum();
+    let required_buys: u128 = buys.iter().map(|(_, a)| *a as u128).sum();
+    if required_sells > required_buys {
+        let shortfall = (required_sells - required_buys) as u64;
+        fill_counterparties(holdings, &deviations, &room, false, shortfall, &mut buys, base_mint);
+    } else if required_buys > required_sells {
+        let shortfall = (required_buys - required_sells) as u64;
+        fill_counterparties(holdings, &deviations, &room, true, shortfall, &mut sells, base_mint);
+    }
+
+    // Greedy matching of the largest flows keeps the leg count at most sells + buys - 1
+    sort_flows(&mut sells);
+    sort_flows(&mut buys);
+    let mut legs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < sells.len() && j < buys.len() {
+        let amount = sells[i].1.min(buys[j].1);
+        if amount > 0 && sells[i].0 != buys[j].0 {
+            legs.push(PlannedLeg {
+                from_mint: sells[i].0,
+                to_mint: buys[j].0,
+                amount,
+                estimated_cost: leg_cost(amount, config),
+            });
+        }
+        sells[i].1 -= amount;
+        buys[j].1 -= amount;
+        if sells[i].1 == 0 {
+            i += 1;
+        }
+        if buys[j].1 == 0 {
+            j += 1;
+        }
+    }
+
+    // Drop dust, then keep the largest legs that fit in one transaction
+    legs.retain(|leg| leg.amount >= config.min_trade_value);
+    legs.sort_by(|a, b| b.amount.cmp(&a.amount).then(a.from_mint.cmp(&b.from_mint)));
+    legs.truncate(config.max_legs as usize);
+
+    let estimated_cost = legs
+        .iter()
+        .try_fold(0u64, |acc, leg| acc.checked_add(leg.estimated_cost))
+        .ok_or(CoreError::MathOverflow)?;
+
+    let (max_residual_drift_bps, within_band) =
+        residual_drift(holdings, &deviations, &bands, &legs, total_value);
+
+    Ok(RebalancePlan {
+        legs,
+        estimated_cost,
+        max_residual_drift_bps,
+        within_band,
+    })
+}
+
+/// Route a deposit of `amount` in `base_mint` to the assets furthest below target
+///
+/// `holdings` are valued before the deposit. Underweight assets are bought up
+/// to their target in the post-deposit portfolio, largest shortfall first,
+/// until the deposit runs out; the rest stays in the base asset. The plan only
+/// buys, so new money closes drift without any sell legs.
+pub fn plan_cash_flow(
+    holdings: &[PlannerHolding],
+    base_mint: Mint,
+    amount: u64,
+    config: &PlannerConfig,
+) -> Result<RebalancePlan> {
+    let total_value = holdings
+        .iter()
+        .try_fold(amount, |acc, h| acc.checked_add(h.value))
+        .ok_or(CoreError::MathOverflow)?;
+    if total_value == 0 {
+        return Ok(RebalancePlan { within_band: true, ..Default::default() });
+    }
+
+    let bands = band_bounds(holdings, total_value, config)?;
+
+    // Deviation from target once the deposit has landed in the base asset
+    let mut deviations = Vec::with_capacity(holdings.len());
+    for holding in holdings {
+        let target = bps_of(total_value, holding.target_percentage)?;
+        let mut value = holding.value as i128;
+        if holding.mint == base_mint {
+            value += amount as i128;
+        }
+        deviations.push(value - target as i128);
+    }
+
+    let mut shortfalls: Vec<(Mint, u64)> = holdings
+        .iter()
+        .zip(&deviations)
+        .filter(|(h, d)| h.mint != base_mint && **d < 0)
+        .map(|(h, d)| (h.mint, d.unsigned_abs().min(u64::MAX as u128) as u64))
+        .collect();
+    sort_flows(&mut shortfalls);
+
+    let mut remaining = amount;
+    let mut legs = Vec::new();
+    for (mint, shortfall) in shortfalls {
+        if remaining == 0 || legs.len() >= config.max_legs as usize {
+            break;
+        }
+        let buy = shortfall.min(remaining);
+        if buy < config.min_trade_value {
+            continue;
+        }
+        legs.push(PlannedLeg {
+            from_mint: base_mint,
+            to_mint: mint,
+            amount: buy,
+            estimated_cost: leg_cost(buy, config),
+        });
+        remaining -= buy;
+    }
+
+    let estimated_cost = legs
+        .iter()
+        .try_fold(0u64, |acc, leg| acc.checked_add(leg.estimated_cost))
+        .ok_or(CoreError::MathOverflow)?;
+    let (max_residual_drift_bps, within_band) =
+        residual_drift(holdings, &deviations, &bands, &legs, total_value);
+
+    Ok(RebalancePlan {
+        legs,
+        estimated_cost,
+        max_residual_drift_bps,
+        within_band,
+    })
+}
+
+/// Which holdings fund a withdrawal paid out in the base asset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiquidationOrder {
+    /// Every holding sells the same share of its value
+    Proportional,
+    /// Holdings above target after the withdrawal sell their excess first,
+    /// largest first; any remainder is sold proportionally
+    OverweightFirst,
+}
+
+/// Sell legs into `base_mint` that fund a withdrawal of `amount` base units
+///
+/// Base asset already held is paid out first. The shortfall is grossed up by
+/// the configured fee and slippage so the legs still cover it after costs,
+/// then split across the other holdings according to `order`. Legs are not
+/// trimmed by `min_trade_value` or `max_legs`: each one caps what a holding
+/// may sell, and dropping one would leave the withdrawal short.
+pub fn plan_liquidation(
+    holdings: &[PlannerHolding],
+    base_mint: Mint,
+    amount: u64,
+    order: LiquidationOrder,
+    config: &PlannerConfig,
+) -> Result<Vec<PlannedLeg>> {
+    let total_value = holdings
+        .iter()
+        .try_fold(0u64, |acc, h| acc.checked_add(h.value))
+        .ok_or(CoreError::MathOverflow)?;
+    let base_value: u64 = holdings
+        .iter()
+        .filter(|h| h.mint == base_mint)
+        .map(|h| h.value)
+        .sum();
+    let shortfall = amount.saturating_sub(base_value);
+    if shortfall == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Sell enough that the shortfall survives fees and slippage
+    let cost_bps = config.fee_bps as u64 + config.slippage_bps as u64;
+    if cost_bps >= BPS_DENOMINATOR {
+        return Err(CoreError::InsufficientLiquidity);
+    }
+    let needed = (shortfall as u128 * BPS_DENOMINATOR as u128)
+        .div_ceil((BPS_DENOMINATOR - cost_bps) as u128);
+    let needed = u64::try_from(needed).map_err(|_| CoreError::MathOverflow)?;
+
+    let mut capacity: Vec<(Mint, u64)> = holdings
+        .iter()
+        .filter(|h| h.mint != base_mint && h.value > 0)
+        .map(|h| (h.mint, h.value))
+        .collect();
+    let available: u128 = capacity.iter().map(|(_, v)| *v as u128).sum();
+    if needed as u128 > available {
+        return Err(CoreError::InsufficientLiquidity);
+    }
+
+    let mut sells: Vec<(Mint, u64)> = Vec::new();
+    let mut remaining = needed;
+    if order == LiquidationOrder::OverweightFirst {
+        // Excess over target in the portfolio left after the withdrawal
+        let post_total = total_value.saturating_sub(amount);
+        let mut excesses = Vec::new();
+        for holding in holdings.iter().filter(|h| h.mint != base_mint) {
+            let target = bps_of(post_total, holding.target_percentage)?;
+            if holding.value > target {
+                excesses.push((holding.mint, holding.value - target));
+            }
+        }
+        sort_flows(&mut excesses);
+
+        for (mint, excess) in excesses {
+            if remaining == 0 {
+                break;
+            }
+            let sell = excess.min(remaining);
+            add_flow(&mut sells, mint, sell);
+            if let Some((_, left)) = capacity.iter_mut().find(|(m, _)| *m == mint) {
+                *left -= sell;
+            }
+            remaining -= sell;
+        }
+    }
+    for (mint, sell) in pro_rata(&capacity, remaining) {
+        add_flow(&mut sells, mint, sell);
+    }
+    sort_flows(&mut sells);
+
+    Ok(sells
+        .into_iter()
+        .map(|(mint, amount)| PlannedLeg {
+            from_mint: mint,
+            to_mint: base_mint,
+            amount,
+            estimated_cost: leg_cost(amount, config),
+        })
+        .collect())
+}
+
+/// Split `amount` across `values` in proportion to each, never taking more
+/// than a value holds; rounding remainders go to the largest values first
+fn pro_rata(values: &[(Mint, u64)], amount: u64) -> Vec<(Mint, u64)> {
+    let total: u128 = values.iter().map(|(_, v)| *v as u128).sum();
+    if amount == 0 || total == 0 {
+        return Vec::new();
+    }
+
+    let mut shares: Vec<(Mint, u64)> = values
+        .iter()
+        .map(|(mint, value)| (*mint, (*value as u128 * amount as u128 / total) as u64))
+        .collect();
+    let mut remainder = amount - shares.iter().map(|(_, s)| *s).sum::<u64>();
+
+    let mut largest: Vec<usize> = (0..values.len()).collect();
+    largest.sort_by(|a, b| values[*b].1.cmp(&values[*a].1).then(values[*a].0.cmp(&values[*b].0)));
+    for i in largest {
+        if remainder == 0 {
+            break;
+        }
+        if shares[i].1 < values[i].1 {
+            shares[i].1 += 1;
+            remainder -= 1;
+        }
+    }
+
+    shares.retain(|(_, share)| *share > 0);
+    shares
+}
+
+/// Band of each holding as (lower, upper) deviations from its target amount
+///
+/// Explicit bands are widened to include the target so a malformed band can
+/// never ask for a trade past it.
+fn band_bounds(
+    holdings: &[PlannerHolding],
+    total_value: u64,
+    config: &PlannerConfig,
+) -> Result<Vec<(i128, i128)>> {
+    let default_width = bps_of(total_value, config.drift_threshold_bps)? as i128;
+    holdings
+        .iter()
+        .map(|holding| {
+            let Some(band) = holding.band else {
+                return Ok((-default_width, default_width));
+            };
+            let target = bps_of(total_value, holding.target_percentage)? as i128;
+            let lower = bps_of(total_value, band.min_percentage)? as i128 - target;
+            let upper = bps_of(total_value, band.max_percentage)? as i128 - target;
+            Ok((lower.min(0), upper.max(0)))
+        })
+        .collect()
+}
+
+/// Largest drift from target after applying `legs` (basis points), and
+/// whether every holding ends up within its band
+fn residual_drift(
+    holdings: &[PlannerHolding],
+    deviations: &[i128],
+    bands: &[(i128, i128)],
+    legs: &[PlannedLeg],
+    total_value: u64,
+) -> (u64, bool) {
+    let mut max_residual_drift_bps = 0;
+    let mut within_band = true;
+    for ((holding, deviation), (lower, upper)) in holdings.iter().zip(deviations).zip(bands) {
+        let mut residual = *deviation;
+        for leg in legs {
+            if leg.from_mint == holding.mint {
+                residual -= leg.amount as i128;
+            }
+            if leg.to_mint == holding.mint {
+                residual += leg.amount as i128;
+            }
+        }
+        let drift = amount_to_bps(residual.unsigned_abs().min(u64::MAX as u128) as u64, total_value);
+        max_residual_drift_bps = max_residual_drift_bps.max(drift);
+        within_band &= (*lower..=*upper).contains(&residual);
+    }
+    (max_residual_drift_bps, within_band)
+}
+
+/// Estimated fee + slippage for one leg
+pub fn leg_cost(amount: u64, config: &PlannerConfig) -> u64 {
+    let cost_bps = config.fee_bps as u128 + config.slippage_bps as u128;
+    (amount as u128 * cost_bps / BPS_DENOMINATOR as u128).min(u64::MAX as u128) as u64
+}
+
+/// Cover `shortfall` from assets on the given side of their target, moving
+/// each at most back to its target, largest deviation first, and fall back
+/// to the base asset for the remainder
+fn fill_counterparties(
+    holdings: &[PlannerHolding],
+    deviations: &[i128],
+    room: &[u64],
+    overweight: bool,
+    mut shortfall: u64,
+    flows: &mut Vec<(Mint, u64)>,
+    base_mint: Mint,
+) {
+    // `room` already excludes the mandatory flow of out-of-band assets
+    let mut candidates: Vec<(Mint, u64)> = holdings
+        .iter()
+        .zip(deviations)
+        .zip(room)
+        .filter(|((_, d), _)| if overweight { **d > 0 } else { **d < 0 })
+        .map(|((h, _), room)| (h.mint, *room))
+        .collect();
+    sort_flows(&mut candidates);
+
+    for (mint, capacity) in candidates {
+        if shortfall == 0 {
+            break;
+        }
+        let amount = capacity.min(shortfall);
+        add_flow(flows, mint, amount);
+        shortfall -= amount;
+    }
+
+    if shortfall > 0 {
+        add_flow(flows, base_mint, shortfall);
+    }
+}
+
+/// Add `amount` to the flow for `mint`
+fn add_flow(flows: &mut Vec<(Mint, u64)>, mint: Mint, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    match flows.iter_mut().find(|(m, _)| *m == mint) {
+        Some((_, existing)) => *existing = existing.saturating_add(amount),
+        None => flows.push((mint, amount)),
+    }
+}
+
+/// Largest flow first, ties broken by mint for deterministic plans
+fn sort_flows(flows: &mut [(Mint, u64)]) {
+    flows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+}
+
+/// Swap direction relative to the base asset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapDirection {
+    /// Base asset -> target mint
+    Buy,
+    /// Target mint -> base asset
+    Sell,
+}
+
+/// Swap routed through the base asset
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoutedSwap {
+    /// Swap direction
+    pub direction: SwapDirection,
+    /// Mint sold
+    pub from_mint: Mint,
+    /// Mint bought
+    pub to_mint: Mint,
+    /// Amount moved
+    pub amount: u64,
+}
+
+/// Swaps that bring every target exactly to its target amount, each routed
+/// through `base_mint` (one sell or buy per target, no netting)
+pub fn route_through_base(
+    holdings: &[(Mint, u64)],
+    targets: &[Target],
+    total_value: u64,
+    base_mint: Mint,
+) -> Result<Vec<RoutedSwap>> {
+    let mut swaps = Vec::new();
+
+    for target in targets {
+        let target_amount = bps_of(total_value, target.target_percentage)?;
+        let current_amount = holdings
+            .iter()
+            .find(|(mint, _)| *mint == target.mint)
+            .map_or(0, |(_, amount)| *amount);
+
+        if current_amount > target_amount {
+            // Sale required
+            swaps.push(RoutedSwap {
+                direction: SwapDirection::Sell,
+                from_mint: target.mint,
+                to_mint: base_mint,
+                amount: current_amount - target_amount,
+            });
+        } else if current_amount < target_amount {
+            // Purchase required
+            swaps.push(RoutedSwap {
+                direction: SwapDirection::Buy,
+                from_mint: base_mint,
+                to_mint: target.mint,
+                amount: target_amount - current_amount,
+            });
+        }
+    }
+
+    Ok(swaps)
+}
@@ -0,0 +1,38 @@
+use alloc::vec::Vec;
+use crate::drift::Band;
+use crate::error::{CoreError, Result};
+use crate::math::{amount_to_bps, bps_of};
+use crate::Mint;
+
+/// Requested target for one mint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Target {
+    /// Token mint address
+    pub mint: Mint,
+    /// Target allocation percentage (basis points)
+    pub target_percentage: u16,
+    /// Allowed range around the target, `None` for the default drift threshold
+    pub band: Option<Band>,
+}
+
+/// Total value of a set of holdings
+pub fn total_value(amounts: impl IntoIterator<Item = u64>) -> Result<u64> {
+    amounts
+        .into_iter()
+        .try_fold(0u64, |acc, val| acc.checked_add(val))
+        .ok_or(CoreError::MathOverflow)
+}
+
+/// Current percentage of each holding (basis points)
+pub fn current_percentages(amounts: &[u64]) -> Result<Vec<u64>> {
+    let total = total_value(amounts.iter().copied())?;
+    Ok(amounts.iter().map(|a| amount_to_bps(*a, total)).collect())
+}
+
+/// Amount each target should hold out of `total_value`
+pub fn target_amounts(targets: &[Target], total_value: u64) -> Result<Vec<(Mint, u64)>> {
+    targets
+        .iter()
+        .map(|t| Ok((t.mint, bps_of(total_value, t.target_percentage)?)))
+        .collect()
+}
@@ -0,0 +1,69 @@
+//! Tests for average cost basis and profit and loss
+
+use proptest::prelude::*;
+use sloomo_core::cost_basis::{average_cost, cost_of, gain, value_at};
+use sloomo_core::math::PRICE_SCALE;
+
+proptest! {
+    #[test]
+    fn selling_in_parts_releases_the_whole_basis(
+        cost_basis in any::<u64>(),
+        held in 1u64..=u64::MAX,
+        parts in prop::collection::vec(1u64..=u64::MAX, 1..8),
+    ) {
+        let (mut basis, mut remaining) = (cost_basis, held);
+        for part in parts {
+            let amount = part % remaining + 1;
+            let cost = cost_of(basis, remaining, amount);
+            prop_assert!(cost <= basis);
+            basis -= cost;
+            remaining -= amount.min(remaining);
+            if remaining == 0 {
+                break;
+            }
+        }
+        if remaining > 0 {
+            // The last unit takes what is left
+            prop_assert_eq!(cost_of(basis, remaining, remaining), basis);
+        } else {
+            prop_assert_eq!(basis, 0);
+        }
+    }
+
+    #[test]
+    fn cost_never_exceeds_the_share_sold(
+        cost_basis in any::<u64>(),
+        held in 1u64..=u64::MAX,
+        amount in any::<u64>(),
+    ) {
+        let cost = cost_of(cost_basis, held, amount);
+        prop_assert!(cost as u128 * held as u128 <= cost_basis as u128 * amount.min(held) as u128);
+    }
+}
+
+#[test]
+fn average_cost_is_released_pro_rata() {
+    // 1_000 units bought for 1_200
+    assert_eq!(cost_of(1_200, 1_000, 250), 300);
+    assert_eq!(cost_of(1_200, 1_000, 1_000), 1_200);
+    assert_eq!(cost_of(1_200, 1_000, 5_000), 1_200);
+    assert_eq!(cost_of(10, 3, 1), 3);
+    assert_eq!(cost_of(0, 0, 0), 0);
+    assert_eq!(average_cost(1_200, 1_000), PRICE_SCALE / 1_000 * 1_200);
+    assert_eq!(average_cost(1_200, 0), 0);
+}
+
+#[test]
+fn gains_are_signed_and_saturate() {
+    assert_eq!(gain(1_100, 1_000), 100);
+    assert_eq!(gain(900, 1_000), -100);
+    assert_eq!(gain(u64::MAX, 0), i64::MAX);
+}
+
+#[test]
+fn values_follow_the_price() {
+    assert_eq!(value_at(1_000, PRICE_SCALE), 1_000);
+    assert_eq!(value_at(1_000, PRICE_SCALE / 2 * 3), 1_500);
+    assert_eq!(value_at(1_000, 0), 0);
+    assert_eq!(value_at(u64::MAX, 2 * PRICE_SCALE), u64::MAX);
+}
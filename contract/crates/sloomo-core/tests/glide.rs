@@ -0,0 +1,61 @@
+//! Tests for glide-path target interpolation
+
+use proptest::prelude::*;
+use sloomo_core::glide::{interpolate_target, interpolate_targets, max_target_move};
+
+const START: i64 = 1_000;
+const END: i64 = 11_000;
+
+#[test]
+fn targets_move_linearly_between_the_endpoints() {
+    // 80% equities / 20% stablecoins gliding to 30% / 70%
+    let (start, end) = ([8000, 2000], [3000, 7000]);
+
+    assert_eq!(interpolate_targets(&start, &end, START, END, 0), vec![8000, 2000]);
+    assert_eq!(interpolate_targets(&start, &end, START, END, START), vec![8000, 2000]);
+    assert_eq!(interpolate_targets(&start, &end, START, END, 6_000), vec![5500, 4500]);
+    assert_eq!(interpolate_targets(&start, &end, START, END, 8_500), vec![4250, 5750]);
+    assert_eq!(interpolate_targets(&start, &end, START, END, END), vec![3000, 7000]);
+    assert_eq!(interpolate_targets(&start, &end, START, END, i64::MAX), vec![3000, 7000]);
+}
+
+#[test]
+fn partial_steps_round_down() {
+    // A third of the way: 6000 -> 3000 is 5000 exactly, 3000 -> 4000 is 3333.3
+    assert_eq!(interpolate_target(6000, 3000, 0, 3, 1), 5000);
+    assert_eq!(interpolate_target(3000, 4000, 0, 3, 1), 3333);
+    assert_eq!(interpolate_target(4000, 3000, 0, 3, 1), 3666);
+}
+
+#[test]
+fn degenerate_windows_use_an_endpoint() {
+    assert_eq!(interpolate_target(8000, 3000, START, START, START - 1), 8000);
+    assert_eq!(interpolate_target(8000, 3000, START, START, START), 3000);
+    assert_eq!(interpolate_target(8000, 3000, END, START, 5_000), 3000);
+}
+
+#[test]
+fn largest_move_is_reported() {
+    assert_eq!(max_target_move(&[8000, 2000], &[7400, 2600]), 600);
+    assert_eq!(max_target_move(&[5000, 5000], &[5000, 5000]), 0);
+    assert_eq!(max_target_move(&[], &[]), 0);
+}
+
+proptest! {
+    #[test]
+    fn interpolated_targets_stay_within_100_percent(
+        split in proptest::collection::vec((0u16..=2500, 0u16..=2500), 1..5),
+        now in -100i64..12_000,
+    ) {
+        let (start, end): (Vec<u16>, Vec<u16>) = split.into_iter().unzip();
+        let targets = interpolate_targets(&start, &end, START, END, now);
+
+        let total: u32 = targets.iter().map(|t| *t as u32).sum();
+        let limit = start.iter().map(|t| *t as u32).sum::<u32>()
+            .max(end.iter().map(|t| *t as u32).sum());
+        prop_assert!(total <= limit);
+        for ((target, start), end) in targets.iter().zip(&start).zip(&end) {
+            prop_assert!(*target >= *start.min(end) && *target <= *start.max(end));
+        }
+    }
+}
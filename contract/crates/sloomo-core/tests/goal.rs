@@ -0,0 +1,81 @@
+//! Tests for savings goal progress
+
+use proptest::prelude::*;
+use sloomo_core::goal::{expected_growth_bps, goal_progress, GrowthSample};
+
+const MONTH: i64 = 30 * 24 * 60 * 60;
+
+fn sample(timestamp: i64, growth_bps: i16) -> GrowthSample {
+    GrowthSample { timestamp, growth_bps }
+}
+
+#[test]
+fn contributions_without_growth_split_the_shortfall() {
+    let progress = goal_progress(4_000, 10_000, 0, 12 * MONTH, MONTH, 0);
+
+    assert_eq!(progress.progress_bps, 4000);
+    assert_eq!(progress.remaining_amount, 6_000);
+    assert_eq!(progress.periods_remaining, 12);
+    assert_eq!(progress.projected_value, 4_000);
+    assert_eq!(progress.required_contribution, 500);
+
+    // A partial interval still counts as one contribution
+    assert_eq!(goal_progress(4_000, 10_000, 1, 12 * MONTH, MONTH, 0).periods_remaining, 12);
+}
+
+#[test]
+fn growth_compounds_value_and_contributions() {
+    // 1% a month: 10_000 = C * 1.01 + C
+    let progress = goal_progress(0, 10_000, 0, 2 * MONTH, MONTH, 100);
+    assert_eq!(progress.required_contribution, 4_976);
+
+    // The current value alone grows to 10_201 and already reaches the goal
+    let progress = goal_progress(10_000, 10_200, 0, 2 * MONTH, MONTH, 100);
+    assert_eq!(progress.projected_value, 10_201);
+    assert_eq!(progress.required_contribution, 0);
+    assert_eq!(progress.remaining_amount, 200);
+}
+
+#[test]
+fn reached_and_overdue_goals() {
+    let reached = goal_progress(12_000, 10_000, 0, MONTH, MONTH, 0);
+    assert_eq!(reached.progress_bps, 10000);
+    assert_eq!(reached.required_contribution, 0);
+
+    // After the target date the whole shortfall is due at once
+    let overdue = goal_progress(7_000, 10_000, 2 * MONTH, MONTH, MONTH, 50);
+    assert_eq!(overdue.periods_remaining, 0);
+    assert_eq!(overdue.required_contribution, 3_000);
+}
+
+#[test]
+fn growth_is_averaged_over_the_history() {
+    // 80 bps over 200 seconds; the first sample's growth predates the window
+    let history = [sample(0, 50), sample(100, 100), sample(200, -20)];
+    assert_eq!(expected_growth_bps(&history, 100), 40);
+
+    assert_eq!(expected_growth_bps(&history[..1], 100), 0);
+    assert_eq!(expected_growth_bps(&[], 100), 0);
+    assert_eq!(expected_growth_bps(&[sample(0, 0), sample(1, 10000)], 100), 10000);
+    assert_eq!(expected_growth_bps(&[sample(0, 0), sample(1, -10000)], 100), -9999);
+}
+
+proptest! {
+    #[test]
+    fn required_contributions_reach_the_goal(
+        current in 0u64..1_000_000,
+        target in 1u64..10_000_000,
+        periods in 1i64..120,
+        growth in 0i16..500,
+    ) {
+        let progress = goal_progress(current, target, 0, periods * MONTH, MONTH, growth);
+
+        // Simulated in millionths of a base unit so rounding stays negligible
+        let mut value = current as u128 * 1_000_000;
+        for _ in 0..periods {
+            value = value * (10000 + growth as u128) / 10000
+                + progress.required_contribution as u128 * 1_000_000;
+        }
+        prop_assert!(value / 1_000_000 + 1 >= target as u128);
+    }
+}
@@ -0,0 +1,125 @@
+//! Tests for asset-class targets on top of per-mint allocations
+
+use proptest::prelude::*;
+use sloomo_core::drift::Band;
+use sloomo_core::hierarchy::{
+    effective_target, needs_class_rebalancing, pin_out_of_band_classes, validate_hierarchy,
+    AssetClass, ClassMember,
+};
+use sloomo_core::planner::{plan_rebalance, PlannerConfig, PlannerHolding};
+use sloomo_core::CoreError;
+use sloomo_core::Mint;
+
+fn mint(index: usize) -> Mint {
+    [index as u8 + 1; 32]
+}
+
+fn class(target_percentage: u16) -> AssetClass {
+    AssetClass { target_percentage, band: None }
+}
+
+fn member(class: u8, weight: u16) -> Option<ClassMember> {
+    Some(ClassMember { class, weight })
+}
+
+/// 60% equities split 50/30/20, 40% stablecoins split 50/50
+fn sixty_forty() -> (Vec<AssetClass>, Vec<Option<ClassMember>>) {
+    (
+        vec![class(6000), class(4000)],
+        vec![member(0, 5000), member(0, 3000), member(0, 2000), member(1, 5000), member(1, 5000)],
+    )
+}
+
+#[test]
+fn member_targets_are_flattened() {
+    let (classes, members) = sixty_forty();
+    let flattened: Vec<u16> = members
+        .iter()
+        .flatten()
+        .map(|m| effective_target(classes[m.class as usize].target_percentage, m.weight))
+        .collect();
+    assert_eq!(flattened, vec![3000, 1800, 1200, 2000, 2000]);
+}
+
+#[test]
+fn each_level_must_sum_correctly() {
+    let (classes, members) = sixty_forty();
+    assert!(validate_hierarchy(&classes, &members).is_ok());
+
+    // Intra-class weights short of 100%
+    let mut short = members.clone();
+    short[0] = member(0, 4000);
+    assert_eq!(validate_hierarchy(&classes, &short), Err(CoreError::InvalidHierarchy));
+
+    // Member of a class that does not exist
+    let mut unknown = members.clone();
+    unknown[4] = member(2, 5000);
+    assert_eq!(validate_hierarchy(&classes, &unknown), Err(CoreError::InvalidHierarchy));
+
+    // Class without members
+    let empty = vec![class(6000), class(4000), class(0)];
+    assert_eq!(validate_hierarchy(&empty, &members), Err(CoreError::InvalidHierarchy));
+
+    // Class targets over 100%
+    let over = vec![class(6000), class(5000)];
+    assert_eq!(validate_hierarchy(&over, &members), Err(CoreError::AllocationOverflow));
+
+    // Class band that misses its target
+    let banded = vec![
+        AssetClass { target_percentage: 6000, band: Some(Band { min_percentage: 6100, max_percentage: 7000 }) },
+        class(4000),
+    ];
+    assert_eq!(validate_hierarchy(&banded, &members), Err(CoreError::InvalidHierarchy));
+}
+
+#[test]
+fn class_drift_is_caught_when_every_member_is_within_its_band() {
+    let (classes, members) = sixty_forty();
+    // Each equity 3% over and each stablecoin 4.5% under target (inside the
+    // 5% threshold), but equities are 69% overall
+    let values = [330, 210, 150, 155, 155];
+    assert!(needs_class_rebalancing(&values, &members, &classes, 1_000, 500));
+
+    let mut holdings: Vec<PlannerHolding> = values
+        .iter()
+        .zip([3000, 1800, 1200, 2000, 2000])
+        .enumerate()
+        .map(|(i, (value, target_percentage))| PlannerHolding {
+            mint: mint(i),
+            value: *value,
+            target_percentage,
+            band: None,
+        })
+        .collect();
+    let config = PlannerConfig { min_trade_value: 0, max_legs: u8::MAX, ..PlannerConfig::default() };
+    assert!(plan_rebalance(&holdings, [0xff; 32], &config).unwrap().legs.is_empty());
+
+    // Both classes are out of band, so every member is traded back to target
+    assert_eq!(pin_out_of_band_classes(&mut holdings, &members, &classes, 500).unwrap(), 2);
+    let plan = plan_rebalance(&holdings, [0xff; 32], &config).unwrap();
+    assert_eq!(plan.max_residual_drift_bps, 0);
+    assert!(plan.within_band);
+}
+
+#[test]
+fn classes_inside_their_band_are_left_alone() {
+    let (classes, members) = sixty_forty();
+    let values = [310, 180, 120, 200, 190];
+    assert!(!needs_class_rebalancing(&values, &members, &classes, 1_000, 500));
+}
+
+proptest! {
+    #[test]
+    fn flattened_targets_never_exceed_their_class(
+        class_target in 0u16..=10000,
+        weights in prop::collection::vec(1u32..1000, 1..10),
+    ) {
+        let total: u32 = weights.iter().sum();
+        let mut bps: Vec<u16> = weights.iter().map(|w| (w * 10000 / total) as u16).collect();
+        bps[0] += 10000 - bps.iter().sum::<u16>();
+
+        let flattened: u32 = bps.iter().map(|w| effective_target(class_target, *w) as u32).sum();
+        prop_assert!(flattened <= class_target as u32);
+        prop_assert!(class_target as u32 - flattened < bps.len() as u32);
+    }
+}
@@ -0,0 +1,457 @@
+//! Property-based tests for the cost-aware rebalance planner
+
+use proptest::prelude::*;
+use sloomo_core::math::bps_of;
+use sloomo_core::planner::{
+    leg_cost, plan_cash_flow, plan_liquidation, plan_rebalance, route_through_base,
+    BandRestore, LiquidationOrder, PlannerConfig, PlannerHolding, RebalancePlan,
+};
+use sloomo_core::drift::Band;
+use sloomo_core::CoreError;
+use sloomo_core::valuation::Target;
+use sloomo_core::Mint;
+
+/// Mirrors the on-chain `MAX_ALLOCATIONS`
+const MAX_ALLOCATIONS: usize = 10;
+
+fn mint(index: usize) -> Mint {
+    [index as u8 + 1; 32]
+}
+
+fn base_mint() -> Mint {
+    [0xff; 32]
+}
+
+fn holdings(values: &[u64], targets: &[u16]) -> Vec<PlannerHolding> {
+    values
+        .iter()
+        .zip(targets)
+        .enumerate()
+        .map(|(i, (value, target_percentage))| PlannerHolding {
+            mint: mint(i),
+            value: *value,
+            target_percentage: *target_percentage,
+            band: None,
+        })
+        .collect()
+}
+
+fn unlimited(drift_threshold_bps: u16) -> PlannerConfig {
+    PlannerConfig {
+        drift_threshold_bps,
+        min_trade_value: 0,
+        max_legs: u8::MAX,
+        ..PlannerConfig::default()
+    }
+}
+
+/// Values and target percentages summing to exactly 10000 bps
+fn values_and_targets() -> impl Strategy<Value = (Vec<u64>, Vec<u16>)> {
+    (1..=MAX_ALLOCATIONS).prop_flat_map(|len| {
+        (
+            prop::collection::vec(0u64..=u64::MAX / MAX_ALLOCATIONS as u64, len),
+            prop::collection::vec(1u32..1000, len).prop_map(|weights| {
+                let total: u32 = weights.iter().sum();
+                let mut percentages: Vec<u16> =
+                    weights.iter().map(|w| (w * 10000 / total) as u16).collect();
+                let assigned: u16 = percentages.iter().sum();
+                percentages[0] += 10000 - assigned;
+                percentages
+            }),
+        )
+    })
+}
+
+/// Apply a plan to the holdings, returning post-trade values
+fn settle(holdings: &[PlannerHolding], plan: &RebalancePlan) -> Vec<i128> {
+    holdings
+        .iter()
+        .map(|h| {
+            let mut value = h.value as i128;
+            for leg in &plan.legs {
+                if leg.from_mint == h.mint {
+                    value -= leg.amount as i128;
+                }
+                if leg.to_mint == h.mint {
+                    value += leg.amount as i128;
+                }
+            }
+            value
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn planner_never_panics(
+        values in prop::collection::vec(any::<u64>(), 0..=MAX_ALLOCATIONS),
+        targets in prop::collection::vec(any::<u16>(), 0..=MAX_ALLOCATIONS),
+        drift_threshold_bps in any::<u16>(),
+        min_trade_value in any::<u64>(),
+        max_legs in any::<u8>(),
+    ) {
+        let config = PlannerConfig {
+            drift_threshold_bps,
+            min_trade_value,
+            max_legs,
+            ..PlannerConfig::default()
+        };
+        let _ = plan_rebalance(&holdings(&values, &targets), base_mint(), &config);
+    }
+
+    #[test]
+    fn unconstrained_plan_lands_within_band(
+        (values, targets) in values_and_targets(),
+        drift_threshold_bps in 0u16..=2000,
+    ) {
+        let holdings = holdings(&values, &targets);
+        let plan = plan_rebalance(&holdings, base_mint(), &unlimited(drift_threshold_bps)).unwrap();
+        let total_value: u64 = values.iter().sum();
+
+        let settled = settle(&holdings, &plan);
+        for value in &settled {
+            prop_assert!(*value >= 0, "plan sells more than is held");
+        }
+
+        // Only rounding dust (< 1 unit per allocation) may flow to the base asset
+        let base_flow: i128 = plan.legs.iter().map(|leg| {
+            let mut flow = 0i128;
+            if leg.to_mint == base_mint() { flow += leg.amount as i128; }
+            if leg.from_mint == base_mint() { flow -= leg.amount as i128; }
+            flow
+        }).sum();
+        prop_assert!(base_flow.unsigned_abs() < targets.len() as u128 + 1);
+        prop_assert_eq!(settled.iter().sum::<i128>() + base_flow, total_value as i128);
+
+        // Greedy matching never needs more legs than there are assets
+        prop_assert!(plan.legs.len() <= holdings.len());
+        prop_assert!(plan.within_band);
+    }
+
+    #[test]
+    fn restoring_to_target_lands_within_band(
+        (values, targets) in values_and_targets(),
+        width in 0u16..=2000,
+    ) {
+        let bands: Vec<(u16, u16)> = targets
+            .iter()
+            .map(|t| (t.saturating_sub(width), t.saturating_add(width / 2).min(10000)))
+            .collect();
+        let holdings = banded(holdings(&values, &targets), &bands);
+        let config = PlannerConfig { restore: BandRestore::Target, ..unlimited(0) };
+        let plan = plan_rebalance(&holdings, base_mint(), &config).unwrap();
+
+        for value in settle(&holdings, &plan) {
+            prop_assert!(value >= 0, "plan sells more than is held");
+        }
+        prop_assert!(plan.within_band);
+    }
+
+    #[test]
+    fn plan_respects_leg_limits(
+        (values, targets) in values_and_targets(),
+        min_trade_value in any::<u64>(),
+        max_legs in 0u8..5,
+    ) {
+        let config = PlannerConfig {
+            min_trade_value,
+            max_legs,
+            ..PlannerConfig::default()
+        };
+        let plan = plan_rebalance(&holdings(&values, &targets), base_mint(), &config).unwrap();
+
+        prop_assert!(plan.legs.len() <= max_legs as usize);
+        let mut cost = 0u64;
+        for leg in &plan.legs {
+            prop_assert!(leg.amount >= min_trade_value);
+            prop_assert!(leg.from_mint != leg.to_mint);
+            prop_assert_eq!(leg.estimated_cost, leg_cost(leg.amount, &config));
+            cost += leg.estimated_cost;
+        }
+        prop_assert_eq!(plan.estimated_cost, cost);
+    }
+
+    #[test]
+    fn netting_never_trades_more_than_routing_through_base((values, targets) in values_and_targets()) {
+        let holdings = holdings(&values, &targets);
+        let plan = plan_rebalance(&holdings, base_mint(), &unlimited(0)).unwrap();
+        let planned_volume: u128 = plan.legs.iter().map(|leg| leg.amount as u128).sum();
+
+        // Routing sells everything overweight into the base asset and buys back out of it
+        let current: Vec<(Mint, u64)> = holdings.iter().map(|h| (h.mint, h.value)).collect();
+        let targets: Vec<Target> = holdings.iter().map(|h| Target {
+            mint: h.mint,
+            target_percentage: h.target_percentage,
+            band: None,
+        }).collect();
+        let total_value: u64 = values.iter().sum();
+        let routed_volume: u128 = route_through_base(&current, &targets, total_value, base_mint())
+            .unwrap()
+            .iter()
+            .map(|swap| swap.amount as u128)
+            .sum();
+
+        prop_assert!(planned_volume <= routed_volume);
+    }
+
+    #[test]
+    fn cash_flow_only_buys_up_to_target(
+        (values, targets) in values_and_targets(),
+        amount in 0u64..=u64::MAX / (MAX_ALLOCATIONS as u64 + 1),
+    ) {
+        let holdings = holdings(&values, &targets);
+        let plan = plan_cash_flow(&holdings, base_mint(), amount, &unlimited(0)).unwrap();
+        let total_value = values.iter().sum::<u64>() + amount;
+
+        let mut spent = 0u128;
+        for leg in &plan.legs {
+            prop_assert_eq!(leg.from_mint, base_mint());
+            spent += leg.amount as u128;
+        }
+        prop_assert!(spent <= amount as u128, "plan spends more than the deposit");
+
+        // No asset is pushed past its target
+        for (holding, value) in holdings.iter().zip(settle(&holdings, &plan)) {
+            let target = bps_of(total_value, holding.target_percentage).unwrap() as i128;
+            let bought = plan.legs.iter().any(|leg| leg.to_mint == holding.mint);
+            prop_assert!(!bought || value <= target);
+        }
+    }
+}
+
+#[test]
+fn overweight_asset_funds_underweight_asset_directly() {
+    // 70/30 against a 50/50 target with a 5% band: move 15% straight across
+    let holdings = holdings(&[700_000_000, 300_000_000], &[5000, 5000]);
+    let plan = plan_rebalance(&holdings, base_mint(), &PlannerConfig::default()).unwrap();
+
+    assert_eq!(plan.legs.len(), 1);
+    assert_eq!(plan.legs[0].from_mint, mint(0));
+    assert_eq!(plan.legs[0].to_mint, mint(1));
+    assert_eq!(plan.legs[0].amount, 150_000_000);
+    assert_eq!(plan.max_residual_drift_bps, 500);
+    assert!(plan.within_band);
+}
+
+#[test]
+fn dust_legs_are_skipped() {
+    let holdings = holdings(&[60_010, 39_990], &[6000, 4000]);
+    let plan = plan_rebalance(&holdings, base_mint(), &unlimited(0)).unwrap();
+    assert_eq!(plan.legs.len(), 1);
+
+    let config = PlannerConfig { drift_threshold_bps: 0, ..PlannerConfig::default() };
+    let plan = plan_rebalance(&holdings, base_mint(), &config).unwrap();
+    assert!(plan.legs.is_empty());
+    assert!(!plan.within_band);
+}
+
+fn banded(mut holdings: Vec<PlannerHolding>, bands: &[(u16, u16)]) -> Vec<PlannerHolding> {
+    for (holding, (min_percentage, max_percentage)) in holdings.iter_mut().zip(bands) {
+        holding.band = Some(Band { min_percentage: *min_percentage, max_percentage: *max_percentage });
+    }
+    holdings
+}
+
+#[test]
+fn assets_inside_their_band_are_not_traded() {
+    // Equity at 34% of a 30% target with a 25-35% band stays put; the
+    // stablecoin at 36% of 40% (band 39-41%) is bought back to its edge
+    let holdings = banded(
+        holdings(&[340, 360, 300], &[3000, 4000, 3000]),
+        &[(2500, 3500), (3900, 4100), (2500, 3500)],
+    );
+    let config = PlannerConfig { min_trade_value: 0, ..costless() };
+    let plan = plan_rebalance(&holdings, base_mint(), &config).unwrap();
+
+    assert_eq!(plan.legs.len(), 1);
+    assert_eq!(plan.legs[0].from_mint, mint(0));
+    assert_eq!(plan.legs[0].to_mint, mint(1));
+    assert_eq!(plan.legs[0].amount, 30);
+    assert!(plan.within_band);
+}
+
+#[test]
+fn out_of_band_assets_can_be_restored_to_target() {
+    // 40/60 against 30/70 with 25-35% and 65-75% bands
+    let holdings = banded(holdings(&[400, 600], &[3000, 7000]), &[(2500, 3500), (6500, 7500)]);
+
+    let edge = PlannerConfig { min_trade_value: 0, ..costless() };
+    let plan = plan_rebalance(&holdings, base_mint(), &edge).unwrap();
+    assert_eq!(plan.legs[0].amount, 50);
+    assert_eq!(plan.max_residual_drift_bps, 500);
+
+    let to_target = PlannerConfig { restore: BandRestore::Target, ..edge };
+    let plan = plan_rebalance(&holdings, base_mint(), &to_target).unwrap();
+    assert_eq!(plan.legs.len(), 1);
+    assert_eq!((plan.legs[0].from_mint, plan.legs[0].to_mint), (mint(0), mint(1)));
+    assert_eq!(plan.legs[0].amount, 100);
+    assert_eq!(plan.max_residual_drift_bps, 0);
+    assert!(plan.within_band);
+}
+
+#[test]
+fn unallocated_value_is_parked_in_base_asset() {
+    // Targets only cover 80%; the overweight remainder is sold into the base asset
+    let holdings = holdings(&[1_000_000_000], &[8000]);
+    let plan = plan_rebalance(&holdings, base_mint(), &unlimited(0)).unwrap();
+    assert_eq!(plan.legs.len(), 1);
+    assert_eq!(plan.legs[0].to_mint, base_mint());
+    assert_eq!(plan.legs[0].amount, bps_of(1_000_000_000, 2000).unwrap());
+}
+
+#[test]
+fn deposit_goes_to_the_most_underweight_asset_first() {
+    // 50/30/20 against 40/30/30; after a 100 deposit mint 2 is furthest below target
+    let holdings = holdings(&[500, 300, 200], &[4000, 3000, 3000]);
+    let config = PlannerConfig { min_trade_value: 0, ..PlannerConfig::default() };
+    let plan = plan_cash_flow(&holdings, base_mint(), 100, &config).unwrap();
+
+    assert_eq!(plan.legs.len(), 1);
+    assert_eq!(plan.legs[0].from_mint, base_mint());
+    assert_eq!(plan.legs[0].to_mint, mint(2));
+    assert_eq!(plan.legs[0].amount, 100);
+}
+
+#[test]
+fn deposit_larger_than_shortfalls_leaves_the_rest_in_base() {
+    let holdings = holdings(&[600, 400], &[5000, 5000]);
+    let config = PlannerConfig { min_trade_value: 0, ..PlannerConfig::default() };
+    let plan = plan_cash_flow(&holdings, base_mint(), 1_000, &config).unwrap();
+
+    // Post-deposit total 2000: mint 0 needs 400, mint 1 needs 600
+    let bought: Vec<_> = plan.legs.iter().map(|leg| (leg.to_mint, leg.amount)).collect();
+    assert_eq!(bought, vec![(mint(1), 600), (mint(0), 400)]);
+}
+
+#[test]
+fn cash_flow_respects_leg_limits() {
+    let holdings = holdings(&[0, 0, 0], &[3000, 3000, 4000]);
+    let config = PlannerConfig { min_trade_value: 0, max_legs: 2, ..PlannerConfig::default() };
+    let plan = plan_cash_flow(&holdings, base_mint(), 1_000, &config).unwrap();
+    assert_eq!(plan.legs.len(), 2);
+    assert_eq!(plan.legs[0].to_mint, mint(2));
+
+    // Dust shortfalls are not worth a swap
+    let plan = plan_cash_flow(&holdings, base_mint(), 1_000, &PlannerConfig::default()).unwrap();
+    assert!(plan.legs.is_empty());
+}
+
+fn with_base(mut holdings: Vec<PlannerHolding>, value: u64) -> Vec<PlannerHolding> {
+    holdings.push(PlannerHolding { mint: base_mint(), value, target_percentage: 0, band: None });
+    holdings
+}
+
+fn costless() -> PlannerConfig {
+    PlannerConfig { fee_bps: 0, slippage_bps: 0, ..PlannerConfig::default() }
+}
+
+#[test]
+fn withdrawal_covered_by_base_sells_nothing() {
+    let holdings = with_base(holdings(&[500, 500], &[5000, 5000]), 300);
+    let legs = plan_liquidation(
+        &holdings,
+        base_mint(),
+        300,
+        LiquidationOrder::Proportional,
+        &PlannerConfig::default(),
+    )
+    .unwrap();
+    assert!(legs.is_empty());
+}
+
+#[test]
+fn proportional_liquidation_sells_pro_rata() {
+    // 100 base covers part of 400; the other 300 comes 2:1 from the holdings
+    let holdings = with_base(holdings(&[600, 300], &[5000, 5000]), 100);
+    let legs =
+        plan_liquidation(&holdings, base_mint(), 400, LiquidationOrder::Proportional, &costless())
+            .unwrap();
+
+    let sold: Vec<_> = legs.iter().map(|leg| (leg.from_mint, leg.amount)).collect();
+    assert_eq!(sold, vec![(mint(0), 200), (mint(1), 100)]);
+    assert!(legs.iter().all(|leg| leg.to_mint == base_mint()));
+}
+
+#[test]
+fn overweight_holdings_are_sold_first() {
+    // 700/300 against 50/50; after withdrawing 200 the target is 400 each,
+    // so the 300 excess of mint 0 funds the whole withdrawal
+    let holdings = holdings(&[700, 300], &[5000, 5000]);
+    let legs =
+        plan_liquidation(&holdings, base_mint(), 200, LiquidationOrder::OverweightFirst, &costless())
+            .unwrap();
+    assert_eq!(legs.len(), 1);
+    assert_eq!((legs[0].from_mint, legs[0].amount), (mint(0), 200));
+
+    // A larger withdrawal lowers the targets until both holdings are overweight
+    let legs =
+        plan_liquidation(&holdings, base_mint(), 700, LiquidationOrder::OverweightFirst, &costless())
+            .unwrap();
+    let sold: Vec<_> = legs.iter().map(|leg| (leg.from_mint, leg.amount)).collect();
+    // Post-withdrawal target 150 each: excess 550 and 150
+    assert_eq!(sold, vec![(mint(0), 550), (mint(1), 150)]);
+}
+
+#[test]
+fn costs_beyond_the_excess_are_sold_pro_rata() {
+    let holdings = holdings(&[600, 400], &[5000, 5000]);
+    let config = PlannerConfig { fee_bps: 0, slippage_bps: 5000, ..PlannerConfig::default() };
+    let legs =
+        plan_liquidation(&holdings, base_mint(), 100, LiquidationOrder::OverweightFirst, &config)
+            .unwrap();
+    let sold: Vec<_> = legs.iter().map(|leg| (leg.from_mint, leg.amount)).collect();
+    // 100 / 50% = 200: the 150 excess of mint 0, then 50 split 450:400
+    assert_eq!(sold, vec![(mint(0), 177), (mint(1), 23)]);
+}
+
+#[test]
+fn liquidation_is_grossed_up_for_costs() {
+    let holdings = holdings(&[10_000], &[10000]);
+    let config = PlannerConfig { fee_bps: 25, slippage_bps: 75, ..PlannerConfig::default() };
+    let legs =
+        plan_liquidation(&holdings, base_mint(), 990, LiquidationOrder::Proportional, &config)
+            .unwrap();
+    // 990 / (1 - 1%) = 1000
+    assert_eq!(legs[0].amount, 1_000);
+    assert_eq!(legs[0].estimated_cost, 10);
+}
+
+#[test]
+fn liquidation_beyond_holdings_fails() {
+    let holdings = with_base(holdings(&[500], &[10000]), 100);
+    assert_eq!(
+        plan_liquidation(&holdings, base_mint(), 601, LiquidationOrder::Proportional, &costless()),
+        Err(CoreError::InsufficientLiquidity)
+    );
+    let config = PlannerConfig { fee_bps: 5000, slippage_bps: 5000, ..PlannerConfig::default() };
+    assert_eq!(
+        plan_liquidation(&holdings, base_mint(), 200, LiquidationOrder::Proportional, &config),
+        Err(CoreError::InsufficientLiquidity)
+    );
+}
+
+proptest! {
+    #[test]
+    fn liquidation_covers_the_shortfall_without_overselling(
+        (values, targets) in values_and_targets(),
+        share_bps in 1u16..=10000,
+        overweight_first in any::<bool>(),
+    ) {
+        let holdings = holdings(&values, &targets);
+        let total_value: u64 = values.iter().sum();
+        let amount = bps_of(total_value, share_bps).unwrap();
+        let order = if overweight_first {
+            LiquidationOrder::OverweightFirst
+        } else {
+            LiquidationOrder::Proportional
+        };
+
+        let legs = plan_liquidation(&holdings, base_mint(), amount, order, &costless()).unwrap();
+        let sold: u64 = legs.iter().map(|leg| leg.amount).sum();
+        prop_assert_eq!(sold, amount);
+        for leg in &legs {
+            let held = holdings.iter().find(|h| h.mint == leg.from_mint).unwrap().value;
+            prop_assert!(leg.amount > 0 && leg.amount <= held, "leg sells more than is held");
+        }
+    }
+}
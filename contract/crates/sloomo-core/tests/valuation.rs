@@ -0,0 +1,128 @@
+//! Tests for valuation, drift and performance math
+
+use proptest::prelude::*;
+use sloomo_core::drift::{max_drift_bps, needs_rebalancing, Band, DEFAULT_DRIFT_THRESHOLD_BPS};
+use sloomo_core::math::{
+    amount_to_bps, bps_of, effective_price, realized_slippage_bps, rescale_decimals,
+    sum_target_percentages, PRICE_SCALE,
+};
+use sloomo_core::performance::growth_bps;
+use sloomo_core::valuation::{current_percentages, target_amounts, total_value, Target};
+use sloomo_core::{CoreError, Mint};
+
+fn mint(index: usize) -> Mint {
+    [index as u8 + 1; 32]
+}
+
+proptest! {
+    #[test]
+    fn percentages_are_bounded(amounts in prop::collection::vec(0u64..=u64::MAX / 16, 0..16)) {
+        let percentages = current_percentages(&amounts).unwrap();
+        prop_assert!(percentages.iter().all(|p| *p <= 10000));
+        prop_assert!(percentages.iter().sum::<u64>() <= 10000);
+    }
+
+    #[test]
+    fn target_amounts_never_exceed_total(
+        total in any::<u64>(),
+        percentages in prop::collection::vec(0u16..=10000, 1..10),
+    ) {
+        let targets: Vec<Target> = percentages
+            .iter()
+            .enumerate()
+            .map(|(i, p)| Target { mint: mint(i), target_percentage: *p, band: None })
+            .collect();
+        for (_, amount) in target_amounts(&targets, total).unwrap() {
+            prop_assert!(amount <= total);
+        }
+    }
+
+    #[test]
+    fn rescaling_up_and_back_is_lossless(amount in 0u64..=u64::MAX / 1_000, from in 0u8..=9) {
+        let scaled = rescale_decimals(amount, from, from + 3).unwrap();
+        prop_assert_eq!(rescale_decimals(scaled, from + 3, from).unwrap(), amount);
+    }
+
+    #[test]
+    fn growth_is_clamped(previous in any::<u64>(), current in any::<u64>()) {
+        let growth = growth_bps(previous, current);
+        prop_assert!((-10000..=10000).contains(&growth));
+    }
+}
+
+#[test]
+fn overflowing_totals_are_rejected() {
+    assert_eq!(total_value([u64::MAX, 1]), Err(CoreError::MathOverflow));
+    assert_eq!(sum_target_percentages([u16::MAX, 1]), Err(CoreError::AllocationOverflow));
+}
+
+#[test]
+fn bps_round_trip() {
+    assert_eq!(amount_to_bps(250, 1000), 2500);
+    assert_eq!(amount_to_bps(1, 0), 0);
+    assert_eq!(bps_of(1000, 2500).unwrap(), 250);
+}
+
+#[test]
+fn swap_outcomes_are_priced() {
+    assert_eq!(effective_price(1_000, 995), PRICE_SCALE / 1_000 * 995);
+    assert_eq!(effective_price(0, 995), 0);
+    assert_eq!(effective_price(1, u64::MAX), u64::MAX);
+
+    assert_eq!(realized_slippage_bps(1_000, 995), 50);
+    assert_eq!(realized_slippage_bps(1_000, 1_010), -100);
+    assert_eq!(realized_slippage_bps(1_000, 0), 10000);
+    assert_eq!(realized_slippage_bps(1, u64::MAX), -10000);
+    assert_eq!(realized_slippage_bps(0, 5), 0);
+}
+
+#[test]
+fn drift_counts_missing_holdings_in_full() {
+    let holdings = [(mint(0), 1_000)];
+    let targets = [
+        Target { mint: mint(0), target_percentage: 9_000, band: None },
+        Target { mint: mint(1), target_percentage: 1_000, band: None },
+    ];
+
+    assert_eq!(max_drift_bps(&holdings, &targets, 1_000), 1_000);
+    assert!(needs_rebalancing(&holdings, &targets, 1_000, DEFAULT_DRIFT_THRESHOLD_BPS));
+    assert!(!needs_rebalancing(&holdings, &targets, 1_000, 1_000));
+    assert!(!needs_rebalancing(&holdings, &targets, 0, 0));
+}
+
+#[test]
+fn banded_targets_only_rebalance_outside_their_band() {
+    // 34/66 against a 30% target: inside 25-35%, but 4% off target
+    let holdings = [(mint(0), 340), (mint(1), 660)];
+    let band = |min_percentage, max_percentage| Some(Band { min_percentage, max_percentage });
+    let targets = [
+        Target { mint: mint(0), target_percentage: 3_000, band: band(2_500, 3_500) },
+        Target { mint: mint(1), target_percentage: 7_000, band: band(6_500, 7_500) },
+    ];
+    assert!(!needs_rebalancing(&holdings, &targets, 1_000, 0));
+
+    // A tighter band around the same target is breached
+    let targets = [Target { mint: mint(0), target_percentage: 3_000, band: band(2_800, 3_200) }];
+    assert!(needs_rebalancing(&holdings, &targets, 1_000, DEFAULT_DRIFT_THRESHOLD_BPS));
+}
+
+#[test]
+fn band_must_contain_its_target() {
+    let band = Band { min_percentage: 2_500, max_percentage: 3_500 };
+    assert!(band.is_valid_for(3_000));
+    assert!(band.is_valid_for(2_500));
+    assert!(!band.is_valid_for(4_000));
+    assert!(!Band { min_percentage: 9_000, max_percentage: 10_001 }.is_valid_for(9_500));
+}
+
+#[test]
+fn decimals_are_rescaled() {
+    // 1.5 SOL (9 decimals) in USDC units (6 decimals)
+    assert_eq!(rescale_decimals(1_500_000_000, 9, 6).unwrap(), 1_500_000);
+    assert_eq!(rescale_decimals(1_500_000, 6, 9).unwrap(), 1_500_000_000);
+    assert_eq!(rescale_decimals(999, 9, 6).unwrap(), 0);
+    assert_eq!(rescale_decimals(42, 6, 6).unwrap(), 42);
+    assert_eq!(rescale_decimals(u64::MAX, 255, 0).unwrap(), 0);
+    assert_eq!(rescale_decimals(1, 0, 255), Err(CoreError::MathOverflow));
+    assert_eq!(rescale_decimals(u64::MAX, 6, 9), Err(CoreError::MathOverflow));
+}
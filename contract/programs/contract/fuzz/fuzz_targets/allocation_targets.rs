@@ -0,0 +1,61 @@
+#![no_main]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorDeserialize;
+use libfuzzer_sys::fuzz_target;
+use sloomo_portfolio::utils::{validate_target_allocations, JupiterSolSwapHelper};
+use sloomo_portfolio::{AllocationData, AllocationTarget, Portfolio};
+
+fuzz_target!(|data: &[u8]| {
+    let input = &mut &data[..];
+    let (Ok(total_value), Ok(amounts), Ok(targets)) = (
+        u64::deserialize(input),
+        Vec::<u64>::deserialize(input),
+        Vec::<AllocationTarget>::deserialize(input),
+    ) else {
+        return;
+    };
+
+    let portfolio = Portfolio {
+        owner: Pubkey::default(),
+        bump: 0,
+        total_value,
+        last_rebalance: 0,
+        allocations: targets
+            .iter()
+            .zip(&amounts)
+            .map(|(target, amount)| AllocationData {
+                mint: target.mint,
+                symbol: String::new(),
+                current_amount: *amount,
+                target_percentage: target.target_percentage,
+                apy: 0,
+                last_yield_update: 0,
+                band: None,
+                asset_class: None,
+                cost_basis: 0,
+                realized_pnl: 0,
+            })
+            .collect(),
+        performance_history: Vec::new(),
+        created_at: 0,
+        updated_at: 0,
+        is_rebalancing: false,
+        base_mint: Pubkey::default(),
+    };
+
+    // Neither calculation may panic, whatever the input
+    let drift = portfolio.needs_rebalancing(&targets, total_value);
+    let operations = JupiterSolSwapHelper::calculate_swap_operations(
+        &portfolio.allocations,
+        &targets,
+        total_value,
+        portfolio.base_mint,
+    );
+
+    // Targets accepted by the rebalance instructions always produce a plan
+    if validate_target_allocations(&targets).is_ok() {
+        assert!(drift.is_ok());
+        assert!(operations.is_ok());
+    }
+});
@@ -0,0 +1,60 @@
+#![no_main]
+
+use anchor_lang::AnchorDeserialize;
+use libfuzzer_sys::fuzz_target;
+use sloomo_portfolio::utils::{
+    validate_init_params, CommonMints, JupiterSolSwapHelper, SwapOperationType,
+};
+use sloomo_portfolio::{AllocationData, AllocationTarget, InitPortfolioParams};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(params) = InitPortfolioParams::deserialize(&mut &data[..]) else {
+        return;
+    };
+    if validate_init_params(&params).is_err() {
+        return;
+    }
+
+    // Mirror initialize_portfolio: the initial SOL lands on the wSOL allocation
+    let wsol_mint = CommonMints::get_wsol_pubkey();
+    let allocations: Vec<AllocationData> = params
+        .initial_allocations
+        .iter()
+        .map(|alloc| AllocationData {
+            mint: alloc.mint,
+            symbol: alloc.symbol.clone(),
+            current_amount: if alloc.mint == wsol_mint { params.initial_sol_amount } else { 0 },
+            target_percentage: alloc.target_percentage,
+            apy: 0,
+            last_yield_update: 0,
+            band: None,
+            asset_class: None,
+            cost_basis: 0,
+            realized_pnl: 0,
+        })
+        .collect();
+    let targets: Vec<AllocationTarget> = allocations
+        .iter()
+        .map(|a| AllocationTarget { mint: a.mint, target_percentage: a.target_percentage })
+        .collect();
+
+    let operations = JupiterSolSwapHelper::calculate_swap_operations(
+        &allocations,
+        &targets,
+        params.initial_sol_amount,
+    )
+    .expect("validated params must not overflow");
+
+    // Never sell more than is held, never buy more than the portfolio is worth
+    let mut bought: u128 = 0;
+    for operation in &operations {
+        match operation.operation_type {
+            SwapOperationType::Sell => {
+                let held = allocations.iter().find(|a| a.mint == operation.from_mint).unwrap();
+                assert!(operation.amount <= held.current_amount);
+            }
+            SwapOperationType::Buy => bought += operation.amount as u128,
+        }
+    }
+    assert!(bought <= params.initial_sol_amount as u128);
+});
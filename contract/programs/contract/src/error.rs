@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use sloomo_core::CoreError;
 
 #[error_code]
 pub enum SloomoError {
@@ -34,4 +35,101 @@ pub enum SloomoError {
     SwapExecutionFailed,
     #[msg("Quote retrieval failed")]
     QuoteRetrievalFailed,
-}
\ No newline at end of file
+    #[msg("Token mint does not match the portfolio base asset")]
+    InvalidBaseMint,
+    #[msg("Vault account does not match the portfolio vault PDA")]
+    InvalidVault,
+    #[msg("Vault account missing for an allocation")]
+    MissingVaultAccount,
+    #[msg("Rebalance session deadline has passed")]
+    RebalanceSessionExpired,
+    #[msg("Rebalance session is still active")]
+    RebalanceSessionActive,
+    #[msg("Rebalance leg index out of range")]
+    InvalidLegIndex,
+    #[msg("Rebalance leg already executed")]
+    LegAlreadyExecuted,
+    #[msg("Rebalance leg not executed yet")]
+    LegNotExecuted,
+    #[msg("Swap returned less than the minimum amount")]
+    SlippageExceeded,
+    #[msg("Vault balance changed outside the rebalance legs")]
+    UnexpectedVaultBalance,
+    #[msg("Invalid swap program")]
+    InvalidSwapProgram,
+    #[msg("Withdrawal share must be between 1 and 10000 basis points")]
+    InvalidWithdrawalShare,
+    #[msg("Portfolio holdings cannot cover the withdrawal")]
+    InsufficientLiquidity,
+    #[msg("Swap sells more than the liquidation plan allows")]
+    LiquidationExceedsPlan,
+    #[msg("New owner must differ from the current owner")]
+    InvalidNewOwner,
+    #[msg("No owner transfer is pending for this key")]
+    NoPendingOwnerTransfer,
+    #[msg("Invalid guardian set")]
+    InvalidGuardianSet,
+    #[msg("Signer is not a guardian of this portfolio")]
+    NotAGuardian,
+    #[msg("A recovery is already in progress")]
+    RecoveryInProgress,
+    #[msg("No recovery is in progress")]
+    NoPendingRecovery,
+    #[msg("Guardian already approved this recovery")]
+    RecoveryAlreadyApproved,
+    #[msg("Recovery has not reached the guardian threshold")]
+    RecoveryNotApproved,
+    #[msg("Recovery timelock has not passed")]
+    RecoveryTimelockActive,
+    #[msg("Session key scope is empty or unknown")]
+    InvalidSessionScope,
+    #[msg("Session key expiry is in the past or too far ahead")]
+    InvalidSessionExpiry,
+    #[msg("Session key has expired or was issued by a previous owner")]
+    SessionKeyExpired,
+    #[msg("Session key does not allow this instruction")]
+    SessionScopeNotAllowed,
+    #[msg("Session key spend limit exceeded")]
+    SessionSpendLimitExceeded,
+    #[msg("Recurring deposit needs a positive amount, an interval of at least an hour and occurrences")]
+    InvalidRecurringDeposit,
+    #[msg("Recurring deposit is not due yet")]
+    RecurringDepositNotDue,
+    #[msg("Recurring deposit has executed all its occurrences")]
+    RecurringDepositCompleted,
+    #[msg("Source account has not approved the portfolio for the recurring amount")]
+    RecurringDepositNotApproved,
+    #[msg("Allocation band must contain its target and stay within 100%")]
+    InvalidAllocationBand,
+    #[msg("Asset classes must have members whose weights sum to 100%")]
+    InvalidAssetClass,
+    #[msg("Allocation target is set by its asset class")]
+    AllocationInAssetClass,
+    #[msg("Glide path needs matching start and end allocations of existing mints, a future target date and a step")]
+    InvalidGlidePath,
+    #[msg("Allocation target is set by the glide path")]
+    AllocationOnGlidePath,
+    #[msg("Savings goal needs a positive target amount, a future target date and a label of at most 32 characters")]
+    InvalidSavingsGoal,
+    #[msg("Portfolio has no savings goal")]
+    SavingsGoalNotSet,
+    #[msg("Contribution interval must be positive")]
+    InvalidContributionInterval,
+    #[msg("Swap accounts include a token account the portfolio must not sign for")]
+    UnauthorizedSwapAccount,
+    #[msg("Mint account missing for an allocation")]
+    MissingMintAccount,
+    #[msg("Portfolio account is not in a layout that can be migrated")]
+    UnsupportedPortfolioLayout,
+}
+
+impl From<CoreError> for SloomoError {
+    fn from(error: CoreError) -> Self {
+        match error {
+            CoreError::MathOverflow => SloomoError::MathOverflow,
+            CoreError::AllocationOverflow => SloomoError::AllocationOverflow,
+            CoreError::InsufficientLiquidity => SloomoError::InsufficientLiquidity,
+            CoreError::InvalidHierarchy => SloomoError::InvalidAssetClass,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{
+    Portfolio, RebalanceSession, SessionKey, VaultBalance, SESSION_SCOPE_REBALANCE,
+};
+use crate::error::SloomoError;
+use crate::instructions::rebalance::{read_snapshot_vaults, snapshot_values};
+use crate::utils::{validate_authority, CommonMints};
+
+/// Account structure for aborting a rebalance session
+///
+/// Remaining accounts: the vaults of `RebalanceSession::vault_mints`, in that order.
+#[derive(Accounts)]
+pub struct AbortRebalance<'info> {
+    /// Portfolio being rebalanced
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Session to abort (closed, rent returned to the owner)
+    #[account(
+        mut,
+        seeds = [b"rebalance_session", portfolio.key().as_ref()],
+        bump = session.bump,
+        has_one = portfolio,
+        close = owner
+    )]
+    pub session: Account<'info, RebalanceSession>,
+
+    /// Portfolio owner (receives the session rent)
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    /// Transaction executor (the owner or a session key at any time, anyone after the deadline)
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted otherwise)
+    #[account(
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    /// Portfolio's base asset vault
+    #[account(
+        seeds = [b"vault", portfolio.key().as_ref(), portfolio.base_mint.as_ref()],
+        bump,
+        token::authority = portfolio,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Close a rebalance session without applying its targets and unlock the portfolio
+///
+/// Legs already executed stay executed; allocation amounts are synced to the
+/// vault balances so accounting keeps following custody.
+pub fn handler(ctx: Context<AbortRebalance>) -> Result<()> {
+    let clock = Clock::get()?;
+    let is_authorized = validate_authority(
+        &ctx.accounts.portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_REBALANCE,
+        0,
+        clock.unix_timestamp,
+    )
+    .is_ok();
+    let session = &ctx.accounts.session;
+    require!(
+        is_authorized || session.is_expired(clock.unix_timestamp),
+        SloomoError::RebalanceSessionActive
+    );
+
+    let portfolio_key = ctx.accounts.portfolio.key();
+    let (base, vaults) = session
+        .snapshot
+        .split_first()
+        .ok_or(SloomoError::MissingVaultAccount)?;
+    let balances: Vec<VaultBalance> = std::iter::once(VaultBalance {
+        amount: ctx.accounts.base_vault.amount,
+        ..base.clone()
+    })
+    .chain(read_snapshot_vaults(&portfolio_key, vaults, ctx.remaining_accounts)?)
+    .collect();
+    let executed_legs = session.legs.iter().filter(|leg| leg.executed).count() as u8;
+
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.apply_vault_balances(&balances, CommonMints::symbol_for)?;
+    portfolio.apply_swap_costs(&session.snapshot, session.executed_swaps())?;
+    portfolio.total_value = sloomo_core::valuation::total_value(snapshot_values(&balances)?)
+        .map_err(SloomoError::from)?;
+    portfolio.updated_at = clock.unix_timestamp;
+    portfolio.is_rebalancing = false;
+
+    emit!(RebalanceAborted {
+        portfolio: portfolio.key(),
+        authority: ctx.accounts.authority.key(),
+        executed_legs,
+        planned_legs: session.legs.len() as u8,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Rebalance aborted after {} of {} legs",
+        executed_legs,
+        session.legs.len()
+    );
+
+    Ok(())
+}
+
+/// Rebalance session abort event
+#[event]
+pub struct RebalanceAborted {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Account that aborted the session
+    pub authority: Pubkey,
+    /// Number of legs executed before the abort
+    pub executed_legs: u8,
+    /// Number of planned swap legs
+    pub planned_legs: u8,
+    /// Abort time
+    pub timestamp: i64,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, GuardianSet};
+use crate::utils::validate_reentrancy;
+
+/// Account structure for accepting a proposed owner transfer
+#[derive(Accounts)]
+pub struct AcceptOwnerTransfer<'info> {
+    /// Portfolio being transferred
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Guardian set PDA of the portfolio (may not exist)
+    /// CHECK: Deserialized as `GuardianSet` only when initialized by this program
+    #[account(
+        mut,
+        seeds = [b"guardians", portfolio.key().as_ref()],
+        bump
+    )]
+    pub guardian_set: UncheckedAccount<'info>,
+
+    /// Transaction executor (the proposed owner)
+    pub new_owner: Signer<'info>,
+}
+
+/// Take ownership of a portfolio proposed by `propose_owner_transfer`
+///
+/// The portfolio address and its vaults stay the same: the PDA is derived
+/// from the stable `portfolio_id`, and the vaults are owned by the PDA.
+/// A guardian recovery in progress is dropped: it was agreed against the
+/// previous owner.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<AcceptOwnerTransfer>) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let new_owner = ctx.accounts.new_owner.key();
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+
+    let previous_owner = portfolio.accept_owner_transfer(&new_owner)?;
+    portfolio.updated_at = clock.unix_timestamp;
+    let recovery_cancelled = drop_pending_recovery(&ctx.accounts.guardian_set)?;
+
+    emit!(OwnerTransferred {
+        portfolio: portfolio.key(),
+        previous_owner,
+        new_owner,
+        recovery_cancelled,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Portfolio owner changed: {} -> {}", previous_owner, new_owner);
+    Ok(())
+}
+
+/// Drop the recovery in progress on the guardian set, if the portfolio has one
+fn drop_pending_recovery(guardian_set: &AccountInfo) -> Result<bool> {
+    if guardian_set.data_is_empty() || guardian_set.owner != &crate::ID {
+        return Ok(false);
+    }
+    let mut data = guardian_set.try_borrow_mut_data()?;
+    let mut set = GuardianSet::try_deserialize(&mut &data[..])?;
+    let cancelled = set.cancel_recovery();
+    set.try_serialize(&mut &mut data[..])?;
+    Ok(cancelled)
+}
+
+/// Completed owner transfer event
+#[event]
+pub struct OwnerTransferred {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Owner before the transfer
+    pub previous_owner: Pubkey,
+    /// Owner after the transfer
+    pub new_owner: Pubkey,
+    /// Whether a guardian recovery in progress was dropped
+    pub recovery_cancelled: bool,
+    /// Transfer time
+    pub timestamp: i64,
+}
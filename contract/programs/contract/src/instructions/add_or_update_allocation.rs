@@ -1,7 +1,16 @@
 use anchor_lang::prelude::*;
-use crate::state::{Portfolio, AllocationData};
+use crate::state::{
+    Portfolio, AllocationData, AllocationBand, SessionKey, MAX_ALLOCATIONS, SESSION_SCOPE_ALLOCATION,
+};
 use crate::error::SloomoError;
-use crate::utils::{validate_reentrancy, validate_token_symbol, validate_allocation_percentage};
+use crate::utils::{
+    validate_reentrancy,
+    validate_authority,
+    validate_token_symbol,
+    validate_allocation_percentage,
+    validate_allocation_band,
+    sum_target_percentages
+};
 
 /// Account structure for adding/editing allocations
 #[derive(Accounts)]
@@ -9,15 +18,21 @@ pub struct AddOrUpdateAllocation<'info> {
     /// Portfolio to be edited
     #[account(
         mut,
-        seeds = [b"portfolio", owner.key().as_ref()],
-        bump = portfolio.bump,
-        has_one = owner
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
     )]
     pub portfolio: Account<'info, Portfolio>,
 
-    /// Transaction executor (portfolio owner)
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Transaction executor (portfolio owner or a session key)
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
 }
 
 /// Add or edit allocation
@@ -27,6 +42,7 @@ pub struct AddOrUpdateAllocation<'info> {
 /// * `mint` - Token mint address
 /// * `symbol` - Token symbol (e.g.: USDC-SOLEND, USDT-MET)
 /// * `target_percentage` - Target allocation percentage (basis points)
+/// * `band` - Allowed range around the target (None: default 5% drift threshold)
 ///
 /// # Returns
 /// * `Result<()>` - Ok(()) on success, error on failure
@@ -35,25 +51,44 @@ pub fn handler(
     mint: Pubkey,
     symbol: String,
     target_percentage: u16,
+    band: Option<AllocationBand>,
 ) -> Result<()> {
     let portfolio = &mut ctx.accounts.portfolio;
     let clock = Clock::get()?;
 
     // Use common validation functions
     validate_reentrancy(portfolio)?;
+    validate_authority(
+        portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_ALLOCATION,
+        0,
+        clock.unix_timestamp,
+    )?;
     validate_token_symbol(&symbol)?;
     
     // Validation: Target allocation percentage (0-100%)
     require!(target_percentage <= 10000, SloomoError::InvalidAllocationPercentage);
+    validate_allocation_band(band, target_percentage)?;
+    require!(!portfolio.on_glide_path(&mint), SloomoError::AllocationOnGlidePath);
 
     // Search for existing allocation
     if let Some(existing_allocation) = portfolio.allocations
         .iter_mut()
         .find(|a| a.mint == mint) {
         
+        // Members of an asset class take their target from the class
+        require!(
+            existing_allocation.asset_class.is_none()
+                || existing_allocation.target_percentage == target_percentage,
+            SloomoError::AllocationInAssetClass
+        );
+
         // Update existing allocation
         existing_allocation.symbol = symbol.clone();
         existing_allocation.target_percentage = target_percentage;
+        existing_allocation.band = band;
         existing_allocation.last_yield_update = clock.unix_timestamp;
 
         msg!(
@@ -67,13 +102,14 @@ pub fn handler(
             mint,
             symbol,
             target_percentage,
+            band,
             timestamp: clock.unix_timestamp,
         });
 
     } else {
         // Add new allocation
         require!(
-            portfolio.allocations.len() < 10, // MAX_ALLOCATIONS
+            portfolio.allocations.len() < MAX_ALLOCATIONS,
             SloomoError::AllocationOverflow
         );
 
@@ -84,6 +120,10 @@ pub fn handler(
             target_percentage,
             apy: 0, // Managed client-side
             last_yield_update: clock.unix_timestamp,
+            band,
+            asset_class: None,
+            cost_basis: 0,
+            realized_pnl: 0,
         };
 
         portfolio.allocations.push(new_allocation);
@@ -99,20 +139,16 @@ pub fn handler(
             mint,
             symbol,
             target_percentage,
+            band,
             timestamp: clock.unix_timestamp,
         });
     }
 
     // Check total allocation validity
-    let total_target: u16 = portfolio.allocations.iter()
-        .map(|a| a.target_percentage)
-        .sum();
-    
-    require!(total_target <= 10000, SloomoError::AllocationOverflow);
-    
-    if total_target > 10000 {
-        msg!("Warning: Total allocation exceeds 100% ({}%)", total_target as f64 / 100.0);
-    }
+    let total_target = sum_target_percentages(
+        portfolio.allocations.iter().map(|a| a.target_percentage),
+    )?;
+    validate_allocation_percentage(total_target)?;
 
     portfolio.updated_at = clock.unix_timestamp;
 
@@ -130,6 +166,8 @@ pub struct AllocationAdded {
     pub symbol: String,
     /// Target allocation percentage
     pub target_percentage: u16,
+    /// Allowed range around the target
+    pub band: Option<AllocationBand>,
     /// Addition execution time
     pub timestamp: i64,
 }
@@ -145,6 +183,8 @@ pub struct AllocationUpdated {
     pub symbol: String,
     /// New target allocation percentage
     pub target_percentage: u16,
+    /// New allowed range around the target
+    pub band: Option<AllocationBand>,
     /// Update execution time
     pub timestamp: i64,
 }
\ No newline at end of file
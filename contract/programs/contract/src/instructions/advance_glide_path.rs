@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, AllocationTarget};
+use crate::error::SloomoError;
+use crate::utils::validate_reentrancy;
+
+/// Account structure for moving stored targets along the glide path
+#[derive(Accounts)]
+pub struct AdvanceGlidePath<'info> {
+    /// Portfolio following a glide path
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (anyone)
+    pub cranker: Signer<'info>,
+}
+
+/// Update the stored targets once the interpolated targets have moved more than the path's step
+///
+/// Rebalances apply the interpolated targets themselves; this crank keeps the
+/// stored targets (and `GlidePathStepped` subscribers) current between rebalances.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<AdvanceGlidePath>) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+    require!(portfolio.glide_path.is_some(), SloomoError::InvalidGlidePath);
+
+    if !step_glide_path(portfolio, clock.unix_timestamp) {
+        msg!("Glide path targets within step, nothing to update");
+    }
+
+    Ok(())
+}
+
+/// Advance the glide path at `now` and emit `GlidePathStepped` when the
+/// stored targets moved (shared with the rebalance instructions)
+pub(crate) fn step_glide_path(portfolio: &mut Account<Portfolio>, now: i64) -> bool {
+    let Some(targets) = portfolio.advance_glide_path(now) else {
+        return false;
+    };
+    portfolio.updated_at = now;
+
+    msg!("Glide path stepped: {} targets updated", targets.len());
+    emit!(GlidePathStepped {
+        portfolio: portfolio.key(),
+        targets,
+        timestamp: now,
+    });
+    true
+}
+
+/// Glide path step event
+#[event]
+pub struct GlidePathStepped {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// New stored target of every mint on the path
+    pub targets: Vec<AllocationTarget>,
+    /// Step time
+    pub timestamp: i64,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, GuardianSet};
+
+/// Account structure for approving a recovery
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    /// Portfolio being recovered
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Guardian set of the portfolio
+    #[account(
+        mut,
+        seeds = [b"guardians", portfolio.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = portfolio
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    /// Transaction executor (a guardian)
+    pub guardian: Signer<'info>,
+}
+
+/// Approve the recovery in progress
+///
+/// The timelock starts when the approvals reach the threshold.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<ApproveRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let guardian = ctx.accounts.guardian.key();
+    let guardian_set = &mut ctx.accounts.guardian_set;
+
+    guardian_set.approve_recovery(&guardian, clock.unix_timestamp)?;
+
+    emit!(RecoveryApproved {
+        portfolio: ctx.accounts.portfolio.key(),
+        guardian,
+        approvals: guardian_set.approval_count(),
+        threshold: guardian_set.threshold,
+        unlocks_at: guardian_set.unlocks_at(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Recovery approved by guardian {} ({}/{})",
+        guardian,
+        guardian_set.approval_count(),
+        guardian_set.threshold
+    );
+    Ok(())
+}
+
+/// Recovery approval event
+#[event]
+pub struct RecoveryApproved {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Approving guardian
+    pub guardian: Pubkey,
+    /// Approvals so far
+    pub approvals: u8,
+    /// Approvals needed
+    pub threshold: u8,
+    /// Completion time once the threshold is reached
+    pub unlocks_at: Option<i64>,
+    /// Approval time
+    pub timestamp: i64,
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::{
+    Portfolio, AllocationTarget, RebalanceSession, SessionKey, MAX_SESSION_LEGS,
+    REBALANCE_SESSION_DURATION, SESSION_SCOPE_REBALANCE,
+};
+use crate::instructions::rebalance::{snapshot_vaults, snapshot_total, plan_from_snapshot};
+use crate::instructions::advance_glide_path::step_glide_path;
+use crate::error::SloomoError;
+use crate::utils::{
+    validate_reentrancy,
+    validate_authority,
+    validate_target_allocations,
+    validate_rebalance_frequency,
+    log_plan,
+    DEFAULT_SLIPPAGE_BPS,
+};
+
+/// Account structure for starting a multi-transaction rebalance
+///
+/// Remaining accounts: the `[b"vault", portfolio, mint]` vault of every mint in
+/// `Portfolio::rebalance_mints`, in that order (vaults not created yet may be passed empty),
+/// then the mint accounts in the same order.
+#[derive(Accounts)]
+pub struct BeginRebalance<'info> {
+    /// Portfolio to be rebalanced
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Session holding the plan until commit or abort
+    #[account(
+        init,
+        payer = authority,
+        space = RebalanceSession::SIZE,
+        seeds = [b"rebalance_session", portfolio.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, RebalanceSession>,
+
+    /// Transaction executor (portfolio owner or a session key, pays the session rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        mut,
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    /// Portfolio's base asset vault
+    #[account(
+        seeds = [b"vault", portfolio.key().as_ref(), portfolio.base_mint.as_ref()],
+        bump,
+        token::authority = portfolio,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base asset mint (total value is kept in its units)
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Snapshot vault balances, plan the swap legs and open a rebalance session
+///
+/// The portfolio stays locked (`is_rebalancing`) until `commit_rebalance` or
+/// `abort_rebalance` closes the session.
+pub fn handler(
+    ctx: Context<BeginRebalance>,
+    target_allocations: Vec<AllocationTarget>,
+    slippage_bps: Option<u16>,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    // Common validation
+    validate_rebalance_frequency(portfolio, &clock)?;
+    validate_reentrancy(portfolio)?;
+
+    // Glide path mints follow their interpolated targets
+    let target_allocations = portfolio.glided_targets(&target_allocations, clock.unix_timestamp);
+    validate_target_allocations(&target_allocations)?;
+    step_glide_path(portfolio, clock.unix_timestamp);
+
+    // Snapshot every vault the plan may touch
+    let snapshot = snapshot_vaults(
+        portfolio,
+        ctx.accounts.base_vault.amount,
+        ctx.accounts.base_mint.decimals,
+        ctx.remaining_accounts,
+        &target_allocations,
+    )?;
+    let total_value = snapshot_total(&snapshot)?;
+
+    let slippage_bps = slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_BPS);
+    // Legs run in their own transactions, so a session may plan one per allocation
+    let plan = plan_from_snapshot(
+        portfolio,
+        &snapshot,
+        &target_allocations,
+        slippage_bps,
+        MAX_SESSION_LEGS as u8,
+    )?;
+    log_plan(&plan);
+
+    // A session key is charged the value the planned legs move (base units)
+    let planned_value = plan.legs.iter()
+        .try_fold(0u64, |acc, leg| acc.checked_add(leg.amount))
+        .ok_or(SloomoError::MathOverflow)?;
+    validate_authority(
+        portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_REBALANCE,
+        planned_value,
+        clock.unix_timestamp,
+    )?;
+
+    let session = &mut ctx.accounts.session;
+    session.portfolio = portfolio.key();
+    session.bump = ctx.bumps.session;
+    session.started_at = clock.unix_timestamp;
+    session.deadline = clock.unix_timestamp + REBALANCE_SESSION_DURATION;
+    session.slippage_bps = slippage_bps;
+    session.total_value_before = total_value;
+    session.target_allocations = target_allocations;
+    session.legs = RebalanceSession::legs_from_plan(&plan, &snapshot)?;
+    session.snapshot = snapshot;
+
+    // Lock the portfolio across transactions
+    portfolio.is_rebalancing = true;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    emit!(RebalanceBegun {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        total_value,
+        planned_legs: session.legs.len() as u8,
+        deadline: session.deadline,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Rebalance session opened: {} legs planned over {}, deadline {}",
+        session.legs.len(),
+        total_value,
+        session.deadline
+    );
+
+    Ok(())
+}
+
+/// Rebalance session start event
+#[event]
+pub struct RebalanceBegun {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Total value across all portfolio vaults
+    pub total_value: u64,
+    /// Number of planned swap legs
+    pub planned_legs: u8,
+    /// Time after which the session can be aborted
+    pub deadline: i64,
+    /// Session start time
+    pub timestamp: i64,
+}
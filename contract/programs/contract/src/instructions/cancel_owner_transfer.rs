@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::Portfolio;
+
+/// Account structure for cancelling a pending owner transfer
+#[derive(Accounts)]
+pub struct CancelOwnerTransfer<'info> {
+    /// Portfolio with a pending transfer
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (current portfolio owner)
+    pub owner: Signer<'info>,
+}
+
+/// Withdraw the pending owner transfer proposal
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<CancelOwnerTransfer>) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    let cancelled_owner = portfolio.cancel_owner_transfer()?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    emit!(OwnerTransferCancelled {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        cancelled_owner,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Owner transfer to {} cancelled", cancelled_owner);
+    Ok(())
+}
+
+/// Owner transfer cancellation event
+#[event]
+pub struct OwnerTransferCancelled {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Current portfolio owner
+    pub owner: Pubkey,
+    /// Owner that had been proposed
+    pub cancelled_owner: Pubkey,
+    /// Cancellation time
+    pub timestamp: i64,
+}
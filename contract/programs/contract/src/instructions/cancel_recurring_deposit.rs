@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, RecurringDeposit};
+
+/// Account structure for cancelling a recurring deposit
+#[derive(Accounts)]
+pub struct CancelRecurringDeposit<'info> {
+    /// Portfolio receiving the deposits
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Schedule to cancel (closed, rent returned to the owner)
+    #[account(
+        mut,
+        seeds = [b"recurring_deposit", portfolio.key().as_ref()],
+        bump = recurring_deposit.bump,
+        has_one = portfolio,
+        close = owner
+    )]
+    pub recurring_deposit: Account<'info, RecurringDeposit>,
+
+    /// Transaction executor (portfolio owner)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Cancel the recurring deposit
+///
+/// The SPL approval on the source account is left to the owner to revoke.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<CancelRecurringDeposit>) -> Result<()> {
+    let clock = Clock::get()?;
+    let recurring_deposit = &ctx.accounts.recurring_deposit;
+
+    emit!(RecurringDepositCancelled {
+        portfolio: ctx.accounts.portfolio.key(),
+        owner: ctx.accounts.owner.key(),
+        executed: recurring_deposit.executed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Recurring deposit cancelled after {} occurrences",
+        recurring_deposit.executed
+    );
+    Ok(())
+}
+
+/// Recurring deposit cancellation event
+#[event]
+pub struct RecurringDepositCancelled {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Occurrences executed before the cancellation
+    pub executed: u32,
+    /// Cancellation time
+    pub timestamp: i64,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, RebalanceRecord};
+
+/// Account structure for closing a rebalance record
+#[derive(Accounts)]
+pub struct CloseRebalanceRecord<'info> {
+    /// Portfolio the record belongs to
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Record to close (rent returned to the owner)
+    #[account(
+        mut,
+        seeds = [b"rebalance_record", portfolio.key().as_ref(), &record.index.to_le_bytes()],
+        bump = record.bump,
+        has_one = portfolio,
+        close = owner
+    )]
+    pub record: Account<'info, RebalanceRecord>,
+
+    /// Transaction executor (portfolio owner)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Close a rebalance record and reclaim its rent
+///
+/// `Portfolio::rebalance_count` keeps counting, so closed indexes are not reused.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<CloseRebalanceRecord>) -> Result<()> {
+    let clock = Clock::get()?;
+    let record = &ctx.accounts.record;
+
+    emit!(RebalanceRecordClosed {
+        portfolio: ctx.accounts.portfolio.key(),
+        owner: ctx.accounts.owner.key(),
+        index: record.index,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Rebalance record {} closed", record.index);
+    Ok(())
+}
+
+/// Rebalance record closing event
+#[event]
+pub struct RebalanceRecordClosed {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Index of the closed record
+    pub index: u64,
+    /// Closing time
+    pub timestamp: i64,
+}
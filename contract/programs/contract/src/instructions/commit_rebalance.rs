@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{
+    Portfolio, RebalanceSession, RebalanceRecord, SessionKey, SESSION_SCOPE_REBALANCE,
+};
+use crate::error::SloomoError;
+use crate::instructions::rebalance::{balance_in, read_snapshot_vaults, snapshot_total};
+use crate::utils::{validate_authority, CommonMints};
+use crate::state::VaultBalance;
+
+/// Account structure for committing a rebalance session
+///
+/// Remaining accounts: the vaults of `RebalanceSession::vault_mints`, in that order.
+#[derive(Accounts)]
+pub struct CommitRebalance<'info> {
+    /// Portfolio being rebalanced
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Session to commit (closed, rent returned to the owner)
+    #[account(
+        mut,
+        seeds = [b"rebalance_session", portfolio.key().as_ref()],
+        bump = session.bump,
+        has_one = portfolio,
+        close = owner
+    )]
+    pub session: Account<'info, RebalanceSession>,
+
+    /// Receipt of this rebalance, numbered by `Portfolio::rebalance_count`
+    #[account(
+        init,
+        payer = authority,
+        space = RebalanceRecord::SIZE,
+        seeds = [
+            b"rebalance_record",
+            portfolio.key().as_ref(),
+            &portfolio.rebalance_count.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub record: Account<'info, RebalanceRecord>,
+
+    /// Portfolio owner (receives the session rent)
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    /// Transaction executor (portfolio owner or a session key, pays the record rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    /// Portfolio's base asset vault
+    #[account(
+        seeds = [b"vault", portfolio.key().as_ref(), portfolio.base_mint.as_ref()],
+        bump,
+        token::authority = portfolio,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Verify every leg landed, apply the targets, write the rebalance record and
+/// unlock the portfolio
+pub fn handler(ctx: Context<CommitRebalance>) -> Result<()> {
+    let clock = Clock::get()?;
+    validate_authority(
+        &ctx.accounts.portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_REBALANCE,
+        0,
+        clock.unix_timestamp,
+    )?;
+    let session = &ctx.accounts.session;
+    require!(session.all_legs_executed(), SloomoError::LegNotExecuted);
+
+    // Vault balances may only have moved by the recorded legs
+    let portfolio_key = ctx.accounts.portfolio.key();
+    let (base, vaults) = session
+        .snapshot
+        .split_first()
+        .ok_or(SloomoError::MissingVaultAccount)?;
+    let balances: Vec<VaultBalance> = std::iter::once(VaultBalance {
+        amount: ctx.accounts.base_vault.amount,
+        ..base.clone()
+    })
+    .chain(read_snapshot_vaults(&portfolio_key, vaults, ctx.remaining_accounts)?)
+    .collect();
+    session.verify_balances(&balances)?;
+    let total_value = snapshot_total(&balances)?;
+
+    // Receipt of the executed legs and the shares before and after
+    let record = &mut ctx.accounts.record;
+    record.portfolio = portfolio_key;
+    record.bump = ctx.bumps.record;
+    record.index = ctx.accounts.portfolio.rebalance_count;
+    record.started_at = session.started_at;
+    record.committed_at = clock.unix_timestamp;
+    record.slippage_bps = session.slippage_bps;
+    record.total_value_before = session.total_value_before;
+    record.total_value_after = total_value;
+    record.legs = RebalanceRecord::legs_from_session(session);
+    record.allocations = RebalanceRecord::allocation_changes(session, &balances, total_value)?;
+
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.apply_vault_balances(&balances, CommonMints::symbol_for)?;
+    portfolio.apply_swap_costs(&session.snapshot, session.executed_swaps())?;
+    portfolio.set_target_allocations(
+        &session.target_allocations,
+        |mint| balance_in(&balances, mint),
+        CommonMints::symbol_for,
+    )?;
+
+    // State update
+    portfolio.last_rebalance = clock.unix_timestamp;
+    portfolio.updated_at = clock.unix_timestamp;
+    portfolio.total_value = total_value;
+    portfolio.is_rebalancing = false;
+    portfolio.rebalance_count = portfolio.rebalance_count
+        .checked_add(1)
+        .ok_or(SloomoError::MathOverflow)?;
+    portfolio.add_performance_snapshot(clock.unix_timestamp)?;
+
+    emit!(RebalanceCommitted {
+        portfolio: portfolio_key,
+        owner: portfolio.owner,
+        executed_legs: session.legs.len() as u8,
+        record_index: record.index,
+        total_value_before: session.total_value_before,
+        total_value_after: total_value,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Rebalance committed: {} legs, value {} -> {}",
+        session.legs.len(),
+        session.total_value_before,
+        total_value
+    );
+
+    Ok(())
+}
+
+/// Rebalance session commit event
+#[event]
+pub struct RebalanceCommitted {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Number of executed swap legs
+    pub executed_legs: u8,
+    /// Index of the `RebalanceRecord` written
+    pub record_index: u64,
+    /// Total vault value at `begin_rebalance`
+    pub total_value_before: u64,
+    /// Total vault value after the legs
+    pub total_value_after: u64,
+    /// Commit time
+    pub timestamp: i64,
+}
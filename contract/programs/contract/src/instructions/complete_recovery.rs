@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, GuardianSet};
+use crate::utils::validate_reentrancy;
+
+/// Account structure for completing a recovery
+#[derive(Accounts)]
+pub struct CompleteRecovery<'info> {
+    /// Portfolio being recovered
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Guardian set of the portfolio
+    #[account(
+        mut,
+        seeds = [b"guardians", portfolio.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = portfolio
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    /// Transaction executor (anyone, typically the new owner)
+    pub executor: Signer<'info>,
+}
+
+/// Reassign the portfolio owner once the recovery is approved and the
+/// timelock has passed without a veto
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<CompleteRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let portfolio = &mut ctx.accounts.portfolio;
+
+    validate_reentrancy(portfolio)?;
+
+    let new_owner = ctx.accounts.guardian_set.complete_recovery(clock.unix_timestamp)?;
+    let previous_owner = portfolio.reassign_owner(new_owner);
+    portfolio.updated_at = clock.unix_timestamp;
+
+    emit!(RecoveryCompleted {
+        portfolio: portfolio.key(),
+        previous_owner,
+        new_owner,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Portfolio recovered: {} -> {}", previous_owner, new_owner);
+    Ok(())
+}
+
+/// Completed recovery event
+#[event]
+pub struct RecoveryCompleted {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Owner before the recovery
+    pub previous_owner: Pubkey,
+    /// Owner after the recovery
+    pub new_owner: Pubkey,
+    /// Completion time
+    pub timestamp: i64,
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Portfolio, RecurringDeposit};
+use crate::error::SloomoError;
+use crate::utils::{
+    validate_reentrancy, transfer_to_vault_as_delegate, received_after_fee,
+};
+
+/// Account structure for executing a due recurring deposit
+#[derive(Accounts)]
+pub struct CrankRecurringDeposit<'info> {
+    /// Portfolio receiving the deposit
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Schedule
+    #[account(
+        mut,
+        seeds = [b"recurring_deposit", portfolio.key().as_ref()],
+        bump = recurring_deposit.bump,
+        has_one = portfolio,
+        has_one = source
+    )]
+    pub recurring_deposit: Account<'info, RecurringDeposit>,
+
+    /// Owner's base asset account (must still belong to the current owner)
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = portfolio.owner,
+        token::token_program = token_program
+    )]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    /// Portfolio's base asset vault (destination)
+    #[account(
+        mut,
+        seeds = [b"vault", portfolio.key().as_ref(), base_mint.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = portfolio,
+        token::token_program = token_program
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base asset mint
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Transaction executor (anyone)
+    pub cranker: Signer<'info>,
+
+    /// Token program of the base asset (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pull the scheduled amount into the base vault once it is due
+///
+/// Credited like any base asset deposit; occurrences missed while nobody
+/// cranked are skipped.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<CrankRecurringDeposit>) -> Result<()> {
+    let clock = Clock::get()?;
+    validate_reentrancy(&ctx.accounts.portfolio)?;
+
+    let amount = ctx.accounts.recurring_deposit.amount;
+    let occurrence = ctx.accounts.recurring_deposit.record_execution(clock.unix_timestamp)?;
+
+    // The owner's approval must cover this occurrence
+    let portfolio_key = ctx.accounts.portfolio.key();
+    let source = &ctx.accounts.source;
+    require!(
+        source.delegate == Some(portfolio_key).into() && source.delegated_amount >= amount,
+        SloomoError::RecurringDepositNotApproved
+    );
+    require!(source.amount >= amount, SloomoError::InsufficientBalance);
+
+    let portfolio_id = ctx.accounts.portfolio.portfolio_id;
+    let signer_seeds: &[&[u8]] = &[
+        b"portfolio",
+        portfolio_id.as_ref(),
+        &[ctx.accounts.portfolio.bump],
+    ];
+    transfer_to_vault_as_delegate(
+        &ctx.accounts.source,
+        &ctx.accounts.base_vault,
+        &ctx.accounts.base_mint,
+        &ctx.accounts.portfolio.to_account_info(),
+        &ctx.accounts.token_program,
+        &[signer_seeds],
+        amount,
+    )?;
+
+    // Credit what the vault received (net of any Token-2022 transfer fee)
+    let received = received_after_fee(
+        &ctx.accounts.base_mint.to_account_info(),
+        amount,
+        clock.epoch,
+    )?;
+    let portfolio = &mut ctx.accounts.portfolio;
+    let base_mint = portfolio.base_mint;
+    portfolio.record_deposit(&base_mint, received, received)?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    // Add performance snapshot
+    portfolio.add_performance_snapshot(clock.unix_timestamp)?;
+
+    let recurring_deposit = &ctx.accounts.recurring_deposit;
+    emit!(RecurringDepositExecuted {
+        portfolio: portfolio_key,
+        cranker: ctx.accounts.cranker.key(),
+        amount,
+        received,
+        occurrence,
+        next_due_at: recurring_deposit.next_due_at,
+        completed: recurring_deposit.is_completed(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Recurring deposit {} executed: {} deposited, next due {}",
+        occurrence,
+        received,
+        recurring_deposit.next_due_at
+    );
+    Ok(())
+}
+
+/// Recurring deposit execution event
+#[event]
+pub struct RecurringDepositExecuted {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Account that cranked the deposit
+    pub cranker: Pubkey,
+    /// Amount pulled (base asset units)
+    pub amount: u64,
+    /// Amount the vault received after transfer fees
+    pub received: u64,
+    /// Occurrence number (1-based)
+    pub occurrence: u32,
+    /// Next due time
+    pub next_due_at: i64,
+    /// Whether this was the last scheduled occurrence
+    pub completed: bool,
+    /// Execution time
+    pub timestamp: i64,
+}
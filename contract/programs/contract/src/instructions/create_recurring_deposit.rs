@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Portfolio, RecurringDeposit};
+use crate::error::SloomoError;
+
+/// Account structure for scheduling a recurring deposit
+#[derive(Accounts)]
+pub struct CreateRecurringDeposit<'info> {
+    /// Portfolio receiving the deposits
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Schedule (replaced if one exists)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = RecurringDeposit::SIZE,
+        seeds = [b"recurring_deposit", portfolio.key().as_ref()],
+        bump
+    )]
+    pub recurring_deposit: Account<'info, RecurringDeposit>,
+
+    /// Owner's base asset account the deposits are pulled from
+    #[account(
+        token::mint = base_mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base asset mint
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Transaction executor (portfolio owner, pays the schedule rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program of the base asset (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Schedule pulling `amount` of the base asset every `interval` seconds
+///
+/// The owner approves the portfolio PDA as delegate of `source` (SPL `approve`)
+/// for the total to be pulled; anyone may then crank each due occurrence.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `amount` - Amount per occurrence (base asset units)
+/// * `interval` - Seconds between occurrences (at least `MIN_RECURRING_INTERVAL`)
+/// * `start_at` - First due time (immediately if None)
+/// * `max_occurrences` - Number of occurrences (unlimited if None)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<CreateRecurringDeposit>,
+    amount: u64,
+    interval: i64,
+    start_at: Option<i64>,
+    max_occurrences: Option<u32>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let portfolio = &ctx.accounts.portfolio;
+    let recurring_deposit = &mut ctx.accounts.recurring_deposit;
+
+    recurring_deposit.configure(amount, interval, start_at, max_occurrences, clock.unix_timestamp)?;
+    recurring_deposit.portfolio = portfolio.key();
+    recurring_deposit.source = ctx.accounts.source.key();
+    recurring_deposit.bump = ctx.bumps.recurring_deposit;
+    recurring_deposit.created_at = clock.unix_timestamp;
+
+    emit!(RecurringDepositCreated {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        source: recurring_deposit.source,
+        amount,
+        interval,
+        next_due_at: recurring_deposit.next_due_at,
+        max_occurrences,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Recurring deposit scheduled: {} every {}s from {}",
+        amount,
+        interval,
+        recurring_deposit.next_due_at
+    );
+    Ok(())
+}
+
+/// Recurring deposit schedule event
+#[event]
+pub struct RecurringDepositCreated {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Source account
+    pub source: Pubkey,
+    /// Amount per occurrence (base asset units)
+    pub amount: u64,
+    /// Seconds between occurrences
+    pub interval: i64,
+    /// First due time
+    pub next_due_at: i64,
+    /// Number of occurrences (unlimited if None)
+    pub max_occurrences: Option<u32>,
+    /// Schedule time
+    pub timestamp: i64,
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, SessionKey};
+use crate::error::SloomoError;
+
+/// Account structure for issuing a session key
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct CreateSessionKey<'info> {
+    /// Portfolio the key acts on
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Session key account (reissuing resets the grant and the amount spent)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = SessionKey::SIZE,
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.as_ref()],
+        bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    /// Transaction executor (portfolio owner, pays the session key rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Issue or reissue a session key
+///
+/// The key may then sign the instructions in `scopes` in place of the owner
+/// until `expires_at`. Withdrawals and ownership changes always need the owner.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `authority` - Session key (e.g. generated on the device)
+/// * `scopes` - Allowed instruction groups (`SESSION_SCOPE_*` bits)
+/// * `expires_at` - Expiry time (at most `MAX_SESSION_DURATION` ahead)
+/// * `spend_limit` - Most the key may move in total (base asset units), unlimited if None
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<CreateSessionKey>,
+    authority: Pubkey,
+    scopes: u8,
+    expires_at: i64,
+    spend_limit: Option<u64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let portfolio = &ctx.accounts.portfolio;
+
+    require_keys_neq!(authority, portfolio.owner, SloomoError::Unauthorized);
+    SessionKey::validate_grant(scopes, expires_at, clock.unix_timestamp)?;
+
+    let session_key = &mut ctx.accounts.session_key;
+    session_key.portfolio = portfolio.key();
+    session_key.authority = authority;
+    session_key.issued_by = portfolio.owner;
+    session_key.bump = ctx.bumps.session_key;
+    session_key.scopes = scopes;
+    session_key.expires_at = expires_at;
+    session_key.spend_limit = spend_limit;
+    session_key.spent = 0;
+    session_key.created_at = clock.unix_timestamp;
+
+    emit!(SessionKeyCreated {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        authority,
+        scopes,
+        expires_at,
+        spend_limit,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Session key {} issued until {}", authority, expires_at);
+    Ok(())
+}
+
+/// Session key issue event
+#[event]
+pub struct SessionKeyCreated {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Session key
+    pub authority: Pubkey,
+    /// Allowed instruction groups
+    pub scopes: u8,
+    /// Expiry time
+    pub expires_at: i64,
+    /// Spend limit (base asset units)
+    pub spend_limit: Option<u64>,
+    /// Issue time
+    pub timestamp: i64,
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, Mint, SyncNative, TokenAccount, TokenInterface};
+use crate::state::Portfolio;
+use crate::error::SloomoError;
+use crate::utils::{validate_reentrancy, validate_amount, rescale_decimals};
+
+/// Account structure for native SOL deposit
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    /// Destination portfolio for deposit
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Portfolio's wSOL vault (destination, wrapped in place)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"vault", portfolio.key().as_ref(), wsol_mint.key().as_ref()],
+        bump,
+        token::mint = wsol_mint,
+        token::authority = portfolio,
+        token::token_program = token_program
+    )]
+    pub portfolio_wsol_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Native mint (wSOL)
+    #[account(address = native_mint::ID @ SloomoError::InvalidTokenMint)]
+    pub wsol_mint: InterfaceAccount<'info, Mint>,
+
+    /// Base asset mint (total value is kept in its units)
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Transaction executor (portfolio owner, source of the lamports)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit native SOL
+///
+/// Lamports move straight into the portfolio's wSOL vault, which `sync_native`
+/// then credits as wSOL, so the owner needs no wSOL account of their own.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `lamports` - Deposit amount (lamports)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<DepositSol>,
+    lamports: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Use common validation functions
+    validate_amount(lamports)?;
+    validate_reentrancy(&ctx.accounts.portfolio)?;
+
+    // Only SOL the portfolio tracks can be deposited
+    let wsol_mint = ctx.accounts.wsol_mint.key();
+    require!(
+        ctx.accounts.portfolio.holds_mint(&wsol_mint),
+        SloomoError::InvalidTokenMint
+    );
+
+    // Lamports: owner -> wSOL vault
+    let transfer_accounts = SystemTransfer {
+        from: ctx.accounts.owner.to_account_info(),
+        to: ctx.accounts.portfolio_wsol_vault.to_account_info(),
+    };
+    system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts),
+        lamports,
+    )?;
+
+    // Wrap: token amount follows the vault lamports
+    let sync_accounts = SyncNative {
+        account: ctx.accounts.portfolio_wsol_vault.to_account_info(),
+    };
+    token_interface::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        sync_accounts,
+    ))?;
+
+    let value = rescale_decimals(
+        lamports,
+        ctx.accounts.wsol_mint.decimals,
+        ctx.accounts.base_mint.decimals,
+    )?;
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.record_deposit(&wsol_mint, lamports, value)?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    // Add performance snapshot
+    portfolio.add_performance_snapshot(clock.unix_timestamp)?;
+
+    // Emit event
+    emit!(SolDeposited {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "SOL deposit completed: {} SOL deposited",
+        lamports as f64 / 1_000_000_000.0 // SOL has 9 decimals
+    );
+
+    Ok(())
+}
+
+/// Native SOL deposit execution event
+#[event]
+pub struct SolDeposited {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Deposit amount (lamports)
+    pub lamports: u64,
+    /// Deposit execution time
+    pub timestamp: i64,
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Portfolio, SessionKey, SESSION_SCOPE_DEPOSIT};
+use crate::error::SloomoError;
+use crate::utils::{
+    validate_reentrancy, validate_amount, validate_authority, rescale_decimals, transfer_to_vault, received_after_fee,
+};
+
+/// Account structure for depositing any token the portfolio holds
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    /// Destination portfolio for deposit
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Owner's token account (source)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = portfolio.owner,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Portfolio's vault for the mint (destination)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"vault", portfolio.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = portfolio,
+        token::token_program = token_program
+    )]
+    pub portfolio_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Deposited mint (base asset or an allocation)
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Base asset mint (total value is kept in its units)
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Transaction executor (portfolio owner, or a session key approved as
+    /// delegate of the owner's token account)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        mut,
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    /// Token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit a token held by the portfolio
+///
+/// The matching allocation is credited with the amount the vault received and
+/// the total value with the same amount in base asset decimals.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `amount` - Deposit amount (base units of `mint`)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<DepositToken>,
+    amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let mint = ctx.accounts.mint.key();
+
+    // Use common validation functions
+    validate_amount(amount)?;
+    validate_reentrancy(&ctx.accounts.portfolio)?;
+    validate_authority(
+        &ctx.accounts.portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_DEPOSIT,
+        rescale_decimals(amount, ctx.accounts.mint.decimals, ctx.accounts.base_mint.decimals)?,
+        clock.unix_timestamp,
+    )?;
+
+    // Whitelist: the base asset and allocated mints
+    require!(
+        ctx.accounts.portfolio.holds_mint(&mint),
+        SloomoError::InvalidTokenMint
+    );
+
+    // Validation: User's token balance
+    require!(
+        ctx.accounts.user_token_account.amount >= amount,
+        SloomoError::InsufficientBalance
+    );
+
+    // Execute token transfer (checked against the mint's decimals)
+    transfer_to_vault(
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.portfolio_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    // Credit what the vault received (net of any Token-2022 transfer fee)
+    let received = received_after_fee(
+        &ctx.accounts.mint.to_account_info(),
+        amount,
+        clock.epoch,
+    )?;
+    let value = rescale_decimals(
+        received,
+        ctx.accounts.mint.decimals,
+        ctx.accounts.base_mint.decimals,
+    )?;
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.record_deposit(&mint, received, value)?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    // Add performance snapshot
+    portfolio.add_performance_snapshot(clock.unix_timestamp)?;
+
+    // Emit event
+    emit!(TokenDeposited {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        mint,
+        amount,
+        received,
+        value,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Token deposit completed: {} of {} deposited (value {})",
+        amount,
+        mint,
+        value
+    );
+
+    Ok(())
+}
+
+/// Token deposit execution event
+#[event]
+pub struct TokenDeposited {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Deposited mint
+    pub mint: Pubkey,
+    /// Deposit amount (base units of `mint`)
+    pub amount: u64,
+    /// Amount the vault received after transfer fees
+    pub received: u64,
+    /// Amount credited to the total value (base asset decimals)
+    pub value: u64,
+    /// Deposit execution time
+    pub timestamp: i64,
+}
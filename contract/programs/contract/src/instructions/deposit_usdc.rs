@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{Mint, TokenAccount, Token, Transfer, transfer},
+    token_interface::{Mint, TokenAccount, TokenInterface},
     associated_token::AssociatedToken,
 };
 use crate::state::Portfolio;
 use crate::error::SloomoError;
-use crate::utils::{validate_reentrancy, validate_amount};
+use crate::utils::{
+    validate_reentrancy, validate_amount, transfer_to_vault, received_after_fee,
+};
 
 /// Account structure for USDC deposit
 #[derive(Accounts)]
@@ -13,7 +15,7 @@ pub struct DepositUsdc<'info> {
     /// Destination portfolio for deposit
     #[account(
         mut,
-        seeds = [b"portfolio", owner.key().as_ref()],
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
         bump = portfolio.bump,
         has_one = owner
     )]
@@ -23,9 +25,10 @@ pub struct DepositUsdc<'info> {
     #[account(
         mut,
         associated_token::mint = usdc_mint,
-        associated_token::authority = owner
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Portfolio's USDC vault (destination)
     #[account(
@@ -34,19 +37,25 @@ pub struct DepositUsdc<'info> {
         seeds = [b"vault", portfolio.key().as_ref(), usdc_mint.key().as_ref()],
         bump,
         token::mint = usdc_mint,
-        token::authority = portfolio
+        token::authority = portfolio,
+        token::token_program = token_program
     )]
-    pub portfolio_usdc_vault: Account<'info, TokenAccount>,
+    pub portfolio_usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// USDC mint (must be the portfolio's base asset)
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
-    /// USDC mint
-    pub usdc_mint: Account<'info, Mint>,
+    /// Base asset mint (the same account as `usdc_mint`)
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
 
     /// Transaction executor (portfolio owner)
     #[account(mut)]
     pub owner: Signer<'info>,
 
-    /// SPL token program
-    pub token_program: Program<'info, Token>,
+    /// Token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
     /// Associated token program
     pub associated_token_program: Program<'info, AssociatedToken>,
     /// System program
@@ -55,6 +64,9 @@ pub struct DepositUsdc<'info> {
 
 /// Deposit USDC
 ///
+/// Deprecated: only accepts the base asset, booked like `deposit_token`, which
+/// also accepts any held mint.
+///
 /// # Arguments
 /// * `ctx` - Transaction context
 /// * `amount` - Deposit amount (USDC basis)
@@ -65,12 +77,11 @@ pub fn handler(
     ctx: Context<DepositUsdc>,
     amount: u64,
 ) -> Result<()> {
-    let portfolio = &mut ctx.accounts.portfolio;
     let clock = Clock::get()?;
 
     // Use common validation functions
     validate_amount(amount)?;
-    validate_reentrancy(portfolio)?;
+    validate_reentrancy(&ctx.accounts.portfolio)?;
     
     // Validation: User's USDC balance
     require!(
@@ -78,24 +89,25 @@ pub fn handler(
         SloomoError::InsufficientBalance
     );
 
-    // Execute SPL Token transfer
-    let transfer_instruction = Transfer {
-        from: ctx.accounts.user_usdc_account.to_account_info(),
-        to: ctx.accounts.portfolio_usdc_vault.to_account_info(),
-        authority: ctx.accounts.owner.to_account_info(),
-    };
-
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        transfer_instruction,
-    );
-
-    transfer(cpi_ctx, amount)?;
+    // Execute token transfer (checked against the mint's decimals)
+    transfer_to_vault(
+        &ctx.accounts.user_usdc_account,
+        &ctx.accounts.portfolio_usdc_vault,
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
 
-    // Update portfolio total value
-    portfolio.total_value = portfolio.total_value
-        .checked_add(amount)
-        .ok_or(SloomoError::MathOverflow)?;
+    // Credit what the vault received (net of any Token-2022 transfer fee)
+    let received = received_after_fee(
+        &ctx.accounts.usdc_mint.to_account_info(),
+        amount,
+        clock.epoch,
+    )?;
+    let portfolio = &mut ctx.accounts.portfolio;
+    let base_mint = portfolio.base_mint;
+    portfolio.record_deposit(&base_mint, received, received)?;
 
     portfolio.updated_at = clock.unix_timestamp;
 
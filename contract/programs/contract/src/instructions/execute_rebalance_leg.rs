@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    Portfolio, RebalanceSession, SessionKey, SESSION_SCOPE_REBALANCE,
+};
+use crate::error::SloomoError;
+use crate::instructions::rebalance::read_snapshot_vaults;
+use crate::utils::{validate_authority, validate_swap_accounts};
+
+/// Account structure for executing one planned swap leg
+///
+/// Remaining accounts: the vaults of the session snapshot (base vault first, in
+/// snapshot order), then the accounts of the swap instruction in the order the
+/// swap program expects them. The portfolio PDA signs for its vaults.
+#[derive(Accounts)]
+pub struct ExecuteRebalanceLeg<'info> {
+    /// Portfolio being rebalanced
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Open rebalance session
+    #[account(
+        mut,
+        seeds = [b"rebalance_session", portfolio.key().as_ref()],
+        bump = session.bump,
+        has_one = portfolio
+    )]
+    pub session: Account<'info, RebalanceSession>,
+
+    /// Transaction executor (portfolio owner or a session key)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    /// Vault of the mint sold
+    #[account(
+        mut,
+        seeds = [b"vault", portfolio.key().as_ref(), from_mint.key().as_ref()],
+        bump,
+        token::mint = from_mint,
+        token::authority = portfolio,
+    )]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault of the mint bought (created on first purchase)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"vault", portfolio.key().as_ref(), to_mint.key().as_ref()],
+        bump,
+        token::mint = to_mint,
+        token::authority = portfolio,
+    )]
+    pub destination_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint sold
+    pub from_mint: InterfaceAccount<'info, Mint>,
+
+    /// Mint bought
+    pub to_mint: InterfaceAccount<'info, Mint>,
+
+    /// Swap program (e.g. Jupiter)
+    /// CHECK: Must be executable and on the portfolio's allowlist; outcomes are checked on the vaults
+    #[account(
+        executable,
+        constraint = portfolio.swap_programs.contains(&swap_program.key()) @ SloomoError::InvalidSwapProgram
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// Token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Execute planned leg `leg_index` through `swap_program`
+///
+/// The leg fails unless the source vault loses at most the planned amount, the
+/// destination vault gains at least the planned minimum and no other vault moves.
+/// The swap accounts may not include other portfolio vaults or token accounts
+/// delegated to the portfolio.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteRebalanceLeg<'info>>,
+    leg_index: u8,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    validate_authority(
+        &ctx.accounts.portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_REBALANCE,
+        0,
+        clock.unix_timestamp,
+    )?;
+    let session = &ctx.accounts.session;
+
+    require!(!session.is_expired(clock.unix_timestamp), SloomoError::RebalanceSessionExpired);
+    let leg = session
+        .legs
+        .get(leg_index as usize)
+        .ok_or(SloomoError::InvalidLegIndex)?;
+    require!(!leg.executed, SloomoError::LegAlreadyExecuted);
+    require_keys_eq!(ctx.accounts.from_mint.key(), leg.from_mint, SloomoError::InvalidTokenMint);
+    require_keys_eq!(ctx.accounts.to_mint.key(), leg.to_mint, SloomoError::InvalidTokenMint);
+    let leg = leg.clone();
+
+    // Every snapshot vault is read around the swap, not just the leg's two
+    let portfolio_key = ctx.accounts.portfolio.key();
+    let snapshot = &session.snapshot;
+    require!(
+        ctx.remaining_accounts.len() >= snapshot.len(),
+        SloomoError::MissingVaultAccount
+    );
+    let (vault_accounts, swap_accounts) = ctx.remaining_accounts.split_at(snapshot.len());
+    let vaults_before = read_snapshot_vaults(&portfolio_key, snapshot, vault_accounts)?;
+
+    // The PDA signs only for the leg's two vaults
+    validate_swap_accounts(
+        &portfolio_key,
+        &[ctx.accounts.source_vault.key(), ctx.accounts.destination_vault.key()],
+        swap_accounts,
+    )?;
+
+    let source_before = ctx.accounts.source_vault.amount;
+    let destination_before = ctx.accounts.destination_vault.amount;
+
+    // Swap with the portfolio PDA signing for its vaults
+    let instruction = Instruction {
+        program_id: ctx.accounts.swap_program.key(),
+        accounts: swap_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == portfolio_key,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: swap_data,
+    };
+    let portfolio_id = ctx.accounts.portfolio.portfolio_id;
+    let signer_seeds: &[&[u8]] = &[
+        b"portfolio",
+        portfolio_id.as_ref(),
+        &[ctx.accounts.portfolio.bump],
+    ];
+    invoke_signed(
+        &instruction,
+        &[swap_accounts, &[ctx.accounts.swap_program.to_account_info()]].concat(),
+        &[signer_seeds],
+    )?;
+    let vaults_after = read_snapshot_vaults(&portfolio_key, snapshot, vault_accounts)?;
+    RebalanceSession::verify_leg_balances(&leg, &vaults_before, &vaults_after)?;
+
+    // Outcome measured on the vaults, not reported by the swap program
+    ctx.accounts.source_vault.reload()?;
+    ctx.accounts.destination_vault.reload()?;
+    let spent = source_before
+        .checked_sub(ctx.accounts.source_vault.amount)
+        .ok_or(SloomoError::SwapExecutionFailed)?;
+    let received = ctx.accounts.destination_vault.amount
+        .checked_sub(destination_before)
+        .ok_or(SloomoError::SwapExecutionFailed)?;
+
+    let session = &mut ctx.accounts.session;
+    session.record_leg(leg_index as usize, spent, received)?;
+
+    emit!(RebalanceLegExecuted {
+        portfolio: portfolio_key,
+        leg_index,
+        from_mint: ctx.accounts.from_mint.key(),
+        to_mint: ctx.accounts.to_mint.key(),
+        spent,
+        received,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Rebalance leg {} executed: {} {} -> {} {}",
+        leg_index,
+        spent,
+        ctx.accounts.from_mint.key(),
+        received,
+        ctx.accounts.to_mint.key()
+    );
+
+    Ok(())
+}
+
+/// Rebalance leg execution event
+#[event]
+pub struct RebalanceLegExecuted {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Index of the leg in the session plan
+    pub leg_index: u8,
+    /// Mint sold
+    pub from_mint: Pubkey,
+    /// Mint bought
+    pub to_mint: Pubkey,
+    /// Amount taken from the source vault
+    pub spent: u64,
+    /// Amount received by the destination vault
+    pub received: u64,
+    /// Execution time
+    pub timestamp: i64,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, DriftReport};
+
+/// Account structure for reading allocation drift
+#[derive(Accounts)]
+pub struct GetDrift<'info> {
+    /// Portfolio to check (read only)
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+/// Measure how far every allocation and asset class is from its effective target
+///
+/// Writes nothing; the result is returned Borsh-encoded through
+/// `set_return_data`, so clients read it with `simulateTransaction`.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<DriftReport>` - Per-allocation and per-class drift and band checks
+pub fn handler(ctx: Context<GetDrift>) -> Result<DriftReport> {
+    let clock = Clock::get()?;
+    ctx.accounts.portfolio.drift_report(clock.unix_timestamp)
+}
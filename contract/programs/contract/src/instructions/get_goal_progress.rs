@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, GoalProgress};
+
+/// Account structure for reading savings goal progress
+#[derive(Accounts)]
+pub struct GetGoalProgress<'info> {
+    /// Portfolio with a savings goal (read only)
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+/// Compute progress towards the savings goal and the contribution needed per interval
+///
+/// Writes nothing; the result is returned Borsh-encoded through
+/// `set_return_data`, so clients read it with `simulateTransaction`.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `contribution_interval` - Seconds between contributions (e.g. 2592000 for 30 days)
+///
+/// # Returns
+/// * `Result<GoalProgress>` - Progress, projected value and required contribution
+pub fn handler(
+    ctx: Context<GetGoalProgress>,
+    contribution_interval: i64,
+) -> Result<GoalProgress> {
+    let clock = Clock::get()?;
+    ctx.accounts.portfolio.goal_progress(clock.unix_timestamp, contribution_interval)
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::{AssetPrice, PnlReport, Portfolio, MAX_ALLOCATIONS};
+use crate::error::SloomoError;
+use crate::utils::validations::validate_unique_mints;
+
+/// Account structure for reading cost basis and profit and loss
+#[derive(Accounts)]
+pub struct GetPnl<'info> {
+    /// Portfolio to value (read only)
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+/// Value every allocation against its average cost basis
+///
+/// The program has no price feed, so current prices come from the caller;
+/// allocations other than the base asset without one have no value in the
+/// report. Writes nothing; the result is returned Borsh-encoded through
+/// `set_return_data`.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `prices` - Current price of each mint, scaled by `PRICE_SCALE`
+///
+/// # Returns
+/// * `Result<PnlReport>` - Cost basis, value, unrealized and realized P&L per allocation
+pub fn handler(ctx: Context<GetPnl>, prices: Vec<AssetPrice>) -> Result<PnlReport> {
+    let portfolio = &ctx.accounts.portfolio;
+    require!(prices.len() <= MAX_ALLOCATIONS, SloomoError::AllocationOverflow);
+    let mints: Vec<Pubkey> = prices.iter().map(|p| p.mint).collect();
+    validate_unique_mints(&mints)?;
+    Ok(portfolio.pnl_report(&prices))
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, PortfolioSummary};
+
+/// Account structure for reading the portfolio summary
+#[derive(Accounts)]
+pub struct GetSummary<'info> {
+    /// Portfolio to summarize (read only)
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+/// Summarize values, shares and effective targets of the portfolio
+///
+/// Writes nothing; the result is returned Borsh-encoded through
+/// `set_return_data`, so clients read it with `simulateTransaction`.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<PortfolioSummary>` - Values, rebalance state and every allocation
+pub fn handler(ctx: Context<GetSummary>) -> Result<PortfolioSummary> {
+    let clock = Clock::get()?;
+    ctx.accounts.portfolio.summary(clock.unix_timestamp)
+}
@@ -1,19 +1,23 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
-use crate::state::{Portfolio, InitPortfolioParams, AllocationData, MAX_ALLOCATIONS, AllocationTarget};
-use crate::error::SloomoError;
-use crate::utils::{validate_allocation_percentage, jupiter::JupiterSolSwapHelper};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    Portfolio, InitPortfolioParams, AllocationData, AllocationTarget, BandRestore, PORTFOLIO_VERSION,
+};
+use crate::utils::{
+    validate_init_params, transfer_to_vault, received_after_fee, jupiter::JupiterSolSwapHelper,
+    JUPITER_PROGRAM_ID,
+};
 
 /// Account structure for portfolio initialization
 #[derive(Accounts)]
-#[instruction(params: InitPortfolioParams)]
+#[instruction(portfolio_id: Pubkey, params: InitPortfolioParams)]
 pub struct InitializePortfolio<'info> {
     /// Portfolio account to be initialized
     #[account(
         init,
         payer = owner,
         space = Portfolio::SIZE,
-        seeds = [b"portfolio", owner.key().as_ref()],
+        seeds = [b"portfolio", portfolio_id.as_ref()],
         bump
     )]
     pub portfolio: Account<'info, Portfolio>,
@@ -22,100 +26,114 @@ pub struct InitializePortfolio<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
 
-    /// User's wSOL token account
+    /// User's base asset token account
     #[account(mut)]
-    pub user_wsol_account: Account<'info, TokenAccount>,
+    pub user_base_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// Portfolio's wSOL vault account
+    /// Portfolio's base asset vault account
     #[account(
         init,
         payer = owner,
-        seeds = [b"vault", portfolio.key().as_ref(), wsol_mint.key().as_ref()],
+        seeds = [b"vault", portfolio.key().as_ref(), base_mint.key().as_ref()],
         bump,
-        token::mint = wsol_mint,
+        token::mint = base_mint,
         token::authority = portfolio,
+        token::token_program = token_program,
     )]
-    pub portfolio_wsol_vault: Account<'info, TokenAccount>,
+    pub portfolio_base_vault: InterfaceAccount<'info, TokenAccount>,
 
-    /// wSOL mint (Native Mint)
-    pub wsol_mint: Account<'info, Mint>,
+    /// Base asset mint (e.g. wSOL or USDC), stored on the portfolio
+    pub base_mint: InterfaceAccount<'info, Mint>,
 
     /// System program
     pub system_program: Program<'info, System>,
 
-    /// Token program
-    pub token_program: Program<'info, Token>,
+    /// Token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-/// Initialize portfolio (with SOL investment and Jupiter swap)
+/// Initialize portfolio (with base asset investment and Jupiter swap)
+///
+/// The PDA is seeded by `portfolio_id` rather than the owner, so it survives
+/// owner transfers and an owner can hold several portfolios.
 ///
 /// # Arguments
 /// * `ctx` - Transaction context
+/// * `portfolio_id` - Fresh key seeding the portfolio PDA
 /// * `params` - Initialization parameters
 ///
 /// # Returns
 /// * `Result<()>` - Ok(()) on success, error on failure
 pub fn handler(
     ctx: Context<InitializePortfolio>,
+    portfolio_id: Pubkey,
     params: InitPortfolioParams,
 ) -> Result<()> {
     let portfolio = &mut ctx.accounts.portfolio;
     let owner = &ctx.accounts.owner;
     let clock = Clock::get()?;
 
-    // Validation: Check allocation count limit
-    require!(
-        params.initial_allocations.len() <= MAX_ALLOCATIONS,
-        SloomoError::AllocationOverflow
-    );
-
-    // Validation: Check total allocation percentage
-    let total_percentage: u16 = params.initial_allocations
-        .iter()
-        .map(|a| a.target_percentage)
-        .sum();
-
-    validate_allocation_percentage(total_percentage)?;
-
-    // Validation: Check initial SOL investment amount
-    require!(
-        params.initial_sol_amount > 0,
-        SloomoError::InvalidAmount
-    );
+    // Validation: Allocation count, symbols, duplicate mints, total percentage and SOL amount
+    validate_init_params(&params)?;
 
     // Set portfolio basic information
     portfolio.owner = owner.key();
     portfolio.bump = ctx.bumps.portfolio;
-    portfolio.total_value = params.initial_sol_amount;
     portfolio.last_rebalance = clock.unix_timestamp;
     portfolio.created_at = clock.unix_timestamp;
     portfolio.updated_at = clock.unix_timestamp;
     portfolio.is_rebalancing = false;
-
-    // SOL investment: Transfer from user's wSOL account to portfolio vault
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_wsol_account.to_account_info(),
-        to: ctx.accounts.portfolio_wsol_vault.to_account_info(),
-        authority: ctx.accounts.owner.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    transfer(cpi_ctx, params.initial_sol_amount)?;
-
-    // Initialize allocation data (starting with wSOL)
+    portfolio.base_mint = ctx.accounts.base_mint.key();
+    portfolio.portfolio_id = portfolio_id;
+    portfolio.pending_owner = None;
+    portfolio.band_restore = BandRestore::Edge;
+    portfolio.asset_classes = Vec::new();
+    portfolio.glide_path = None;
+    portfolio.goal = None;
+    portfolio.rebalance_count = 0;
+    portfolio.swap_programs = vec![JUPITER_PROGRAM_ID];
+    portfolio.version = PORTFOLIO_VERSION;
+
+    // Base asset investment: Transfer from user's token account to portfolio vault
+    transfer_to_vault(
+        &ctx.accounts.user_base_account,
+        &ctx.accounts.portfolio_base_vault,
+        &ctx.accounts.base_mint,
+        owner,
+        &ctx.accounts.token_program,
+        params.initial_sol_amount,
+    )?;
+
+    // Book what the vault received (net of any Token-2022 transfer fee)
+    let received = received_after_fee(
+        &ctx.accounts.base_mint.to_account_info(),
+        params.initial_sol_amount,
+        clock.epoch,
+    )?;
+    portfolio.total_value = received;
+
+    // Initialize allocation data (starting with the base asset)
     portfolio.allocations = params.initial_allocations
         .into_iter()
-        .map(|alloc_params| AllocationData {
-            mint: alloc_params.mint,
-            symbol: alloc_params.symbol,
-            current_amount: if alloc_params.mint == ctx.accounts.wsol_mint.key() {
-                params.initial_sol_amount
+        .map(|alloc_params| {
+            // The initial deposit is held at cost in the base asset
+            let current_amount = if alloc_params.mint == ctx.accounts.base_mint.key() {
+                received
             } else {
                 0
-            },
-            target_percentage: alloc_params.target_percentage,
-            apy: 0,
-            last_yield_update: clock.unix_timestamp,
+            };
+            AllocationData {
+                mint: alloc_params.mint,
+                symbol: alloc_params.symbol,
+                current_amount,
+                target_percentage: alloc_params.target_percentage,
+                apy: 0,
+                last_yield_update: clock.unix_timestamp,
+                band: alloc_params.band,
+                asset_class: None,
+                cost_basis: current_amount,
+                realized_pnl: 0,
+            }
         })
         .collect();
 
@@ -137,6 +155,7 @@ pub fn handler(
             &portfolio.allocations,
             &target_allocations,
             portfolio.total_value,
+            portfolio.base_mint,
         )?;
 
         // Log swap operations (actual swap executed externally)
@@ -154,13 +173,14 @@ pub fn handler(
     emit!(PortfolioInitialized {
         owner: owner.key(),
         portfolio: portfolio.key(),
+        base_mint: portfolio.base_mint,
         allocations_count: portfolio.allocations.len() as u8,
         initial_sol_amount: params.initial_sol_amount,
         jupiter_swap_enabled: params.enable_jupiter_swap,
     });
 
     msg!("Portfolio successfully initialized: {}", portfolio.key());
-    msg!("Initial SOL investment amount: {} lamports", params.initial_sol_amount);
+    msg!("Initial base asset investment amount: {}", params.initial_sol_amount);
     Ok(())
 }
 
@@ -171,9 +191,11 @@ pub struct PortfolioInitialized {
     pub owner: Pubkey,
     /// Portfolio account
     pub portfolio: Pubkey,
+    /// Base asset mint
+    pub base_mint: Pubkey,
     /// Initial allocation count
     pub allocations_count: u8,
-    /// Initial SOL investment amount
+    /// Initial base asset investment amount
     pub initial_sol_amount: u64,
     /// Whether Jupiter auto-swap is enabled
     pub jupiter_swap_enabled: bool,
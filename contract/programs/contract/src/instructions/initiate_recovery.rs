@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, GuardianSet};
+use crate::error::SloomoError;
+
+/// Account structure for a guardian acting on a recovery
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    /// Portfolio to be recovered
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Guardian set of the portfolio
+    #[account(
+        mut,
+        seeds = [b"guardians", portfolio.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = portfolio
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    /// Transaction executor (a guardian)
+    pub guardian: Signer<'info>,
+}
+
+/// Propose reassigning the portfolio to `new_owner`
+///
+/// The initiating guardian's approval is counted; the owner can veto until
+/// the recovery completes.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `new_owner` - Owner the portfolio is reassigned to
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<InitiateRecovery>,
+    new_owner: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let guardian = ctx.accounts.guardian.key();
+    let portfolio = &ctx.accounts.portfolio;
+    let guardian_set = &mut ctx.accounts.guardian_set;
+
+    require_keys_neq!(new_owner, portfolio.owner, SloomoError::InvalidNewOwner);
+    guardian_set.initiate_recovery(&guardian, new_owner, clock.unix_timestamp)?;
+
+    emit!(RecoveryInitiated {
+        portfolio: portfolio.key(),
+        guardian,
+        new_owner,
+        approvals: guardian_set.approval_count(),
+        threshold: guardian_set.threshold,
+        unlocks_at: guardian_set.unlocks_at(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Recovery to {} initiated by guardian {}", new_owner, guardian);
+    Ok(())
+}
+
+/// Recovery initiation event
+#[event]
+pub struct RecoveryInitiated {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Initiating guardian
+    pub guardian: Pubkey,
+    /// Proposed owner
+    pub new_owner: Pubkey,
+    /// Approvals so far
+    pub approvals: u8,
+    /// Approvals needed
+    pub threshold: u8,
+    /// Completion time if the threshold is already reached
+    pub unlocks_at: Option<i64>,
+    /// Initiation time
+    pub timestamp: i64,
+}
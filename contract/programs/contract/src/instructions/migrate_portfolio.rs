@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::{LegacyPortfolio, Portfolio};
+use crate::error::SloomoError;
+use crate::utils::JUPITER_PROGRAM_ID;
+
+/// Account structure for migrating a portfolio to the current layout
+#[derive(Accounts)]
+pub struct MigratePortfolio<'info> {
+    /// Portfolio in the legacy layout (seeded by its owner)
+    /// CHECK: Parsed as `LegacyPortfolio`, which checks the discriminator and size
+    #[account(
+        mut,
+        seeds = [b"portfolio", owner.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub portfolio: UncheckedAccount<'info>,
+
+    /// Base asset vault the portfolio already holds (becomes the base asset)
+    #[account(
+        seeds = [b"vault", portfolio.key().as_ref(), base_mint.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = portfolio,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base asset mint (the wSOL mint for portfolios funded in SOL)
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Transaction executor (portfolio owner, pays the extra rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrate a legacy portfolio account to the current layout
+///
+/// The account is grown to `Portfolio::SIZE` and rewritten with the legacy
+/// data; its `portfolio_id` is the owner's key, so the PDA and vaults are kept.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<MigratePortfolio>) -> Result<()> {
+    let portfolio_info = ctx.accounts.portfolio.to_account_info();
+    let owner = &ctx.accounts.owner;
+    let clock = Clock::get()?;
+
+    let legacy = LegacyPortfolio::try_from_account_data(&portfolio_info.try_borrow_data()?)?;
+    require_keys_eq!(legacy.owner, owner.key(), SloomoError::Unauthorized);
+    let portfolio = legacy.migrate(ctx.accounts.base_mint.key(), JUPITER_PROGRAM_ID);
+
+    // Rent for the larger account: owner -> portfolio
+    let rent_due = Rent::get()?
+        .minimum_balance(Portfolio::SIZE)
+        .saturating_sub(portfolio_info.lamports());
+    if rent_due > 0 {
+        let transfer_accounts = SystemTransfer {
+            from: owner.to_account_info(),
+            to: portfolio_info.clone(),
+        };
+        system_program::transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts),
+            rent_due,
+        )?;
+    }
+
+    portfolio_info.resize(Portfolio::SIZE)?;
+    portfolio.try_serialize(&mut &mut portfolio_info.try_borrow_mut_data()?[..])?;
+
+    emit!(PortfolioMigrated {
+        portfolio: portfolio_info.key(),
+        owner: owner.key(),
+        base_mint: portfolio.base_mint,
+        version: portfolio.version,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Portfolio migrated to layout version {}", portfolio.version);
+    Ok(())
+}
+
+/// Portfolio layout migration event
+#[event]
+pub struct PortfolioMigrated {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Base asset mint
+    pub base_mint: Pubkey,
+    /// Layout version after migration
+    pub version: u8,
+    /// Migration time
+    pub timestamp: i64,
+}
@@ -1,12 +1,180 @@
+
 pub mod initialize_portfolio;
+pub mod migrate_portfolio;
 pub mod deposit_usdc;
+pub mod deposit_sol;
+pub mod deposit_token;
+pub mod withdraw_sol;
+pub mod withdraw_proportional;
+pub mod withdraw_to_base;
 pub mod add_or_update_allocation;
+pub mod rebalance;
 pub mod real_jupiter_rebalance;
 pub mod sol_jupiter_rebalance;
+pub mod begin_rebalance;
+pub mod execute_rebalance_leg;
+pub mod commit_rebalance;
+pub mod abort_rebalance;
+pub mod close_rebalance_record;
+pub mod set_deposit_routing;
+pub mod set_band_restore;
+pub mod set_swap_programs;
+pub mod set_asset_classes;
+pub mod set_glide_path;
+pub mod advance_glide_path;
+pub mod set_savings_goal;
+pub mod get_goal_progress;
+pub mod get_summary;
+pub mod get_drift;
+pub mod preview_rebalance;
+pub mod get_pnl;
+pub mod propose_owner_transfer;
+pub mod cancel_owner_transfer;
+pub mod accept_owner_transfer;
+pub mod set_guardians;
+pub mod initiate_recovery;
+pub mod approve_recovery;
+pub mod veto_recovery;
+pub mod complete_recovery;
+pub mod create_session_key;
+pub mod revoke_session_key;
+pub mod create_recurring_deposit;
+pub mod crank_recurring_deposit;
+pub mod cancel_recurring_deposit;
+
+// Explicit re-exports: every module also defines a `handler`, called by path
+// from the program module, so glob re-exports would collide
+pub use initialize_portfolio::{InitializePortfolio, PortfolioInitialized};
+pub use deposit_usdc::{DepositUsdc, UsdcDeposited};
+pub use deposit_sol::{DepositSol, SolDeposited};
+pub use deposit_token::{DepositToken, TokenDeposited};
+pub use withdraw_sol::{WithdrawSol, SolWithdrawn};
+pub use withdraw_proportional::{WithdrawProportional, ProportionalWithdrawn};
+pub use withdraw_to_base::{WithdrawToBase, WithdrawnToBase, liquidation_budgets, liquidated_value};
+pub use add_or_update_allocation::{AddOrUpdateAllocation, AllocationAdded, AllocationUpdated};
+pub use rebalance::{Rebalance, PortfolioRebalanced};
+pub use real_jupiter_rebalance::{RealJupiterRebalance, StablecoinPortfolioRebalanced};
+pub use sol_jupiter_rebalance::{SolJupiterRebalance, SolPortfolioRebalanced};
+pub use begin_rebalance::{BeginRebalance, RebalanceBegun};
+pub use execute_rebalance_leg::{ExecuteRebalanceLeg, RebalanceLegExecuted};
+pub use commit_rebalance::{CommitRebalance, RebalanceCommitted};
+pub use migrate_portfolio::{MigratePortfolio, PortfolioMigrated};
+pub use abort_rebalance::{AbortRebalance, RebalanceAborted};
+pub use close_rebalance_record::{CloseRebalanceRecord, RebalanceRecordClosed};
+pub use set_deposit_routing::{SetDepositRouting, DepositRoutingSet};
+pub use set_band_restore::{SetBandRestore, BandRestoreSet};
+pub use set_swap_programs::{SetSwapPrograms, SwapProgramsSet};
+pub use set_asset_classes::{SetAssetClasses, AssetClassesSet};
+pub use set_glide_path::{SetGlidePath, GlidePathSet};
+pub use advance_glide_path::{AdvanceGlidePath, GlidePathStepped};
+pub use set_savings_goal::{SetSavingsGoal, SavingsGoalSet};
+pub use get_goal_progress::{GetGoalProgress};
+pub use get_summary::{GetSummary};
+pub use get_drift::{GetDrift};
+pub use preview_rebalance::{PreviewRebalance, dry_run_rebalance};
+pub use get_pnl::{GetPnl};
+pub use propose_owner_transfer::{ProposeOwnerTransfer, OwnerTransferProposed};
+pub use cancel_owner_transfer::{CancelOwnerTransfer, OwnerTransferCancelled};
+pub use accept_owner_transfer::{AcceptOwnerTransfer, OwnerTransferred};
+pub use set_guardians::{SetGuardians, GuardiansSet};
+pub use initiate_recovery::{InitiateRecovery, RecoveryInitiated};
+pub use approve_recovery::{ApproveRecovery, RecoveryApproved};
+pub use veto_recovery::{VetoRecovery, RecoveryVetoed};
+pub use complete_recovery::{CompleteRecovery, RecoveryCompleted};
+pub use create_session_key::{CreateSessionKey, SessionKeyCreated};
+pub use revoke_session_key::{RevokeSessionKey, SessionKeyRevoked};
+pub use create_recurring_deposit::{CreateRecurringDeposit, RecurringDepositCreated};
+pub use crank_recurring_deposit::{CrankRecurringDeposit, RecurringDepositExecuted};
+pub use cancel_recurring_deposit::{CancelRecurringDeposit, RecurringDepositCancelled};
 
-// Re-export everything from each module
-pub use initialize_portfolio::*;
-pub use deposit_usdc::*;
-pub use add_or_update_allocation::*;
-pub use real_jupiter_rebalance::*;
-pub use sol_jupiter_rebalance::*;
\ No newline at end of file
+// Client account modules generated by `#[derive(Accounts)]`, which `#[program]`
+// expects at the crate root
+pub(crate) use self::{
+    initialize_portfolio::__client_accounts_initialize_portfolio,
+    migrate_portfolio::__client_accounts_migrate_portfolio,
+    deposit_usdc::__client_accounts_deposit_usdc,
+    deposit_sol::__client_accounts_deposit_sol,
+    deposit_token::__client_accounts_deposit_token,
+    withdraw_sol::__client_accounts_withdraw_sol,
+    withdraw_proportional::__client_accounts_withdraw_proportional,
+    withdraw_to_base::__client_accounts_withdraw_to_base,
+    add_or_update_allocation::__client_accounts_add_or_update_allocation,
+    rebalance::__client_accounts_rebalance,
+    real_jupiter_rebalance::__client_accounts_real_jupiter_rebalance,
+    sol_jupiter_rebalance::__client_accounts_sol_jupiter_rebalance,
+    begin_rebalance::__client_accounts_begin_rebalance,
+    execute_rebalance_leg::__client_accounts_execute_rebalance_leg,
+    commit_rebalance::__client_accounts_commit_rebalance,
+    abort_rebalance::__client_accounts_abort_rebalance,
+    close_rebalance_record::__client_accounts_close_rebalance_record,
+    set_deposit_routing::__client_accounts_set_deposit_routing,
+    set_band_restore::__client_accounts_set_band_restore,
+    set_swap_programs::__client_accounts_set_swap_programs,
+    set_asset_classes::__client_accounts_set_asset_classes,
+    set_glide_path::__client_accounts_set_glide_path,
+    advance_glide_path::__client_accounts_advance_glide_path,
+    set_savings_goal::__client_accounts_set_savings_goal,
+    get_goal_progress::__client_accounts_get_goal_progress,
+    get_summary::__client_accounts_get_summary,
+    get_drift::__client_accounts_get_drift,
+    preview_rebalance::__client_accounts_preview_rebalance,
+    get_pnl::__client_accounts_get_pnl,
+    propose_owner_transfer::__client_accounts_propose_owner_transfer,
+    cancel_owner_transfer::__client_accounts_cancel_owner_transfer,
+    accept_owner_transfer::__client_accounts_accept_owner_transfer,
+    set_guardians::__client_accounts_set_guardians,
+    initiate_recovery::__client_accounts_initiate_recovery,
+    approve_recovery::__client_accounts_approve_recovery,
+    veto_recovery::__client_accounts_veto_recovery,
+    complete_recovery::__client_accounts_complete_recovery,
+    create_session_key::__client_accounts_create_session_key,
+    revoke_session_key::__client_accounts_revoke_session_key,
+    create_recurring_deposit::__client_accounts_create_recurring_deposit,
+    crank_recurring_deposit::__client_accounts_crank_recurring_deposit,
+    cancel_recurring_deposit::__client_accounts_cancel_recurring_deposit,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use self::{
+    initialize_portfolio::__cpi_client_accounts_initialize_portfolio,
+    migrate_portfolio::__cpi_client_accounts_migrate_portfolio,
+    deposit_usdc::__cpi_client_accounts_deposit_usdc,
+    deposit_sol::__cpi_client_accounts_deposit_sol,
+    deposit_token::__cpi_client_accounts_deposit_token,
+    withdraw_sol::__cpi_client_accounts_withdraw_sol,
+    withdraw_proportional::__cpi_client_accounts_withdraw_proportional,
+    withdraw_to_base::__cpi_client_accounts_withdraw_to_base,
+    add_or_update_allocation::__cpi_client_accounts_add_or_update_allocation,
+    rebalance::__cpi_client_accounts_rebalance,
+    real_jupiter_rebalance::__cpi_client_accounts_real_jupiter_rebalance,
+    sol_jupiter_rebalance::__cpi_client_accounts_sol_jupiter_rebalance,
+    begin_rebalance::__cpi_client_accounts_begin_rebalance,
+    execute_rebalance_leg::__cpi_client_accounts_execute_rebalance_leg,
+    commit_rebalance::__cpi_client_accounts_commit_rebalance,
+    abort_rebalance::__cpi_client_accounts_abort_rebalance,
+    close_rebalance_record::__cpi_client_accounts_close_rebalance_record,
+    set_deposit_routing::__cpi_client_accounts_set_deposit_routing,
+    set_band_restore::__cpi_client_accounts_set_band_restore,
+    set_swap_programs::__cpi_client_accounts_set_swap_programs,
+    set_asset_classes::__cpi_client_accounts_set_asset_classes,
+    set_glide_path::__cpi_client_accounts_set_glide_path,
+    advance_glide_path::__cpi_client_accounts_advance_glide_path,
+    set_savings_goal::__cpi_client_accounts_set_savings_goal,
+    get_goal_progress::__cpi_client_accounts_get_goal_progress,
+    get_summary::__cpi_client_accounts_get_summary,
+    get_drift::__cpi_client_accounts_get_drift,
+    preview_rebalance::__cpi_client_accounts_preview_rebalance,
+    get_pnl::__cpi_client_accounts_get_pnl,
+    propose_owner_transfer::__cpi_client_accounts_propose_owner_transfer,
+    cancel_owner_transfer::__cpi_client_accounts_cancel_owner_transfer,
+    accept_owner_transfer::__cpi_client_accounts_accept_owner_transfer,
+    set_guardians::__cpi_client_accounts_set_guardians,
+    initiate_recovery::__cpi_client_accounts_initiate_recovery,
+    approve_recovery::__cpi_client_accounts_approve_recovery,
+    veto_recovery::__cpi_client_accounts_veto_recovery,
+    complete_recovery::__cpi_client_accounts_complete_recovery,
+    create_session_key::__cpi_client_accounts_create_session_key,
+    revoke_session_key::__cpi_client_accounts_revoke_session_key,
+    create_recurring_deposit::__cpi_client_accounts_create_recurring_deposit,
+    crank_recurring_deposit::__cpi_client_accounts_crank_recurring_deposit,
+    cancel_recurring_deposit::__cpi_client_accounts_cancel_recurring_deposit,
+};
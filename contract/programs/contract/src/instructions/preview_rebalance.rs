@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::{Portfolio, AllocationTarget, VaultBalance, RebalancePreview, PreviewLeg};
+use crate::instructions::rebalance::{
+    snapshot_vaults, snapshot_total, plan_from_snapshot, holdings_from_snapshot,
+};
+use crate::error::SloomoError;
+use crate::utils::{
+    validate_target_allocations,
+    amount_to_bps,
+    leg_cost,
+    CommonMints,
+    PlannerConfig,
+    DEFAULT_MAX_LEGS,
+    DEFAULT_SLIPPAGE_BPS,
+    MIN_REBALANCE_INTERVAL,
+};
+
+/// Account structure for previewing a rebalance
+///
+/// Remaining accounts: the `[b"vault", portfolio, mint]` vault of every mint in
+/// `Portfolio::rebalance_mints`, in that order (vaults not created yet may be passed empty),
+/// then the mint accounts in the same order.
+#[derive(Accounts)]
+pub struct PreviewRebalance<'info> {
+    /// Portfolio to preview (read only)
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Portfolio's base asset vault
+    #[account(
+        seeds = [b"vault", portfolio.key().as_ref(), portfolio.base_mint.as_ref()],
+        bump,
+        token::authority = portfolio,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base asset mint (total value is kept in its units)
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+}
+
+/// Dry run of `rebalance` from the same vault balances
+///
+/// Writes nothing; the result is returned Borsh-encoded through
+/// `set_return_data`, so clients read it with `simulateTransaction`.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `target_allocations` - Requested targets (glide path mints use their interpolated target)
+/// * `slippage_bps` - Slippage tolerance (default 0.5%)
+///
+/// # Returns
+/// * `Result<RebalancePreview>` - Drift, planned legs, fee estimates and whether `rebalance` would run
+pub fn handler(
+    ctx: Context<PreviewRebalance>,
+    target_allocations: Vec<AllocationTarget>,
+    slippage_bps: Option<u16>,
+) -> Result<RebalancePreview> {
+    let portfolio = &ctx.accounts.portfolio;
+    let now = Clock::get()?.unix_timestamp;
+
+    let snapshot = snapshot_vaults(
+        portfolio,
+        ctx.accounts.base_vault.amount,
+        ctx.accounts.base_mint.decimals,
+        ctx.remaining_accounts,
+        &portfolio.glided_targets(&target_allocations, now),
+    )?;
+
+    dry_run_rebalance(portfolio, &snapshot, &target_allocations, slippage_bps, now)
+}
+
+/// Plan a rebalance of `portfolio` from a vault snapshot (base vault first)
+/// without touching any account
+///
+/// Runs the same steps as `rebalance` (glide path, target validation,
+/// accounting from the vaults, asset class pinning and leg planning) on a copy
+/// of the portfolio, so it can also be called off-chain with fetched balances.
+pub fn dry_run_rebalance(
+    portfolio: &Portfolio,
+    snapshot: &[VaultBalance],
+    target_allocations: &[AllocationTarget],
+    slippage_bps: Option<u16>,
+    now: i64,
+) -> Result<RebalancePreview> {
+    let target_allocations = portfolio.glided_targets(target_allocations, now);
+    validate_target_allocations(&target_allocations)?;
+    let total_value = snapshot_total(snapshot)?;
+
+    // Accounting follows custody, as in `rebalance`
+    let mut planned = portfolio.clone();
+    planned.apply_vault_balances(snapshot, CommonMints::symbol_for)?;
+
+    // Drift of each vault's value in base asset decimals
+    let max_drift_bps = holdings_from_snapshot(&planned, snapshot, &target_allocations)?
+        .iter()
+        .map(|h| amount_to_bps(h.value, total_value).abs_diff(h.target_percentage as u64))
+        .max()
+        .unwrap_or(0);
+
+    let slippage_bps = slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_BPS);
+    let plan = plan_from_snapshot(
+        &planned,
+        snapshot,
+        &target_allocations,
+        slippage_bps,
+        DEFAULT_MAX_LEGS,
+    )?;
+
+    // Fee part of each leg's cost
+    let fee_config = PlannerConfig { slippage_bps: 0, ..PlannerConfig::default() };
+    let legs: Vec<PreviewLeg> = plan
+        .legs
+        .iter()
+        .map(|leg| PreviewLeg {
+            from_mint: Pubkey::new_from_array(leg.from_mint),
+            to_mint: Pubkey::new_from_array(leg.to_mint),
+            amount: leg.amount,
+            estimated_cost: leg.estimated_cost,
+            estimated_fee: leg_cost(leg.amount, &fee_config).min(leg.estimated_cost),
+        })
+        .collect();
+    let estimated_fees = legs.iter().map(|leg| leg.estimated_fee).sum();
+
+    let next_rebalance_at = portfolio.last_rebalance.saturating_add(MIN_REBALANCE_INTERVAL);
+
+    Ok(RebalancePreview {
+        total_value,
+        needs_rebalancing: planned.needs_rebalancing(&target_allocations, total_value)?,
+        max_drift_bps,
+        legs,
+        estimated_cost: plan.estimated_cost,
+        estimated_fees,
+        estimated_slippage: plan.estimated_cost.saturating_sub(estimated_fees),
+        max_residual_drift_bps: plan.max_residual_drift_bps,
+        within_band: plan.within_band,
+        next_rebalance_at,
+        can_execute: now >= next_rebalance_at && !portfolio.is_rebalancing,
+        target_allocations,
+    })
+}
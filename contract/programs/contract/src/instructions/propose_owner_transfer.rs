@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::Portfolio;
+use crate::utils::validate_reentrancy;
+
+/// Account structure for proposing a new portfolio owner
+#[derive(Accounts)]
+pub struct ProposeOwnerTransfer<'info> {
+    /// Portfolio to be transferred
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (current portfolio owner)
+    pub owner: Signer<'info>,
+}
+
+/// Propose `new_owner` as the portfolio owner
+///
+/// Ownership only moves once `new_owner` signs `accept_owner_transfer`; a new
+/// proposal replaces the pending one.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `new_owner` - Proposed owner
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<ProposeOwnerTransfer>,
+    new_owner: Pubkey,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+
+    portfolio.propose_owner_transfer(new_owner)?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    emit!(OwnerTransferProposed {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        new_owner,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Owner transfer proposed: {} -> {}", portfolio.owner, new_owner);
+    Ok(())
+}
+
+/// Owner transfer proposal event
+#[event]
+pub struct OwnerTransferProposed {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Current portfolio owner
+    pub owner: Pubkey,
+    /// Proposed owner
+    pub new_owner: Pubkey,
+    /// Proposal time
+    pub timestamp: i64,
+}
@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Portfolio, AllocationTarget, VaultBalance, SessionKey, SESSION_SCOPE_REBALANCE};
+use crate::instructions::advance_glide_path::step_glide_path;
+use crate::instructions::set_deposit_routing::plan_base_cash_flow;
+use crate::error::SloomoError;
+use crate::utils::{
+    validate_reentrancy,
+    validate_authority,
+    validate_target_allocations,
+    validate_rebalance_frequency,
+    CommonMints,
+    PlannerConfig,
+    PlannerHolding,
+    plan_rebalance,
+    pin_asset_classes,
+    holdings_from_allocations,
+    log_plan,
+    read_mint_decimals,
+    read_vault_balances,
+    RebalancePlan,
+    DEFAULT_MAX_LEGS,
+    DEFAULT_SLIPPAGE_BPS,
+};
+
+/// Account structure for rebalancing from the portfolio's base asset
+///
+/// Remaining accounts: the `[b"vault", portfolio, mint]` vault of every mint in
+/// `Portfolio::rebalance_mints`, in that order (vaults not created yet may be passed empty),
+/// then the mint accounts in the same order.
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+    /// Portfolio to be rebalanced
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = base_mint @ SloomoError::InvalidBaseMint
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner or a session key)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    /// Portfolio's base asset vault
+    #[account(
+        mut,
+        seeds = [b"vault", portfolio.key().as_ref(), base_mint.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = portfolio,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base asset mint (must match the portfolio setting)
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Execute rebalancing from the portfolio's vaults
+/// Plan Jupiter swaps between the vault balances and each target and output rebalancing instructions
+pub fn handler(
+    ctx: Context<Rebalance>,
+    target_allocations: Vec<AllocationTarget>,
+    slippage_bps: Option<u16>,
+) -> Result<()> {
+    // Planning only: nothing is spent
+    validate_authority(
+        &ctx.accounts.portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_REBALANCE,
+        0,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let base_balance = ctx.accounts.base_vault.amount;
+    let base_decimals = ctx.accounts.base_mint.decimals;
+    execute_rebalance(
+        &mut ctx.accounts.portfolio,
+        base_balance,
+        base_decimals,
+        ctx.remaining_accounts,
+        &target_allocations,
+        slippage_bps,
+    )
+}
+
+/// Rebalancing shared by `rebalance` and the deprecated base-specific instructions
+pub(crate) fn execute_rebalance(
+    portfolio: &mut Account<Portfolio>,
+    base_balance: u64,
+    base_decimals: u8,
+    accounts: &[AccountInfo],
+    target_allocations: &[AllocationTarget],
+    slippage_bps: Option<u16>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Glide path mints follow their interpolated targets
+    let target_allocations = &portfolio.glided_targets(target_allocations, clock.unix_timestamp);
+
+    // Custody: every allocation is valued from its portfolio-owned vault
+    let snapshot = snapshot_vaults(
+        portfolio,
+        base_balance,
+        base_decimals,
+        accounts,
+        target_allocations,
+    )?;
+    let balance_of = |mint: &Pubkey| balance_in(&snapshot, mint);
+
+    // Common validation
+    validate_rebalance_frequency(portfolio, &clock)?;
+    validate_reentrancy(portfolio)?;
+
+    // Set rebalancing start flag
+    portfolio.is_rebalancing = true;
+
+    // Validation: Check target allocation validity
+    validate_target_allocations(target_allocations)?;
+
+    // Total portfolio value across all vaults
+    let total_value = snapshot_total(&snapshot)?;
+
+    // Accounting follows custody
+    portfolio.apply_vault_balances(&snapshot, CommonMints::symbol_for)?;
+
+    msg!(
+        "Rebalancing started: portfolio vaults hold {} ({} in base asset {})",
+        total_value,
+        base_balance,
+        portfolio.base_mint
+    );
+
+    // Plan netted swap legs from current and target allocations
+    let slippage_bps = slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_BPS);
+    let plan = plan_from_snapshot(
+        portfolio,
+        &snapshot,
+        target_allocations,
+        slippage_bps,
+        DEFAULT_MAX_LEGS,
+    )?;
+
+    // Log each planned leg (swaps are not executed by this instruction)
+    if plan.legs.is_empty() {
+        msg!("No swap operations needed - already close to target allocation");
+    } else {
+        log_plan(&plan);
+    }
+
+    // Update portfolio target allocations
+    step_glide_path(portfolio, clock.unix_timestamp);
+    portfolio.set_target_allocations(target_allocations, balance_of, CommonMints::symbol_for)?;
+
+    // State update
+    portfolio.last_rebalance = clock.unix_timestamp;
+    portfolio.updated_at = clock.unix_timestamp;
+    portfolio.total_value = total_value;
+    portfolio.is_rebalancing = false;
+
+    // Emit event
+    emit!(PortfolioRebalanced {
+        owner: portfolio.owner,
+        base_mint: portfolio.base_mint,
+        base_amount: base_balance,
+        total_value,
+        target_allocations_count: target_allocations.len() as u8,
+        planned_legs: plan.legs.len() as u8,
+        timestamp: clock.unix_timestamp,
+        slippage_bps,
+    });
+
+    msg!(
+        "Portfolio rebalancing completed: planned {} to {} types of tokens",
+        total_value,
+        target_allocations.len()
+    );
+
+    Ok(())
+}
+
+/// Read the base vault balance and the vaults of `Portfolio::rebalance_mints`
+/// (base vault first)
+///
+/// `accounts` holds the vaults of `rebalance_mints`, then their mint accounts
+/// in the same order.
+pub(crate) fn snapshot_vaults(
+    portfolio: &Account<Portfolio>,
+    base_balance: u64,
+    base_decimals: u8,
+    accounts: &[AccountInfo],
+    target_allocations: &[AllocationTarget],
+) -> Result<Vec<VaultBalance>> {
+    let mints = portfolio.rebalance_mints(target_allocations);
+    let (vault_accounts, mint_accounts) = accounts.split_at(mints.len().min(accounts.len()));
+    let vault_balances = read_vault_balances(&portfolio.key(), &mints, vault_accounts)?;
+    let decimals = read_mint_decimals(&mints, mint_accounts)?;
+
+    Ok(std::iter::once(VaultBalance {
+        mint: portfolio.base_mint,
+        amount: base_balance,
+        decimals: base_decimals,
+    })
+    .chain(
+        vault_balances
+            .into_iter()
+            .zip(decimals)
+            .map(|((mint, amount), decimals)| VaultBalance { mint, amount, decimals }),
+    )
+    .collect())
+}
+
+/// Re-read the vaults of `snapshot`, keeping its mints and decimals
+pub(crate) fn read_snapshot_vaults(
+    portfolio: &Pubkey,
+    snapshot: &[VaultBalance],
+    vault_accounts: &[AccountInfo],
+) -> Result<Vec<VaultBalance>> {
+    let mints: Vec<Pubkey> = snapshot.iter().map(|balance| balance.mint).collect();
+    Ok(read_vault_balances(portfolio, &mints, vault_accounts)?
+        .into_iter()
+        .zip(snapshot)
+        .map(|((_, amount), before)| VaultBalance { amount, ..before.clone() })
+        .collect())
+}
+
+/// Balance of `mint` in a vault snapshot (0 when absent)
+pub(crate) fn balance_in(snapshot: &[VaultBalance], mint: &Pubkey) -> u64 {
+    snapshot
+        .iter()
+        .find(|b| b.mint == *mint)
+        .map_or(0, |b| b.amount)
+}
+
+/// Total value of a vault snapshot in base asset decimals (must be positive to
+/// rebalance)
+pub(crate) fn snapshot_total(snapshot: &[VaultBalance]) -> Result<u64> {
+    let values = snapshot_values(snapshot)?;
+    let total_value = sloomo_core::valuation::total_value(values)
+        .map_err(SloomoError::from)?;
+    require!(total_value > 0, SloomoError::InsufficientBalance);
+    Ok(total_value)
+}
+
+/// Value of every vault of a snapshot in the decimals of its first (base) vault
+pub(crate) fn snapshot_values(snapshot: &[VaultBalance]) -> Result<Vec<u64>> {
+    let base_decimals = snapshot.first().map_or(0, |base| base.decimals);
+    snapshot.iter().map(|balance| balance.value(base_decimals)).collect()
+}
+
+/// Plan at most `max_legs` netted swap legs from vault balances towards
+/// `target_allocations`
+pub(crate) fn plan_from_snapshot(
+    portfolio: &Portfolio,
+    snapshot: &[VaultBalance],
+    target_allocations: &[AllocationTarget],
+    slippage_bps: u16,
+    max_legs: u8,
+) -> Result<RebalancePlan> {
+    let planner_config = PlannerConfig {
+        slippage_bps,
+        restore: portfolio.band_restore.to_core(),
+        max_legs,
+        ..PlannerConfig::default()
+    };
+    portfolio.validate_class_targets(target_allocations)?;
+
+    let mut holdings = holdings_from_snapshot(portfolio, snapshot, target_allocations)?;
+    let pinned = pin_asset_classes(&portfolio.allocations, &portfolio.asset_classes, &mut holdings)?;
+    if pinned > 0 {
+        msg!("{} asset classes out of band, trading their members to target", pinned);
+    }
+    if portfolio.route_deposits {
+        if let Some(plan) = plan_base_cash_flow(&holdings, portfolio.base_mint, &planner_config)? {
+            msg!("Routing base asset above target to underweight allocations");
+            return Ok(plan);
+        }
+    }
+    plan_rebalance(&holdings, portfolio.base_mint, &planner_config)
+}
+
+/// Planner holdings valued from a vault snapshot in base asset decimals,
+/// including the base asset
+///
+/// Plan legs are therefore in base units; they are converted back to the
+/// units of each mint before any swap is built.
+pub(crate) fn holdings_from_snapshot(
+    portfolio: &Portfolio,
+    snapshot: &[VaultBalance],
+    target_allocations: &[AllocationTarget],
+) -> Result<Vec<PlannerHolding>> {
+    let base_mint = portfolio.base_mint;
+    let base_decimals = snapshot.first().map_or(0, |base| base.decimals);
+    let value_in = |mint: &Pubkey| {
+        snapshot
+            .iter()
+            .find(|b| b.mint == *mint)
+            .map_or(Ok(0), |b| b.value(base_decimals))
+    };
+
+    let mut holdings = holdings_from_allocations(&portfolio.allocations, target_allocations);
+    for holding in holdings.iter_mut() {
+        holding.value = value_in(&Pubkey::new_from_array(holding.mint))?;
+    }
+    if !holdings.iter().any(|h| h.mint == base_mint.to_bytes()) {
+        // Unallocated base asset funds the buys
+        holdings.push(PlannerHolding {
+            mint: base_mint.to_bytes(),
+            value: value_in(&base_mint)?,
+            target_percentage: 0,
+            band: None,
+        });
+    }
+    Ok(holdings)
+}
+
+/// Portfolio rebalancing event
+#[event]
+pub struct PortfolioRebalanced {
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Base asset mint
+    pub base_mint: Pubkey,
+    /// Base asset vault balance at rebalancing
+    pub base_amount: u64,
+    /// Total value across all portfolio vaults
+    pub total_value: u64,
+    /// Number of allocated token types
+    pub target_allocations_count: u8,
+    /// Number of planned swap legs
+    pub planned_legs: u8,
+    /// Rebalancing execution time
+    pub timestamp: i64,
+    /// Slippage used
+    pub slippage_bps: u16,
+}
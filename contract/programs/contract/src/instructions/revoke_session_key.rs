@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, SessionKey};
+
+/// Account structure for revoking a session key
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    /// Portfolio the key acts on
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Session key to revoke (closed, rent returned to the owner)
+    #[account(
+        mut,
+        seeds = [b"session_key", portfolio.key().as_ref(), session_key.authority.as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio,
+        close = owner
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    /// Transaction executor (portfolio owner)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Revoke a session key before it expires
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<RevokeSessionKey>) -> Result<()> {
+    let clock = Clock::get()?;
+    let session_key = &ctx.accounts.session_key;
+
+    emit!(SessionKeyRevoked {
+        portfolio: ctx.accounts.portfolio.key(),
+        owner: ctx.accounts.owner.key(),
+        authority: session_key.authority,
+        spent: session_key.spent,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Session key {} revoked", session_key.authority);
+    Ok(())
+}
+
+/// Session key revocation event
+#[event]
+pub struct SessionKeyRevoked {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Revoked session key
+    pub authority: Pubkey,
+    /// Amount the key moved (base asset units)
+    pub spent: u64,
+    /// Revocation time
+    pub timestamp: i64,
+}
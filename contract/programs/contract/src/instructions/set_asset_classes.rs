@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    Portfolio, AssetClass, ClassMembership, AllocationTarget, SessionKey, SESSION_SCOPE_ALLOCATION,
+};
+use crate::utils::{validate_reentrancy, validate_authority};
+
+/// Account structure for setting asset classes
+#[derive(Accounts)]
+pub struct SetAssetClasses<'info> {
+    /// Portfolio to be edited
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner or a session key)
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+}
+
+/// Replace the asset classes and the allocations belonging to them
+///
+/// Each member allocation's target becomes its class target times its weight
+/// within the class; allocations left out of `memberships` stay unclassified.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `classes` - Asset classes with their target percentage and optional band
+/// * `memberships` - Class index and intra-class weight (basis points) per mint
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<SetAssetClasses>,
+    classes: Vec<AssetClass>,
+    memberships: Vec<ClassMembership>,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+    validate_authority(
+        portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_ALLOCATION,
+        0,
+        clock.unix_timestamp,
+    )?;
+
+    portfolio.set_asset_classes(classes.clone(), &memberships)?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "Asset classes set: {} classes, {} member allocations",
+        classes.len(),
+        memberships.len()
+    );
+
+    emit!(AssetClassesSet {
+        portfolio: portfolio.key(),
+        classes,
+        memberships,
+        effective_targets: portfolio
+            .allocations
+            .iter()
+            .map(|a| AllocationTarget {
+                mint: a.mint,
+                target_percentage: a.target_percentage,
+            })
+            .collect(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Asset classes set event
+#[event]
+pub struct AssetClassesSet {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// New asset classes
+    pub classes: Vec<AssetClass>,
+    /// Class membership of each classified allocation
+    pub memberships: Vec<ClassMembership>,
+    /// Flattened target of every allocation after the change
+    pub effective_targets: Vec<AllocationTarget>,
+    /// Update execution time
+    pub timestamp: i64,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, BandRestore};
+use crate::utils::validate_reentrancy;
+
+/// Account structure for choosing how far rebalances restore allocations
+#[derive(Accounts)]
+pub struct SetBandRestore<'info> {
+    /// Portfolio to be configured
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner)
+    pub owner: Signer<'info>,
+}
+
+/// Choose whether allocations that left their band are rebalanced back to
+/// the band edge or all the way to their target
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `restore` - Band edge (smallest trades) or target
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<SetBandRestore>,
+    restore: BandRestore,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+
+    portfolio.band_restore = restore;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    emit!(BandRestoreSet {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        restore,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Band restore set to {:?}", restore);
+    Ok(())
+}
+
+/// Band restore setting event
+#[event]
+pub struct BandRestoreSet {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// How far allocations outside their band are rebalanced
+    pub restore: BandRestore,
+    /// Update time
+    pub timestamp: i64,
+}
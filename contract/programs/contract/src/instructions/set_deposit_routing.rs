@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::Portfolio;
+use crate::error::SloomoError;
+use crate::utils::{
+    validate_reentrancy, plan_cash_flow, bps_of, PlannerConfig, PlannerHolding, RebalancePlan,
+};
+
+/// Account structure for toggling cash-flow rebalancing
+#[derive(Accounts)]
+pub struct SetDepositRouting<'info> {
+    /// Portfolio to be configured
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner)
+    pub owner: Signer<'info>,
+}
+
+/// Enable or disable routing of base asset deposits to underweight allocations
+///
+/// While enabled, rebalances spend base asset held above its target (new
+/// deposits) on the allocations furthest below target, without selling,
+/// whenever those buys bring every holding within its band.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `enabled` - Whether deposits are routed
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<SetDepositRouting>,
+    enabled: bool,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+
+    portfolio.route_deposits = enabled;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    emit!(DepositRoutingSet {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        enabled,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Deposit routing {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Buys spending the base asset held above its target, when they restore every band
+///
+/// `holdings` include the base asset as it stands, deposits included. The
+/// excess over the base target is planned as a deposit; `None` when there is
+/// no excess or the buys alone leave a holding out of band, so the regular
+/// plan (which may sell) applies.
+pub(crate) fn plan_base_cash_flow(
+    holdings: &[PlannerHolding],
+    base_mint: Pubkey,
+    config: &PlannerConfig,
+) -> Result<Option<RebalancePlan>> {
+    let total_value = holdings
+        .iter()
+        .try_fold(0u64, |acc, h| acc.checked_add(h.value))
+        .ok_or(SloomoError::MathOverflow)?;
+    let Some(base) = holdings.iter().position(|h| h.mint == base_mint.to_bytes()) else {
+        return Ok(None);
+    };
+    let cash = holdings[base]
+        .value
+        .saturating_sub(bps_of(total_value, holdings[base].target_percentage)?);
+    if cash == 0 {
+        return Ok(None);
+    }
+
+    // Cash-flow rebalancing: direct new base asset to underweight allocations
+    let mut before_cash = holdings.to_vec();
+    before_cash[base].value -= cash;
+    let plan = plan_cash_flow(&before_cash, base_mint, cash, config)?;
+    Ok((!plan.legs.is_empty() && plan.within_band).then_some(plan))
+}
+
+/// Cash-flow rebalancing toggle event
+#[event]
+pub struct DepositRoutingSet {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Whether deposits are routed
+    pub enabled: bool,
+    /// Update time
+    pub timestamp: i64,
+}
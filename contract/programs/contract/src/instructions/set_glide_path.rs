@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, AllocationTarget, SessionKey, SESSION_SCOPE_ALLOCATION};
+use crate::utils::{validate_reentrancy, validate_authority};
+
+/// Account structure for setting the glide path
+#[derive(Accounts)]
+pub struct SetGlidePath<'info> {
+    /// Portfolio to be edited
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner or a session key)
+    pub authority: Signer<'info>,
+
+    /// Session key of `authority` (omitted when the owner signs)
+    #[account(
+        seeds = [b"session_key", portfolio.key().as_ref(), authority.key().as_ref()],
+        bump = session_key.bump,
+        has_one = portfolio
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+}
+
+/// Move allocation targets linearly from a start to an end allocation by a target date
+///
+/// The start targets apply immediately; drift checks and rebalance plans use
+/// the targets interpolated at the current time.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `start_allocations` - Targets now (empty with `end_allocations` to remove the path)
+/// * `end_allocations` - Targets from `target_date` on, for the same mints
+/// * `target_date` - Unix timestamp the end allocation is reached
+/// * `step_bps` - Move of any target that updates the stored targets and emits `GlidePathStepped`
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<SetGlidePath>,
+    start_allocations: Vec<AllocationTarget>,
+    end_allocations: Vec<AllocationTarget>,
+    target_date: i64,
+    step_bps: u16,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+    validate_authority(
+        portfolio,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.session_key.as_deref_mut(),
+        SESSION_SCOPE_ALLOCATION,
+        0,
+        clock.unix_timestamp,
+    )?;
+
+    portfolio.set_glide_path(
+        &start_allocations,
+        &end_allocations,
+        target_date,
+        step_bps,
+        clock.unix_timestamp,
+    )?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    if portfolio.glide_path.is_some() {
+        msg!(
+            "Glide path set: {} allocations until {} (step {} bps)",
+            start_allocations.len(),
+            target_date,
+            step_bps
+        );
+    } else {
+        msg!("Glide path removed");
+    }
+
+    emit!(GlidePathSet {
+        portfolio: portfolio.key(),
+        start_allocations,
+        end_allocations,
+        target_date,
+        step_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Glide path set event
+#[event]
+pub struct GlidePathSet {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Targets at the start of the path (empty: path removed)
+    pub start_allocations: Vec<AllocationTarget>,
+    /// Targets from the target date on
+    pub end_allocations: Vec<AllocationTarget>,
+    /// When the end allocation is reached
+    pub target_date: i64,
+    /// Target move that updates the stored targets (basis points)
+    pub step_bps: u16,
+    /// Update execution time
+    pub timestamp: i64,
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, GuardianSet};
+
+/// Account structure for configuring the recovery guardians
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    /// Portfolio to be protected
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Guardian set (created on first configuration)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = GuardianSet::SIZE,
+        seeds = [b"guardians", portfolio.key().as_ref()],
+        bump
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    /// Transaction executor (portfolio owner, pays the guardian set rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the guardians that can recover the portfolio
+///
+/// A recovery in progress is dropped. An empty list with a zero threshold
+/// disables recovery.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `guardians` - Guardian keys (at most `MAX_GUARDIANS`, excluding the owner)
+/// * `threshold` - Approvals needed to start the timelock
+/// * `timelock` - Seconds the owner has to veto (at least `MIN_RECOVERY_TIMELOCK`)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<SetGuardians>,
+    guardians: Vec<Pubkey>,
+    threshold: u8,
+    timelock: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let portfolio = &ctx.accounts.portfolio;
+    let guardian_set = &mut ctx.accounts.guardian_set;
+
+    guardian_set.configure(&portfolio.owner, guardians, threshold, timelock)?;
+    guardian_set.portfolio = portfolio.key();
+    guardian_set.bump = ctx.bumps.guardian_set;
+
+    emit!(GuardiansSet {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        guardians: guardian_set.guardians.clone(),
+        threshold,
+        timelock,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Guardians set: {} of {} with a {}s timelock",
+        threshold,
+        guardian_set.guardians.len(),
+        timelock
+    );
+    Ok(())
+}
+
+/// Guardian configuration event
+#[event]
+pub struct GuardiansSet {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Guardian keys
+    pub guardians: Vec<Pubkey>,
+    /// Approvals needed to start the timelock
+    pub threshold: u8,
+    /// Owner veto window (seconds)
+    pub timelock: i64,
+    /// Update time
+    pub timestamp: i64,
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, SavingsGoal};
+use crate::utils::validate_reentrancy;
+
+/// Account structure for setting the savings goal
+#[derive(Accounts)]
+pub struct SetSavingsGoal<'info> {
+    /// Portfolio to be configured
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner)
+    pub owner: Signer<'info>,
+}
+
+/// Set or remove the amount the portfolio is saving towards
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `goal` - Target amount in base asset units, target date and label (None: remove)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<SetSavingsGoal>,
+    goal: Option<SavingsGoal>,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+
+    portfolio.set_goal(goal.clone(), clock.unix_timestamp)?;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    match &goal {
+        Some(goal) => msg!(
+            "Savings goal set: {} of {} by {}",
+            goal.label,
+            goal.target_amount,
+            goal.target_date
+        ),
+        None => msg!("Savings goal removed"),
+    }
+
+    emit!(SavingsGoalSet {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        goal,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Savings goal setting event
+#[event]
+pub struct SavingsGoalSet {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// New goal (None: removed)
+    pub goal: Option<SavingsGoal>,
+    /// Update time
+    pub timestamp: i64,
+}
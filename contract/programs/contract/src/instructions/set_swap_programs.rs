@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::Portfolio;
+use crate::utils::{validate_reentrancy, validate_swap_programs};
+
+/// Account structure for choosing the swap programs rebalance legs and base
+/// withdrawals may use
+#[derive(Accounts)]
+pub struct SetSwapPrograms<'info> {
+    /// Portfolio to be configured
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner)
+    pub owner: Signer<'info>,
+}
+
+/// Replace the programs rebalance legs and `withdraw_to_base` may swap through
+///
+/// Session keys can execute legs, so only allowlisted programs receive the
+/// portfolio's signature.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `programs` - Allowed swap programs (at most `MAX_SWAP_PROGRAMS`, empty disables legs)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<SetSwapPrograms>,
+    programs: Vec<Pubkey>,
+) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    let clock = Clock::get()?;
+
+    validate_reentrancy(portfolio)?;
+    validate_swap_programs(&programs)?;
+
+    portfolio.swap_programs = programs.clone();
+    portfolio.updated_at = clock.unix_timestamp;
+
+    emit!(SwapProgramsSet {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        programs,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Swap programs set: {}", portfolio.swap_programs.len());
+    Ok(())
+}
+
+/// Swap program allowlist event
+#[event]
+pub struct SwapProgramsSet {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Allowed swap programs
+    pub programs: Vec<Pubkey>,
+    /// Update time
+    pub timestamp: i64,
+}
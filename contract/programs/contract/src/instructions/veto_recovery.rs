@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::{Portfolio, GuardianSet};
+
+/// Account structure for the owner vetoing a recovery
+#[derive(Accounts)]
+pub struct VetoRecovery<'info> {
+    /// Portfolio being recovered
+    #[account(
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Guardian set of the portfolio
+    #[account(
+        mut,
+        seeds = [b"guardians", portfolio.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = portfolio
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    /// Transaction executor (portfolio owner)
+    pub owner: Signer<'info>,
+}
+
+/// Cancel the recovery in progress
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(ctx: Context<VetoRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let recovery = ctx.accounts.guardian_set.veto_recovery()?;
+
+    emit!(RecoveryVetoed {
+        portfolio: ctx.accounts.portfolio.key(),
+        owner: ctx.accounts.owner.key(),
+        new_owner: recovery.new_owner,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Recovery to {} vetoed by the owner", recovery.new_owner);
+    Ok(())
+}
+
+/// Recovery veto event
+#[event]
+pub struct RecoveryVetoed {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Owner the vetoed recovery proposed
+    pub new_owner: Pubkey,
+    /// Veto time
+    pub timestamp: i64,
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount, TransferChecked, transfer_checked};
+use crate::state::{Portfolio, WithdrawnAmount};
+use crate::error::SloomoError;
+use crate::utils::{validate_reentrancy, vault_address, bps_of, read_mint_decimals, rescale_decimals};
+
+/// Account structure for withdrawing a share of every holding in kind
+///
+/// Remaining accounts: for every mint in `Portfolio::held_mints`, in that order,
+/// `[vault, owner token account, mint]` (vaults not created yet may be passed empty).
+#[derive(Accounts)]
+pub struct WithdrawProportional<'info> {
+    /// Source portfolio for withdrawal
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner, receives the tokens)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// SPL token program (for SPL Token mints)
+    pub token_program: Program<'info, Token>,
+    /// Token-2022 program (for Token-2022 mints)
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+/// Withdraw `bps` of every vault to the owner without swapping
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `bps` - Share of each vault to withdraw (basis points, 10000 = everything)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawProportional<'info>>,
+    bps: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(bps > 0 && bps <= 10000, SloomoError::InvalidWithdrawalShare);
+    validate_reentrancy(&ctx.accounts.portfolio)?;
+
+    let portfolio_key = ctx.accounts.portfolio.key();
+    let owner_key = ctx.accounts.owner.key();
+    let portfolio_id = ctx.accounts.portfolio.portfolio_id;
+    let mints = ctx.accounts.portfolio.held_mints();
+    require!(
+        ctx.remaining_accounts.len() >= mints.len() * 3,
+        SloomoError::MissingVaultAccount
+    );
+
+    // Withdrawn values are debited in base asset decimals (the base mint comes first)
+    let base_decimals = read_mint_decimals(&mints[..1], &ctx.remaining_accounts[2..3])?[0];
+
+    let signer_seeds: &[&[u8]] = &[
+        b"portfolio",
+        portfolio_id.as_ref(),
+        &[ctx.accounts.portfolio.bump],
+    ];
+    let portfolio_info = ctx.accounts.portfolio.to_account_info();
+
+    let mut withdrawals = Vec::with_capacity(mints.len());
+    let mut values = Vec::with_capacity(mints.len());
+    for (mint, accounts) in mints.iter().zip(ctx.remaining_accounts.chunks_exact(3)) {
+        let (vault_info, destination_info, mint_info) = (&accounts[0], &accounts[1], &accounts[2]);
+        require_keys_eq!(vault_info.key(), vault_address(&portfolio_key, mint), SloomoError::InvalidVault);
+        require_keys_eq!(mint_info.key(), *mint, SloomoError::InvalidTokenMint);
+
+        // A vault that has not been created yet holds nothing
+        if vault_info.data_is_empty() {
+            continue;
+        }
+        let vault = InterfaceAccount::<TokenAccount>::try_from(vault_info)?;
+        require_keys_eq!(vault.owner, portfolio_key, SloomoError::InvalidVault);
+
+        let amount = bps_of(vault.amount, bps)?;
+        if amount == 0 {
+            continue;
+        }
+
+        let destination = InterfaceAccount::<TokenAccount>::try_from(destination_info)?;
+        require_keys_eq!(destination.owner, owner_key, SloomoError::Unauthorized);
+        require_keys_eq!(destination.mint, *mint, SloomoError::InvalidTokenMint);
+
+        // Each mint moves through the token program that owns it
+        let mint_account = InterfaceAccount::<Mint>::try_from(mint_info)?;
+        let program_info = if *mint_info.owner == token_2022::ID {
+            ctx.accounts.token_2022_program.to_account_info()
+        } else {
+            ctx.accounts.token_program.to_account_info()
+        };
+        let cpi_accounts = TransferChecked {
+            from: vault.to_account_info(),
+            mint: mint_account.to_account_info(),
+            to: destination.to_account_info(),
+            authority: portfolio_info.clone(),
+        };
+        transfer_checked(
+            CpiContext::new_with_signer(program_info, cpi_accounts, &[signer_seeds]),
+            amount,
+            mint_account.decimals,
+        )?;
+
+        withdrawals.push(WithdrawnAmount { mint: *mint, amount });
+        values.push(rescale_decimals(amount, mint_account.decimals, base_decimals)?);
+    }
+
+    let portfolio = &mut ctx.accounts.portfolio;
+    for (withdrawal, value) in withdrawals.iter().zip(values) {
+        portfolio.record_withdrawal(&withdrawal.mint, withdrawal.amount, value);
+    }
+    portfolio.updated_at = clock.unix_timestamp;
+
+    // Add performance snapshot
+    portfolio.add_performance_snapshot(clock.unix_timestamp)?;
+
+    msg!(
+        "Proportional withdrawal completed: {} bps of {} vaults withdrawn",
+        bps,
+        withdrawals.len()
+    );
+
+    // Emit event
+    emit!(ProportionalWithdrawn {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        bps,
+        withdrawals,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Proportional in-kind withdrawal event
+#[event]
+pub struct ProportionalWithdrawn {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Share withdrawn from each vault (basis points)
+    pub bps: u16,
+    /// Amount paid out per mint
+    pub withdrawals: Vec<WithdrawnAmount>,
+    /// Withdrawal execution time
+    pub timestamp: i64,
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+use crate::state::Portfolio;
+use crate::error::SloomoError;
+use crate::utils::{validate_reentrancy, validate_amount, transfer_from_vault_with_signer, rescale_decimals};
+
+/// Account structure for native SOL withdrawal
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    /// Source portfolio for withdrawal
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Portfolio's wSOL vault (source)
+    #[account(
+        mut,
+        seeds = [b"vault", portfolio.key().as_ref(), wsol_mint.key().as_ref()],
+        bump,
+        token::mint = wsol_mint,
+        token::authority = portfolio,
+        token::token_program = token_program
+    )]
+    pub portfolio_wsol_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Temporary wSOL account, closed to the owner to unwrap
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"unwrap", portfolio.key().as_ref()],
+        bump,
+        token::mint = wsol_mint,
+        token::authority = portfolio,
+        token::token_program = token_program
+    )]
+    pub unwrap_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Native mint (wSOL)
+    #[account(address = native_mint::ID @ SloomoError::InvalidTokenMint)]
+    pub wsol_mint: InterfaceAccount<'info, Mint>,
+
+    /// Base asset mint (total value is kept in its units)
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Transaction executor (portfolio owner, receives the lamports)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraw native SOL
+///
+/// wSOL moves from the vault into a temporary account that is closed to the
+/// owner, so the owner receives plain lamports (and the temporary rent back).
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `lamports` - Withdrawal amount (lamports)
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler(
+    ctx: Context<WithdrawSol>,
+    lamports: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Use common validation functions
+    validate_amount(lamports)?;
+    validate_reentrancy(&ctx.accounts.portfolio)?;
+
+    // Validation: Vault balance
+    require!(
+        ctx.accounts.portfolio_wsol_vault.amount >= lamports,
+        SloomoError::InsufficientBalance
+    );
+
+    let portfolio_id = ctx.accounts.portfolio.portfolio_id;
+    let signer_seeds: &[&[u8]] = &[
+        b"portfolio",
+        portfolio_id.as_ref(),
+        &[ctx.accounts.portfolio.bump],
+    ];
+
+    // wSOL: vault -> temporary account
+    transfer_from_vault_with_signer(
+        &ctx.accounts.portfolio_wsol_vault,
+        &ctx.accounts.unwrap_account,
+        &ctx.accounts.wsol_mint,
+        &ctx.accounts.portfolio.to_account_info(),
+        &ctx.accounts.token_program,
+        &[signer_seeds],
+        lamports,
+    )?;
+
+    // Unwrap: closing a wSOL account releases all of its lamports
+    let close_accounts = CloseAccount {
+        account: ctx.accounts.unwrap_account.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: ctx.accounts.portfolio.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_accounts,
+        &[signer_seeds],
+    ))?;
+
+    let value = rescale_decimals(
+        lamports,
+        ctx.accounts.wsol_mint.decimals,
+        ctx.accounts.base_mint.decimals,
+    )?;
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.record_withdrawal(&ctx.accounts.wsol_mint.key(), lamports, value);
+    portfolio.updated_at = clock.unix_timestamp;
+
+    // Add performance snapshot
+    portfolio.add_performance_snapshot(clock.unix_timestamp)?;
+
+    // Emit event
+    emit!(SolWithdrawn {
+        portfolio: portfolio.key(),
+        owner: portfolio.owner,
+        lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "SOL withdrawal completed: {} SOL withdrawn",
+        lamports as f64 / 1_000_000_000.0 // SOL has 9 decimals
+    );
+
+    Ok(())
+}
+
+/// Native SOL withdrawal execution event
+#[event]
+pub struct SolWithdrawn {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Withdrawal amount (lamports)
+    pub lamports: u64,
+    /// Withdrawal execution time
+    pub timestamp: i64,
+}
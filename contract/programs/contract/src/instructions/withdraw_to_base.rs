@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    Portfolio, LiquidationOrder, LiquidationSwap, LiquidatedAmount, VaultBalance,
+};
+use crate::error::SloomoError;
+use crate::instructions::rebalance::{
+    snapshot_vaults, holdings_from_snapshot, balance_in, snapshot_total, read_snapshot_vaults,
+};
+use crate::utils::{
+    validate_reentrancy, validate_amount, read_vault_balance, rescale_decimals,
+    plan_liquidation, transfer_from_vault_with_signer, bps_of, validate_swap_accounts,
+    CommonMints, PlannerConfig,
+};
+
+/// Account structure for withdrawing an amount of the base asset, selling
+/// allocations as needed
+///
+/// Remaining accounts: the vaults of `Portfolio::rebalance_mints` (no new
+/// targets), in that order, then the mint accounts in the same order, followed
+/// by the accounts of each swap in `swaps`, `account_count` at a time.
+#[derive(Accounts)]
+pub struct WithdrawToBase<'info> {
+    /// Source portfolio for withdrawal
+    #[account(
+        mut,
+        seeds = [b"portfolio", portfolio.portfolio_id.as_ref()],
+        bump = portfolio.bump,
+        has_one = owner
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// Transaction executor (portfolio owner)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Portfolio's base asset vault (receives the swaps, pays the owner)
+    #[account(
+        mut,
+        seeds = [b"vault", portfolio.key().as_ref(), base_mint.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = portfolio,
+        token::token_program = token_program
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Owner's base asset account (destination)
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub user_base_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base asset mint
+    #[account(address = portfolio.base_mint @ SloomoError::InvalidBaseMint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Swap program (e.g. Jupiter)
+    /// CHECK: Must be executable and on the portfolio's allowlist; outcomes are checked on the vaults
+    #[account(
+        executable,
+        constraint = portfolio.swap_programs.contains(&swap_program.key()) @ SloomoError::InvalidSwapProgram
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// Token program of the base asset (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw `amount` of the base asset, selling allocations for any shortfall
+///
+/// Base asset already in the vault is paid out first. The shortfall is planned
+/// across the allocations in `order`, and each swap may only sell what the plan
+/// assigns to its mint; its accounts may not include other portfolio vaults or
+/// token accounts delegated to the portfolio. The whole withdrawal fails unless the swaps return at
+/// least `10000 - max_slippage_bps` of the value they sold (each mint rescaled
+/// to base asset decimals) and the vault ends up holding `amount`.
+///
+/// # Arguments
+/// * `ctx` - Transaction context
+/// * `amount` - Base asset amount to withdraw
+/// * `order` - Which allocations are sold first
+/// * `max_slippage_bps` - Slippage tolerated across the swaps (basis points)
+/// * `swaps` - Swaps executed through `swap_program`
+///
+/// # Returns
+/// * `Result<()>` - Ok(()) on success, error on failure
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawToBase<'info>>,
+    amount: u64,
+    order: LiquidationOrder,
+    max_slippage_bps: u16,
+    swaps: Vec<LiquidationSwap>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    validate_amount(amount)?;
+    validate_reentrancy(&ctx.accounts.portfolio)?;
+
+    let portfolio_key = ctx.accounts.portfolio.key();
+    let owner_key = ctx.accounts.owner.key();
+    let portfolio_id = ctx.accounts.portfolio.portfolio_id;
+    let base_mint = ctx.accounts.portfolio.base_mint;
+    let mints = ctx.accounts.portfolio.rebalance_mints(&[]);
+    require!(
+        ctx.remaining_accounts.len() >= 2 * mints.len(),
+        SloomoError::MissingMintAccount
+    );
+    let (snapshot_accounts, swap_accounts) = ctx.remaining_accounts.split_at(2 * mints.len());
+    let vault_accounts = &snapshot_accounts[..mints.len()];
+
+    // Plan what each allocation may sell to cover the shortfall
+    let snapshot = snapshot_vaults(
+        &ctx.accounts.portfolio,
+        ctx.accounts.base_vault.amount,
+        ctx.accounts.base_mint.decimals,
+        snapshot_accounts,
+        &[],
+    )?;
+    let mut budgets = liquidation_budgets(
+        &ctx.accounts.portfolio,
+        &snapshot,
+        amount,
+        order,
+        max_slippage_bps,
+    )?;
+
+    let signer_seeds: &[&[u8]] = &[
+        b"portfolio",
+        portfolio_id.as_ref(),
+        &[ctx.accounts.portfolio.bump],
+    ];
+
+    let mut sold: Vec<LiquidatedAmount> = Vec::new();
+    let mut offset = 0;
+    for swap in &swaps {
+        let end = offset + swap.account_count as usize;
+        require!(end <= swap_accounts.len(), SloomoError::SwapExecutionFailed);
+        let accounts = &swap_accounts[offset..end];
+        offset = end;
+
+        let budget = budgets
+            .iter_mut()
+            .find(|(mint, _)| *mint == swap.from_mint)
+            .ok_or(SloomoError::InvalidTokenMint)?;
+        require!(swap.amount_in <= budget.1, SloomoError::LiquidationExceedsPlan);
+        let vault_index = mints
+            .iter()
+            .position(|mint| *mint == swap.from_mint)
+            .ok_or(SloomoError::InvalidTokenMint)?;
+        let source_vault = &vault_accounts[vault_index];
+
+        // The PDA signs only for the swap's source vault and the base vault
+        validate_swap_accounts(
+            &portfolio_key,
+            &[source_vault.key(), ctx.accounts.base_vault.key()],
+            accounts,
+        )?;
+
+        let source_before = read_vault_balance(&portfolio_key, &swap.from_mint, source_vault)?;
+        let base_before = ctx.accounts.base_vault.amount;
+
+        // Swap with the portfolio PDA signing for its vaults
+        let instruction = Instruction {
+            program_id: ctx.accounts.swap_program.key(),
+            accounts: accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer || account.key() == portfolio_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: swap.data.clone(),
+        };
+        invoke_signed(
+            &instruction,
+            &[accounts, &[ctx.accounts.swap_program.to_account_info()]].concat(),
+            &[signer_seeds],
+        )?;
+
+        // Outcome measured on the vaults, not reported by the swap program
+        ctx.accounts.base_vault.reload()?;
+        let spent = source_before
+            .checked_sub(read_vault_balance(&portfolio_key, &swap.from_mint, source_vault)?)
+            .ok_or(SloomoError::SwapExecutionFailed)?;
+        require!(spent <= swap.amount_in, SloomoError::SwapExecutionFailed);
+        let received = ctx.accounts.base_vault.amount
+            .checked_sub(base_before)
+            .ok_or(SloomoError::SwapExecutionFailed)?;
+        budget.1 -= spent;
+
+        match sold.iter_mut().find(|s| s.mint == swap.from_mint) {
+            Some(entry) => {
+                entry.spent = entry.spent.checked_add(spent).ok_or(SloomoError::MathOverflow)?;
+                entry.received = entry.received.checked_add(received).ok_or(SloomoError::MathOverflow)?;
+            }
+            None => sold.push(LiquidatedAmount { mint: swap.from_mint, spent, received }),
+        }
+    }
+
+    // Allocation vaults may only have moved by the swaps
+    let balances = read_snapshot_vaults(&portfolio_key, &snapshot[1..], vault_accounts)?;
+    for balance in &balances {
+        let spent = sold.iter().find(|s| s.mint == balance.mint).map_or(0, |s| s.spent);
+        require!(
+            balance_in(&snapshot, &balance.mint).checked_sub(spent) == Some(balance.amount),
+            SloomoError::UnexpectedVaultBalance
+        );
+    }
+
+    let total_spent = liquidated_value(&snapshot, &sold)?;
+    let total_received = sold.iter().try_fold(0u64, |acc, s| acc.checked_add(s.received))
+        .ok_or(SloomoError::MathOverflow)?;
+    require!(
+        total_received >= bps_of(total_spent, 10000u16.saturating_sub(max_slippage_bps))?,
+        SloomoError::SlippageExceeded
+    );
+    require!(
+        ctx.accounts.base_vault.amount >= amount,
+        SloomoError::InsufficientLiquidity
+    );
+    let base_held = ctx.accounts.base_vault.amount;
+
+    transfer_from_vault_with_signer(
+        &ctx.accounts.base_vault,
+        &ctx.accounts.user_base_account,
+        &ctx.accounts.base_mint,
+        &ctx.accounts.portfolio.to_account_info(),
+        &ctx.accounts.token_program,
+        &[signer_seeds],
+        amount,
+    )?;
+    ctx.accounts.base_vault.reload()?;
+
+    // Accounting follows custody
+    let balances: Vec<VaultBalance> = std::iter::once(VaultBalance {
+        amount: ctx.accounts.base_vault.amount,
+        ..snapshot[0].clone()
+    })
+    .chain(balances)
+    .collect();
+    let total_value = snapshot_total(&balances)?;
+
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.apply_vault_balances(&balances, CommonMints::symbol_for)?;
+    portfolio.apply_swap_costs(
+        &snapshot,
+        sold.iter().map(|s| (s.mint, base_mint, s.spent, s.received)),
+    )?;
+    portfolio.release_cost(&base_mint, amount, base_held);
+    portfolio.total_value = total_value;
+    portfolio.updated_at = clock.unix_timestamp;
+
+    // Add performance snapshot
+    portfolio.add_performance_snapshot(clock.unix_timestamp)?;
+
+    msg!(
+        "Withdrawal to base completed: {} paid out, {} allocations sold for {}",
+        amount,
+        sold.len(),
+        total_received
+    );
+
+    // Emit event
+    emit!(WithdrawnToBase {
+        portfolio: portfolio_key,
+        owner: owner_key,
+        amount,
+        order,
+        sold,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// What each allocation may sell, in its own units, to fund a withdrawal of
+/// `amount` base units from `snapshot` (base vault first)
+///
+/// Holdings are planned at their value in base asset decimals, and each sell
+/// leg is converted back to the units of its mint.
+pub fn liquidation_budgets(
+    portfolio: &Portfolio,
+    snapshot: &[VaultBalance],
+    amount: u64,
+    order: LiquidationOrder,
+    max_slippage_bps: u16,
+) -> Result<Vec<(Pubkey, u64)>> {
+    let base_decimals = base_decimals(snapshot)?;
+    let holdings = holdings_from_snapshot(portfolio, snapshot, &portfolio.current_targets())?;
+
+    let config = PlannerConfig {
+        slippage_bps: max_slippage_bps,
+        ..PlannerConfig::default()
+    };
+    plan_liquidation(&holdings, portfolio.base_mint, amount, order.to_core(), &config)?
+        .iter()
+        .map(|leg| {
+            let mint = Pubkey::new_from_array(leg.from_mint);
+            Ok((mint, rescale_decimals(leg.amount, base_decimals, decimals_of(snapshot, &mint)?)?))
+        })
+        .collect()
+}
+
+/// Value of what the swaps sold, each mint rescaled to the base asset decimals
+/// of `snapshot` (base vault first)
+pub fn liquidated_value(snapshot: &[VaultBalance], sold: &[LiquidatedAmount]) -> Result<u64> {
+    let base_decimals = base_decimals(snapshot)?;
+    sold.iter().try_fold(0u64, |acc, s| {
+        let value = rescale_decimals(s.spent, decimals_of(snapshot, &s.mint)?, base_decimals)?;
+        acc.checked_add(value).ok_or(SloomoError::MathOverflow.into())
+    })
+}
+
+/// Decimals of the base vault, the first entry of `snapshot`
+fn base_decimals(snapshot: &[VaultBalance]) -> Result<u8> {
+    Ok(snapshot.first().ok_or(SloomoError::MissingVaultAccount)?.decimals)
+}
+
+/// Decimals of the vault of `mint` in `snapshot`
+fn decimals_of(snapshot: &[VaultBalance], mint: &Pubkey) -> Result<u8> {
+    snapshot
+        .iter()
+        .find(|b| b.mint == *mint)
+        .map(|b| b.decimals)
+        .ok_or(SloomoError::InvalidTokenMint.into())
+}
+
+/// Base asset withdrawal event
+#[event]
+pub struct WithdrawnToBase {
+    /// Portfolio account
+    pub portfolio: Pubkey,
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Base asset amount paid out
+    pub amount: u64,
+    /// Which allocations were sold first
+    pub order: LiquidationOrder,
+    /// Allocations sold into the base asset
+    pub sold: Vec<LiquidatedAmount>,
+    /// Withdrawal execution time
+    pub timestamp: i64,
+}
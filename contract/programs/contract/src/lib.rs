@@ -1,10 +1,11 @@
+
 use anchor_lang::prelude::*;
 
 // Module declarations
 mod error;
 mod state;
 mod instructions;
-mod utils;
+pub mod utils;
 
 // Public exports
 pub use error::*;
@@ -13,53 +14,332 @@ pub use instructions::*;
 
 declare_id!("EAkD1pREBvpRtoAY88hmwKYr2qhdbU1rLYQ9sxTAzxhC");
 
-#[program]
-pub mod sloomo_portfolio {
+// Anchor 0.31's #[program] expansion (the generated IDL instructions) still calls
+// the deprecated AccountInfo::realloc; the allowance covers that expansion only
+#[allow(deprecated)]
+mod program_entry {
     use super::*;
 
-    /// Initialize portfolio
-    pub fn initialize_portfolio(
-        ctx: Context<InitializePortfolio>,
-        params: InitPortfolioParams,
-    ) -> Result<()> {
-        instructions::initialize_portfolio::handler(ctx, params)
-    }
+    #[program]
+    pub mod sloomo_portfolio {
+        use super::*;
 
-    /// Deposit USDC
-    pub fn deposit_usdc(
-        ctx: Context<DepositUsdc>,
-        amount: u64,
-    ) -> Result<()> {
-        instructions::deposit_usdc::handler(ctx, amount)
-    }
+        /// Initialize portfolio
+        pub fn initialize_portfolio(
+            ctx: Context<InitializePortfolio>,
+            portfolio_id: Pubkey,
+            params: InitPortfolioParams,
+        ) -> Result<()> {
+            instructions::initialize_portfolio::handler(ctx, portfolio_id, params)
+        }
 
-    /// Add/edit allocation
-    pub fn add_or_update_allocation(
-        ctx: Context<AddOrUpdateAllocation>,
-        mint: Pubkey,
-        symbol: String,
-        target_percentage: u16,
-    ) -> Result<()> {
-        instructions::add_or_update_allocation::handler(ctx, mint, symbol, target_percentage)
-    }
+        /// Migrate a legacy portfolio account to the current layout
+        pub fn migrate_portfolio(ctx: Context<MigratePortfolio>) -> Result<()> {
+            instructions::migrate_portfolio::handler(ctx)
+        }
 
-    /// Execute Jupiter rebalance with actual asset movement
-    pub fn real_jupiter_rebalance(
-        ctx: Context<RealJupiterRebalance>,
-        target_allocations: Vec<AllocationTarget>,
-        slippage_bps: Option<u16>,
-    ) -> Result<()> {
-        instructions::real_jupiter_rebalance::handler(ctx, target_allocations, slippage_bps)
-    }
+        /// Deposit USDC
+        /// Deprecated: use `deposit_token`
+        pub fn deposit_usdc(
+            ctx: Context<DepositUsdc>,
+            amount: u64,
+        ) -> Result<()> {
+            instructions::deposit_usdc::handler(ctx, amount)
+        }
 
-    /// Execute SOL-based Jupiter rebalance
-    pub fn sol_jupiter_rebalance(
-        ctx: Context<SolJupiterRebalance>,
-        target_allocations: Vec<AllocationTarget>,
-        slippage_bps: Option<u16>,
-    ) -> Result<()> {
-        instructions::sol_jupiter_rebalance::handler(ctx, target_allocations, slippage_bps)
-    }
+        /// Deposit the base asset or an allocated token
+        pub fn deposit_token(
+            ctx: Context<DepositToken>,
+            amount: u64,
+        ) -> Result<()> {
+            instructions::deposit_token::handler(ctx, amount)
+        }
+
+        /// Deposit native SOL (wrapped into the wSOL vault)
+        pub fn deposit_sol(
+            ctx: Context<DepositSol>,
+            lamports: u64,
+        ) -> Result<()> {
+            instructions::deposit_sol::handler(ctx, lamports)
+        }
+
+        /// Withdraw native SOL (unwrapped from the wSOL vault)
+        pub fn withdraw_sol(
+            ctx: Context<WithdrawSol>,
+            lamports: u64,
+        ) -> Result<()> {
+            instructions::withdraw_sol::handler(ctx, lamports)
+        }
+
+        /// Withdraw a share (basis points) of every vault in kind
+        pub fn withdraw_proportional<'info>(
+            ctx: Context<'_, '_, 'info, 'info, WithdrawProportional<'info>>,
+            bps: u16,
+        ) -> Result<()> {
+            instructions::withdraw_proportional::handler(ctx, bps)
+        }
+
+        /// Withdraw an amount of the base asset, selling allocations for any shortfall
+        pub fn withdraw_to_base<'info>(
+            ctx: Context<'_, '_, 'info, 'info, WithdrawToBase<'info>>,
+            amount: u64,
+            order: LiquidationOrder,
+            max_slippage_bps: u16,
+            swaps: Vec<LiquidationSwap>,
+        ) -> Result<()> {
+            instructions::withdraw_to_base::handler(ctx, amount, order, max_slippage_bps, swaps)
+        }
+
+        /// Add/edit allocation
+        pub fn add_or_update_allocation(
+            ctx: Context<AddOrUpdateAllocation>,
+            mint: Pubkey,
+            symbol: String,
+            target_percentage: u16,
+            band: Option<AllocationBand>,
+        ) -> Result<()> {
+            instructions::add_or_update_allocation::handler(ctx, mint, symbol, target_percentage, band)
+        }
+
+        /// Rebalance from the portfolio's base asset
+        pub fn rebalance(
+            ctx: Context<Rebalance>,
+            target_allocations: Vec<AllocationTarget>,
+            slippage_bps: Option<u16>,
+        ) -> Result<()> {
+            instructions::rebalance::handler(ctx, target_allocations, slippage_bps)
+        }
+
+        /// Execute Jupiter rebalance with actual asset movement
+        /// Deprecated: use `rebalance`
+        pub fn real_jupiter_rebalance(
+            ctx: Context<RealJupiterRebalance>,
+            target_allocations: Vec<AllocationTarget>,
+            slippage_bps: Option<u16>,
+        ) -> Result<()> {
+            instructions::real_jupiter_rebalance::handler(ctx, target_allocations, slippage_bps)
+        }
+
+        /// Execute SOL-based Jupiter rebalance
+        /// Deprecated: use `rebalance`
+        pub fn sol_jupiter_rebalance(
+            ctx: Context<SolJupiterRebalance>,
+            target_allocations: Vec<AllocationTarget>,
+            slippage_bps: Option<u16>,
+        ) -> Result<()> {
+            instructions::sol_jupiter_rebalance::handler(ctx, target_allocations, slippage_bps)
+        }
+
+        /// Open a rebalance session spanning several transactions
+        pub fn begin_rebalance(
+            ctx: Context<BeginRebalance>,
+            target_allocations: Vec<AllocationTarget>,
+            slippage_bps: Option<u16>,
+        ) -> Result<()> {
+            instructions::begin_rebalance::handler(ctx, target_allocations, slippage_bps)
+        }
+
+        /// Execute one planned leg of the open rebalance session
+        pub fn execute_rebalance_leg<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ExecuteRebalanceLeg<'info>>,
+            leg_index: u8,
+            swap_data: Vec<u8>,
+        ) -> Result<()> {
+            instructions::execute_rebalance_leg::handler(ctx, leg_index, swap_data)
+        }
+
+        /// Verify the executed legs, apply the session targets and write a rebalance record
+        pub fn commit_rebalance(ctx: Context<CommitRebalance>) -> Result<()> {
+            instructions::commit_rebalance::handler(ctx)
+        }
+
+        /// Close the rebalance session without applying its targets
+        pub fn abort_rebalance(ctx: Context<AbortRebalance>) -> Result<()> {
+            instructions::abort_rebalance::handler(ctx)
+        }
+
+        /// Close a rebalance record and reclaim its rent
+        pub fn close_rebalance_record(ctx: Context<CloseRebalanceRecord>) -> Result<()> {
+            instructions::close_rebalance_record::handler(ctx)
+        }
+
+        /// Enable or disable routing of base asset deposits to underweight allocations
+        pub fn set_deposit_routing(
+            ctx: Context<SetDepositRouting>,
+            enabled: bool,
+        ) -> Result<()> {
+            instructions::set_deposit_routing::handler(ctx, enabled)
+        }
+
+        /// Choose whether rebalances restore out-of-band allocations to the band edge or target
+        pub fn set_band_restore(
+            ctx: Context<SetBandRestore>,
+            restore: BandRestore,
+        ) -> Result<()> {
+            instructions::set_band_restore::handler(ctx, restore)
+        }
+
+        /// Replace the swap programs rebalance legs and base withdrawals may use
+        pub fn set_swap_programs(
+            ctx: Context<SetSwapPrograms>,
+            programs: Vec<Pubkey>,
+        ) -> Result<()> {
+            instructions::set_swap_programs::handler(ctx, programs)
+        }
 
+        /// Group allocations into asset classes with class targets and intra-class weights
+        pub fn set_asset_classes(
+            ctx: Context<SetAssetClasses>,
+            classes: Vec<AssetClass>,
+            memberships: Vec<ClassMembership>,
+        ) -> Result<()> {
+            instructions::set_asset_classes::handler(ctx, classes, memberships)
+        }
+
+        /// Move allocation targets from a start to an end allocation by a target date
+        pub fn set_glide_path(
+            ctx: Context<SetGlidePath>,
+            start_allocations: Vec<AllocationTarget>,
+            end_allocations: Vec<AllocationTarget>,
+            target_date: i64,
+            step_bps: u16,
+        ) -> Result<()> {
+            instructions::set_glide_path::handler(ctx, start_allocations, end_allocations, target_date, step_bps)
+        }
+
+        /// Update stored targets along the glide path (permissionless crank)
+        pub fn advance_glide_path(ctx: Context<AdvanceGlidePath>) -> Result<()> {
+            instructions::advance_glide_path::handler(ctx)
+        }
+
+        /// Set or remove the savings goal (target amount, target date, label)
+        pub fn set_savings_goal(
+            ctx: Context<SetSavingsGoal>,
+            goal: Option<SavingsGoal>,
+        ) -> Result<()> {
+            instructions::set_savings_goal::handler(ctx, goal)
+        }
+
+        /// Savings goal progress and required contribution (view, via return data)
+        pub fn get_goal_progress(
+            ctx: Context<GetGoalProgress>,
+            contribution_interval: i64,
+        ) -> Result<GoalProgress> {
+            instructions::get_goal_progress::handler(ctx, contribution_interval)
+        }
+
+        /// Portfolio values, shares and effective targets (view, via return data)
+        pub fn get_summary(ctx: Context<GetSummary>) -> Result<PortfolioSummary> {
+            instructions::get_summary::handler(ctx)
+        }
+
+        /// Drift of every allocation and asset class (view, via return data)
+        pub fn get_drift(ctx: Context<GetDrift>) -> Result<DriftReport> {
+            instructions::get_drift::handler(ctx)
+        }
+
+        /// Dry run of a rebalance: drift, legs and fee estimates (view, via return data)
+        pub fn preview_rebalance(
+            ctx: Context<PreviewRebalance>,
+            target_allocations: Vec<AllocationTarget>,
+            slippage_bps: Option<u16>,
+        ) -> Result<RebalancePreview> {
+            instructions::preview_rebalance::handler(ctx, target_allocations, slippage_bps)
+        }
+
+        /// Cost basis, unrealized and realized P&L per allocation (view, via return data)
+        pub fn get_pnl(ctx: Context<GetPnl>, prices: Vec<AssetPrice>) -> Result<PnlReport> {
+            instructions::get_pnl::handler(ctx, prices)
+        }
+
+        /// Propose a new portfolio owner (takes effect on acceptance)
+        pub fn propose_owner_transfer(
+            ctx: Context<ProposeOwnerTransfer>,
+            new_owner: Pubkey,
+        ) -> Result<()> {
+            instructions::propose_owner_transfer::handler(ctx, new_owner)
+        }
+
+        /// Cancel the pending owner transfer
+        pub fn cancel_owner_transfer(ctx: Context<CancelOwnerTransfer>) -> Result<()> {
+            instructions::cancel_owner_transfer::handler(ctx)
+        }
+
+        /// Accept a proposed owner transfer as the new owner
+        pub fn accept_owner_transfer(ctx: Context<AcceptOwnerTransfer>) -> Result<()> {
+            instructions::accept_owner_transfer::handler(ctx)
+        }
+
+        /// Configure the M-of-N guardians that can recover the portfolio
+        pub fn set_guardians(
+            ctx: Context<SetGuardians>,
+            guardians: Vec<Pubkey>,
+            threshold: u8,
+            timelock: i64,
+        ) -> Result<()> {
+            instructions::set_guardians::handler(ctx, guardians, threshold, timelock)
+        }
+
+        /// Start a guardian recovery to a new owner
+        pub fn initiate_recovery(
+            ctx: Context<InitiateRecovery>,
+            new_owner: Pubkey,
+        ) -> Result<()> {
+            instructions::initiate_recovery::handler(ctx, new_owner)
+        }
+
+        /// Approve the guardian recovery in progress
+        pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+            instructions::approve_recovery::handler(ctx)
+        }
+
+        /// Veto the guardian recovery in progress (owner)
+        pub fn veto_recovery(ctx: Context<VetoRecovery>) -> Result<()> {
+            instructions::veto_recovery::handler(ctx)
+        }
+
+        /// Reassign the owner once the recovery timelock has passed
+        pub fn complete_recovery(ctx: Context<CompleteRecovery>) -> Result<()> {
+            instructions::complete_recovery::handler(ctx)
+        }
+
+        /// Issue an expiring, scoped session key that may sign in place of the owner
+        pub fn create_session_key(
+            ctx: Context<CreateSessionKey>,
+            authority: Pubkey,
+            scopes: u8,
+            expires_at: i64,
+            spend_limit: Option<u64>,
+        ) -> Result<()> {
+            instructions::create_session_key::handler(ctx, authority, scopes, expires_at, spend_limit)
+        }
+
+        /// Revoke a session key
+        pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+            instructions::revoke_session_key::handler(ctx)
+        }
+
+        /// Schedule a recurring base asset deposit pulled through an SPL delegate approval
+        pub fn create_recurring_deposit(
+            ctx: Context<CreateRecurringDeposit>,
+            amount: u64,
+            interval: i64,
+            start_at: Option<i64>,
+            max_occurrences: Option<u32>,
+        ) -> Result<()> {
+            instructions::create_recurring_deposit::handler(ctx, amount, interval, start_at, max_occurrences)
+        }
+
+        /// Execute a due recurring deposit (permissionless)
+        pub fn crank_recurring_deposit(ctx: Context<CrankRecurringDeposit>) -> Result<()> {
+            instructions::crank_recurring_deposit::handler(ctx)
+        }
+
+        /// Cancel the recurring deposit
+        pub fn cancel_recurring_deposit(ctx: Context<CancelRecurringDeposit>) -> Result<()> {
+            instructions::cancel_recurring_deposit::handler(ctx)
+        }
+    }
+}
 
-}
\ No newline at end of file
+pub use program_entry::*;
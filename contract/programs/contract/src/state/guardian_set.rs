@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use crate::error::SloomoError;
+
+/// Guardian set account
+/// Guardians who can reassign the portfolio owner once `threshold` of them agree
+/// and `timelock` has passed without an owner veto
+/// (`initiate_recovery` -> `approve_recovery`* -> `complete_recovery`, or `veto_recovery`)
+#[account]
+pub struct GuardianSet {
+    /// Portfolio protected by the guardians
+    pub portfolio: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Guardian keys (empty when recovery is disabled)
+    pub guardians: Vec<Pubkey>,
+    /// Approvals needed before the timelock starts
+    pub threshold: u8,
+    /// Seconds the owner has to veto once the threshold is reached
+    pub timelock: i64,
+    /// Recovery in progress
+    pub recovery: Option<PendingRecovery>,
+}
+
+/// Owner reassignment proposed by the guardians
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingRecovery {
+    /// Owner the portfolio is reassigned to
+    pub new_owner: Pubkey,
+    /// Guardians that approved (bit `i` is `guardians[i]`)
+    pub approvals: u16,
+    /// Time the recovery was initiated
+    pub initiated_at: i64,
+    /// Time the threshold was reached (the timelock runs from here)
+    pub approved_at: Option<i64>,
+}
+
+impl PendingRecovery {
+    pub const SIZE: usize = 32 + // new_owner
+        2 + // approvals
+        8 + // initiated_at
+        1 + 8; // approved_at
+}
+
+impl GuardianSet {
+    /// Account size calculation
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // portfolio
+        1 + // bump
+        4 + (MAX_GUARDIANS * 32) + // guardians
+        1 + // threshold
+        8 + // timelock
+        1 + PendingRecovery::SIZE; // recovery
+
+    /// Replace the guardians, dropping any recovery in progress
+    ///
+    /// An empty set with a zero threshold disables recovery.
+    pub fn configure(
+        &mut self,
+        owner: &Pubkey,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        timelock: i64,
+    ) -> Result<()> {
+        require!(guardians.len() <= MAX_GUARDIANS, SloomoError::InvalidGuardianSet);
+        if guardians.is_empty() {
+            require!(threshold == 0, SloomoError::InvalidGuardianSet);
+        } else {
+            require!(
+                threshold >= 1 && threshold as usize <= guardians.len(),
+                SloomoError::InvalidGuardianSet
+            );
+        }
+        require!(timelock >= MIN_RECOVERY_TIMELOCK, SloomoError::InvalidGuardianSet);
+        for (i, guardian) in guardians.iter().enumerate() {
+            // The owner guarding itself would defeat the veto
+            require!(guardian != owner, SloomoError::InvalidGuardianSet);
+            require!(!guardians[..i].contains(guardian), SloomoError::InvalidGuardianSet);
+        }
+
+        self.guardians = guardians;
+        self.threshold = threshold;
+        self.timelock = timelock;
+        self.recovery = None;
+        Ok(())
+    }
+
+    /// Position of `guardian` in the set
+    pub fn guardian_index(&self, guardian: &Pubkey) -> Result<usize> {
+        self.guardians
+            .iter()
+            .position(|g| g == guardian)
+            .ok_or_else(|| SloomoError::NotAGuardian.into())
+    }
+
+    /// Start a recovery to `new_owner`, counting the initiating guardian's approval
+    pub fn initiate_recovery(&mut self, guardian: &Pubkey, new_owner: Pubkey, now: i64) -> Result<()> {
+        let index = self.guardian_index(guardian)?;
+        require!(self.recovery.is_none(), SloomoError::RecoveryInProgress);
+
+        self.recovery = Some(PendingRecovery {
+            new_owner,
+            approvals: 0,
+            initiated_at: now,
+            approved_at: None,
+        });
+        self.record_approval(index, now)
+    }
+
+    /// Add `guardian`'s approval to the recovery in progress
+    pub fn approve_recovery(&mut self, guardian: &Pubkey, now: i64) -> Result<()> {
+        let index = self.guardian_index(guardian)?;
+        self.record_approval(index, now)
+    }
+
+    fn record_approval(&mut self, index: usize, now: i64) -> Result<()> {
+        let threshold = self.threshold as u32;
+        let recovery = self.recovery.as_mut().ok_or(SloomoError::NoPendingRecovery)?;
+        let bit = 1u16 << index;
+        require!(recovery.approvals & bit == 0, SloomoError::RecoveryAlreadyApproved);
+
+        recovery.approvals |= bit;
+        if recovery.approved_at.is_none() && recovery.approvals.count_ones() >= threshold {
+            recovery.approved_at = Some(now);
+        }
+        Ok(())
+    }
+
+    /// Number of guardians that approved the recovery in progress
+    pub fn approval_count(&self) -> u8 {
+        self.recovery
+            .as_ref()
+            .map_or(0, |recovery| recovery.approvals.count_ones() as u8)
+    }
+
+    /// Time from which the recovery in progress can be completed
+    pub fn unlocks_at(&self) -> Option<i64> {
+        self.recovery
+            .as_ref()
+            .and_then(|recovery| recovery.approved_at)
+            .map(|approved_at| approved_at.saturating_add(self.timelock))
+    }
+
+    /// Drop any recovery in progress (the owner changed), returning whether one was
+    pub fn cancel_recovery(&mut self) -> bool {
+        self.recovery.take().is_some()
+    }
+
+    /// Cancel the recovery in progress (owner veto)
+    pub fn veto_recovery(&mut self) -> Result<PendingRecovery> {
+        self.recovery.take().ok_or_else(|| SloomoError::NoPendingRecovery.into())
+    }
+
+    /// Finish the recovery once approved and past the timelock, returning the new owner
+    pub fn complete_recovery(&mut self, now: i64) -> Result<Pubkey> {
+        require!(self.recovery.is_some(), SloomoError::NoPendingRecovery);
+        let unlocks_at = self.unlocks_at().ok_or(SloomoError::RecoveryNotApproved)?;
+        require!(now >= unlocks_at, SloomoError::RecoveryTimelockActive);
+
+        let recovery = self.recovery.take().ok_or(SloomoError::NoPendingRecovery)?;
+        Ok(recovery.new_owner)
+    }
+}
+
+// Constant definitions
+pub const MAX_GUARDIANS: usize = 10;
+/// Shortest veto window the owner can configure (seconds)
+pub const MIN_RECOVERY_TIMELOCK: i64 = 24 * 60 * 60;
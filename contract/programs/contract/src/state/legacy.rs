@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::portfolio::{Portfolio, PORTFOLIO_VERSION, MAX_ALLOCATIONS, MAX_PERFORMANCE_SNAPSHOTS};
+use crate::state::types::{AllocationData, BandRestore, PerformanceSnapshot};
+use crate::error::SloomoError;
+
+/// Allocation data of a portfolio created before versioning
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyAllocationData {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Token symbol
+    pub symbol: String,
+    /// Current investment amount
+    pub current_amount: u64,
+    /// Target allocation percentage (basis points)
+    pub target_percentage: u16,
+    /// Annual percentage yield (basis points)
+    pub apy: u16,
+    /// Last yield update timestamp
+    pub last_yield_update: i64,
+}
+
+impl LegacyAllocationData {
+    pub const SIZE: usize = 32 + // mint
+        4 + 32 + // symbol (max 32 chars)
+        8 + // current_amount
+        2 + // target_percentage
+        2 + // apy
+        8; // last_yield_update
+}
+
+/// Portfolio account created before versioning
+///
+/// Seeded by `[b"portfolio", owner]` and shares the `Portfolio` discriminator,
+/// so it is only told apart by its size.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyPortfolio {
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Total portfolio value
+    pub total_value: u64,
+    /// Last rebalancing execution time
+    pub last_rebalance: i64,
+    /// Allocation data
+    pub allocations: Vec<LegacyAllocationData>,
+    /// Performance history
+    pub performance_history: Vec<PerformanceSnapshot>,
+    /// Creation date
+    pub created_at: i64,
+    /// Update date
+    pub updated_at: i64,
+    /// Rebalancing in progress flag
+    pub is_rebalancing: bool,
+}
+
+impl LegacyPortfolio {
+    /// Account size of the legacy layout
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // owner
+        1 + // bump
+        8 + // total_value
+        8 + // last_rebalance
+        4 + (MAX_ALLOCATIONS * LegacyAllocationData::SIZE) + // allocations
+        4 + (MAX_PERFORMANCE_SNAPSHOTS * PerformanceSnapshot::SIZE) + // performance_history
+        8 + // created_at
+        8 + // updated_at
+        1; // is_rebalancing
+
+    /// Parse raw account data in the legacy layout
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() == Self::SIZE && data.starts_with(Portfolio::DISCRIMINATOR),
+            SloomoError::UnsupportedPortfolioLayout
+        );
+        Self::deserialize(&mut &data[Portfolio::DISCRIMINATOR.len()..])
+            .map_err(|_| SloomoError::UnsupportedPortfolioLayout.into())
+    }
+
+    /// Current portfolio holding the legacy data
+    ///
+    /// The owner's key stays the `portfolio_id` so the PDA still derives, and
+    /// fields added since default to their `initialize_portfolio` values.
+    pub fn migrate(self, base_mint: Pubkey, swap_program: Pubkey) -> Portfolio {
+        Portfolio {
+            owner: self.owner,
+            bump: self.bump,
+            total_value: self.total_value,
+            last_rebalance: self.last_rebalance,
+            allocations: self
+                .allocations
+                .into_iter()
+                .map(|allocation| AllocationData {
+                    mint: allocation.mint,
+                    symbol: allocation.symbol,
+                    current_amount: allocation.current_amount,
+                    target_percentage: allocation.target_percentage,
+                    apy: allocation.apy,
+                    last_yield_update: allocation.last_yield_update,
+                    band: None,
+                    asset_class: None,
+                    // Held amounts carry no price history: booked at cost
+                    cost_basis: allocation.current_amount,
+                    realized_pnl: 0,
+                })
+                .collect(),
+            performance_history: self.performance_history,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            is_rebalancing: self.is_rebalancing,
+            base_mint,
+            route_deposits: false,
+            portfolio_id: self.owner,
+            pending_owner: None,
+            band_restore: BandRestore::Edge,
+            asset_classes: Vec::new(),
+            glide_path: None,
+            goal: None,
+            rebalance_count: 0,
+            swap_programs: vec![swap_program],
+            version: PORTFOLIO_VERSION,
+        }
+    }
+}
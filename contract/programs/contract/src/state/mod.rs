@@ -1,5 +1,17 @@
 pub mod portfolio;
 pub mod types;
+pub mod rebalance_session;
+pub mod rebalance_record;
+pub mod guardian_set;
+pub mod session_key;
+pub mod recurring_deposit;
+pub mod legacy;
 
 pub use portfolio::*;
-pub use types::*;
\ No newline at end of file
+pub use types::*;
+pub use rebalance_session::*;
+pub use rebalance_record::*;
+pub use guardian_set::*;
+pub use session_key::*;
+pub use recurring_deposit::*;
+pub use legacy::*;
\ No newline at end of file
@@ -1,6 +1,19 @@
 use anchor_lang::prelude::*;
 use crate::state::types::*;
+use crate::state::rebalance_session::VaultBalance;
 use crate::error::SloomoError;
+use crate::utils::math::{growth_bps, rescale_decimals};
+use crate::utils::validations::{
+    validate_allocation_band, validate_allocation_percentage, validate_target_allocations,
+    validate_unique_mints,
+};
+use sloomo_core::glide::max_target_move;
+use sloomo_core::goal::{expected_growth_bps, GrowthSample};
+use sloomo_core::hierarchy::{effective_target, needs_class_rebalancing, validate_hierarchy};
+use sloomo_core::drift::{within_band, DEFAULT_DRIFT_THRESHOLD_BPS};
+use sloomo_core::hierarchy::class_values;
+use sloomo_core::math::{amount_to_bps, PRICE_SCALE};
+use sloomo_core::cost_basis::{average_cost, cost_of, gain, value_at};
 
 /// Portfolio account
 /// Manages user's investment portfolio
@@ -24,6 +37,28 @@ pub struct Portfolio {
     pub updated_at: i64,
     /// Rebalancing in progress flag (reentrancy prevention)
     pub is_rebalancing: bool,
+    /// Base asset mint (chosen at initialization, funds every rebalance)
+    pub base_mint: Pubkey,
+    /// Route base asset deposits to underweight allocations (cash-flow rebalancing)
+    pub route_deposits: bool,
+    /// Stable PDA seed chosen at initialization, unchanged by owner transfers
+    pub portfolio_id: Pubkey,
+    /// Owner proposed by `propose_owner_transfer`, pending acceptance
+    pub pending_owner: Option<Pubkey>,
+    /// How far rebalances trade allocations that left their band
+    pub band_restore: BandRestore,
+    /// Asset classes splitting the portfolio above the allocations (empty: flat)
+    pub asset_classes: Vec<AssetClass>,
+    /// Targets moving towards an end allocation over time (None: fixed targets)
+    pub glide_path: Option<GlidePath>,
+    /// Savings goal shown alongside the value (None: no goal)
+    pub goal: Option<SavingsGoal>,
+    /// Committed rebalance sessions (seed of the next `RebalanceRecord`)
+    pub rebalance_count: u64,
+    /// Programs rebalance legs may swap through (set by the owner)
+    pub swap_programs: Vec<Pubkey>,
+    /// Account layout version (`PORTFOLIO_VERSION` once initialized or migrated)
+    pub version: u8,
 }
 
 impl Portfolio {
@@ -37,51 +72,578 @@ impl Portfolio {
         4 + (MAX_PERFORMANCE_SNAPSHOTS * PerformanceSnapshot::SIZE) + // performance_history
         8 + // created_at
         8 + // updated_at
-        1; // is_rebalancing
+        1 + // is_rebalancing
+        32 + // base_mint
+        1 + // route_deposits
+        32 + // portfolio_id
+        1 + 32 + // pending_owner
+        1 + // band_restore
+        4 + (MAX_ASSET_CLASSES * AssetClass::SIZE) + // asset_classes
+        1 + GlidePath::SIZE + // glide_path
+        1 + SavingsGoal::SIZE + // goal
+        8 + // rebalance_count
+        4 + (MAX_SWAP_PROGRAMS * 32) + // swap_programs
+        1; // version
 
 
     /// Calculate total portfolio value
     pub fn calculate_total_value(&self) -> Result<u64> {
-        self.allocations
-            .iter()
-            .map(|a| a.current_amount)
-            .fold(Some(0u64), |acc, val| acc?.checked_add(val))
-            .ok_or(SloomoError::MathOverflow.into())
+        sloomo_core::valuation::total_value(self.allocations.iter().map(|a| a.current_amount))
+            .map_err(|e| SloomoError::from(e).into())
     }
 
     /// Determine if rebalancing is needed
+    ///
+    /// Allocations and asset classes with a band are checked against it, the
+    /// rest against the default 5% threshold.
     pub fn needs_rebalancing(
         &self,
         target_allocations: &[AllocationTarget],
         total_value: u64,
     ) -> Result<bool> {
-        if total_value == 0 {
-            return Ok(false);
+        let holdings: Vec<_> = self.allocations.iter().map(|a| a.holding()).collect();
+        let targets: Vec<_> = target_allocations
+            .iter()
+            .map(|t| sloomo_core::valuation::Target { band: self.band_for(&t.mint), ..t.to_core() })
+            .collect();
+
+        let values: Vec<u64> = self.allocations.iter().map(|a| a.current_amount).collect();
+
+        Ok(sloomo_core::drift::needs_rebalancing(
+            &holdings,
+            &targets,
+            total_value,
+            DEFAULT_DRIFT_THRESHOLD_BPS,
+        ) || needs_class_rebalancing(
+            &values,
+            &self.class_members(),
+            &self.core_asset_classes(),
+            total_value,
+            DEFAULT_DRIFT_THRESHOLD_BPS,
+        ))
+    }
+
+    /// Asset classes for the core math
+    pub fn core_asset_classes(&self) -> Vec<sloomo_core::hierarchy::AssetClass> {
+        self.asset_classes.iter().map(|c| c.to_core()).collect()
+    }
+
+    /// Asset class membership of every allocation, in allocation order
+    pub fn class_members(&self) -> Vec<Option<sloomo_core::hierarchy::ClassMember>> {
+        self.allocations.iter().map(|a| a.class_member()).collect()
+    }
+
+    /// Replace the asset classes and class memberships
+    ///
+    /// Member allocations get their flattened target (class target times
+    /// weight); allocations not listed in `memberships` leave their class and
+    /// keep their own target. Empty `classes` and `memberships` return the
+    /// portfolio to flat allocations.
+    pub fn set_asset_classes(
+        &mut self,
+        classes: Vec<AssetClass>,
+        memberships: &[ClassMembership],
+    ) -> Result<()> {
+        require!(classes.len() <= MAX_ASSET_CLASSES, SloomoError::InvalidAssetClass);
+        for class in &classes {
+            require!(
+                !class.name.is_empty() && class.name.len() <= 16,
+                SloomoError::InvalidAssetClass
+            );
+        }
+        let mints: Vec<Pubkey> = memberships.iter().map(|m| m.mint).collect();
+        validate_unique_mints(&mints)?;
+        for membership in memberships {
+            require!(
+                self.allocations.iter().any(|a| a.mint == membership.mint),
+                SloomoError::InvalidTokenMint
+            );
+            require!(!self.on_glide_path(&membership.mint), SloomoError::AllocationOnGlidePath);
+        }
+
+        let assigned: Vec<Option<AllocationClass>> = self
+            .allocations
+            .iter()
+            .map(|a| {
+                memberships
+                    .iter()
+                    .find(|m| m.mint == a.mint)
+                    .map(|m| AllocationClass { asset_class: m.asset_class, weight: m.weight })
+            })
+            .collect();
+        let members: Vec<_> = assigned.iter().map(|c| c.map(|c| c.to_core())).collect();
+        let core_classes: Vec<_> = classes.iter().map(|c| c.to_core()).collect();
+        validate_hierarchy(&core_classes, &members).map_err(SloomoError::from)?;
+
+        let mut targets = Vec::with_capacity(self.allocations.len());
+        for (allocation, class) in self.allocations.iter().zip(&assigned) {
+            let target = match class {
+                Some(class) => effective_target(
+                    classes[class.asset_class as usize].target_percentage,
+                    class.weight,
+                ),
+                None => allocation.target_percentage,
+            };
+            validate_allocation_band(allocation.band, target)?;
+            targets.push(target);
+        }
+        let total_target = sloomo_core::math::sum_target_percentages(targets.iter().copied())
+            .map_err(SloomoError::from)?;
+        validate_allocation_percentage(total_target)?;
+
+        for ((allocation, class), target) in self.allocations.iter_mut().zip(assigned).zip(targets) {
+            allocation.asset_class = class;
+            allocation.target_percentage = target;
+        }
+        self.asset_classes = classes;
+        Ok(())
+    }
+
+    /// Whether `mint`'s target follows the glide path
+    pub fn on_glide_path(&self, mint: &Pubkey) -> bool {
+        self.glide_path.as_ref().is_some_and(|path| path.contains(mint))
+    }
+
+    /// Replace the glide path
+    ///
+    /// `start` and `end` list the same existing, unclassified allocations;
+    /// their targets move linearly from `start` (now) to `end` (at
+    /// `target_date`). Together with the allocations off the path they must
+    /// stay within 100% and inside each allocation's band at both ends, which
+    /// keeps every point in between valid too. The start targets apply
+    /// immediately. Empty `start` and `end` remove the path and keep the
+    /// current targets.
+    pub fn set_glide_path(
+        &mut self,
+        start: &[AllocationTarget],
+        end: &[AllocationTarget],
+        target_date: i64,
+        step_bps: u16,
+        now: i64,
+    ) -> Result<()> {
+        if start.is_empty() && end.is_empty() {
+            self.glide_path = None;
+            return Ok(());
         }
+        require!(
+            !start.is_empty() && start.len() == end.len(),
+            SloomoError::InvalidGlidePath
+        );
+        require!(target_date > now, SloomoError::InvalidGlidePath);
+        require!(step_bps > 0 && step_bps <= 10000, SloomoError::InvalidGlidePath);
+        validate_target_allocations(start)?;
+        validate_target_allocations(end)?;
 
-        const THRESHOLD: u16 = 500; // 5% threshold (basis points)
+        let mut legs = Vec::with_capacity(start.len());
+        for from in start {
+            let to = end
+                .iter()
+                .find(|t| t.mint == from.mint)
+                .ok_or(SloomoError::InvalidGlidePath)?;
+            let allocation = self.allocations
+                .iter()
+                .find(|a| a.mint == from.mint)
+                .ok_or(SloomoError::InvalidTokenMint)?;
+            require!(allocation.asset_class.is_none(), SloomoError::AllocationInAssetClass);
+            validate_allocation_band(allocation.band, from.target_percentage)?;
+            validate_allocation_band(allocation.band, to.target_percentage)?;
+            legs.push(GlideLeg {
+                mint: from.mint,
+                start_percentage: from.target_percentage,
+                end_percentage: to.target_percentage,
+            });
+        }
 
+        // Allocations off the path keep their targets at both ends
+        let fixed: Vec<u16> = self.allocations
+            .iter()
+            .filter(|a| !start.iter().any(|t| t.mint == a.mint))
+            .map(|a| a.target_percentage)
+            .collect();
+        for endpoint in [start, end] {
+            let total = sloomo_core::math::sum_target_percentages(
+                fixed.iter().copied().chain(endpoint.iter().map(|t| t.target_percentage)),
+            )
+            .map_err(SloomoError::from)?;
+            validate_allocation_percentage(total)?;
+        }
+
+        for leg in &legs {
+            if let Some(allocation) = self.allocations.iter_mut().find(|a| a.mint == leg.mint) {
+                allocation.target_percentage = leg.start_percentage;
+            }
+        }
+        self.glide_path = Some(GlidePath {
+            start_time: now,
+            target_date,
+            step_bps,
+            legs,
+        });
+        Ok(())
+    }
+
+    /// Requested targets with glide path mints moved to their interpolated
+    /// target at `now` (path mints missing from `target_allocations` are added)
+    pub fn glided_targets(
+        &self,
+        target_allocations: &[AllocationTarget],
+        now: i64,
+    ) -> Vec<AllocationTarget> {
+        let Some(path) = &self.glide_path else {
+            return target_allocations.to_vec();
+        };
+        let glided = path.targets_at(now);
+        let mut targets: Vec<AllocationTarget> = target_allocations
+            .iter()
+            .map(|t| glided.iter().find(|g| g.mint == t.mint).unwrap_or(t).clone())
+            .collect();
+        for target in glided {
+            if !targets.iter().any(|t| t.mint == target.mint) {
+                targets.push(target);
+            }
+        }
+        targets
+    }
+
+    /// Move the stored targets of glide path mints to their interpolated
+    /// target once any of them has moved more than the path's step (or the
+    /// target date has been reached)
+    ///
+    /// Returns the new targets of the path mints when they moved.
+    pub fn advance_glide_path(&mut self, now: i64) -> Option<Vec<AllocationTarget>> {
+        let path = self.glide_path.as_ref()?;
+        let glided = path.targets_at(now);
+        let stored: Vec<u16> = glided
+            .iter()
+            .map(|g| {
+                self.allocations
+                    .iter()
+                    .find(|a| a.mint == g.mint)
+                    .map_or(0, |a| a.target_percentage)
+            })
+            .collect();
+        let current: Vec<u16> = glided.iter().map(|g| g.target_percentage).collect();
+        let moved = max_target_move(&stored, &current);
+        let stepped = moved > path.step_bps || (now >= path.target_date && moved > 0);
+        if !stepped {
+            return None;
+        }
+
+        for target in &glided {
+            if let Some(allocation) = self.allocations.iter_mut().find(|a| a.mint == target.mint) {
+                allocation.target_percentage = target.target_percentage;
+            }
+        }
+        Some(glided)
+    }
+
+    /// Check that requested targets leave asset class members on their
+    /// flattened target
+    pub fn validate_class_targets(&self, target_allocations: &[AllocationTarget]) -> Result<()> {
         for target in target_allocations {
-            if let Some(current) = self.allocations
+            if let Some(allocation) = self.allocations
                 .iter()
+                .find(|a| a.mint == target.mint && a.asset_class.is_some()) {
+                require!(
+                    allocation.target_percentage == target.target_percentage,
+                    SloomoError::AllocationInAssetClass
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Band of the allocation for `mint`, if it has one
+    pub fn band_for(&self, mint: &Pubkey) -> Option<sloomo_core::drift::Band> {
+        self.allocations
+            .iter()
+            .find(|a| a.mint == *mint)
+            .and_then(|a| a.core_band())
+    }
+
+    /// Current target percentages of every allocation
+    pub fn current_targets(&self) -> Vec<AllocationTarget> {
+        self.allocations
+            .iter()
+            .map(|a| AllocationTarget {
+                mint: a.mint,
+                target_percentage: a.target_percentage,
+            })
+            .collect()
+    }
+
+    /// Mints whose vaults a rebalance reads besides the base vault:
+    /// existing allocations first, then new target mints, in order
+    pub fn rebalance_mints(&self, target_allocations: &[AllocationTarget]) -> Vec<Pubkey> {
+        let mut mints: Vec<Pubkey> = self.allocations.iter().map(|a| a.mint).collect();
+        for target in target_allocations {
+            if !mints.contains(&target.mint) {
+                mints.push(target.mint);
+            }
+        }
+        mints.retain(|mint| *mint != self.base_mint);
+        mints
+    }
+
+    /// Record new target percentages
+    ///
+    /// Existing allocations are updated in place and keep their band, which
+    /// must still contain the new target; asset class members must keep their
+    /// flattened target. New mints are appended without a band or class, with
+    /// their vault balance from `balance_of` and the symbol from `symbol_for`.
+    pub fn set_target_allocations(
+        &mut self,
+        target_allocations: &[AllocationTarget],
+        balance_of: impl Fn(&Pubkey) -> u64,
+        symbol_for: impl Fn(&Pubkey) -> String,
+    ) -> Result<()> {
+        self.validate_class_targets(target_allocations)?;
+
+        for target in target_allocations {
+            if let Some(allocation) = self.allocations
+                .iter_mut()
                 .find(|a| a.mint == target.mint) {
-                
-                let current_percentage = (current.current_amount as u128 * 10000u128 / total_value as u128) as u16;
-                let diff = if current_percentage > target.target_percentage {
-                    current_percentage - target.target_percentage
-                } else {
-                    target.target_percentage - current_percentage
-                };
-
-                if diff > THRESHOLD {
-                    return Ok(true);
+                validate_allocation_band(allocation.band, target.target_percentage)?;
+                allocation.target_percentage = target.target_percentage;
+            } else {
+                require!(
+                    self.allocations.len() < MAX_ALLOCATIONS,
+                    SloomoError::AllocationOverflow
+                );
+
+                self.allocations.push(AllocationData {
+                    mint: target.mint,
+                    symbol: symbol_for(&target.mint),
+                    current_amount: balance_of(&target.mint),
+                    target_percentage: target.target_percentage,
+                    apy: 0, // Managed client-side
+                    last_yield_update: 0,
+                    band: None,
+                    asset_class: None,
+                    cost_basis: 0,
+                    realized_pnl: 0,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sync allocation amounts with vault balances
+    ///
+    /// Non-base vaults holding tokens without an allocation are tracked with a
+    /// 0% target so their value is not lost to accounting.
+    pub fn apply_vault_balances(
+        &mut self,
+        balances: &[VaultBalance],
+        symbol_for: impl Fn(&Pubkey) -> String,
+    ) -> Result<()> {
+        for balance in balances {
+            if let Some(allocation) = self.allocations
+                .iter_mut()
+                .find(|a| a.mint == balance.mint) {
+                allocation.current_amount = balance.amount;
+            } else if balance.mint != self.base_mint && balance.amount > 0 {
+                require!(
+                    self.allocations.len() < MAX_ALLOCATIONS,
+                    SloomoError::AllocationOverflow
+                );
+
+                self.allocations.push(AllocationData {
+                    mint: balance.mint,
+                    symbol: symbol_for(&balance.mint),
+                    current_amount: balance.amount,
+                    target_percentage: 0,
+                    apy: 0, // Managed client-side
+                    last_yield_update: 0,
+                    band: None,
+                    asset_class: None,
+                    cost_basis: 0,
+                    realized_pnl: 0,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every mint the portfolio keeps a vault for: base asset first, then
+    /// allocations in order
+    pub fn held_mints(&self) -> Vec<Pubkey> {
+        std::iter::once(self.base_mint)
+            .chain(self.rebalance_mints(&[]))
+            .collect()
+    }
+
+    /// Whether `mint` is the base asset or has an allocation
+    pub fn holds_mint(&self, mint: &Pubkey) -> bool {
+        self.base_mint == *mint || self.allocations.iter().any(|a| a.mint == *mint)
+    }
+
+    /// Credit a deposit of `amount` of `mint` to its allocation, at a cost of
+    /// its `value` (in total value units), and the value to the total value
+    pub fn record_deposit(&mut self, mint: &Pubkey, amount: u64, value: u64) -> Result<()> {
+        if let Some(allocation) = self.allocations.iter_mut().find(|a| a.mint == *mint) {
+            allocation.current_amount = allocation.current_amount
+                .checked_add(amount)
+                .ok_or(SloomoError::MathOverflow)?;
+            allocation.cost_basis = allocation.cost_basis
+                .checked_add(value)
+                .ok_or(SloomoError::MathOverflow)?;
+        }
+        self.total_value = self.total_value
+            .checked_add(value)
+            .ok_or(SloomoError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Debit a withdrawal of `amount` of `mint` from its allocation, and its
+    /// `value` (in total value units) from the total value
+    ///
+    /// The allocation's average cost leaves with the units; nothing is realized.
+    /// Vault custody is checked by the caller; stored amounts may lag the vaults
+    /// until the next rebalance, so they floor at zero.
+    pub fn record_withdrawal(&mut self, mint: &Pubkey, amount: u64, value: u64) {
+        if let Some(allocation) = self.allocations.iter_mut().find(|a| a.mint == *mint) {
+            allocation.cost_basis -= cost_of(allocation.cost_basis, allocation.current_amount, amount);
+            allocation.current_amount = allocation.current_amount.saturating_sub(amount);
+        }
+        self.total_value = self.total_value.saturating_sub(value);
+    }
+
+    /// Release the average cost of `amount` of `mint` leaving `held` units,
+    /// returning the cost released (0 without an allocation)
+    pub fn release_cost(&mut self, mint: &Pubkey, amount: u64, held: u64) -> u64 {
+        match self.allocations.iter_mut().find(|a| a.mint == *mint) {
+            Some(allocation) => {
+                let cost = cost_of(allocation.cost_basis, held, amount);
+                allocation.cost_basis -= cost;
+                cost
+            }
+            None => 0,
+        }
+    }
+
+    /// Move cost basis through swaps executed in order from `snapshot` (base vault first)
+    ///
+    /// Each swap is `(from_mint, to_mint, amount_in, amount_out)` and is valued at
+    /// the base asset side, or at `amount_out` rescaled to base asset decimals
+    /// between two other tokens (the par valuation used everywhere else). The
+    /// sold allocation releases its average
+    /// cost and realizes the difference; the bought allocation takes on the value.
+    /// Allocation amounts are not touched (they follow the vaults).
+    pub fn apply_swap_costs(
+        &mut self,
+        snapshot: &[VaultBalance],
+        swaps: impl IntoIterator<Item = (Pubkey, Pubkey, u64, u64)>,
+    ) -> Result<()> {
+        let base_decimals = snapshot.first().ok_or(SloomoError::MissingVaultAccount)?.decimals;
+        let mut held = snapshot.to_vec();
+        for (from_mint, to_mint, amount_in, amount_out) in swaps {
+            let value = if from_mint == self.base_mint {
+                amount_in
+            } else if to_mint == self.base_mint {
+                amount_out
+            } else {
+                let to_decimals = held
+                    .iter()
+                    .find(|b| b.mint == to_mint)
+                    .ok_or(SloomoError::MissingVaultAccount)?
+                    .decimals;
+                rescale_decimals(amount_out, to_decimals, base_decimals)?
+            };
+
+            let from_held = held.iter().find(|b| b.mint == from_mint).map_or(0, |b| b.amount);
+            let cost = self.release_cost(&from_mint, amount_in, from_held);
+            if let Some(allocation) = self.allocations.iter_mut().find(|a| a.mint == from_mint) {
+                allocation.realized_pnl = allocation.realized_pnl.saturating_add(gain(value, cost));
+            }
+            if let Some(allocation) = self.allocations.iter_mut().find(|a| a.mint == to_mint) {
+                allocation.cost_basis = allocation.cost_basis
+                    .checked_add(value)
+                    .ok_or(SloomoError::MathOverflow)?;
+            }
+
+            for balance in held.iter_mut() {
+                if balance.mint == from_mint {
+                    balance.amount = balance.amount.saturating_sub(amount_in);
+                } else if balance.mint == to_mint {
+                    balance.amount = balance.amount.saturating_add(amount_out);
                 }
-            } else if target.target_percentage > THRESHOLD {
-                return Ok(true);
             }
         }
+        Ok(())
+    }
+
+    /// Cost basis and profit and loss of every allocation, valued at `prices`
+    /// (see `get_pnl`)
+    ///
+    /// The base asset is worth one base unit per unit unless priced; any other
+    /// mint without a price has an unknown value, as do the totals.
+    pub fn pnl_report(&self, prices: &[AssetPrice]) -> PnlReport {
+        let allocations: Vec<AllocationPnl> = self.allocations
+            .iter()
+            .map(|allocation| {
+                let price = prices
+                    .iter()
+                    .find(|p| p.mint == allocation.mint)
+                    .map(|p| p.price)
+                    .or((allocation.mint == self.base_mint).then_some(PRICE_SCALE));
+                let value = price.map(|price| value_at(allocation.current_amount, price));
+                AllocationPnl {
+                    mint: allocation.mint,
+                    current_amount: allocation.current_amount,
+                    cost_basis: allocation.cost_basis,
+                    average_cost: average_cost(allocation.cost_basis, allocation.current_amount),
+                    price,
+                    value,
+                    unrealized_pnl: value.map(|value| gain(value, allocation.cost_basis)),
+                    realized_pnl: allocation.realized_pnl,
+                }
+            })
+            .collect();
+
+        PnlReport {
+            cost_basis: allocations.iter().fold(0u64, |acc, a| acc.saturating_add(a.cost_basis)),
+            value: allocations.iter().try_fold(0u64, |acc, a| Some(acc.saturating_add(a.value?))),
+            unrealized_pnl: allocations
+                .iter()
+                .try_fold(0i64, |acc, a| Some(acc.saturating_add(a.unrealized_pnl?))),
+            realized_pnl: allocations.iter().fold(0i64, |acc, a| acc.saturating_add(a.realized_pnl)),
+            allocations,
+        }
+    }
+
+    /// Propose `new_owner` as the next owner, replacing any pending proposal
+    pub fn propose_owner_transfer(&mut self, new_owner: Pubkey) -> Result<()> {
+        require_keys_neq!(new_owner, self.owner, SloomoError::InvalidNewOwner);
+        self.pending_owner = Some(new_owner);
+        Ok(())
+    }
+
+    /// Withdraw the pending proposal, returning the owner it named
+    pub fn cancel_owner_transfer(&mut self) -> Result<Pubkey> {
+        self.pending_owner.take().ok_or_else(|| SloomoError::NoPendingOwnerTransfer.into())
+    }
 
-        Ok(false)
+    /// Hand the portfolio to `new_owner` if it is the pending owner,
+    /// returning the previous owner
+    ///
+    /// The PDA stays derived from `portfolio_id`, so vaults and their
+    /// authority are unaffected.
+    pub fn accept_owner_transfer(&mut self, new_owner: &Pubkey) -> Result<Pubkey> {
+        require!(
+            self.pending_owner == Some(*new_owner),
+            SloomoError::NoPendingOwnerTransfer
+        );
+        Ok(self.reassign_owner(*new_owner))
+    }
+
+    /// Make `new_owner` the owner, dropping any pending transfer, and return
+    /// the previous owner
+    pub fn reassign_owner(&mut self, new_owner: Pubkey) -> Pubkey {
+        self.pending_owner = None;
+        std::mem::replace(&mut self.owner, new_owner)
     }
 
     /// Update portfolio based on actual balances (not apparent rebalancing)
@@ -109,18 +671,145 @@ impl Portfolio {
         Ok(())
     }
 
+    /// Effective target of every allocation at `now` (glide path applied)
+    pub fn effective_targets(&self, now: i64) -> Vec<AllocationTarget> {
+        self.glided_targets(&self.current_targets(), now)
+    }
+
+    /// Overview of the portfolio at `now` (see `get_summary`)
+    pub fn summary(&self, now: i64) -> Result<PortfolioSummary> {
+        let allocated_value = self.calculate_total_value()?;
+        let targets = self.effective_targets(now);
+
+        Ok(PortfolioSummary {
+            owner: self.owner,
+            base_mint: self.base_mint,
+            total_value: self.total_value,
+            allocated_value,
+            last_rebalance: self.last_rebalance,
+            is_rebalancing: self.is_rebalancing,
+            needs_rebalancing: self.needs_rebalancing(&targets, allocated_value)?,
+            latest_growth_bps: self.performance_history.last().map_or(0, |s| s.growth_rate),
+            allocations: self.allocations
+                .iter()
+                .zip(&targets)
+                .map(|(allocation, target)| AllocationSummary {
+                    mint: allocation.mint,
+                    symbol: allocation.symbol.clone(),
+                    current_amount: allocation.current_amount,
+                    current_percentage: amount_to_bps(allocation.current_amount, allocated_value),
+                    target_percentage: target.target_percentage,
+                })
+                .collect(),
+        })
+    }
+
+    /// Drift of every allocation and asset class at `now` (see `get_drift`)
+    pub fn drift_report(&self, now: i64) -> Result<DriftReport> {
+        let total_value = self.calculate_total_value()?;
+        let targets = self.effective_targets(now);
+
+        let allocations: Vec<AllocationDrift> = self.allocations
+            .iter()
+            .zip(&targets)
+            .map(|(allocation, target)| {
+                let current_percentage = amount_to_bps(allocation.current_amount, total_value);
+                AllocationDrift {
+                    mint: allocation.mint,
+                    current_percentage,
+                    target_percentage: target.target_percentage,
+                    drift_bps: current_percentage.abs_diff(target.target_percentage as u64),
+                    within_band: within_band(
+                        current_percentage,
+                        target.target_percentage,
+                        allocation.core_band(),
+                        DEFAULT_DRIFT_THRESHOLD_BPS,
+                    ),
+                }
+            })
+            .collect();
+
+        let values: Vec<u64> = self.allocations.iter().map(|a| a.current_amount).collect();
+        let class_values = class_values(&values, &self.class_members(), self.asset_classes.len());
+        let asset_classes = self.asset_classes
+            .iter()
+            .zip(class_values)
+            .map(|(class, value)| {
+                let current_percentage = amount_to_bps(value, total_value);
+                ClassDrift {
+                    name: class.name.clone(),
+                    current_percentage,
+                    target_percentage: class.target_percentage,
+                    within_band: within_band(
+                        current_percentage,
+                        class.target_percentage,
+                        class.band.map(|band| band.to_core()),
+                        DEFAULT_DRIFT_THRESHOLD_BPS,
+                    ),
+                }
+            })
+            .collect();
+
+        Ok(DriftReport {
+            total_value,
+            needs_rebalancing: self.needs_rebalancing(&targets, total_value)?,
+            max_drift_bps: allocations.iter().map(|a| a.drift_bps).max().unwrap_or(0),
+            allocations,
+            asset_classes,
+        })
+    }
+
+    /// Replace the savings goal (`None` removes it)
+    pub fn set_goal(&mut self, goal: Option<SavingsGoal>, now: i64) -> Result<()> {
+        if let Some(goal) = &goal {
+            require!(
+                goal.target_amount > 0 && goal.target_date > now && goal.label.len() <= 32,
+                SloomoError::InvalidSavingsGoal
+            );
+        }
+        self.goal = goal;
+        Ok(())
+    }
+
+    /// Progress of `total_value` towards the savings goal at `now`, with
+    /// contributions every `contribution_interval` seconds and growth
+    /// expected from the performance history
+    pub fn goal_progress(&self, now: i64, contribution_interval: i64) -> Result<GoalProgress> {
+        let goal = self.goal.as_ref().ok_or(SloomoError::SavingsGoalNotSet)?;
+        require!(contribution_interval > 0, SloomoError::InvalidContributionInterval);
+
+        let history: Vec<GrowthSample> = self.performance_history
+            .iter()
+            .map(|s| GrowthSample { timestamp: s.timestamp, growth_bps: s.growth_rate })
+            .collect();
+        let growth = expected_growth_bps(&history, contribution_interval);
+        let progress = sloomo_core::goal::goal_progress(
+            self.total_value,
+            goal.target_amount,
+            now,
+            goal.target_date,
+            contribution_interval,
+            growth,
+        );
+
+        Ok(GoalProgress {
+            total_value: self.total_value,
+            target_amount: goal.target_amount,
+            target_date: goal.target_date,
+            progress_bps: progress.progress_bps,
+            remaining_amount: progress.remaining_amount,
+            periods_remaining: progress.periods_remaining,
+            expected_growth_bps: progress.expected_growth_bps,
+            projected_value: progress.projected_value,
+            required_contribution: progress.required_contribution,
+        })
+    }
+
     /// Add performance snapshot
     pub fn add_performance_snapshot(&mut self, timestamp: i64) -> Result<()> {
-        let growth_rate = if self.performance_history.is_empty() {
-            0
-        } else {
-            let last_snapshot = &self.performance_history[self.performance_history.len() - 1];
-            if last_snapshot.total_value > 0 {
-                let growth = ((self.total_value as i128 - last_snapshot.total_value as i128) * 10000i128 / last_snapshot.total_value as i128) as i16;
-                growth.clamp(-10000, 10000) // -100% to +100%
-            } else {
-                0
-            }
+        let growth_rate = match self.performance_history.last() {
+            Some(last_snapshot) => growth_bps(last_snapshot.total_value, self.total_value), // -100% to +100%
+            None => 0,
         };
 
         let snapshot = PerformanceSnapshot {
@@ -141,4 +830,7 @@ impl Portfolio {
 
 // Constant definitions
 pub const MAX_ALLOCATIONS: usize = 10;
-pub const MAX_PERFORMANCE_SNAPSHOTS: usize = 100;
\ No newline at end of file
+pub const MAX_PERFORMANCE_SNAPSHOTS: usize = 100;
+pub const MAX_ASSET_CLASSES: usize = 5;
+pub const MAX_SWAP_PROGRAMS: usize = 4;
+pub const PORTFOLIO_VERSION: u8 = 1;
\ No newline at end of file
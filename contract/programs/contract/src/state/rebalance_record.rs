@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use crate::state::portfolio::MAX_ALLOCATIONS;
+use crate::state::rebalance_session::{RebalanceSession, VaultBalance, MAX_SESSION_LEGS};
+use sloomo_core::math::{amount_to_bps, effective_price, realized_slippage_bps};
+
+/// Rebalance record account
+/// Receipt of one committed rebalance session, seeded by the portfolio and
+/// `Portfolio::rebalance_count` at commit (closed by the owner to reclaim rent)
+#[account]
+pub struct RebalanceRecord {
+    /// Portfolio that was rebalanced
+    pub portfolio: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Rebalance number within the portfolio (PDA seed)
+    pub index: u64,
+    /// Session start time
+    pub started_at: i64,
+    /// Commit time
+    pub committed_at: i64,
+    /// Slippage used for planning
+    pub slippage_bps: u16,
+    /// Total vault value at `begin_rebalance`
+    pub total_value_before: u64,
+    /// Total vault value at commit
+    pub total_value_after: u64,
+    /// Executed legs
+    pub legs: Vec<RecordLeg>,
+    /// Share of every session vault before and after
+    pub allocations: Vec<AllocationChange>,
+}
+
+/// Outcome of one executed leg
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecordLeg {
+    /// Mint sold
+    pub from_mint: Pubkey,
+    /// Mint bought
+    pub to_mint: Pubkey,
+    /// Amount taken from the source vault
+    pub amount_in: u64,
+    /// Amount received by the destination vault
+    pub amount_out: u64,
+    /// `amount_out` per `amount_in`, scaled by `sloomo_core::math::PRICE_SCALE`
+    pub effective_price: u64,
+    /// Shortfall of `amount_out` against `amount_in` (basis points, negative if more was received)
+    pub realized_slippage_bps: i16,
+}
+
+impl RecordLeg {
+    pub const SIZE: usize = 32 + // from_mint
+        32 + // to_mint
+        8 + // amount_in
+        8 + // amount_out
+        8 + // effective_price
+        2; // realized_slippage_bps
+}
+
+/// Share of one vault before and after a rebalance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationChange {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Share at `begin_rebalance` (basis points)
+    pub percentage_before: u16,
+    /// Share at commit (basis points)
+    pub percentage_after: u16,
+}
+
+impl AllocationChange {
+    pub const SIZE: usize = 32 + // mint
+        2 + // percentage_before
+        2; // percentage_after
+}
+
+impl RebalanceRecord {
+    /// Account size calculation
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // portfolio
+        1 + // bump
+        8 + // index
+        8 + // started_at
+        8 + // committed_at
+        2 + // slippage_bps
+        8 + // total_value_before
+        8 + // total_value_after
+        4 + (MAX_SESSION_LEGS * RecordLeg::SIZE) + // legs
+        4 + ((MAX_ALLOCATIONS + 1) * AllocationChange::SIZE); // allocations
+
+    /// Executed legs of a session with their price and slippage
+    pub fn legs_from_session(session: &RebalanceSession) -> Vec<RecordLeg> {
+        session
+            .legs
+            .iter()
+            .filter(|leg| leg.executed)
+            .map(|leg| RecordLeg {
+                from_mint: leg.from_mint,
+                to_mint: leg.to_mint,
+                amount_in: leg.spent,
+                amount_out: leg.received,
+                effective_price: effective_price(leg.spent, leg.received),
+                realized_slippage_bps: realized_slippage_bps(leg.spent, leg.received),
+            })
+            .collect()
+    }
+
+    /// Share of every session vault at begin and in `balances` (base vault
+    /// first), valued in base asset decimals like the totals
+    pub fn allocation_changes(
+        session: &RebalanceSession,
+        balances: &[VaultBalance],
+        total_value_after: u64,
+    ) -> Result<Vec<AllocationChange>> {
+        // A vault never holds more than the total, so shares fit in u16
+        let share = |amount, total| amount_to_bps(amount, total).min(10000) as u16;
+        let base_decimals = session.snapshot.first().map_or(0, |base| base.decimals);
+        session
+            .snapshot
+            .iter()
+            .map(|before| {
+                let after = balances
+                    .iter()
+                    .find(|after| after.mint == before.mint)
+                    .map_or(Ok(0), |after| after.value(base_decimals))?;
+                Ok(AllocationChange {
+                    mint: before.mint,
+                    percentage_before: share(
+                        before.value(base_decimals)?,
+                        session.total_value_before,
+                    ),
+                    percentage_after: share(after, total_value_after),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,242 @@
+use anchor_lang::prelude::*;
+use crate::state::types::AllocationTarget;
+use crate::state::portfolio::MAX_ALLOCATIONS;
+use crate::error::SloomoError;
+use crate::utils::math::rescale_decimals;
+use sloomo_core::planner::RebalancePlan;
+
+/// Rebalance session account
+/// Holds the plan of a rebalance spanning several transactions
+/// (`begin_rebalance` -> `execute_rebalance_leg`* -> `commit_rebalance` or `abort_rebalance`)
+#[account]
+pub struct RebalanceSession {
+    /// Portfolio being rebalanced
+    pub portfolio: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Session start time
+    pub started_at: i64,
+    /// Time after which anyone may abort the session
+    pub deadline: i64,
+    /// Slippage used for planning
+    pub slippage_bps: u16,
+    /// Total vault value at `begin_rebalance`
+    pub total_value_before: u64,
+    /// Targets applied on commit
+    pub target_allocations: Vec<AllocationTarget>,
+    /// Vault balances at `begin_rebalance` (base vault first)
+    pub snapshot: Vec<VaultBalance>,
+    /// Planned legs
+    pub legs: Vec<SessionLeg>,
+}
+
+/// Vault balance of one mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VaultBalance {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Vault balance
+    pub amount: u64,
+    /// Decimals of `mint`
+    pub decimals: u8,
+}
+
+impl VaultBalance {
+    pub const SIZE: usize = 32 + // mint
+        8 + // amount
+        1; // decimals
+
+    /// Balance expressed in `base_decimals` (total value units)
+    pub fn value(&self, base_decimals: u8) -> Result<u64> {
+        rescale_decimals(self.amount, self.decimals, base_decimals)
+    }
+}
+
+/// Planned swap leg and its outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SessionLeg {
+    /// Mint sold
+    pub from_mint: Pubkey,
+    /// Mint bought
+    pub to_mint: Pubkey,
+    /// Maximum amount taken from the source vault
+    pub amount: u64,
+    /// Minimum amount the destination vault must receive
+    pub min_amount_out: u64,
+    /// Whether the leg has been executed
+    pub executed: bool,
+    /// Amount actually taken from the source vault
+    pub spent: u64,
+    /// Amount actually received by the destination vault
+    pub received: u64,
+}
+
+impl SessionLeg {
+    pub const SIZE: usize = 32 + // from_mint
+        32 + // to_mint
+        8 + // amount
+        8 + // min_amount_out
+        1 + // executed
+        8 + // spent
+        8; // received
+}
+
+impl RebalanceSession {
+    /// Account size calculation
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // portfolio
+        1 + // bump
+        8 + // started_at
+        8 + // deadline
+        2 + // slippage_bps
+        8 + // total_value_before
+        4 + (MAX_ALLOCATIONS * (32 + 2)) + // target_allocations
+        4 + ((MAX_ALLOCATIONS + 1) * VaultBalance::SIZE) + // snapshot
+        4 + (MAX_SESSION_LEGS * SessionLeg::SIZE); // legs
+
+    /// Legs from a plan, converted from base units to the units of each mint
+    /// of `snapshot`: the amount sold in source-mint units, and the minimum
+    /// (amount minus the estimated cost) in destination-mint units
+    pub fn legs_from_plan(plan: &RebalancePlan, snapshot: &[VaultBalance]) -> Result<Vec<SessionLeg>> {
+        require!(plan.legs.len() <= MAX_SESSION_LEGS, SloomoError::AllocationOverflow);
+        let base_decimals = snapshot.first().ok_or(SloomoError::MissingVaultAccount)?.decimals;
+        let decimals_of = |mint: &Pubkey| {
+            snapshot
+                .iter()
+                .find(|b| b.mint == *mint)
+                .map(|b| b.decimals)
+                .ok_or(SloomoError::MissingVaultAccount)
+        };
+
+        plan.legs
+            .iter()
+            .map(|leg| {
+                let from_mint = Pubkey::new_from_array(leg.from_mint);
+                let to_mint = Pubkey::new_from_array(leg.to_mint);
+                Ok(SessionLeg {
+                    from_mint,
+                    to_mint,
+                    amount: rescale_decimals(leg.amount, base_decimals, decimals_of(&from_mint)?)?,
+                    min_amount_out: rescale_decimals(
+                        leg.amount.saturating_sub(leg.estimated_cost),
+                        base_decimals,
+                        decimals_of(&to_mint)?,
+                    )?,
+                    executed: false,
+                    spent: 0,
+                    received: 0,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the deadline has passed
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.deadline
+    }
+
+    /// Mints of the non-base snapshot vaults, in the order commit expects them
+    pub fn vault_mints(&self) -> Vec<Pubkey> {
+        self.snapshot.iter().skip(1).map(|balance| balance.mint).collect()
+    }
+
+    /// Check a leg's outcome and record it
+    pub fn record_leg(&mut self, leg_index: usize, spent: u64, received: u64) -> Result<()> {
+        let leg = self
+            .legs
+            .get_mut(leg_index)
+            .ok_or(SloomoError::InvalidLegIndex)?;
+        require!(!leg.executed, SloomoError::LegAlreadyExecuted);
+        require!(spent <= leg.amount, SloomoError::SwapExecutionFailed);
+        require!(received >= leg.min_amount_out, SloomoError::SlippageExceeded);
+
+        leg.executed = true;
+        leg.spent = spent;
+        leg.received = received;
+        Ok(())
+    }
+
+    /// Whether every leg has been executed
+    pub fn all_legs_executed(&self) -> bool {
+        self.legs.iter().all(|leg| leg.executed)
+    }
+
+    /// `(from_mint, to_mint, spent, received)` of every executed leg, in order
+    pub fn executed_swaps(&self) -> impl Iterator<Item = (Pubkey, Pubkey, u64, u64)> + '_ {
+        self.legs
+            .iter()
+            .filter(|leg| leg.executed)
+            .map(|leg| (leg.from_mint, leg.to_mint, leg.spent, leg.received))
+    }
+
+    /// Snapshot balances with the recorded outcome of every executed leg applied
+    pub fn expected_balances(&self) -> Result<Vec<VaultBalance>> {
+        let mut balances = self.snapshot.clone();
+        for leg in self.legs.iter().filter(|leg| leg.executed) {
+            let from = balances
+                .iter_mut()
+                .find(|b| b.mint == leg.from_mint)
+                .ok_or(SloomoError::MissingVaultAccount)?;
+            from.amount = from.amount
+                .checked_sub(leg.spent)
+                .ok_or(SloomoError::MathOverflow)?;
+
+            let to = balances
+                .iter_mut()
+                .find(|b| b.mint == leg.to_mint)
+                .ok_or(SloomoError::MissingVaultAccount)?;
+            to.amount = to.amount
+                .checked_add(leg.received)
+                .ok_or(SloomoError::MathOverflow)?;
+        }
+        Ok(balances)
+    }
+
+    /// Check that a leg's swap moved no vault other than its source and destination
+    ///
+    /// `before` and `after` are the snapshot vaults read around the swap, in
+    /// snapshot order.
+    pub fn verify_leg_balances(
+        leg: &SessionLeg,
+        before: &[VaultBalance],
+        after: &[VaultBalance],
+    ) -> Result<()> {
+        require!(before.len() == after.len(), SloomoError::MissingVaultAccount);
+        for (before, after) in before.iter().zip(after) {
+            require_keys_eq!(before.mint, after.mint, SloomoError::InvalidVault);
+            if before.mint == leg.from_mint || before.mint == leg.to_mint {
+                continue;
+            }
+            require!(
+                before.amount == after.amount,
+                SloomoError::UnexpectedVaultBalance
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that no vault holds less than the recorded legs left it
+    ///
+    /// Tokens sent to a vault during the session are accepted, so a transfer
+    /// of dust cannot block the commit.
+    pub fn verify_balances(&self, actual: &[VaultBalance]) -> Result<()> {
+        let expected = self.expected_balances()?;
+        require!(expected.len() == actual.len(), SloomoError::MissingVaultAccount);
+        for (expected, actual) in expected.iter().zip(actual) {
+            require_keys_eq!(expected.mint, actual.mint, SloomoError::InvalidVault);
+            require!(
+                actual.amount >= expected.amount,
+                SloomoError::UnexpectedVaultBalance
+            );
+        }
+        Ok(())
+    }
+}
+
+// Constant definitions
+/// Legs one session may plan: enough to trade every allocation, since the
+/// legs run in separate transactions (single-transaction rebalances keep
+/// `DEFAULT_MAX_LEGS`)
+pub const MAX_SESSION_LEGS: usize = MAX_ALLOCATIONS;
+/// Seconds a keeper has to execute and commit a session before it can be aborted
+pub const REBALANCE_SESSION_DURATION: i64 = 15 * 60;
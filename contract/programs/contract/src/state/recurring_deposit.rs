@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::error::SloomoError;
+
+/// Recurring deposit account
+/// Schedule pulling `amount` of the base asset from the owner's account, which
+/// has approved the portfolio PDA as delegate, every `interval` seconds
+/// (`create_recurring_deposit` -> `crank_recurring_deposit`* -> `cancel_recurring_deposit`)
+#[account]
+pub struct RecurringDeposit {
+    /// Portfolio receiving the deposits
+    pub portfolio: Pubkey,
+    /// Owner's base asset account (source)
+    pub source: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Amount pulled per occurrence (base asset units)
+    pub amount: u64,
+    /// Seconds between occurrences
+    pub interval: i64,
+    /// Time from which the next occurrence can be cranked
+    pub next_due_at: i64,
+    /// Number of occurrences after which the schedule ends, unlimited if None
+    pub max_occurrences: Option<u32>,
+    /// Occurrences executed so far
+    pub executed: u32,
+    /// Schedule creation time
+    pub created_at: i64,
+}
+
+impl RecurringDeposit {
+    /// Account size calculation
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // portfolio
+        32 + // source
+        1 + // bump
+        8 + // amount
+        8 + // interval
+        8 + // next_due_at
+        1 + 4 + // max_occurrences
+        4 + // executed
+        8; // created_at
+
+    /// Set the schedule, restarting the occurrence count
+    ///
+    /// The first occurrence is due at `start_at`, or immediately if None.
+    pub fn configure(
+        &mut self,
+        amount: u64,
+        interval: i64,
+        start_at: Option<i64>,
+        max_occurrences: Option<u32>,
+        now: i64,
+    ) -> Result<()> {
+        require!(amount > 0, SloomoError::InvalidRecurringDeposit);
+        require!(interval >= MIN_RECURRING_INTERVAL, SloomoError::InvalidRecurringDeposit);
+        require!(max_occurrences != Some(0), SloomoError::InvalidRecurringDeposit);
+
+        self.amount = amount;
+        self.interval = interval;
+        self.next_due_at = start_at.unwrap_or(now).max(now);
+        self.max_occurrences = max_occurrences;
+        self.executed = 0;
+        Ok(())
+    }
+
+    /// Whether every scheduled occurrence has been executed
+    pub fn is_completed(&self) -> bool {
+        self.max_occurrences.is_some_and(|max| self.executed >= max)
+    }
+
+    /// Count the occurrence due at `now` and schedule the next one, returning
+    /// the occurrence number
+    ///
+    /// Occurrences missed while nobody cranked are skipped, not made up.
+    pub fn record_execution(&mut self, now: i64) -> Result<u32> {
+        require!(!self.is_completed(), SloomoError::RecurringDepositCompleted);
+        require!(now >= self.next_due_at, SloomoError::RecurringDepositNotDue);
+
+        let missed = (now - self.next_due_at) / self.interval;
+        let advance = missed
+            .checked_add(1)
+            .and_then(|periods| periods.checked_mul(self.interval))
+            .ok_or(SloomoError::MathOverflow)?;
+        self.next_due_at = self.next_due_at
+            .checked_add(advance)
+            .ok_or(SloomoError::MathOverflow)?;
+        self.executed = self.executed
+            .checked_add(1)
+            .ok_or(SloomoError::MathOverflow)?;
+        Ok(self.executed)
+    }
+}
+
+// Constant definitions
+/// Shortest interval between recurring deposits (seconds)
+pub const MIN_RECURRING_INTERVAL: i64 = 60 * 60;
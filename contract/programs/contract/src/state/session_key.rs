@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::error::SloomoError;
+
+/// Session key account
+/// Owner-issued key that may sign the scoped instructions in place of the owner
+/// until `expires_at` (`create_session_key` -> use -> expiry or `revoke_session_key`)
+#[account]
+pub struct SessionKey {
+    /// Portfolio the key acts on
+    pub portfolio: Pubkey,
+    /// Session key (signs as `authority` in the scoped instructions)
+    pub authority: Pubkey,
+    /// Owner that issued the key (the key dies with an owner change)
+    pub issued_by: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Allowed instruction groups (`SESSION_SCOPE_*` bits)
+    pub scopes: u8,
+    /// Time from which the key is rejected
+    pub expires_at: i64,
+    /// Most the key may move in total (base asset units), unlimited if None
+    pub spend_limit: Option<u64>,
+    /// Amount moved so far (base asset units)
+    pub spent: u64,
+    /// Issue time
+    pub created_at: i64,
+}
+
+impl SessionKey {
+    /// Account size calculation
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // portfolio
+        32 + // authority
+        32 + // issued_by
+        1 + // bump
+        1 + // scopes
+        8 + // expires_at
+        1 + 8 + // spend_limit
+        8 + // spent
+        8; // created_at
+
+    /// Check the scope and lifetime requested when issuing a key
+    pub fn validate_grant(scopes: u8, expires_at: i64, now: i64) -> Result<()> {
+        require!(
+            scopes != 0 && scopes & !SESSION_SCOPE_ALL == 0,
+            SloomoError::InvalidSessionScope
+        );
+        require!(
+            expires_at > now && expires_at - now <= MAX_SESSION_DURATION,
+            SloomoError::InvalidSessionExpiry
+        );
+        Ok(())
+    }
+
+    /// Check that the key may act for `owner` within `scope` at `now`
+    pub fn authorize(&self, owner: &Pubkey, scope: u8, now: i64) -> Result<()> {
+        require_keys_eq!(self.issued_by, *owner, SloomoError::SessionKeyExpired);
+        require!(now < self.expires_at, SloomoError::SessionKeyExpired);
+        require!(self.scopes & scope == scope, SloomoError::SessionScopeNotAllowed);
+        Ok(())
+    }
+
+    /// Count `amount` against the spend limit
+    pub fn record_spend(&mut self, amount: u64) -> Result<()> {
+        let spent = self.spent.checked_add(amount).ok_or(SloomoError::MathOverflow)?;
+        if let Some(limit) = self.spend_limit {
+            require!(spent <= limit, SloomoError::SessionSpendLimitExceeded);
+        }
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+// Constant definitions
+/// Deposits (`deposit_token`)
+pub const SESSION_SCOPE_DEPOSIT: u8 = 1 << 0;
+/// Allocation edits (`add_or_update_allocation`)
+pub const SESSION_SCOPE_ALLOCATION: u8 = 1 << 1;
+/// Rebalancing (`rebalance` and the rebalance session instructions)
+pub const SESSION_SCOPE_REBALANCE: u8 = 1 << 2;
+/// Every scope a session key can hold (withdrawals and ownership changes are never delegated)
+pub const SESSION_SCOPE_ALL: u8 = SESSION_SCOPE_DEPOSIT | SESSION_SCOPE_ALLOCATION | SESSION_SCOPE_REBALANCE;
+/// Longest lifetime of a session key (seconds)
+pub const MAX_SESSION_DURATION: i64 = 7 * 24 * 60 * 60;
@@ -1,4 +1,10 @@
 use anchor_lang::prelude::*;
+use sloomo_core::drift::Band;
+use sloomo_core::hierarchy::ClassMember;
+use sloomo_core::valuation::Target;
+use sloomo_core::Mint;
+use sloomo_core::planner::DEFAULT_MAX_LEGS;
+use crate::state::MAX_ALLOCATIONS;
 
 /// Allocation data structure
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -15,15 +21,464 @@ pub struct AllocationData {
     pub apy: u16,
     /// Last yield update timestamp
     pub last_yield_update: i64,
+    /// Allowed range around the target (None: default 5% drift threshold)
+    pub band: Option<AllocationBand>,
+    /// Asset class this allocation belongs to (its target is then derived)
+    pub asset_class: Option<AllocationClass>,
+    /// Base units paid for `current_amount` (average cost)
+    pub cost_basis: u64,
+    /// Gains and losses realized by selling (base units)
+    pub realized_pnl: i64,
 }
 
 impl AllocationData {
+    /// (mint, current amount) pair for the core math
+    pub fn holding(&self) -> (Mint, u64) {
+        (self.mint.to_bytes(), self.current_amount)
+    }
+
+    /// Band for the core math
+    pub fn core_band(&self) -> Option<Band> {
+        self.band.map(|band| band.to_core())
+    }
+
+    /// Asset class membership for the core math
+    pub fn class_member(&self) -> Option<ClassMember> {
+        self.asset_class.map(|class| class.to_core())
+    }
+
     pub const SIZE: usize = 32 + // mint
         4 + 32 + // symbol (max 32 chars)
         8 + // current_amount
         2 + // target_percentage
         2 + // apy
-        8; // last_yield_update
+        8 + // last_yield_update
+        1 + AllocationBand::SIZE + // band
+        1 + AllocationClass::SIZE + // asset_class
+        8 + // cost_basis
+        8; // realized_pnl
+}
+
+/// Position of an allocation inside its asset class
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocationClass {
+    /// Index into `Portfolio::asset_classes`
+    pub asset_class: u8,
+    /// Share of the class (basis points; members of a class sum to 10000)
+    pub weight: u16,
+}
+
+impl AllocationClass {
+    /// Membership for the core math
+    pub fn to_core(&self) -> ClassMember {
+        ClassMember {
+            class: self.asset_class,
+            weight: self.weight,
+        }
+    }
+
+    pub const SIZE: usize = 1 + // asset_class
+        2; // weight
+}
+
+/// Asset class grouping allocations under one target (e.g. equities, stablecoins)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AssetClass {
+    /// Class name (e.g.: EQUITIES)
+    pub name: String,
+    /// Share of the portfolio (basis points)
+    pub target_percentage: u16,
+    /// Allowed range of the class share (None: default 5% drift threshold)
+    pub band: Option<AllocationBand>,
+}
+
+impl AssetClass {
+    pub const SIZE: usize = 4 + 16 + // name (max 16 chars)
+        2 + // target_percentage
+        1 + AllocationBand::SIZE; // band
+
+    /// Asset class for the core math
+    pub fn to_core(&self) -> sloomo_core::hierarchy::AssetClass {
+        sloomo_core::hierarchy::AssetClass {
+            target_percentage: self.target_percentage,
+            band: self.band.map(|band| band.to_core()),
+        }
+    }
+}
+
+/// Assignment of one allocation to an asset class
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClassMembership {
+    /// Token mint of the allocation
+    pub mint: Pubkey,
+    /// Index into the asset classes
+    pub asset_class: u8,
+    /// Share of the class (basis points)
+    pub weight: u16,
+}
+
+/// Amount the owner is saving towards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SavingsGoal {
+    /// Goal amount (base asset units)
+    pub target_amount: u64,
+    /// When the goal should be reached
+    pub target_date: i64,
+    /// Goal name (e.g.: House deposit)
+    pub label: String,
+}
+
+impl SavingsGoal {
+    pub const SIZE: usize = 8 + // target_amount
+        8 + // target_date
+        4 + 32; // label (max 32 chars)
+}
+
+/// Progress towards the savings goal, returned by `get_goal_progress`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GoalProgress {
+    /// Portfolio value the progress is measured from (base asset units)
+    pub total_value: u64,
+    /// Goal amount (base asset units)
+    pub target_amount: u64,
+    /// When the goal should be reached
+    pub target_date: i64,
+    /// Current value as a share of the goal (basis points, at most 10000)
+    pub progress_bps: u16,
+    /// Amount still missing today (base asset units)
+    pub remaining_amount: u64,
+    /// Contribution intervals left until the target date
+    pub periods_remaining: u64,
+    /// Growth per interval expected from the performance history (basis points)
+    pub expected_growth_bps: i16,
+    /// Current value grown until the target date without contributions
+    pub projected_value: u64,
+    /// Contribution per interval that reaches the goal (the whole shortfall once overdue)
+    pub required_contribution: u64,
+}
+
+/// Portfolio overview, returned by `get_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PortfolioSummary {
+    /// Portfolio owner
+    pub owner: Pubkey,
+    /// Base asset mint
+    pub base_mint: Pubkey,
+    /// Total portfolio value (as of the last valuation)
+    pub total_value: u64,
+    /// Sum of the allocation amounts
+    pub allocated_value: u64,
+    /// Last rebalancing execution time
+    pub last_rebalance: i64,
+    /// Rebalance session in progress
+    pub is_rebalancing: bool,
+    /// Whether any allocation or asset class has left its band
+    pub needs_rebalancing: bool,
+    /// Growth recorded by the latest performance snapshot (basis points)
+    pub latest_growth_bps: i16,
+    /// Every allocation with its current share and effective target
+    pub allocations: Vec<AllocationSummary>,
+}
+
+/// One allocation in a `PortfolioSummary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationSummary {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Token symbol
+    pub symbol: String,
+    /// Current holding
+    pub current_amount: u64,
+    /// Share of the allocated value (basis points)
+    pub current_percentage: u64,
+    /// Effective target now (flattened class target, interpolated glide path target)
+    pub target_percentage: u16,
+}
+
+/// Drift of every allocation and asset class, returned by `get_drift`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DriftReport {
+    /// Value the shares are measured against (sum of the allocation amounts)
+    pub total_value: u64,
+    /// Whether any allocation or asset class has left its band
+    pub needs_rebalancing: bool,
+    /// Largest allocation drift (basis points)
+    pub max_drift_bps: u64,
+    /// Drift of each allocation, in allocation order
+    pub allocations: Vec<AllocationDrift>,
+    /// Drift of each asset class, in class order
+    pub asset_classes: Vec<ClassDrift>,
+}
+
+/// One allocation in a `DriftReport`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationDrift {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Current share (basis points)
+    pub current_percentage: u64,
+    /// Effective target now (basis points)
+    pub target_percentage: u16,
+    /// Absolute distance from the target (basis points)
+    pub drift_bps: u64,
+    /// Inside its band (or the default 5% either side without one)
+    pub within_band: bool,
+}
+
+/// One asset class in a `DriftReport`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ClassDrift {
+    /// Class name
+    pub name: String,
+    /// Current share of the class members (basis points)
+    pub current_percentage: u64,
+    /// Class target (basis points)
+    pub target_percentage: u16,
+    /// Inside its band (or the default 5% either side without one)
+    pub within_band: bool,
+}
+
+/// Everything a rebalance would do now, returned by `preview_rebalance`
+/// (see `dry_run_rebalance`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RebalancePreview {
+    /// Value of the vaults the plan was made from
+    pub total_value: u64,
+    /// Targets the plan moves towards (glide path and class targets applied)
+    pub target_allocations: Vec<AllocationTarget>,
+    /// Whether the vault balances drift outside a band or the threshold
+    pub needs_rebalancing: bool,
+    /// Largest drift from target before the plan (basis points)
+    pub max_drift_bps: u64,
+    /// Planned legs, largest first (at most `DEFAULT_MAX_LEGS`)
+    pub legs: Vec<PreviewLeg>,
+    /// Total estimated fee + slippage (base units)
+    pub estimated_cost: u64,
+    /// Swap fee part of `estimated_cost` (base units)
+    pub estimated_fees: u64,
+    /// Slippage part of `estimated_cost` (base units)
+    pub estimated_slippage: u64,
+    /// Largest drift from target after the plan (basis points)
+    pub max_residual_drift_bps: u64,
+    /// Whether every asset lands within its band
+    pub within_band: bool,
+    /// Earliest time `rebalance` accepts (once per day)
+    pub next_rebalance_at: i64,
+    /// Whether `rebalance` would run now (frequency and no session in progress)
+    pub can_execute: bool,
+}
+
+impl RebalancePreview {
+    /// Largest encoding (`MAX_ALLOCATIONS` targets, `DEFAULT_MAX_LEGS` legs),
+    /// which must fit in the 1024-byte return data
+    pub const MAX_SIZE: usize = 8 + // total_value
+        4 + (MAX_ALLOCATIONS * AllocationTarget::SIZE) + // target_allocations
+        1 + // needs_rebalancing
+        8 + // max_drift_bps
+        4 + (DEFAULT_MAX_LEGS as usize * PreviewLeg::SIZE) + // legs
+        8 + // estimated_cost
+        8 + // estimated_fees
+        8 + // estimated_slippage
+        8 + // max_residual_drift_bps
+        1 + // within_band
+        8 + // next_rebalance_at
+        1; // can_execute
+}
+
+/// One planned swap in a `RebalancePreview`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PreviewLeg {
+    /// Mint sold
+    pub from_mint: Pubkey,
+    /// Mint bought
+    pub to_mint: Pubkey,
+    /// Value moved (base units)
+    pub amount: u64,
+    /// Estimated fee + slippage (base units)
+    pub estimated_cost: u64,
+    /// Swap fee part of `estimated_cost` (base units)
+    pub estimated_fee: u64,
+}
+
+impl PreviewLeg {
+    pub const SIZE: usize = 32 + // from_mint
+        32 + // to_mint
+        8 + // amount
+        8 + // estimated_cost
+        8; // estimated_fee
+}
+
+/// Price of one mint supplied to `get_pnl`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AssetPrice {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Base units per unit, scaled by `PRICE_SCALE`
+    pub price: u64,
+}
+
+/// Cost basis and profit and loss of the portfolio, returned by `get_pnl`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PnlReport {
+    /// Sum of the allocation cost bases (base units)
+    pub cost_basis: u64,
+    /// Sum of the allocation values (base units), unknown while any allocation is unpriced
+    pub value: Option<u64>,
+    /// `value` minus `cost_basis`
+    pub unrealized_pnl: Option<i64>,
+    /// Gains realized by sells so far
+    pub realized_pnl: i64,
+    /// Every allocation, in allocation order
+    pub allocations: Vec<AllocationPnl>,
+}
+
+impl PnlReport {
+    /// Largest encoding (`MAX_ALLOCATIONS` allocations), which must fit in
+    /// the 1024-byte return data
+    pub const MAX_SIZE: usize = 8 + // cost_basis
+        1 + 8 + // value
+        1 + 8 + // unrealized_pnl
+        8 + // realized_pnl
+        4 + (MAX_ALLOCATIONS * AllocationPnl::SIZE); // allocations
+}
+
+/// One allocation in a `PnlReport`
+///
+/// Symbols are left out to keep the report within the return data; they
+/// are on the portfolio account under the same mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationPnl {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Current holding
+    pub current_amount: u64,
+    /// Base units paid for `current_amount`
+    pub cost_basis: u64,
+    /// Cost per unit held, scaled by `PRICE_SCALE`
+    pub average_cost: u64,
+    /// Price the holding is valued at, scaled by `PRICE_SCALE`
+    /// (`None` when the caller gave none for a non-base mint)
+    pub price: Option<u64>,
+    /// Value of `current_amount` at `price` (base units)
+    pub value: Option<u64>,
+    /// `value` minus `cost_basis`
+    pub unrealized_pnl: Option<i64>,
+    /// Gains realized by sells of this allocation
+    pub realized_pnl: i64,
+}
+
+impl AllocationPnl {
+    pub const SIZE: usize = 32 + // mint
+        8 + // current_amount
+        8 + // cost_basis
+        8 + // average_cost
+        1 + 8 + // price
+        1 + 8 + // value
+        1 + 8 + // unrealized_pnl
+        8; // realized_pnl
+}
+
+/// Targets that move from a start allocation to an end allocation by a target date
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GlidePath {
+    /// When the path started (start allocation applies)
+    pub start_time: i64,
+    /// When the end allocation is reached
+    pub target_date: i64,
+    /// Move of any interpolated target that updates the stored targets (basis points)
+    pub step_bps: u16,
+    /// Start and end target of each mint on the path
+    pub legs: Vec<GlideLeg>,
+}
+
+impl GlidePath {
+    pub const SIZE: usize = 8 + // start_time
+        8 + // target_date
+        2 + // step_bps
+        4 + (crate::state::MAX_ALLOCATIONS * GlideLeg::SIZE); // legs
+
+    /// Interpolated target of every mint on the path at `now`
+    pub fn targets_at(&self, now: i64) -> Vec<AllocationTarget> {
+        self.legs
+            .iter()
+            .map(|leg| AllocationTarget {
+                mint: leg.mint,
+                target_percentage: sloomo_core::glide::interpolate_target(
+                    leg.start_percentage,
+                    leg.end_percentage,
+                    self.start_time,
+                    self.target_date,
+                    now,
+                ),
+            })
+            .collect()
+    }
+
+    /// Whether `mint` follows the path
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        self.legs.iter().any(|leg| leg.mint == *mint)
+    }
+}
+
+/// One mint's targets on a glide path
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlideLeg {
+    /// Token mint of the allocation
+    pub mint: Pubkey,
+    /// Target at the start of the path (basis points)
+    pub start_percentage: u16,
+    /// Target from the target date on (basis points)
+    pub end_percentage: u16,
+}
+
+impl GlideLeg {
+    pub const SIZE: usize = 32 + // mint
+        2 + // start_percentage
+        2; // end_percentage
+}
+
+/// Allowed range of an allocation's share of the portfolio
+///
+/// The allocation is only traded once its share leaves the band
+/// (e.g. 25-35% around a 30% target).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocationBand {
+    /// Lower bound (basis points)
+    pub min_percentage: u16,
+    /// Upper bound (basis points)
+    pub max_percentage: u16,
+}
+
+impl AllocationBand {
+    pub const SIZE: usize = 2 + // min_percentage
+        2; // max_percentage
+
+    /// Band for the core math
+    pub fn to_core(self) -> Band {
+        Band {
+            min_percentage: self.min_percentage,
+            max_percentage: self.max_percentage,
+        }
+    }
+}
+
+/// How far a rebalance trades an allocation that left its band
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BandRestore {
+    /// Back to the nearest edge of its band (smallest trade)
+    Edge,
+    /// All the way back to its target percentage
+    Target,
+}
+
+impl BandRestore {
+    /// Restore mode for the core planner
+    pub fn to_core(self) -> sloomo_core::planner::BandRestore {
+        match self {
+            BandRestore::Edge => sloomo_core::planner::BandRestore::Edge,
+            BandRestore::Target => sloomo_core::planner::BandRestore::Target,
+        }
+    }
 }
 
 /// Performance snapshot
@@ -44,18 +499,18 @@ impl PerformanceSnapshot {
 }
 
 /// Portfolio initialization parameters
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct InitPortfolioParams {
     /// Initial allocation settings
     pub initial_allocations: Vec<AllocationParams>,
-    /// Initial SOL investment amount (lamports)
+    /// Initial base asset investment amount (base units; lamports for wSOL)
     pub initial_sol_amount: u64,
     /// Whether to execute Jupiter auto-swap
     pub enable_jupiter_swap: bool,
 }
 
 /// Allocation parameters
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct AllocationParams {
     /// Token mint address
     pub mint: Pubkey,
@@ -63,10 +518,12 @@ pub struct AllocationParams {
     pub symbol: String,
     /// Target allocation percentage (basis points)
     pub target_percentage: u16,
+    /// Allowed range around the target (None: default 5% drift threshold)
+    pub band: Option<AllocationBand>,
 }
 
 /// Rebalance target allocation
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub struct AllocationTarget {
     /// Token mint address
     pub mint: Pubkey,
@@ -74,3 +531,68 @@ pub struct AllocationTarget {
     pub target_percentage: u16,
 }
 
+impl AllocationTarget {
+    pub const SIZE: usize = 32 + // mint
+        2; // target_percentage
+
+    /// Target for the core math, with no band of its own
+    pub fn to_core(&self) -> Target {
+        Target {
+            mint: self.mint.to_bytes(),
+            target_percentage: self.target_percentage,
+            band: None,
+        }
+    }
+}
+
+/// Amount of one mint paid out of its vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawnAmount {
+    /// Token mint withdrawn
+    pub mint: Pubkey,
+    /// Amount transferred to the owner (base units of `mint`)
+    pub amount: u64,
+}
+
+/// Which allocations are sold to fund a withdrawal in the base asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiquidationOrder {
+    /// Every allocation sells the same share of its value
+    Proportional,
+    /// Allocations above target sell their excess first
+    OverweightFirst,
+}
+
+impl LiquidationOrder {
+    /// Order for the core planner
+    pub fn to_core(self) -> sloomo_core::planner::LiquidationOrder {
+        match self {
+            LiquidationOrder::Proportional => sloomo_core::planner::LiquidationOrder::Proportional,
+            LiquidationOrder::OverweightFirst => sloomo_core::planner::LiquidationOrder::OverweightFirst,
+        }
+    }
+}
+
+/// Swap selling one allocation into the base asset during a withdrawal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquidationSwap {
+    /// Token mint sold
+    pub from_mint: Pubkey,
+    /// Most the swap may take from the vault of `from_mint`
+    pub amount_in: u64,
+    /// Number of remaining accounts the swap instruction uses
+    pub account_count: u8,
+    /// Swap instruction data for the swap program
+    pub data: Vec<u8>,
+}
+
+/// Allocation sold into the base asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LiquidatedAmount {
+    /// Token mint sold
+    pub mint: Pubkey,
+    /// Amount taken from the vault of `mint`
+    pub spent: u64,
+    /// Base asset received for it
+    pub received: u64,
+}
@@ -1,56 +1,45 @@
 use anchor_lang::prelude::*;
+use sloomo_core::planner::{route_through_base, SwapDirection};
+use crate::error::SloomoError;
+
+/// Jupiter aggregator v6 program (the swap program new portfolios allow)
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
 
 /// Jupiter SOL swap helper functions
 pub struct JupiterSolSwapHelper;
 
 impl JupiterSolSwapHelper {
-    /// Calculate swap operations required for rebalancing
+    /// Calculate swap operations required for rebalancing, routed through `base_mint`
+    /// (see `sloomo_core::planner::route_through_base`)
     pub fn calculate_swap_operations(
         current_allocations: &[crate::state::AllocationData],
         target_allocations: &[crate::state::AllocationTarget],
         total_value: u64,
+        base_mint: Pubkey,
     ) -> Result<Vec<SwapOperation>> {
-        let mut swap_operations = Vec::new();
+        let holdings: Vec<_> = current_allocations.iter().map(|a| a.holding()).collect();
+        let targets: Vec<_> = target_allocations.iter().map(|t| t.to_core()).collect();
 
-        for target in target_allocations {
-            if let Some(current) = current_allocations
-                .iter()
-                .find(|a| a.mint == target.mint) {
-                
-                let target_amount = (total_value as u128 * target.target_percentage as u128 / 10000u128) as u64;
-                
-                if current.current_amount > target_amount {
-                    // Sale required
-                    let sell_amount = current.current_amount - target_amount;
-                    swap_operations.push(SwapOperation {
-                        operation_type: SwapOperationType::Sell,
-                        from_mint: target.mint,
-                        to_mint: CommonMints::get_wsol_pubkey(),
-                        amount: sell_amount,
-                    });
-                } else if current.current_amount < target_amount {
-                    // Purchase required
-                    let buy_amount = target_amount - current.current_amount;
-                    swap_operations.push(SwapOperation {
-                        operation_type: SwapOperationType::Buy,
-                        from_mint: CommonMints::get_wsol_pubkey(),
-                        to_mint: target.mint,
-                        amount: buy_amount,
-                    });
-                }
-            } else if target.target_percentage > 0 {
-                // New investment required
-                let target_amount = (total_value as u128 * target.target_percentage as u128 / 10000u128) as u64;
-                swap_operations.push(SwapOperation {
-                    operation_type: SwapOperationType::Buy,
-                    from_mint: CommonMints::get_wsol_pubkey(),
-                    to_mint: target.mint,
-                    amount: target_amount,
-                });
-            }
-        }
+        let routed = route_through_base(
+            &holdings,
+            &targets,
+            total_value,
+            base_mint.to_bytes(),
+        )
+        .map_err(SloomoError::from)?;
 
-        Ok(swap_operations)
+        Ok(routed
+            .into_iter()
+            .map(|swap| SwapOperation {
+                operation_type: match swap.direction {
+                    SwapDirection::Buy => SwapOperationType::Buy,
+                    SwapDirection::Sell => SwapOperationType::Sell,
+                },
+                from_mint: Pubkey::new_from_array(swap.from_mint),
+                to_mint: Pubkey::new_from_array(swap.to_mint),
+                amount: swap.amount,
+            })
+            .collect())
     }
 
     /// Log swap operations (actual swap executed externally)
@@ -78,18 +67,22 @@ impl JupiterSolSwapHelper {
 }
 
 /// Swap operation type
-#[derive(Debug, Clone, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq, Eq)]
 pub enum SwapOperationType {
     Buy,
     Sell,
 }
 
 /// Swap operation
-#[derive(Debug, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SwapOperation {
+    /// Sell into or buy with the base asset
     pub operation_type: SwapOperationType,
+    /// Mint sold
     pub from_mint: Pubkey,
+    /// Mint bought
     pub to_mint: Pubkey,
+    /// Value moved (base units)
     pub amount: u64,
 }
 
@@ -109,4 +102,17 @@ impl CommonMints {
     pub fn get_wsol_pubkey() -> Pubkey {
         "So11111111111111111111111111111111111111112".parse().unwrap()
     }
+
+    /// Derive display symbol from mint address (simplified implementation)
+    pub fn symbol_for(mint: &Pubkey) -> String {
+        // In actual implementation, get from known mint address mapping or
+        // on-chain metadata
+        if *mint == Self::get_usdc_pubkey() {
+            "USDC".to_string()
+        } else if *mint == Self::get_wsol_pubkey() {
+            "SOL".to_string()
+        } else {
+            format!("TOKEN-{}", &mint.to_string()[0..8])
+        }
+    }
 }
\ No newline at end of file
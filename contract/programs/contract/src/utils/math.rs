@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::error::SloomoError;
+
+pub use sloomo_core::math::{amount_to_bps, BPS_DENOMINATOR};
+pub use sloomo_core::performance::growth_bps;
+
+/// Amount corresponding to `bps` basis points of `total_value`
+pub fn bps_of(total_value: u64, bps: u16) -> Result<u64> {
+    sloomo_core::math::bps_of(total_value, bps).map_err(|e| SloomoError::from(e).into())
+}
+
+/// Re-express `amount` with `from_decimals` in units of `to_decimals`
+pub fn rescale_decimals(amount: u64, from_decimals: u8, to_decimals: u8) -> Result<u64> {
+    sloomo_core::math::rescale_decimals(amount, from_decimals, to_decimals)
+        .map_err(|e| SloomoError::from(e).into())
+}
@@ -1,7 +1,13 @@
 pub mod validations;
 pub mod token_transfers;
 pub mod jupiter;
+pub mod math;
+pub mod planner;
+pub mod vaults;
 
 pub use validations::*;
 pub use token_transfers::*;
-pub use jupiter::*;
\ No newline at end of file
+pub use jupiter::*;
+pub use math::*;
+pub use planner::*;
+pub use vaults::*;
\ No newline at end of file
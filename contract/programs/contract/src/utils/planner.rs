@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use crate::error::SloomoError;
+use crate::state::{AllocationData, AllocationTarget, AssetClass};
+
+pub use sloomo_core::drift::DEFAULT_DRIFT_THRESHOLD_BPS;
+pub use sloomo_core::planner::{
+    leg_cost, PlannedLeg, PlannerConfig, PlannerHolding, RebalancePlan, DEFAULT_FEE_BPS,
+    DEFAULT_MAX_LEGS, DEFAULT_MIN_TRADE_VALUE, DEFAULT_SLIPPAGE_BPS,
+};
+
+/// Plan the cheapest set of direct swaps that brings every asset within the drift band
+/// (see `sloomo_core::planner::plan_rebalance`)
+pub fn plan_rebalance(
+    holdings: &[PlannerHolding],
+    base_mint: Pubkey,
+    config: &PlannerConfig,
+) -> Result<RebalancePlan> {
+    sloomo_core::planner::plan_rebalance(holdings, base_mint.to_bytes(), config)
+        .map_err(|e| SloomoError::from(e).into())
+}
+
+/// Route a base asset deposit to the assets furthest below target
+/// (see `sloomo_core::planner::plan_cash_flow`)
+pub fn plan_cash_flow(
+    holdings: &[PlannerHolding],
+    base_mint: Pubkey,
+    amount: u64,
+    config: &PlannerConfig,
+) -> Result<RebalancePlan> {
+    sloomo_core::planner::plan_cash_flow(holdings, base_mint.to_bytes(), amount, config)
+        .map_err(|e| SloomoError::from(e).into())
+}
+
+/// Sell legs into the base asset that fund a withdrawal of `amount`
+/// (see `sloomo_core::planner::plan_liquidation`)
+pub fn plan_liquidation(
+    holdings: &[PlannerHolding],
+    base_mint: Pubkey,
+    amount: u64,
+    order: sloomo_core::planner::LiquidationOrder,
+    config: &PlannerConfig,
+) -> Result<Vec<PlannedLeg>> {
+    sloomo_core::planner::plan_liquidation(holdings, base_mint.to_bytes(), amount, order, config)
+        .map_err(|e| SloomoError::from(e).into())
+}
+
+/// Build planner holdings from stored allocations and requested targets
+///
+/// Allocations keep their band. Allocations missing from `target_allocations`
+/// get a 0% target, and targets without an allocation are added with no
+/// current value and no band.
+pub fn holdings_from_allocations(
+    allocations: &[AllocationData],
+    target_allocations: &[AllocationTarget],
+) -> Vec<PlannerHolding> {
+    let mut holdings: Vec<PlannerHolding> = allocations
+        .iter()
+        .map(|a| PlannerHolding {
+            mint: a.mint.to_bytes(),
+            value: a.current_amount,
+            target_percentage: target_allocations
+                .iter()
+                .find(|t| t.mint == a.mint)
+                .map_or(0, |t| t.target_percentage),
+            band: a.core_band(),
+        })
+        .collect();
+
+    for target in target_allocations {
+        if !allocations.iter().any(|a| a.mint == target.mint) {
+            holdings.push(PlannerHolding {
+                mint: target.mint.to_bytes(),
+                value: 0,
+                target_percentage: target.target_percentage,
+                band: None,
+            });
+        }
+    }
+    holdings
+}
+
+/// Pin the members of asset classes that left their band to their targets,
+/// so the planner also trades classes whose members are each within band
+/// (see `sloomo_core::hierarchy::pin_out_of_band_classes`)
+pub fn pin_asset_classes(
+    allocations: &[AllocationData],
+    asset_classes: &[AssetClass],
+    holdings: &mut [PlannerHolding],
+) -> Result<usize> {
+    let members: Vec<_> = holdings
+        .iter()
+        .map(|h| {
+            allocations
+                .iter()
+                .find(|a| a.mint.to_bytes() == h.mint)
+                .and_then(|a| a.class_member())
+        })
+        .collect();
+    let classes: Vec<_> = asset_classes.iter().map(|c| c.to_core()).collect();
+
+    sloomo_core::hierarchy::pin_out_of_band_classes(
+        holdings,
+        &members,
+        &classes,
+        DEFAULT_DRIFT_THRESHOLD_BPS,
+    )
+    .map_err(|e| SloomoError::from(e).into())
+}
+
+/// Log planned legs (actual swap executed externally)
+pub fn log_plan(plan: &RebalancePlan) {
+    for leg in &plan.legs {
+        msg!(
+            "Planned leg: {} -> {} for {} (estimated cost {})",
+            Pubkey::new_from_array(leg.from_mint),
+            Pubkey::new_from_array(leg.to_mint),
+            leg.amount,
+            leg.estimated_cost
+        );
+    }
+    msg!(
+        "Plan: {} legs, estimated cost {}, residual drift {} bps, within band: {}",
+        plan.legs.len(),
+        plan.estimated_cost,
+        plan.max_residual_drift_bps,
+        plan.within_band
+    );
+}
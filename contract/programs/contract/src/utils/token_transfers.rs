@@ -1,43 +1,104 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Transfer, TokenAccount, Token, transfer};
+use anchor_spl::token_interface::{
+    spl_token_2022::{
+        self,
+        extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    },
+    Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked,
+};
+use crate::error::SloomoError;
 
 /// Token transfer from user to portfolio vault (during investment)
 pub fn transfer_to_vault<'info>(
-    user_account: &Account<'info, TokenAccount>,
-    vault_account: &Account<'info, TokenAccount>, 
+    user_account: &InterfaceAccount<'info, TokenAccount>,
+    vault_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
     authority: &Signer<'info>,
-    token_program: &Program<'info, Token>,
+    token_program: &Interface<'info, TokenInterface>,
     amount: u64,
 ) -> Result<()> {
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: user_account.to_account_info(),
+        mint: mint.to_account_info(),
         to: vault_account.to_account_info(),
         authority: authority.to_account_info(),
     };
 
     let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
-    transfer(cpi_ctx, amount)
+    transfer_checked(cpi_ctx, amount, mint.decimals)
+}
+
+/// Token transfer from user to portfolio vault, pulled by the portfolio PDA as
+/// the user account's delegate (recurring deposits)
+pub fn transfer_to_vault_as_delegate<'info>(
+    user_account: &InterfaceAccount<'info, TokenAccount>,
+    vault_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    portfolio_authority: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    authority_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: user_account.to_account_info(),
+        mint: mint.to_account_info(),
+        to: vault_account.to_account_info(),
+        authority: portfolio_authority.clone(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        authority_seeds
+    );
+    transfer_checked(cpi_ctx, amount, mint.decimals)
 }
 
 /// Token transfer from portfolio vault to user (during withdrawal)
 pub fn transfer_from_vault_with_signer<'info>(
-    vault_account: &Account<'info, TokenAccount>,
-    user_account: &Account<'info, TokenAccount>,
+    vault_account: &InterfaceAccount<'info, TokenAccount>,
+    user_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
     portfolio_authority: &AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    token_program: &Interface<'info, TokenInterface>,
     authority_seeds: &[&[&[u8]]],
     amount: u64,
 ) -> Result<()> {
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: vault_account.to_account_info(),
+        mint: mint.to_account_info(),
         to: user_account.to_account_info(),
         authority: portfolio_authority.clone(),
     };
 
     let cpi_ctx = CpiContext::new_with_signer(
-        token_program.to_account_info(), 
-        cpi_accounts, 
+        token_program.to_account_info(),
+        cpi_accounts,
         authority_seeds
     );
-    transfer(cpi_ctx, amount)
-}
\ No newline at end of file
+    transfer_checked(cpi_ctx, amount, mint.decimals)
+}
+
+/// Fee withheld by the Token-2022 transfer-fee extension when sending `amount` in `epoch`
+///
+/// SPL Token mints and Token-2022 mints without the extension charge nothing.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64, epoch: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or_else(|| SloomoError::MathOverflow.into()),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Amount a transfer of `amount` of `mint` actually delivers in `epoch`
+pub fn received_after_fee(mint: &AccountInfo, amount: u64, epoch: u64) -> Result<u64> {
+    let fee = transfer_fee(mint, amount, epoch)?;
+    amount.checked_sub(fee).ok_or_else(|| SloomoError::MathOverflow.into())
+}
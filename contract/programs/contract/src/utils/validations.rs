@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
 use crate::error::SloomoError;
-use crate::state::Portfolio;
+use crate::state::{
+    Portfolio, SessionKey, InitPortfolioParams, AllocationTarget, AllocationBand, MAX_ALLOCATIONS,
+    MAX_SWAP_PROGRAMS,
+};
+
+/// Minimum time between rebalances (seconds, once per day)
+pub const MIN_REBALANCE_INTERVAL: i64 = 86400;
 
 /// Reentrancy check (common for all instructions)
 pub fn validate_reentrancy(portfolio: &Portfolio) -> Result<()> {
@@ -8,6 +14,27 @@ pub fn validate_reentrancy(portfolio: &Portfolio) -> Result<()> {
     Ok(())
 }
 
+/// Signer check for instructions a session key may sign in place of the owner
+///
+/// The owner always passes. Any other signer needs its session key, unexpired,
+/// issued by the current owner and granting `scope`; `spend` (base asset units)
+/// is counted against the key's limit.
+pub fn validate_authority(
+    portfolio: &Portfolio,
+    authority: &Pubkey,
+    session_key: Option<&mut SessionKey>,
+    scope: u8,
+    spend: u64,
+    now: i64,
+) -> Result<()> {
+    if *authority == portfolio.owner {
+        return Ok(());
+    }
+    let session_key = session_key.ok_or(SloomoError::Unauthorized)?;
+    session_key.authorize(&portfolio.owner, scope, now)?;
+    session_key.record_spend(spend)
+}
+
 /// Amount validation (common for investment and withdrawal)
 pub fn validate_amount(amount: u64) -> Result<()> {
     require!(amount > 0, SloomoError::InvalidAmount);
@@ -29,6 +56,87 @@ pub fn validate_allocation_percentage(total: u16) -> Result<()> {
     Ok(())
 }
 
+/// Allocation band check (common for initialization and allocation edits)
+pub fn validate_allocation_band(band: Option<AllocationBand>, target_percentage: u16) -> Result<()> {
+    if let Some(band) = band {
+        require!(
+            band.to_core().is_valid_for(target_percentage),
+            SloomoError::InvalidAllocationBand
+        );
+    }
+    Ok(())
+}
+
+/// Sum target percentages without wrapping (common for initialization and rebalancing)
+pub fn sum_target_percentages(percentages: impl IntoIterator<Item = u16>) -> Result<u16> {
+    sloomo_core::math::sum_target_percentages(percentages).map_err(|e| SloomoError::from(e).into())
+}
+
+/// Duplicate mint check (allocations are looked up by mint)
+pub fn validate_unique_mints(mints: &[Pubkey]) -> Result<()> {
+    for (i, mint) in mints.iter().enumerate() {
+        require!(!mints[..i].contains(mint), SloomoError::InvalidTokenMint);
+    }
+    Ok(())
+}
+
+/// Swap program allowlist validation (bounded, unique, never this program)
+pub fn validate_swap_programs(programs: &[Pubkey]) -> Result<()> {
+    require!(
+        programs.len() <= MAX_SWAP_PROGRAMS,
+        SloomoError::InvalidSwapProgram
+    );
+    for (i, program) in programs.iter().enumerate() {
+        require!(
+            *program != crate::ID && !programs[..i].contains(program),
+            SloomoError::InvalidSwapProgram
+        );
+    }
+    Ok(())
+}
+
+/// Portfolio initialization parameter validation
+pub fn validate_init_params(params: &InitPortfolioParams) -> Result<()> {
+    // Check allocation count limit
+    require!(
+        params.initial_allocations.len() <= MAX_ALLOCATIONS,
+        SloomoError::AllocationOverflow
+    );
+
+    for allocation in &params.initial_allocations {
+        validate_token_symbol(&allocation.symbol)?;
+        validate_allocation_band(allocation.band, allocation.target_percentage)?;
+    }
+
+    let mints: Vec<Pubkey> = params.initial_allocations.iter().map(|a| a.mint).collect();
+    validate_unique_mints(&mints)?;
+
+    // Check total allocation percentage
+    let total_percentage = sum_target_percentages(
+        params.initial_allocations.iter().map(|a| a.target_percentage),
+    )?;
+    validate_allocation_percentage(total_percentage)?;
+
+    // Check initial base asset investment amount
+    validate_amount(params.initial_sol_amount)
+}
+
+/// Rebalance target allocation validation
+pub fn validate_target_allocations(target_allocations: &[AllocationTarget]) -> Result<()> {
+    require!(
+        target_allocations.len() <= MAX_ALLOCATIONS,
+        SloomoError::AllocationOverflow
+    );
+
+    let mints: Vec<Pubkey> = target_allocations.iter().map(|t| t.mint).collect();
+    validate_unique_mints(&mints)?;
+
+    let total_target = sum_target_percentages(
+        target_allocations.iter().map(|t| t.target_percentage),
+    )?;
+    validate_allocation_percentage(total_target)
+}
+
 /// APY value validation (used for yield updates)
 pub fn validate_apy(apy: u64) -> Result<()> {
     require!(apy <= 100000, SloomoError::InvalidAmount); // Maximum 1000%
@@ -38,7 +146,7 @@ pub fn validate_apy(apy: u64) -> Result<()> {
 /// Rebalancing frequency limit check
 pub fn validate_rebalance_frequency(portfolio: &Portfolio, clock: &Clock) -> Result<()> {
     require!(
-        clock.unix_timestamp - portfolio.last_rebalance >= 86400, // Once per day
+        clock.unix_timestamp - portfolio.last_rebalance >= MIN_REBALANCE_INTERVAL,
         SloomoError::RebalanceTooFrequent
     );
     Ok(())
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Owners;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::error::SloomoError;
+
+/// Portfolio vault PDA for `mint`
+pub fn vault_address(portfolio: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", portfolio.as_ref(), mint.as_ref()], &crate::ID).0
+}
+
+/// Balance of a portfolio vault passed as an unchecked account
+///
+/// The account must be the `[b"vault", portfolio, mint]` PDA. A vault that has
+/// not been created yet holds nothing and counts as an empty balance.
+pub fn read_vault_balance(portfolio: &Pubkey, mint: &Pubkey, vault: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(vault.key(), vault_address(portfolio, mint), SloomoError::InvalidVault);
+
+    if vault.owner == &System::id() && vault.data_is_empty() {
+        return Ok(0);
+    }
+    require!(
+        TokenAccount::owners().contains(vault.owner),
+        SloomoError::InvalidVault
+    );
+
+    let data = vault.try_borrow_data()?;
+    let account = TokenAccount::try_deserialize(&mut &data[..])?;
+    require_keys_eq!(account.mint, *mint, SloomoError::InvalidVault);
+    require_keys_eq!(account.owner, *portfolio, SloomoError::InvalidVault);
+    Ok(account.amount)
+}
+
+/// Balances of the vaults for `mints`, passed in the same order as remaining accounts
+pub fn read_vault_balances(
+    portfolio: &Pubkey,
+    mints: &[Pubkey],
+    vaults: &[AccountInfo],
+) -> Result<Vec<(Pubkey, u64)>> {
+    require!(vaults.len() >= mints.len(), SloomoError::MissingVaultAccount);
+
+    mints
+        .iter()
+        .zip(vaults)
+        .map(|(mint, vault)| Ok((*mint, read_vault_balance(portfolio, mint, vault)?)))
+        .collect()
+}
+
+/// Decimals of `mints`, from their mint accounts passed in the same order
+pub fn read_mint_decimals(mints: &[Pubkey], accounts: &[AccountInfo]) -> Result<Vec<u8>> {
+    require!(accounts.len() >= mints.len(), SloomoError::MissingMintAccount);
+
+    mints
+        .iter()
+        .zip(accounts)
+        .map(|(mint, account)| {
+            require_keys_eq!(account.key(), *mint, SloomoError::InvalidTokenMint);
+            require!(
+                Mint::owners().contains(account.owner),
+                SloomoError::InvalidTokenMint
+            );
+            let data = account.try_borrow_data()?;
+            Ok(Mint::try_deserialize(&mut &data[..])?.decimals)
+        })
+        .collect()
+}
+
+/// Reject swap accounts the portfolio PDA's signature could move outside a leg
+///
+/// Token accounts owned by the portfolio must be among `vaults`, and token
+/// accounts delegated to the portfolio (the owner's recurring deposit source)
+/// are never passed. Accounts that are not token accounts are left to the swap.
+pub fn validate_swap_accounts(
+    portfolio: &Pubkey,
+    vaults: &[Pubkey],
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    for account in accounts {
+        if !TokenAccount::owners().contains(account.owner) {
+            continue;
+        }
+        let data = account.try_borrow_data()?;
+        let Ok(token) = TokenAccount::try_deserialize(&mut &data[..]) else {
+            // Mints and other token program accounts
+            continue;
+        };
+        require!(
+            token.delegate != Some(*portfolio).into(),
+            SloomoError::UnauthorizedSwapAccount
+        );
+        require!(
+            token.owner != *portfolio || vaults.contains(&account.key()),
+            SloomoError::UnauthorizedSwapAccount
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! Unit tests for per-allocation drift bands
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{allocation, band, base_mint, mint, targets};
+use sloomo_portfolio::utils::{
+    holdings_from_allocations, plan_rebalance, validate_allocation_band, validate_init_params,
+    PlannerConfig,
+};
+use sloomo_portfolio::{
+    AllocationData, AllocationParams, BandRestore, InitPortfolioParams,
+    Portfolio, SloomoError,
+};
+
+/// Volatile equity token (target 30%, band 25-35%) next to a stablecoin
+/// (target 70%, band 68-72%)
+fn portfolio_with(amounts: [u64; 2]) -> Portfolio {
+    common::portfolio_with(vec![
+        AllocationData { band: band(2500, 3500), ..allocation(0, amounts[0], 3000) },
+        AllocationData { band: band(6800, 7200), ..allocation(1, amounts[1], 7000) },
+    ])
+}
+
+fn plan_config(portfolio: &Portfolio) -> PlannerConfig {
+    PlannerConfig {
+        min_trade_value: 0,
+        fee_bps: 0,
+        slippage_bps: 0,
+        restore: portfolio.band_restore.to_core(),
+        ..PlannerConfig::default()
+    }
+}
+
+#[test]
+fn drift_inside_the_band_needs_no_rebalance() {
+    // Equity 4% over its 30% target is still inside its 25-35% band
+    let portfolio = portfolio_with([340, 660]);
+    let targets = portfolio.current_targets();
+    assert!(!portfolio.needs_rebalancing(&targets[..1], 1_000).unwrap());
+
+    // The same 4% leaves the stablecoin's 2% band
+    assert!(portfolio.needs_rebalancing(&targets, 1_000).unwrap());
+}
+
+#[test]
+fn only_out_of_band_allocations_trade() {
+    let mut portfolio = portfolio_with([340, 660]);
+    let holdings = holdings_from_allocations(&portfolio.allocations, &portfolio.current_targets());
+
+    // Stablecoin back to its 68% edge, funded by the in-band equity overweight
+    let plan = plan_rebalance(&holdings, base_mint(), &plan_config(&portfolio)).unwrap();
+    assert_eq!(plan.legs.len(), 1);
+    assert_eq!(Pubkey::new_from_array(plan.legs[0].from_mint), mint(0));
+    assert_eq!(Pubkey::new_from_array(plan.legs[0].to_mint), mint(1));
+    assert_eq!(plan.legs[0].amount, 20);
+
+    portfolio.band_restore = BandRestore::Target;
+    let plan = plan_rebalance(&holdings, base_mint(), &plan_config(&portfolio)).unwrap();
+    assert_eq!(plan.legs[0].amount, 40);
+    assert_eq!(plan.max_residual_drift_bps, 0);
+}
+
+#[test]
+fn new_target_must_stay_inside_the_band() {
+    let mut portfolio = portfolio_with([300, 700]);
+    let err = portfolio
+        .set_target_allocations(&targets(&[2000, 8000]), |_| 0, |_| String::new())
+        .unwrap_err();
+    assert_eq!(err, SloomoError::InvalidAllocationBand.into());
+
+    portfolio
+        .set_target_allocations(&targets(&[3200, 6800]), |_| 0, |_| String::new())
+        .unwrap();
+    assert_eq!(portfolio.allocations[0].band, band(2500, 3500));
+    assert_eq!(portfolio.allocations[1].target_percentage, 6800);
+}
+
+#[test]
+fn bands_are_validated() {
+    assert!(validate_allocation_band(None, 3000).is_ok());
+    assert!(validate_allocation_band(band(3000, 3000), 3000).is_ok());
+    assert_eq!(
+        validate_allocation_band(band(3100, 3500), 3000).unwrap_err(),
+        SloomoError::InvalidAllocationBand.into()
+    );
+    assert_eq!(
+        validate_allocation_band(band(9000, 10001), 9500).unwrap_err(),
+        SloomoError::InvalidAllocationBand.into()
+    );
+
+    let params = InitPortfolioParams {
+        initial_allocations: vec![AllocationParams {
+            mint: mint(0),
+            symbol: "EQ".to_string(),
+            target_percentage: 3000,
+            band: band(3500, 2500),
+        }],
+        initial_sol_amount: 1,
+        enable_jupiter_swap: false,
+    };
+    assert!(validate_init_params(&params).is_err());
+}
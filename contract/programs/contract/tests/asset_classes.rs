@@ -0,0 +1,124 @@
+//! Unit tests for hierarchical asset-class allocations
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{allocation, band, base_mint, mint, targets};
+use sloomo_portfolio::utils::{
+    holdings_from_allocations, pin_asset_classes, plan_rebalance, PlannerConfig,
+};
+use sloomo_portfolio::{
+    AllocationData, AssetClass, ClassMembership, Portfolio, SloomoError,
+};
+
+/// Two equity tokens (30% each, band 20-40%) and a stablecoin (40%, band 30-50%)
+fn portfolio_with(amounts: [u64; 3]) -> Portfolio {
+    common::portfolio_with(vec![
+        AllocationData { band: band(2000, 4000), ..allocation(0, amounts[0], 3000) },
+        AllocationData { band: band(2000, 4000), ..allocation(1, amounts[1], 3000) },
+        AllocationData { band: band(3000, 5000), ..allocation(2, amounts[2], 4000) },
+    ])
+}
+
+/// Equities 60% (band 55-65%) split evenly, stablecoins 40% (band 30-50%)
+fn classes() -> Vec<AssetClass> {
+    vec![
+        AssetClass {
+            name: "equities".to_string(),
+            target_percentage: 6000,
+            band: band(5500, 6500),
+        },
+        AssetClass {
+            name: "stables".to_string(),
+            target_percentage: 4000,
+            band: band(3000, 5000),
+        },
+    ]
+}
+
+fn memberships(equity_weights: [u16; 2]) -> Vec<ClassMembership> {
+    vec![
+        ClassMembership { mint: mint(0), asset_class: 0, weight: equity_weights[0] },
+        ClassMembership { mint: mint(1), asset_class: 0, weight: equity_weights[1] },
+        ClassMembership { mint: mint(2), asset_class: 1, weight: 10000 },
+    ]
+}
+
+#[test]
+fn members_take_the_flattened_target() {
+    let mut portfolio = portfolio_with([3000, 3000, 4000]);
+    let mut classes = classes();
+    classes[0].band = band(5000, 7000);
+    portfolio.set_asset_classes(classes, &memberships([6000, 4000])).unwrap();
+
+    let targets: Vec<u16> = portfolio.allocations.iter().map(|a| a.target_percentage).collect();
+    assert_eq!(targets, vec![3600, 2400, 4000]);
+
+    // Returning to flat allocations keeps the flattened targets
+    portfolio.set_asset_classes(Vec::new(), &[]).unwrap();
+    assert!(portfolio.allocations.iter().all(|a| a.asset_class.is_none()));
+    assert_eq!(portfolio.allocations[0].target_percentage, 3600);
+}
+
+#[test]
+fn each_level_must_add_up() {
+    let mut portfolio = portfolio_with([3000, 3000, 4000]);
+
+    // Intra-class weights short of 100%
+    let err = portfolio.set_asset_classes(classes(), &memberships([5000, 4000])).unwrap_err();
+    assert_eq!(err, SloomoError::InvalidAssetClass.into());
+
+    // Class targets above 100%
+    let mut over = classes();
+    over[1].target_percentage = 5000;
+    assert!(portfolio.set_asset_classes(over, &memberships([5000, 5000])).is_err());
+
+    // Unknown class index
+    let mut unknown = memberships([5000, 5000]);
+    unknown[2].asset_class = 2;
+    let err = portfolio.set_asset_classes(classes(), &unknown).unwrap_err();
+    assert_eq!(err, SloomoError::InvalidAssetClass.into());
+
+    // Nothing changed on failure
+    assert!(portfolio.asset_classes.is_empty());
+    assert!(portfolio.allocations.iter().all(|a| a.asset_class.is_none()));
+}
+
+#[test]
+fn member_targets_are_set_by_their_class() {
+    let mut portfolio = portfolio_with([3000, 3000, 4000]);
+    portfolio.set_asset_classes(classes(), &memberships([5000, 5000])).unwrap();
+
+    let err = portfolio
+        .set_target_allocations(&targets(&[3500, 2500]), |_| 0, |_| String::new())
+        .unwrap_err();
+    assert_eq!(err, SloomoError::AllocationInAssetClass.into());
+}
+
+#[test]
+fn out_of_band_class_trades_members_within_their_own_band() {
+    // Each equity is 5% over target (inside its 20-40% band), but the equity
+    // class is at 70%, outside its 55-65% band
+    let mut portfolio = portfolio_with([3500, 3500, 3000]);
+    portfolio.set_asset_classes(classes(), &memberships([5000, 5000])).unwrap();
+    let targets = portfolio.current_targets();
+    assert!(portfolio.needs_rebalancing(&targets, 10_000).unwrap());
+
+    let mut holdings = holdings_from_allocations(&portfolio.allocations, &targets);
+    let config = PlannerConfig {
+        min_trade_value: 0,
+        fee_bps: 0,
+        slippage_bps: 0,
+        ..PlannerConfig::default()
+    };
+    let plan = plan_rebalance(&holdings, base_mint(), &config).unwrap();
+    assert!(plan.legs.is_empty());
+
+    let pinned =
+        pin_asset_classes(&portfolio.allocations, &portfolio.asset_classes, &mut holdings).unwrap();
+    assert_eq!(pinned, 1);
+    let plan = plan_rebalance(&holdings, base_mint(), &config).unwrap();
+    assert_eq!(plan.legs.len(), 2);
+    assert!(plan.legs.iter().all(|leg| Pubkey::new_from_array(leg.to_mint) == mint(2)));
+    assert_eq!(plan.legs.iter().map(|leg| leg.amount).sum::<u64>(), 1000);
+}
@@ -0,0 +1,58 @@
+//! Tests for planning and checking withdrawals to the base asset
+
+mod common;
+
+use common::{allocation, base_mint, mint, DECIMALS};
+use sloomo_portfolio::{
+    liquidated_value, liquidation_budgets, LiquidatedAmount, LiquidationOrder, Portfolio,
+    VaultBalance,
+};
+
+/// Base asset (6 decimals) holding 1.0 beside mint 0 (9 decimals) holding 3.0
+fn snapshot() -> Vec<VaultBalance> {
+    vec![
+        VaultBalance { mint: base_mint(), amount: 1_000_000, decimals: DECIMALS },
+        VaultBalance { mint: mint(0), amount: 3_000_000_000, decimals: 9 },
+    ]
+}
+
+fn portfolio() -> Portfolio {
+    common::portfolio_with(vec![allocation(0, 3_000_000_000, 10000)])
+}
+
+#[test]
+fn budgets_are_planned_in_base_value_and_sold_in_mint_units() {
+    // 1.0 short, grossed up by the 0.25% fee and 0.75% slippage
+    let budgets = liquidation_budgets(
+        &portfolio(),
+        &snapshot(),
+        2_000_000,
+        LiquidationOrder::Proportional,
+        75,
+    )
+    .unwrap();
+    assert_eq!(budgets, vec![(mint(0), 1_010_102_000)]);
+
+    // 3.0 of mint 0 covers at most about 3.0 of base asset
+    assert!(liquidation_budgets(
+        &portfolio(),
+        &snapshot(),
+        5_000_000,
+        LiquidationOrder::Proportional,
+        75,
+    )
+    .is_err());
+}
+
+#[test]
+fn sold_value_is_rescaled_per_mint() {
+    let sold = vec![
+        LiquidatedAmount { mint: mint(0), spent: 1_000_000_000, received: 990_000 },
+        LiquidatedAmount { mint: base_mint(), spent: 10, received: 10 },
+    ];
+    assert_eq!(liquidated_value(&snapshot(), &sold).unwrap(), 1_000_010);
+
+    // Mints outside the snapshot cannot be valued
+    let unknown = vec![LiquidatedAmount { mint: mint(1), spent: 1, received: 1 }];
+    assert!(liquidated_value(&snapshot(), &unknown).is_err());
+}
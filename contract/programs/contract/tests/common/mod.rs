@@ -0,0 +1,138 @@
+//! Fixtures shared by the program tests
+//!
+//! Every test crate compiles this module and uses a subset of it.
+#![allow(dead_code)]
+
+use anchor_lang::prelude::Pubkey;
+use sloomo_portfolio::{
+    AllocationBand, AllocationData, AllocationTarget, BandRestore, Portfolio, RebalanceSession,
+    SessionLeg, VaultBalance, PORTFOLIO_VERSION,
+};
+
+/// Distinct key for `index` (owners, guardians, session keys)
+pub fn key(index: u8) -> Pubkey {
+    Pubkey::new_from_array([index + 1; 32])
+}
+
+/// Token mint `index`
+pub fn mint(index: u8) -> Pubkey {
+    key(index)
+}
+
+/// Base asset mint, distinct from every `mint(index)`
+pub fn base_mint() -> Pubkey {
+    Pubkey::new_from_array([0xff; 32])
+}
+
+pub fn band(min_percentage: u16, max_percentage: u16) -> Option<AllocationBand> {
+    Some(AllocationBand { min_percentage, max_percentage })
+}
+
+/// `current_amount` of `mint(index)` targeting `target_percentage`, without
+/// band, asset class or cost basis
+pub fn allocation(index: u8, current_amount: u64, target_percentage: u16) -> AllocationData {
+    AllocationData {
+        mint: mint(index),
+        symbol: format!("TKN{}", index),
+        current_amount,
+        target_percentage,
+        apy: 0,
+        last_yield_update: 0,
+        band: None,
+        asset_class: None,
+        cost_basis: 0,
+        realized_pnl: 0,
+    }
+}
+
+/// Empty portfolio on `base_mint()`; tests set the fields they exercise
+pub fn portfolio() -> Portfolio {
+    Portfolio {
+        owner: Pubkey::default(),
+        bump: 255,
+        total_value: 0,
+        last_rebalance: 0,
+        allocations: Vec::new(),
+        performance_history: Vec::new(),
+        created_at: 0,
+        updated_at: 0,
+        is_rebalancing: false,
+        base_mint: base_mint(),
+        route_deposits: false,
+        portfolio_id: Pubkey::default(),
+        pending_owner: None,
+        band_restore: BandRestore::Edge,
+        asset_classes: Vec::new(),
+        glide_path: None,
+        goal: None,
+        rebalance_count: 0,
+        swap_programs: Vec::new(),
+        version: PORTFOLIO_VERSION,
+    }
+}
+
+/// Portfolio holding `allocations`, valued at the sum of their amounts
+pub fn portfolio_with(allocations: Vec<AllocationData>) -> Portfolio {
+    Portfolio {
+        total_value: allocations
+            .iter()
+            .fold(0u64, |acc, a| acc.saturating_add(a.current_amount)),
+        allocations,
+        ..portfolio()
+    }
+}
+
+/// Targets for `mint(0)`, `mint(1)`, ... in order
+pub fn targets(percentages: &[u16]) -> Vec<AllocationTarget> {
+    percentages
+        .iter()
+        .enumerate()
+        .map(|(i, target_percentage)| AllocationTarget {
+            mint: mint(i as u8),
+            target_percentage: *target_percentage,
+        })
+        .collect()
+}
+
+/// Decimals of every fixture mint unless a test sets its own
+pub const DECIMALS: u8 = 6;
+
+/// Vault balance of `mint(index)`
+pub fn balance(index: u8, amount: u64) -> VaultBalance {
+    VaultBalance { mint: mint(index), amount, decimals: DECIMALS }
+}
+
+/// Vault snapshot: the base vault holding `base`, then `mint(0)`, `mint(1)`, ...
+pub fn snapshot(base: u64, amounts: &[u64]) -> Vec<VaultBalance> {
+    std::iter::once(VaultBalance { mint: base_mint(), amount: base, decimals: DECIMALS })
+        .chain(amounts.iter().enumerate().map(|(i, amount)| balance(i as u8, *amount)))
+        .collect()
+}
+
+/// Unexecuted session leg swapping `amount` of `from` into at least `min_amount_out` of `to`
+pub fn leg(from: Pubkey, to: Pubkey, amount: u64, min_amount_out: u64) -> SessionLeg {
+    SessionLeg {
+        from_mint: from,
+        to_mint: to,
+        amount,
+        min_amount_out,
+        executed: false,
+        spent: 0,
+        received: 0,
+    }
+}
+
+/// Session spending a base vault of `total` on `mint(0)` and `mint(1)` at 50/50
+pub fn session(total: u64, legs: Vec<SessionLeg>) -> RebalanceSession {
+    RebalanceSession {
+        portfolio: Pubkey::new_unique(),
+        bump: 255,
+        started_at: 1_000,
+        deadline: 1_900,
+        slippage_bps: 50,
+        total_value_before: total,
+        target_allocations: targets(&[5000, 5000]),
+        snapshot: snapshot(total, &[0, 0]),
+        legs,
+    }
+}
@@ -0,0 +1,183 @@
+//! Tests for average cost basis and profit and loss of allocations
+
+mod common;
+
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use anchor_lang::AnchorSerialize;
+use common::{allocation, base_mint, leg, mint, snapshot};
+use sloomo_core::math::PRICE_SCALE;
+use sloomo_portfolio::{
+    AllocationData, AssetPrice, PnlReport, Portfolio, RebalanceSession, SessionLeg, MAX_ALLOCATIONS,
+};
+
+/// Base asset holding 600 at cost, mint 0 holding 400 bought for 300
+fn portfolio() -> Portfolio {
+    common::portfolio_with(vec![
+        AllocationData { mint: base_mint(), cost_basis: 600, ..allocation(0, 600, 5000) },
+        AllocationData { cost_basis: 300, ..allocation(0, 400, 5000) },
+    ])
+}
+
+#[test]
+fn deposits_add_their_value_to_the_cost_basis() {
+    let mut portfolio = portfolio();
+    portfolio.record_deposit(&mint(0), 2_000, 100).unwrap();
+    assert_eq!(portfolio.allocations[1].current_amount, 2_400);
+    assert_eq!(portfolio.allocations[1].cost_basis, 400);
+    assert_eq!(portfolio.allocations[1].realized_pnl, 0);
+}
+
+#[test]
+fn withdrawals_take_their_average_cost_without_realizing() {
+    let mut portfolio = portfolio();
+    portfolio.record_withdrawal(&mint(0), 100, 100);
+    assert_eq!(portfolio.allocations[1].current_amount, 300);
+    assert_eq!(portfolio.allocations[1].cost_basis, 225);
+    assert_eq!(portfolio.allocations[1].realized_pnl, 0);
+
+    // The last unit takes whatever basis is left
+    portfolio.record_withdrawal(&mint(0), 10_000, 10_000);
+    assert_eq!(portfolio.allocations[1].cost_basis, 0);
+}
+
+#[test]
+fn sells_realize_the_gain_over_average_cost() {
+    let mut portfolio = portfolio();
+    // Sell half of mint 0 (cost 150) for 200 of the base asset
+    portfolio
+        .apply_swap_costs(&snapshot(600, &[400]), [(mint(0), base_mint(), 200, 200)])
+        .unwrap();
+    assert_eq!(portfolio.allocations[1].cost_basis, 150);
+    assert_eq!(portfolio.allocations[1].realized_pnl, 50);
+    assert_eq!(portfolio.allocations[0].cost_basis, 800);
+    assert_eq!(portfolio.allocations[0].realized_pnl, 0);
+}
+
+#[test]
+fn buys_are_valued_at_the_base_asset_spent() {
+    let mut portfolio = portfolio();
+    // 300 of the base asset buys 250 units of mint 0
+    portfolio
+        .apply_swap_costs(&snapshot(600, &[400]), [(base_mint(), mint(0), 300, 250)])
+        .unwrap();
+    assert_eq!(portfolio.allocations[0].cost_basis, 300);
+    assert_eq!(portfolio.allocations[0].realized_pnl, 0);
+    assert_eq!(portfolio.allocations[1].cost_basis, 600);
+}
+
+#[test]
+fn swaps_run_against_the_balances_left_by_earlier_legs() {
+    let mut portfolio = portfolio();
+    portfolio.allocations.push(allocation(1, 0, 0));
+    // Buy 100 of mint 0, then sell 250 of the 500 now held
+    portfolio
+        .apply_swap_costs(
+            &snapshot(600, &[400, 0]),
+            [(base_mint(), mint(0), 100, 100), (mint(0), mint(1), 250, 240)],
+        )
+        .unwrap();
+    assert_eq!(portfolio.allocations[1].cost_basis, 200);
+    assert_eq!(portfolio.allocations[1].realized_pnl, 40);
+    assert_eq!(portfolio.allocations[2].cost_basis, 240);
+}
+
+#[test]
+fn swaps_between_tokens_book_their_value_in_base_decimals() {
+    let mut portfolio = portfolio();
+    portfolio.allocations.push(allocation(1, 0, 0));
+    // Mint 1 has 9 decimals: 240_000 of it is worth 240 base units
+    let mut snapshot = snapshot(600, &[400, 0]);
+    snapshot[2].decimals = 9;
+    portfolio.apply_swap_costs(&snapshot, [(mint(0), mint(1), 200, 240_000)]).unwrap();
+
+    assert_eq!(portfolio.allocations[1].cost_basis, 150);
+    assert_eq!(portfolio.allocations[1].realized_pnl, 90);
+    assert_eq!(portfolio.allocations[2].cost_basis, 240);
+}
+
+#[test]
+fn executed_session_legs_feed_the_swaps() {
+    let sell = |executed| SessionLeg {
+        min_amount_out: 90,
+        executed,
+        spent: if executed { 100 } else { 0 },
+        received: if executed { 95 } else { 0 },
+        ..leg(mint(0), base_mint(), 100, 0)
+    };
+    let session = RebalanceSession {
+        slippage_bps: 100,
+        snapshot: snapshot(600, &[400]),
+        ..common::session(1_000, vec![sell(true), sell(false)])
+    };
+    let swaps: Vec<_> = session.executed_swaps().collect();
+    assert_eq!(swaps, vec![(mint(0), base_mint(), 100, 95)]);
+
+    let mut portfolio = portfolio();
+    portfolio.apply_swap_costs(&session.snapshot, session.executed_swaps()).unwrap();
+    assert_eq!(portfolio.allocations[1].cost_basis, 225);
+    assert_eq!(portfolio.allocations[1].realized_pnl, 20);
+}
+
+#[test]
+fn pnl_values_holdings_at_the_given_prices() {
+    let mut portfolio = portfolio();
+    portfolio.allocations[1].realized_pnl = 7;
+    let prices = [AssetPrice { mint: mint(0), price: PRICE_SCALE / 2 }];
+    let report = portfolio.pnl_report(&prices);
+
+    let base = &report.allocations[0];
+    assert_eq!(base.price, Some(PRICE_SCALE));
+    assert_eq!(base.value, Some(600));
+    assert_eq!(base.unrealized_pnl, Some(0));
+    assert_eq!(base.average_cost, PRICE_SCALE);
+
+    let token = &report.allocations[1];
+    assert_eq!(token.value, Some(200));
+    assert_eq!(token.unrealized_pnl, Some(-100));
+    assert_eq!(token.average_cost, PRICE_SCALE * 3 / 4);
+    assert_eq!(token.realized_pnl, 7);
+
+    assert_eq!(report.cost_basis, 900);
+    assert_eq!(report.value, Some(800));
+    assert_eq!(report.unrealized_pnl, Some(-100));
+    assert_eq!(report.realized_pnl, 7);
+}
+
+#[test]
+fn pnl_without_prices_leaves_other_mints_unknown() {
+    let report = portfolio().pnl_report(&[]);
+
+    // The base asset is its own unit
+    assert_eq!(report.allocations[0].value, Some(600));
+    assert_eq!(report.allocations[0].unrealized_pnl, Some(0));
+
+    let token = &report.allocations[1];
+    assert_eq!((token.price, token.value, token.unrealized_pnl), (None, None, None));
+    assert_eq!(token.average_cost, PRICE_SCALE * 3 / 4);
+
+    assert_eq!(report.cost_basis, 900);
+    assert_eq!((report.value, report.unrealized_pnl), (None, None));
+}
+
+#[test]
+fn largest_pnl_report_fits_in_the_return_data() {
+    let portfolio = common::portfolio_with(
+        (0..MAX_ALLOCATIONS as u8)
+            .map(|i| AllocationData {
+                symbol: "X".repeat(32),
+                cost_basis: u64::MAX,
+                realized_pnl: i64::MIN,
+                ..allocation(i, u64::MAX, 1000)
+            })
+            .collect(),
+    );
+    let prices: Vec<AssetPrice> = (0..MAX_ALLOCATIONS as u8)
+        .map(|i| AssetPrice { mint: mint(i), price: u64::MAX })
+        .collect();
+    let report = portfolio.pnl_report(&prices);
+
+    let mut encoded = Vec::new();
+    report.serialize(&mut encoded).unwrap();
+    assert_eq!(encoded.len(), PnlReport::MAX_SIZE);
+    assert!(encoded.len() <= MAX_RETURN_DATA);
+}
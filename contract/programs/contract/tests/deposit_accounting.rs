@@ -0,0 +1,102 @@
+//! Tests for deposit and withdrawal bookkeeping on the portfolio
+
+mod common;
+
+use common::{allocation, base_mint, mint};
+use sloomo_portfolio::{AllocationData, Portfolio};
+
+/// One allocation on mint 0 beside the base asset
+fn portfolio() -> Portfolio {
+    Portfolio { total_value: 1_000, ..common::portfolio_with(vec![allocation(0, 400, 4000)]) }
+}
+
+#[test]
+fn only_base_and_allocated_mints_are_held() {
+    let portfolio = portfolio();
+    assert!(portfolio.holds_mint(&base_mint()));
+    assert!(portfolio.holds_mint(&mint(0)));
+    assert!(!portfolio.holds_mint(&mint(1)));
+}
+
+#[test]
+fn deposit_credits_the_allocation_and_total() {
+    let mut portfolio = portfolio();
+    portfolio.record_deposit(&mint(0), 100, 100).unwrap();
+    assert_eq!(portfolio.allocations[0].current_amount, 500);
+    assert_eq!(portfolio.total_value, 1_100);
+
+    // Base asset without an allocation only raises the total
+    portfolio.record_deposit(&base_mint(), 50, 50).unwrap();
+    assert_eq!(portfolio.allocations[0].current_amount, 500);
+    assert_eq!(portfolio.total_value, 1_150);
+
+    assert!(portfolio.record_deposit(&mint(0), u64::MAX, 0).is_err());
+    assert!(portfolio.record_deposit(&mint(1), 0, u64::MAX).is_err());
+}
+
+#[test]
+fn deposit_value_may_differ_from_the_token_amount() {
+    // 2 SOL (9 decimals) credited to a USDC-denominated (6 decimals) total
+    let mut portfolio = portfolio();
+    portfolio.record_deposit(&mint(0), 2_000_000_000, 2_000_000).unwrap();
+    assert_eq!(portfolio.allocations[0].current_amount, 2_000_000_400);
+    assert_eq!(portfolio.total_value, 2_001_000);
+}
+
+#[test]
+fn withdrawal_debits_and_floors_at_zero() {
+    let mut portfolio = portfolio();
+    portfolio.record_withdrawal(&mint(0), 150, 150);
+    assert_eq!(portfolio.allocations[0].current_amount, 250);
+    assert_eq!(portfolio.total_value, 850);
+
+    // Stored amounts may lag the vault; never wrap below zero
+    portfolio.record_withdrawal(&mint(0), 10_000, 10_000);
+    assert_eq!(portfolio.allocations[0].current_amount, 0);
+    assert_eq!(portfolio.total_value, 0);
+}
+
+#[test]
+fn withdrawal_debits_its_value_from_the_total() {
+    // 0.5 SOL (9 decimals) out of a USDC-denominated (6 decimals) total
+    let mut portfolio = portfolio();
+    portfolio.record_deposit(&mint(0), 2_000_000_000, 2_000_000).unwrap();
+    portfolio.record_withdrawal(&mint(0), 500_000_000, 500_000);
+    assert_eq!(portfolio.allocations[0].current_amount, 1_500_000_400);
+    assert_eq!(portfolio.total_value, 1_501_000);
+}
+
+#[test]
+fn current_targets_are_the_allocation_targets() {
+    let portfolio = portfolio();
+    let targets = portfolio.current_targets();
+    assert_eq!(targets.len(), 1);
+    assert_eq!(targets[0].mint, mint(0));
+    assert_eq!(targets[0].target_percentage, 4000);
+    assert!(!portfolio.route_deposits);
+}
+
+#[test]
+fn held_mints_start_with_the_base_asset() {
+    let mut portfolio = portfolio();
+    assert_eq!(portfolio.held_mints(), vec![base_mint(), mint(0)]);
+
+    // An allocated base asset is listed once
+    portfolio
+        .allocations
+        .push(AllocationData { mint: base_mint(), ..allocation(1, 600, 6000) });
+    assert_eq!(portfolio.held_mints(), vec![base_mint(), mint(0)]);
+}
+
+#[test]
+fn proportional_withdrawal_debits_every_allocation() {
+    let mut portfolio = portfolio();
+    portfolio.allocations.push(allocation(1, 600, 6000));
+
+    // 25% of each vault
+    portfolio.record_withdrawal(&mint(0), 100, 100);
+    portfolio.record_withdrawal(&mint(1), 150, 150);
+    assert_eq!(portfolio.allocations[0].current_amount, 300);
+    assert_eq!(portfolio.allocations[1].current_amount, 450);
+    assert_eq!(portfolio.total_value, 750);
+}
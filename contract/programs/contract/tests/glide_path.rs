@@ -0,0 +1,109 @@
+//! Unit tests for glide-path allocations
+
+mod common;
+
+use common::{allocation, band, mint, targets};
+use sloomo_portfolio::{AssetClass, ClassMembership, Portfolio, SloomoError};
+
+const NOW: i64 = 1_000;
+const TARGET_DATE: i64 = 11_000;
+
+fn stored_targets(portfolio: &Portfolio) -> Vec<u16> {
+    portfolio.allocations.iter().map(|a| a.target_percentage).collect()
+}
+
+/// Equity token and stablecoin at 50/50
+fn portfolio() -> Portfolio {
+    common::portfolio_with(vec![allocation(0, 0, 5000), allocation(1, 0, 5000)])
+}
+
+/// 80% equities / 20% stablecoins gliding to 30% / 70%, stepping every 5%
+fn gliding() -> Portfolio {
+    let mut portfolio = portfolio();
+    portfolio
+        .set_glide_path(&targets(&[8000, 2000]), &targets(&[3000, 7000]), TARGET_DATE, 500, NOW)
+        .unwrap();
+    portfolio
+}
+
+#[test]
+fn targets_are_interpolated_from_the_clock() {
+    let portfolio = gliding();
+    assert_eq!(stored_targets(&portfolio), vec![8000, 2000]);
+
+    // Requested targets of path mints are replaced; path mints not requested are added
+    let midway = portfolio.glided_targets(&targets(&[6000]), 6_000);
+    assert_eq!(midway, targets(&[5500, 4500]));
+    let after = portfolio.glided_targets(&[], TARGET_DATE + 1);
+    assert_eq!(after, targets(&[3000, 7000]));
+}
+
+#[test]
+fn stored_targets_move_in_steps() {
+    let mut portfolio = gliding();
+
+    // A tenth of the way is exactly one step: not more than it
+    assert_eq!(portfolio.advance_glide_path(2_000), None);
+    assert_eq!(stored_targets(&portfolio), vec![8000, 2000]);
+
+    assert_eq!(portfolio.advance_glide_path(3_000), Some(targets(&[7000, 3000])));
+    assert_eq!(stored_targets(&portfolio), vec![7000, 3000]);
+    assert_eq!(portfolio.advance_glide_path(3_500), None);
+
+    // The end allocation is always reached, even by a partial step
+    let mut portfolio = gliding();
+    portfolio.advance_glide_path(10_500).unwrap();
+    assert_eq!(stored_targets(&portfolio), vec![3250, 6750]);
+    assert_eq!(portfolio.advance_glide_path(TARGET_DATE), Some(targets(&[3000, 7000])));
+    assert_eq!(portfolio.advance_glide_path(TARGET_DATE + 100), None);
+}
+
+#[test]
+fn glide_paths_are_validated() {
+    let mut portfolio = portfolio();
+    let set = |portfolio: &mut Portfolio, start: &[u16], end: &[u16], target_date| {
+        portfolio.set_glide_path(&targets(start), &targets(end), target_date, 500, NOW)
+    };
+
+    let err = set(&mut portfolio, &[8000, 2000], &[3000, 7000], NOW).unwrap_err();
+    assert_eq!(err, SloomoError::InvalidGlidePath.into());
+    let err = set(&mut portfolio, &[8000, 2000], &[10000], TARGET_DATE).unwrap_err();
+    assert_eq!(err, SloomoError::InvalidGlidePath.into());
+
+    // The allocation off the path keeps its 50%
+    let err = set(&mut portfolio, &[8000], &[3000], TARGET_DATE).unwrap_err();
+    assert_eq!(err, SloomoError::AllocationOverflow.into());
+
+    portfolio.allocations[0].band = band(4000, 9000);
+    let err = set(&mut portfolio, &[8000, 2000], &[3000, 7000], TARGET_DATE).unwrap_err();
+    assert_eq!(err, SloomoError::InvalidAllocationBand.into());
+    assert!(portfolio.glide_path.is_none());
+    assert_eq!(stored_targets(&portfolio), vec![5000, 5000]);
+
+    set(&mut portfolio, &[8000, 2000], &[4000, 6000], TARGET_DATE).unwrap();
+    assert!(portfolio.on_glide_path(&mint(1)));
+
+    // Empty endpoints remove the path and keep the targets
+    set(&mut portfolio, &[], &[], 0).unwrap();
+    assert!(portfolio.glide_path.is_none());
+    assert_eq!(stored_targets(&portfolio), vec![8000, 2000]);
+}
+
+#[test]
+fn glide_paths_and_asset_classes_do_not_overlap() {
+    let mut gliding = gliding();
+    let classes = vec![AssetClass { name: "ALL".to_string(), target_percentage: 10000, band: None }];
+    let memberships = vec![
+        ClassMembership { mint: mint(0), asset_class: 0, weight: 5000 },
+        ClassMembership { mint: mint(1), asset_class: 0, weight: 5000 },
+    ];
+    let err = gliding.set_asset_classes(classes.clone(), &memberships).unwrap_err();
+    assert_eq!(err, SloomoError::AllocationOnGlidePath.into());
+
+    let mut portfolio = portfolio();
+    portfolio.set_asset_classes(classes, &memberships).unwrap();
+    let err = portfolio
+        .set_glide_path(&targets(&[8000, 2000]), &targets(&[3000, 7000]), TARGET_DATE, 500, NOW)
+        .unwrap_err();
+    assert_eq!(err, SloomoError::AllocationInAssetClass.into());
+}
@@ -0,0 +1,201 @@
+//! Tests for guardian recovery: threshold, timelock and owner veto
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::key;
+use sloomo_portfolio::{GuardianSet, SloomoError, MAX_GUARDIANS, MIN_RECOVERY_TIMELOCK};
+
+const TIMELOCK: i64 = MIN_RECOVERY_TIMELOCK;
+
+fn owner() -> Pubkey {
+    key(0)
+}
+
+/// Empty guardian set, as created by `set_guardians`
+fn empty() -> GuardianSet {
+    GuardianSet {
+        portfolio: key(9),
+        bump: 255,
+        guardians: Vec::new(),
+        threshold: 0,
+        timelock: 0,
+        recovery: None,
+    }
+}
+
+/// Guardians 1, 2 and 3 with a threshold of 2
+fn two_of_three() -> GuardianSet {
+    let mut set = empty();
+    set.configure(&owner(), vec![key(1), key(2), key(3)], 2, TIMELOCK).unwrap();
+    set
+}
+
+#[test]
+fn recovery_completes_after_threshold_and_timelock() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+    assert_eq!(set.approval_count(), 1);
+    assert_eq!(set.unlocks_at(), None);
+
+    set.approve_recovery(&key(3), 200).unwrap();
+    assert_eq!(set.approval_count(), 2);
+    assert_eq!(set.unlocks_at(), Some(200 + TIMELOCK));
+
+    assert_eq!(set.complete_recovery(200 + TIMELOCK).unwrap(), key(7));
+    assert_eq!(set.recovery, None);
+}
+
+#[test]
+fn recovery_below_threshold_cannot_complete() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+
+    assert_eq!(
+        set.complete_recovery(100 + 10 * TIMELOCK).unwrap_err(),
+        SloomoError::RecoveryNotApproved.into()
+    );
+}
+
+#[test]
+fn recovery_cannot_complete_during_the_timelock() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+    set.approve_recovery(&key(2), 100).unwrap();
+
+    assert_eq!(
+        set.complete_recovery(100 + TIMELOCK - 1).unwrap_err(),
+        SloomoError::RecoveryTimelockActive.into()
+    );
+    assert!(set.recovery.is_some());
+}
+
+#[test]
+fn later_approvals_do_not_restart_the_timelock() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+    set.approve_recovery(&key(2), 200).unwrap();
+    set.approve_recovery(&key(3), 5_000).unwrap();
+
+    assert_eq!(set.approval_count(), 3);
+    assert_eq!(set.unlocks_at(), Some(200 + TIMELOCK));
+}
+
+#[test]
+fn vetoed_recovery_cannot_complete() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+    set.approve_recovery(&key(2), 100).unwrap();
+
+    let vetoed = set.veto_recovery().unwrap();
+    assert_eq!(vetoed.new_owner, key(7));
+    assert_eq!(
+        set.complete_recovery(100 + TIMELOCK).unwrap_err(),
+        SloomoError::NoPendingRecovery.into()
+    );
+    assert_eq!(
+        set.veto_recovery().unwrap_err(),
+        SloomoError::NoPendingRecovery.into()
+    );
+
+    // Guardians can start over after a veto, from zero approvals
+    set.initiate_recovery(&key(2), key(8), 300).unwrap();
+    assert_eq!(set.approval_count(), 1);
+}
+
+#[test]
+fn guardians_cannot_approve_twice_or_start_a_second_recovery() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+
+    assert_eq!(
+        set.approve_recovery(&key(1), 100).unwrap_err(),
+        SloomoError::RecoveryAlreadyApproved.into()
+    );
+    assert_eq!(
+        set.initiate_recovery(&key(2), key(8), 100).unwrap_err(),
+        SloomoError::RecoveryInProgress.into()
+    );
+    assert_eq!(set.approval_count(), 1);
+}
+
+#[test]
+fn non_guardians_cannot_act() {
+    let mut set = two_of_three();
+    assert_eq!(
+        set.initiate_recovery(&key(4), key(7), 100).unwrap_err(),
+        SloomoError::NotAGuardian.into()
+    );
+
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+    assert_eq!(
+        set.approve_recovery(&owner(), 100).unwrap_err(),
+        SloomoError::NotAGuardian.into()
+    );
+    assert_eq!(
+        set.approve_recovery(&key(4), 100).unwrap_err(),
+        SloomoError::NotAGuardian.into()
+    );
+}
+
+#[test]
+fn approval_without_a_recovery_is_rejected() {
+    let mut set = two_of_three();
+    assert_eq!(
+        set.approve_recovery(&key(1), 100).unwrap_err(),
+        SloomoError::NoPendingRecovery.into()
+    );
+}
+
+#[test]
+fn owner_change_drops_the_recovery_in_progress() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+    set.approve_recovery(&key(2), 100).unwrap();
+
+    assert!(set.cancel_recovery());
+    assert_eq!(
+        set.complete_recovery(100 + TIMELOCK).unwrap_err(),
+        SloomoError::NoPendingRecovery.into()
+    );
+    assert!(!set.cancel_recovery());
+}
+
+#[test]
+fn reconfiguring_drops_the_recovery_in_progress() {
+    let mut set = two_of_three();
+    set.initiate_recovery(&key(1), key(7), 100).unwrap();
+    set.approve_recovery(&key(2), 100).unwrap();
+
+    set.configure(&owner(), vec![key(4)], 1, TIMELOCK).unwrap();
+    assert_eq!(set.recovery, None);
+
+    set.configure(&owner(), Vec::new(), 0, TIMELOCK).unwrap();
+    assert_eq!(
+        set.initiate_recovery(&key(4), key(7), 100).unwrap_err(),
+        SloomoError::NotAGuardian.into()
+    );
+}
+
+#[test]
+fn invalid_configurations_are_rejected() {
+    let too_many: Vec<Pubkey> = (1..=MAX_GUARDIANS as u8 + 1).map(key).collect();
+    let cases: Vec<(Vec<Pubkey>, u8, i64)> = vec![
+        (vec![key(1), key(2)], 0, TIMELOCK),
+        (vec![key(1), key(2)], 3, TIMELOCK),
+        (Vec::new(), 1, TIMELOCK),
+        (vec![key(1)], 1, TIMELOCK - 1),
+        (vec![key(1), key(1)], 1, TIMELOCK),
+        (vec![key(1), owner()], 1, TIMELOCK),
+        (too_many, 1, TIMELOCK),
+    ];
+
+    for (guardians, threshold, timelock) in cases {
+        let mut set = two_of_three();
+        assert_eq!(
+            set.configure(&owner(), guardians, threshold, timelock).unwrap_err(),
+            SloomoError::InvalidGuardianSet.into()
+        );
+        assert_eq!(set.guardians, vec![key(1), key(2), key(3)]);
+    }
+}
@@ -0,0 +1,87 @@
+//! Tests for the two-step owner transfer
+
+mod common;
+
+use common::key;
+use sloomo_portfolio::{Portfolio, SloomoError};
+
+/// Portfolio created by key 0
+fn portfolio() -> Portfolio {
+    Portfolio { owner: key(0), portfolio_id: key(0), ..common::portfolio() }
+}
+
+#[test]
+fn accepted_transfer_moves_ownership_but_not_the_pda_seed() {
+    let mut portfolio = portfolio();
+    portfolio.propose_owner_transfer(key(1)).unwrap();
+    assert_eq!(portfolio.owner, key(0));
+    assert_eq!(portfolio.pending_owner, Some(key(1)));
+
+    let previous_owner = portfolio.accept_owner_transfer(&key(1)).unwrap();
+    assert_eq!(previous_owner, key(0));
+    assert_eq!(portfolio.owner, key(1));
+    assert_eq!(portfolio.pending_owner, None);
+    assert_eq!(portfolio.portfolio_id, key(0));
+}
+
+#[test]
+fn only_the_pending_owner_can_accept() {
+    let mut portfolio = portfolio();
+    assert_eq!(
+        portfolio.accept_owner_transfer(&key(1)).unwrap_err(),
+        SloomoError::NoPendingOwnerTransfer.into()
+    );
+
+    portfolio.propose_owner_transfer(key(1)).unwrap();
+    assert_eq!(
+        portfolio.accept_owner_transfer(&key(2)).unwrap_err(),
+        SloomoError::NoPendingOwnerTransfer.into()
+    );
+    assert_eq!(portfolio.owner, key(0));
+}
+
+#[test]
+fn new_proposal_replaces_the_pending_one() {
+    let mut portfolio = portfolio();
+    portfolio.propose_owner_transfer(key(1)).unwrap();
+    portfolio.propose_owner_transfer(key(2)).unwrap();
+
+    assert!(portfolio.accept_owner_transfer(&key(1)).is_err());
+    portfolio.accept_owner_transfer(&key(2)).unwrap();
+    assert_eq!(portfolio.owner, key(2));
+}
+
+#[test]
+fn transfer_to_the_current_owner_is_rejected() {
+    let mut portfolio = portfolio();
+    assert_eq!(
+        portfolio.propose_owner_transfer(key(0)).unwrap_err(),
+        SloomoError::InvalidNewOwner.into()
+    );
+    assert_eq!(portfolio.pending_owner, None);
+}
+
+#[test]
+fn cancelled_transfer_cannot_be_accepted() {
+    let mut portfolio = portfolio();
+    assert_eq!(
+        portfolio.cancel_owner_transfer().unwrap_err(),
+        SloomoError::NoPendingOwnerTransfer.into()
+    );
+
+    portfolio.propose_owner_transfer(key(1)).unwrap();
+    assert_eq!(portfolio.cancel_owner_transfer().unwrap(), key(1));
+    assert!(portfolio.accept_owner_transfer(&key(1)).is_err());
+    assert_eq!(portfolio.owner, key(0));
+}
+
+#[test]
+fn reassigned_owner_clears_the_pending_transfer() {
+    let mut portfolio = portfolio();
+    portfolio.propose_owner_transfer(key(1)).unwrap();
+
+    assert_eq!(portfolio.reassign_owner(key(2)), key(0));
+    assert_eq!(portfolio.owner, key(2));
+    assert_eq!(portfolio.pending_owner, None);
+    assert!(portfolio.accept_owner_transfer(&key(1)).is_err());
+}
@@ -0,0 +1,101 @@
+//! Tests for migrating legacy portfolio accounts
+
+mod common;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorSerialize, Discriminator};
+use common::{base_mint, key, mint};
+use sloomo_portfolio::utils::JUPITER_PROGRAM_ID;
+use sloomo_portfolio::{
+    BandRestore, LegacyAllocationData, LegacyPortfolio, PerformanceSnapshot, Portfolio,
+    PORTFOLIO_VERSION,
+};
+
+// A single instruction may grow an account by at most 10 KiB
+const _: () = assert!(Portfolio::SIZE - LegacyPortfolio::SIZE <= 10 * 1024);
+
+fn legacy() -> LegacyPortfolio {
+    LegacyPortfolio {
+        owner: key(7),
+        bump: 254,
+        total_value: 3_000_000,
+        last_rebalance: 100,
+        allocations: vec![LegacyAllocationData {
+            mint: mint(0),
+            symbol: "SOL".to_string(),
+            current_amount: 3_000_000,
+            target_percentage: 10000,
+            apy: 500,
+            last_yield_update: 50,
+        }],
+        performance_history: vec![PerformanceSnapshot {
+            timestamp: 90,
+            total_value: 2_900_000,
+            growth_rate: 0,
+        }],
+        created_at: 10,
+        updated_at: 100,
+        is_rebalancing: false,
+    }
+}
+
+/// Account data as a legacy portfolio was stored (zero padded to its size)
+fn legacy_account_data(portfolio: &LegacyPortfolio) -> Vec<u8> {
+    let mut data = Portfolio::DISCRIMINATOR.to_vec();
+    portfolio.serialize(&mut data).unwrap();
+    data.resize(LegacyPortfolio::SIZE, 0);
+    data
+}
+
+#[test]
+fn legacy_accounts_migrate_with_their_data_and_current_defaults() {
+    let data = legacy_account_data(&legacy());
+    let portfolio = LegacyPortfolio::try_from_account_data(&data)
+        .unwrap()
+        .migrate(base_mint(), JUPITER_PROGRAM_ID);
+
+    assert_eq!(portfolio.owner, key(7));
+    assert_eq!(portfolio.bump, 254);
+    assert_eq!(portfolio.total_value, 3_000_000);
+    assert_eq!(portfolio.created_at, 10);
+    assert_eq!(portfolio.performance_history.len(), 1);
+    assert_eq!(portfolio.allocations[0].symbol, "SOL");
+    assert_eq!(portfolio.allocations[0].apy, 500);
+    assert_eq!(portfolio.allocations[0].cost_basis, 3_000_000);
+    assert_eq!(portfolio.allocations[0].band, None);
+
+    // The legacy seed stays the PDA seed
+    assert_eq!(portfolio.portfolio_id, key(7));
+    assert_eq!(portfolio.base_mint, base_mint());
+    assert_eq!(portfolio.band_restore, BandRestore::Edge);
+    assert_eq!(portfolio.swap_programs, vec![JUPITER_PROGRAM_ID]);
+    assert_eq!(portfolio.pending_owner, None);
+    assert_eq!(portfolio.version, PORTFOLIO_VERSION);
+}
+
+#[test]
+fn migrated_accounts_fit_the_current_size_and_read_back() {
+    let portfolio = LegacyPortfolio::try_from_account_data(&legacy_account_data(&legacy()))
+        .unwrap()
+        .migrate(base_mint(), JUPITER_PROGRAM_ID);
+
+    let mut data = vec![0u8; Portfolio::SIZE];
+    portfolio.try_serialize(&mut &mut data[..]).unwrap();
+    let read = Portfolio::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(read.portfolio_id, key(7));
+    assert_eq!(read.version, PORTFOLIO_VERSION);
+}
+
+#[test]
+fn only_the_legacy_layout_is_migrated() {
+    let data = legacy_account_data(&legacy());
+
+    // Current-size accounts were initialized or migrated already
+    let mut current = data.clone();
+    current.resize(Portfolio::SIZE, 0);
+    assert!(LegacyPortfolio::try_from_account_data(&current).is_err());
+
+    // Other accounts of the program have other discriminators
+    let mut other = data;
+    other[0] ^= 0xff;
+    assert!(LegacyPortfolio::try_from_account_data(&other).is_err());
+}
@@ -0,0 +1,270 @@
+//! Property-based tests for the rebalance and performance math
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{allocation, base_mint, mint};
+use proptest::prelude::*;
+use sloomo_portfolio::utils::{
+    amount_to_bps, bps_of, holdings_from_allocations, plan_liquidation, validate_init_params,
+    validate_target_allocations, JupiterSolSwapHelper, PlannerConfig, SwapOperationType,
+};
+use sloomo_portfolio::{
+    AllocationParams, AllocationTarget, InitPortfolioParams, LiquidationOrder, Portfolio, SloomoError,
+    MAX_ALLOCATIONS, MAX_PERFORMANCE_SNAPSHOTS,
+};
+
+fn portfolio_with(amounts: &[u64], targets: &[u16]) -> Portfolio {
+    common::portfolio_with(
+        amounts
+            .iter()
+            .zip(targets)
+            .enumerate()
+            .map(|(i, (amount, target))| allocation(i as u8, *amount, *target))
+            .collect(),
+    )
+}
+
+/// Target percentages that sum to exactly 10000 bps
+fn full_targets(len: usize) -> impl Strategy<Value = Vec<u16>> {
+    prop::collection::vec(1u32..1000, len).prop_map(|weights| {
+        let total: u32 = weights.iter().sum();
+        let mut percentages: Vec<u16> = weights
+            .iter()
+            .map(|w| (w * 10000 / total) as u16)
+            .collect();
+        let assigned: u16 = percentages.iter().sum();
+        percentages[0] += 10000 - assigned;
+        percentages
+    })
+}
+
+/// Holdings and matching full targets for 1..=MAX_ALLOCATIONS mints
+fn holdings_and_targets() -> impl Strategy<Value = (Vec<u64>, Vec<u16>)> {
+    (1..=MAX_ALLOCATIONS).prop_flat_map(|len| {
+        (
+            prop::collection::vec(0u64..=u64::MAX / MAX_ALLOCATIONS as u64, len),
+            full_targets(len),
+        )
+    })
+}
+
+proptest! {
+    #[test]
+    fn swap_operations_never_panic(
+        amounts in prop::collection::vec(any::<u64>(), 0..=MAX_ALLOCATIONS),
+        targets in prop::collection::vec(any::<u16>(), 0..=MAX_ALLOCATIONS),
+        total_value in any::<u64>(),
+    ) {
+        let portfolio = portfolio_with(&amounts, &targets);
+        let _ = JupiterSolSwapHelper::calculate_swap_operations(
+            &portfolio.allocations,
+            &common::targets(&targets),
+            total_value,
+            base_mint(),
+        );
+    }
+
+    #[test]
+    fn buys_and_sells_settle((amounts, targets) in holdings_and_targets()) {
+        let portfolio = portfolio_with(&amounts, &targets);
+        let total_value = portfolio.calculate_total_value().unwrap();
+        let target_allocations = common::targets(&targets);
+        let operations = JupiterSolSwapHelper::calculate_swap_operations(
+            &portfolio.allocations,
+            &target_allocations,
+            total_value,
+            base_mint(),
+        )
+        .unwrap();
+
+        // Settle every operation against the holdings through the wSOL leg
+        let mut holdings = amounts.clone();
+        let mut sells: u128 = 0;
+        let mut buys: u128 = 0;
+        for operation in &operations {
+            match operation.operation_type {
+                SwapOperationType::Sell => {
+                    let i = target_allocations.iter().position(|t| t.mint == operation.from_mint).unwrap();
+                    holdings[i] -= operation.amount;
+                    sells += operation.amount as u128;
+                }
+                SwapOperationType::Buy => {
+                    let i = target_allocations.iter().position(|t| t.mint == operation.to_mint).unwrap();
+                    holdings[i] += operation.amount;
+                    buys += operation.amount as u128;
+                }
+            }
+        }
+
+        // Every holding lands exactly on its target amount
+        let mut target_total: u128 = 0;
+        for (holding, target) in holdings.iter().zip(&targets) {
+            let target_amount = bps_of(total_value, *target).unwrap();
+            prop_assert_eq!(*holding, target_amount);
+            target_total += target_amount as u128;
+        }
+
+        // Sells fund buys; only rounding dust (< 1 unit per allocation) stays in wSOL
+        prop_assert!(sells >= buys);
+        prop_assert_eq!(sells - buys, total_value as u128 - target_total);
+        prop_assert!(sells - buys < targets.len() as u128);
+    }
+
+    #[test]
+    fn balanced_portfolio_needs_no_rebalance((amounts, targets) in holdings_and_targets()) {
+        let portfolio = portfolio_with(&amounts, &targets);
+        let total_value = portfolio.calculate_total_value().unwrap();
+        let target_allocations = common::targets(&targets);
+        let operations = JupiterSolSwapHelper::calculate_swap_operations(
+            &portfolio.allocations,
+            &target_allocations,
+            total_value,
+            base_mint(),
+        )
+        .unwrap();
+
+        let settled: Vec<u64> = targets.iter().map(|t| bps_of(total_value, *t).unwrap()).collect();
+        let settled_portfolio = portfolio_with(&settled, &targets);
+        prop_assert!(!settled_portfolio.needs_rebalancing(&target_allocations, total_value).unwrap());
+
+        // Anything flagged as drifting must produce at least one operation
+        if portfolio.needs_rebalancing(&target_allocations, total_value).unwrap() {
+            prop_assert!(!operations.is_empty());
+        }
+    }
+
+    #[test]
+    fn needs_rebalancing_never_panics(
+        amounts in prop::collection::vec(any::<u64>(), 0..=MAX_ALLOCATIONS),
+        targets in prop::collection::vec(any::<u16>(), 0..=MAX_ALLOCATIONS),
+        total_value in any::<u64>(),
+    ) {
+        let portfolio = portfolio_with(&amounts, &targets);
+        let _ = portfolio.needs_rebalancing(&common::targets(&targets), total_value);
+    }
+
+    #[test]
+    fn percentages_are_bounded(total_value in 1u64.., share in 0u64..=10000) {
+        let amount = (total_value as u128 * share as u128 / 10000) as u64;
+        let bps = amount_to_bps(amount, total_value);
+        prop_assert!(bps <= 10000);
+        prop_assert!(bps <= share);
+    }
+
+    #[test]
+    fn growth_rate_is_clamped(values in prop::collection::vec(any::<u64>(), 1..(2 * MAX_PERFORMANCE_SNAPSHOTS))) {
+        let mut portfolio = portfolio_with(&[], &[]);
+        for (timestamp, value) in values.iter().enumerate() {
+            portfolio.total_value = *value;
+            portfolio.add_performance_snapshot(timestamp as i64).unwrap();
+        }
+
+        prop_assert!(portfolio.performance_history.len() <= MAX_PERFORMANCE_SNAPSHOTS);
+        for window in portfolio.performance_history.windows(2) {
+            let growth = window[1].growth_rate;
+            prop_assert!((-10000..=10000).contains(&growth));
+            if window[1].total_value > window[0].total_value {
+                prop_assert!(growth >= 0);
+            } else {
+                prop_assert!(growth <= 0);
+            }
+        }
+    }
+
+    #[test]
+    fn init_params_validation_matches_limits(
+        targets in prop::collection::vec(any::<u16>(), 0..(MAX_ALLOCATIONS + 3)),
+        symbol_len in 0usize..40,
+        initial_sol_amount in any::<u64>(),
+    ) {
+        let params = InitPortfolioParams {
+            initial_allocations: targets
+                .iter()
+                .enumerate()
+                .map(|(i, target_percentage)| AllocationParams {
+                    mint: mint(i as u8),
+                    symbol: "S".repeat(symbol_len),
+                    target_percentage: *target_percentage,
+                    band: None,
+                })
+                .collect(),
+            initial_sol_amount,
+            enable_jupiter_swap: false,
+        };
+
+        let total: u32 = targets.iter().map(|t| *t as u32).sum();
+        let expected_valid = targets.len() <= MAX_ALLOCATIONS
+            && (targets.is_empty() || (1..=32).contains(&symbol_len))
+            && total <= 10000
+            && initial_sol_amount > 0;
+        prop_assert_eq!(validate_init_params(&params).is_ok(), expected_valid);
+        prop_assert_eq!(
+            validate_target_allocations(&common::targets(&targets)).is_ok(),
+            targets.len() <= MAX_ALLOCATIONS && total <= 10000
+        );
+    }
+}
+
+#[test]
+fn oversized_allocation_is_detected_as_drift() {
+    // 7x the total value used to wrap to 4464 bps when cast to u16
+    let portfolio = portfolio_with(&[700, 0], &[4500, 5500]);
+    let targets = common::targets(&[4500, 5500]);
+    assert!(portfolio.needs_rebalancing(&targets[..1], 100).unwrap());
+}
+
+#[test]
+fn large_loss_does_not_wrap_growth_rate() {
+    let mut portfolio = portfolio_with(&[], &[]);
+    portfolio.total_value = 1;
+    portfolio.add_performance_snapshot(0).unwrap();
+    // +655_000% used to wrap to a negative i16
+    portfolio.total_value = 6_551;
+    portfolio.add_performance_snapshot(1).unwrap();
+    assert_eq!(portfolio.performance_history[1].growth_rate, 10000);
+}
+
+#[test]
+fn duplicate_mints_are_rejected() {
+    let targets = vec![
+        AllocationTarget { mint: mint(0), target_percentage: 3000 },
+        AllocationTarget { mint: mint(0), target_percentage: 3000 },
+    ];
+    assert!(validate_target_allocations(&targets).is_err());
+}
+
+#[test]
+fn overflowing_target_sum_is_rejected() {
+    // 4 * 20000 overflows u16; the sum used to panic under overflow checks
+    assert!(validate_target_allocations(&common::targets(&[20000, 20000, 20000, 20000])).is_err());
+}
+
+#[test]
+fn liquidation_sells_allocations_into_the_base_asset() {
+    let portfolio = portfolio_with(&[700, 300], &[5000, 5000]);
+    let holdings = holdings_from_allocations(&portfolio.allocations, &portfolio.current_targets());
+    let config = PlannerConfig { fee_bps: 0, slippage_bps: 0, ..PlannerConfig::default() };
+
+    let legs = plan_liquidation(
+        &holdings,
+        base_mint(),
+        200,
+        LiquidationOrder::OverweightFirst.to_core(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(legs.len(), 1);
+    assert_eq!(Pubkey::new_from_array(legs[0].from_mint), mint(0));
+    assert_eq!(Pubkey::new_from_array(legs[0].to_mint), base_mint());
+
+    let err = plan_liquidation(
+        &holdings,
+        base_mint(),
+        1_001,
+        LiquidationOrder::Proportional.to_core(),
+        &config,
+    )
+    .unwrap_err();
+    assert_eq!(err, SloomoError::InsufficientLiquidity.into());
+}
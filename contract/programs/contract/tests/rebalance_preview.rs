@@ -0,0 +1,205 @@
+//! Tests for the rebalance dry run behind `preview_rebalance`
+
+mod common;
+
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use anchor_lang::AnchorSerialize;
+use common::{allocation, base_mint, mint, targets};
+use sloomo_portfolio::utils::DEFAULT_MAX_LEGS;
+use sloomo_portfolio::{
+    dry_run_rebalance, AllocationTarget, Portfolio, PreviewLeg, RebalancePreview, SloomoError,
+    VaultBalance, MAX_ALLOCATIONS,
+};
+
+const DAY: i64 = 86400;
+
+/// Vault snapshot with an empty base vault
+fn snapshot(amounts: &[u64]) -> Vec<VaultBalance> {
+    common::snapshot(0, amounts)
+}
+
+/// Two allocations at 50/50 whose stored amounts are stale
+fn portfolio() -> Portfolio {
+    common::portfolio_with(vec![allocation(0, 500_000, 5000), allocation(1, 500_000, 5000)])
+}
+
+#[test]
+fn drift_and_legs_come_from_the_vaults() {
+    let portfolio = portfolio();
+    let preview =
+        dry_run_rebalance(&portfolio, &snapshot(&[800_000, 200_000]), &targets(&[5000, 5000]), None, DAY)
+            .unwrap();
+
+    assert_eq!(preview.total_value, 1_000_000);
+    assert!(preview.needs_rebalancing);
+    assert_eq!(preview.max_drift_bps, 3000);
+
+    assert_eq!(preview.legs.len(), 1);
+    let leg = &preview.legs[0];
+    // Traded back to the edge of the 5% threshold (`BandRestore::Edge`)
+    assert_eq!((leg.from_mint, leg.to_mint, leg.amount), (mint(0), mint(1), 250_000));
+    assert_eq!(preview.max_residual_drift_bps, 500);
+    assert!(preview.within_band);
+
+    // The stored portfolio is untouched
+    assert_eq!(portfolio.allocations[0].current_amount, 500_000);
+}
+
+#[test]
+fn total_value_rescales_each_vault_to_base_decimals() {
+    // Mint 1 has 9 decimals: 200_000_000 of it is 200_000 in base units
+    let mut snapshot = snapshot(&[800_000, 200_000_000]);
+    snapshot[2].decimals = 9;
+    let preview =
+        dry_run_rebalance(&portfolio(), &snapshot, &targets(&[5000, 5000]), None, DAY).unwrap();
+
+    assert_eq!(preview.total_value, 1_000_000);
+    assert_eq!(preview.max_drift_bps, 3000);
+
+    // Mint 0 is overweight; the leg moves 250_000 base units into mint 1
+    assert_eq!(preview.legs.len(), 1);
+    let leg = &preview.legs[0];
+    assert_eq!((leg.from_mint, leg.to_mint, leg.amount), (mint(0), mint(1), 250_000));
+    assert!(preview.within_band);
+}
+
+#[test]
+fn routed_deposits_buy_underweight_allocations_without_selling() {
+    // 200_000 of new base asset while mint 1 is 20% under target
+    let snapshot = common::snapshot(200_000, &[500_000, 300_000]);
+    let routed = Portfolio { route_deposits: true, ..portfolio() };
+    let preview = dry_run_rebalance(&routed, &snapshot, &targets(&[5000, 5000]), None, DAY).unwrap();
+    let legs: Vec<_> = preview.legs.iter().map(|l| (l.from_mint, l.to_mint, l.amount)).collect();
+    assert_eq!(legs, vec![(base_mint(), mint(1), 200_000)]);
+
+    // Without routing the base asset only restores the band edge
+    let preview = dry_run_rebalance(&portfolio(), &snapshot, &targets(&[5000, 5000]), None, DAY).unwrap();
+    assert_eq!(preview.legs[0].amount, 150_000);
+}
+
+#[test]
+fn routed_deposits_fall_back_to_selling_when_buys_cannot_restore_the_bands() {
+    let routed = Portfolio { route_deposits: true, ..portfolio() };
+    let preview = dry_run_rebalance(
+        &routed,
+        &common::snapshot(100_000, &[700_000, 200_000]),
+        &targets(&[5000, 5000]),
+        None,
+        DAY,
+    )
+    .unwrap();
+    assert!(preview.legs.iter().any(|leg| leg.from_mint == mint(0)));
+    assert!(preview.within_band);
+}
+
+#[test]
+fn cost_is_split_into_fees_and_slippage() {
+    let preview = dry_run_rebalance(
+        &portfolio(),
+        &snapshot(&[800_000, 200_000]),
+        &targets(&[5000, 5000]),
+        Some(100),
+        DAY,
+    )
+    .unwrap();
+
+    // 0.25% fee + 1% slippage on 250_000
+    assert_eq!(preview.legs[0].estimated_cost, 3_125);
+    assert_eq!(preview.legs[0].estimated_fee, 625);
+    assert_eq!(preview.estimated_cost, 3_125);
+    assert_eq!(preview.estimated_fees, 625);
+    assert_eq!(preview.estimated_slippage, 2_500);
+}
+
+#[test]
+fn balanced_vaults_plan_nothing() {
+    let preview =
+        dry_run_rebalance(&portfolio(), &snapshot(&[500_000, 500_000]), &targets(&[5000, 5000]), None, DAY)
+            .unwrap();
+
+    assert!(!preview.needs_rebalancing);
+    assert_eq!(preview.max_drift_bps, 0);
+    assert!(preview.legs.is_empty());
+    assert_eq!(preview.estimated_cost, 0);
+}
+
+#[test]
+fn frequency_and_sessions_are_reported_not_enforced() {
+    let mut portfolio = portfolio();
+    portfolio.last_rebalance = DAY;
+    let balances = snapshot(&[800_000, 200_000]);
+
+    let early = dry_run_rebalance(&portfolio, &balances, &targets(&[5000, 5000]), None, DAY + 1).unwrap();
+    assert_eq!(early.next_rebalance_at, 2 * DAY);
+    assert!(!early.can_execute);
+    assert_eq!(early.legs.len(), 1);
+
+    let due = dry_run_rebalance(&portfolio, &balances, &targets(&[5000, 5000]), None, 2 * DAY).unwrap();
+    assert!(due.can_execute);
+
+    portfolio.is_rebalancing = true;
+    let busy = dry_run_rebalance(&portfolio, &balances, &targets(&[5000, 5000]), None, 2 * DAY).unwrap();
+    assert!(!busy.can_execute);
+}
+
+#[test]
+fn invalid_requests_fail_like_rebalance() {
+    let portfolio = portfolio();
+
+    let err = dry_run_rebalance(&portfolio, &snapshot(&[0, 0]), &targets(&[5000, 5000]), None, DAY)
+        .unwrap_err();
+    assert_eq!(err, SloomoError::InsufficientBalance.into());
+
+    assert!(dry_run_rebalance(
+        &portfolio,
+        &snapshot(&[800_000, 200_000]),
+        &targets(&[6000, 5000]),
+        None,
+        DAY
+    )
+    .is_err());
+}
+
+#[test]
+fn largest_preview_fits_in_the_return_data() {
+    let leg = PreviewLeg {
+        from_mint: mint(0),
+        to_mint: base_mint(),
+        amount: u64::MAX,
+        estimated_cost: u64::MAX,
+        estimated_fee: u64::MAX,
+    };
+    let preview = RebalancePreview {
+        total_value: u64::MAX,
+        target_allocations: (0..MAX_ALLOCATIONS as u8)
+            .map(|i| AllocationTarget { mint: mint(i), target_percentage: 1000 })
+            .collect(),
+        needs_rebalancing: true,
+        max_drift_bps: 10000,
+        legs: vec![leg; DEFAULT_MAX_LEGS as usize],
+        estimated_cost: u64::MAX,
+        estimated_fees: u64::MAX,
+        estimated_slippage: u64::MAX,
+        max_residual_drift_bps: 10000,
+        within_band: false,
+        next_rebalance_at: i64::MAX,
+        can_execute: false,
+    };
+
+    let mut encoded = Vec::new();
+    preview.serialize(&mut encoded).unwrap();
+    assert_eq!(encoded.len(), RebalancePreview::MAX_SIZE);
+    assert!(encoded.len() <= MAX_RETURN_DATA);
+}
+
+#[test]
+fn previews_plan_at_most_the_default_legs() {
+    // Five overweight and five underweight allocations
+    let portfolio = common::portfolio_with((0..10).map(|i| allocation(i, 0, 1000)).collect());
+    let amounts = [200_000, 0, 200_000, 0, 200_000, 0, 200_000, 0, 200_000, 0];
+    let preview =
+        dry_run_rebalance(&portfolio, &snapshot(&amounts), &targets(&[1000; 10]), None, DAY).unwrap();
+
+    assert!(preview.needs_rebalancing);
+    assert_eq!(preview.legs.len(), DEFAULT_MAX_LEGS as usize);
+}
@@ -0,0 +1,109 @@
+//! Tests for the per-rebalance receipts written on commit
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use common::{base_mint, leg, mint, snapshot};
+use sloomo_portfolio::{
+    AllocationChange, RebalanceRecord, RebalanceSession, RecordLeg, SessionLeg, MAX_ALLOCATIONS,
+    MAX_SESSION_LEGS,
+};
+
+fn executed(to: Pubkey, spent: u64, received: u64) -> SessionLeg {
+    SessionLeg { executed: true, spent, received, ..leg(base_mint(), to, spent, 0) }
+}
+
+/// Base asset bought into two tokens
+fn session() -> RebalanceSession {
+    common::session(
+        1_000_000,
+        vec![executed(mint(0), 500_000, 497_500), executed(mint(1), 500_000, 501_000)],
+    )
+}
+
+#[test]
+fn legs_record_price_and_slippage() {
+    let legs = RebalanceRecord::legs_from_session(&session());
+    assert_eq!(
+        legs,
+        vec![
+            RecordLeg {
+                from_mint: base_mint(),
+                to_mint: mint(0),
+                amount_in: 500_000,
+                amount_out: 497_500,
+                effective_price: 995_000_000,
+                realized_slippage_bps: 50,
+            },
+            RecordLeg {
+                from_mint: base_mint(),
+                to_mint: mint(1),
+                amount_in: 500_000,
+                amount_out: 501_000,
+                effective_price: 1_002_000_000,
+                realized_slippage_bps: -20,
+            },
+        ]
+    );
+}
+
+#[test]
+fn unexecuted_legs_are_left_out() {
+    let mut session = session();
+    session.legs[1].executed = false;
+    session.legs[1].spent = 0;
+    session.legs[1].received = 0;
+
+    let legs = RebalanceRecord::legs_from_session(&session);
+    assert_eq!(legs.len(), 1);
+    assert_eq!(legs[0].to_mint, mint(0));
+}
+
+#[test]
+fn shares_are_recorded_before_and_after() {
+    let session = session();
+    let after = snapshot(0, &[497_500, 501_000]);
+
+    let changes = RebalanceRecord::allocation_changes(&session, &after, 998_500).unwrap();
+    assert_eq!(
+        changes,
+        vec![
+            AllocationChange { mint: base_mint(), percentage_before: 10000, percentage_after: 0 },
+            AllocationChange { mint: mint(0), percentage_before: 0, percentage_after: 4982 },
+            AllocationChange { mint: mint(1), percentage_before: 0, percentage_after: 5017 },
+        ]
+    );
+}
+
+#[test]
+fn shares_value_each_vault_in_base_decimals() {
+    // Mint 1 has 9 decimals: 500_000_000 of it is 500_000 in base units
+    let session = session();
+    let mut after = snapshot(0, &[500_000, 500_000_000]);
+    after[2].decimals = 9;
+
+    let changes = RebalanceRecord::allocation_changes(&session, &after, 1_000_000).unwrap();
+    let shares: Vec<u16> = changes.iter().map(|c| c.percentage_after).collect();
+    assert_eq!(shares, vec![0, 5000, 5000]);
+}
+
+#[test]
+fn a_full_record_fits_its_account() {
+    let record = RebalanceRecord {
+        portfolio: Pubkey::new_unique(),
+        bump: 255,
+        index: u64::MAX,
+        started_at: 0,
+        committed_at: 0,
+        slippage_bps: 50,
+        total_value_before: 0,
+        total_value_after: 0,
+        legs: vec![RebalanceRecord::legs_from_session(&session())[0].clone(); MAX_SESSION_LEGS],
+        allocations: vec![
+            AllocationChange { mint: base_mint(), percentage_before: 0, percentage_after: 0 };
+            MAX_ALLOCATIONS + 1
+        ],
+    };
+    assert_eq!(8 + record.try_to_vec().unwrap().len(), RebalanceRecord::SIZE);
+}
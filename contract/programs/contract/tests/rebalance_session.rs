@@ -0,0 +1,193 @@
+//! Tests for the multi-transaction rebalance session bookkeeping
+
+mod common;
+
+use anchor_lang::error::Error;
+use common::{allocation, base_mint, leg, mint, snapshot};
+use sloomo_core::planner::{PlannedLeg, RebalancePlan, DEFAULT_MAX_LEGS};
+use sloomo_portfolio::{RebalanceSession, SloomoError, MAX_ALLOCATIONS, MAX_SESSION_LEGS};
+
+fn error(code: SloomoError) -> Error {
+    code.into()
+}
+
+/// Base asset funding two buys
+fn session() -> RebalanceSession {
+    common::session(
+        1_000,
+        vec![leg(base_mint(), mint(0), 500, 495), leg(base_mint(), mint(1), 500, 495)],
+    )
+}
+
+#[test]
+fn plan_legs_carry_a_minimum_output() {
+    let plan = RebalancePlan {
+        legs: vec![PlannedLeg {
+            from_mint: base_mint().to_bytes(),
+            to_mint: mint(0).to_bytes(),
+            amount: 1_000,
+            estimated_cost: 8,
+        }],
+        ..RebalancePlan::default()
+    };
+    assert_eq!(
+        RebalanceSession::legs_from_plan(&plan, &snapshot(0, &[0])).unwrap(),
+        vec![leg(base_mint(), mint(0), 1_000, 992)]
+    );
+
+    let too_many = RebalancePlan {
+        legs: vec![plan.legs[0].clone(); MAX_SESSION_LEGS + 1],
+        ..RebalancePlan::default()
+    };
+    assert!(RebalanceSession::legs_from_plan(&too_many, &snapshot(0, &[0])).is_err());
+}
+
+#[test]
+fn plan_legs_are_in_the_units_of_their_mints() {
+    // Planned in base units (6 decimals); mint 1 has 9 decimals
+    let plan = RebalancePlan {
+        legs: vec![PlannedLeg {
+            from_mint: mint(1).to_bytes(),
+            to_mint: mint(0).to_bytes(),
+            amount: 1_000,
+            estimated_cost: 8,
+        }],
+        ..RebalancePlan::default()
+    };
+    let mut snapshot = snapshot(0, &[0, 0]);
+    snapshot[2].decimals = 9;
+
+    let legs = RebalanceSession::legs_from_plan(&plan, &snapshot).unwrap();
+    assert_eq!(legs[0].amount, 1_000_000);
+    // Mint 0 has 6 decimals: the minimum is what it must receive
+    assert_eq!(legs[0].min_amount_out, 992);
+
+    // The other way, the minimum is in mint 1's 9 decimals
+    let reversed = RebalancePlan {
+        legs: vec![PlannedLeg {
+            from_mint: mint(0).to_bytes(),
+            to_mint: mint(1).to_bytes(),
+            ..plan.legs[0].clone()
+        }],
+        ..RebalancePlan::default()
+    };
+    let legs = RebalanceSession::legs_from_plan(&reversed, &snapshot).unwrap();
+    assert_eq!((legs[0].amount, legs[0].min_amount_out), (1_000, 992_000));
+}
+
+#[test]
+fn session_expires_after_the_deadline() {
+    let session = session();
+    assert!(!session.is_expired(1_900));
+    assert!(session.is_expired(1_901));
+}
+
+#[test]
+fn commit_expects_vaults_after_the_base_vault() {
+    assert_eq!(session().vault_mints(), vec![mint(0), mint(1)]);
+}
+
+#[test]
+fn leg_outcome_must_respect_the_plan() {
+    let mut session = session();
+    assert_eq!(
+        session.record_leg(2, 500, 500).unwrap_err(),
+        error(SloomoError::InvalidLegIndex)
+    );
+    assert_eq!(
+        session.record_leg(0, 501, 600).unwrap_err(),
+        error(SloomoError::SwapExecutionFailed)
+    );
+    assert_eq!(
+        session.record_leg(0, 500, 494).unwrap_err(),
+        error(SloomoError::SlippageExceeded)
+    );
+
+    session.record_leg(0, 500, 495).unwrap();
+    assert!(!session.all_legs_executed());
+    assert_eq!(
+        session.record_leg(0, 500, 495).unwrap_err(),
+        error(SloomoError::LegAlreadyExecuted)
+    );
+
+    session.record_leg(1, 480, 510).unwrap();
+    assert!(session.all_legs_executed());
+}
+
+#[test]
+fn expected_balances_apply_executed_legs_only() {
+    let mut session = session();
+    session.record_leg(0, 500, 498).unwrap();
+    assert_eq!(
+        session.expected_balances().unwrap(),
+        snapshot(500, &[498, 0])
+    );
+}
+
+#[test]
+fn commit_rejects_vaults_below_the_legs() {
+    let mut session = session();
+    session.record_leg(0, 500, 498).unwrap();
+    session.record_leg(1, 500, 499).unwrap();
+
+    let landed = snapshot(0, &[498, 499]);
+    session.verify_balances(&landed).unwrap();
+
+    // Dust sent to a vault does not block the commit
+    session.verify_balances(&snapshot(1, &[498, 499])).unwrap();
+
+    // Something drained a vault during the session
+    let drained = snapshot(0, &[400, 499]);
+    assert_eq!(
+        session.verify_balances(&drained).unwrap_err(),
+        error(SloomoError::UnexpectedVaultBalance)
+    );
+    assert_eq!(
+        session.verify_balances(&landed[..2]).unwrap_err(),
+        error(SloomoError::MissingVaultAccount)
+    );
+}
+
+#[test]
+fn leg_swap_may_only_move_its_own_vaults() {
+    let session = session();
+    let leg = &session.legs[0];
+    let before = snapshot(1_000, &[0, 7]);
+
+    // Base into mint 0: only those two vaults move
+    let landed = snapshot(500, &[498, 7]);
+    RebalanceSession::verify_leg_balances(leg, &before, &landed).unwrap();
+
+    // The swap also drained the mint 1 vault
+    assert_eq!(
+        RebalanceSession::verify_leg_balances(leg, &before, &snapshot(500, &[498, 0]))
+            .unwrap_err(),
+        error(SloomoError::UnexpectedVaultBalance)
+    );
+    assert_eq!(
+        RebalanceSession::verify_leg_balances(leg, &before, &landed[..2]).unwrap_err(),
+        error(SloomoError::MissingVaultAccount)
+    );
+}
+
+#[test]
+fn sessions_plan_a_leg_per_allocation() {
+    assert_eq!(MAX_SESSION_LEGS, MAX_ALLOCATIONS);
+    assert!(MAX_SESSION_LEGS > DEFAULT_MAX_LEGS as usize);
+}
+
+#[test]
+fn vault_balances_sync_allocations_and_track_untargeted_tokens() {
+    let mut portfolio = common::portfolio_with(vec![allocation(0, 0, 10000)]);
+    portfolio
+        .apply_vault_balances(&snapshot(100, &[300, 200, 0]), |_| "TKN".to_string())
+        .unwrap();
+
+    let tracked: Vec<_> = portfolio
+        .allocations
+        .iter()
+        .map(|a| (a.mint, a.current_amount, a.target_percentage))
+        .collect();
+    // Base asset and empty vaults stay untracked
+    assert_eq!(tracked, vec![(mint(0), 300, 10000), (mint(1), 200, 0)]);
+}
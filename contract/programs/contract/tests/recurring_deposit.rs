@@ -0,0 +1,107 @@
+//! Tests for the recurring deposit schedule
+
+use anchor_lang::prelude::Pubkey;
+use sloomo_portfolio::{RecurringDeposit, SloomoError, MIN_RECURRING_INTERVAL};
+
+const NOW: i64 = 1_000_000;
+const WEEK: i64 = 7 * 24 * 60 * 60;
+
+fn unscheduled() -> RecurringDeposit {
+    RecurringDeposit {
+        portfolio: Pubkey::new_from_array([1; 32]),
+        source: Pubkey::new_from_array([2; 32]),
+        bump: 255,
+        amount: 0,
+        interval: 0,
+        next_due_at: 0,
+        max_occurrences: None,
+        executed: 0,
+        created_at: NOW,
+    }
+}
+
+/// 50 USDC every week, starting now
+fn weekly(max_occurrences: Option<u32>) -> RecurringDeposit {
+    let mut schedule = unscheduled();
+    schedule.configure(50_000_000, WEEK, None, max_occurrences, NOW).unwrap();
+    schedule
+}
+
+#[test]
+fn occurrences_are_due_once_per_interval() {
+    let mut schedule = weekly(None);
+    assert_eq!(schedule.record_execution(NOW).unwrap(), 1);
+    assert_eq!(schedule.next_due_at, NOW + WEEK);
+
+    assert_eq!(
+        schedule.record_execution(NOW + WEEK - 1).unwrap_err(),
+        SloomoError::RecurringDepositNotDue.into()
+    );
+    assert_eq!(schedule.record_execution(NOW + WEEK).unwrap(), 2);
+    assert_eq!(schedule.next_due_at, NOW + 2 * WEEK);
+}
+
+#[test]
+fn missed_occurrences_are_skipped() {
+    let mut schedule = weekly(None);
+    schedule.record_execution(NOW).unwrap();
+
+    // Nobody cranked for three and a half weeks
+    let late = NOW + WEEK + 3 * WEEK + WEEK / 2;
+    assert_eq!(schedule.record_execution(late).unwrap(), 2);
+    assert_eq!(schedule.next_due_at, NOW + 5 * WEEK);
+    assert!(schedule.record_execution(late).is_err());
+}
+
+#[test]
+fn schedule_ends_after_max_occurrences() {
+    let mut schedule = weekly(Some(2));
+    schedule.record_execution(NOW).unwrap();
+    assert!(!schedule.is_completed());
+    schedule.record_execution(NOW + WEEK).unwrap();
+    assert!(schedule.is_completed());
+
+    assert_eq!(
+        schedule.record_execution(NOW + 10 * WEEK).unwrap_err(),
+        SloomoError::RecurringDepositCompleted.into()
+    );
+    assert_eq!(schedule.executed, 2);
+}
+
+#[test]
+fn start_time_is_not_in_the_past() {
+    let mut schedule = unscheduled();
+    schedule.configure(1, WEEK, Some(NOW + 100), None, NOW).unwrap();
+    assert_eq!(schedule.next_due_at, NOW + 100);
+
+    schedule.configure(1, WEEK, Some(NOW - 100), None, NOW).unwrap();
+    assert_eq!(schedule.next_due_at, NOW);
+}
+
+#[test]
+fn reconfiguring_restarts_the_count() {
+    let mut schedule = weekly(Some(1));
+    schedule.record_execution(NOW).unwrap();
+    assert!(schedule.is_completed());
+
+    schedule.configure(10, WEEK, None, Some(1), NOW + WEEK).unwrap();
+    assert_eq!(schedule.executed, 0);
+    assert_eq!(schedule.record_execution(NOW + WEEK).unwrap(), 1);
+}
+
+#[test]
+fn invalid_schedules_are_rejected() {
+    let cases = [
+        (0, WEEK, None),
+        (1, MIN_RECURRING_INTERVAL - 1, None),
+        (1, 0, None),
+        (1, WEEK, Some(0)),
+    ];
+    for (amount, interval, max_occurrences) in cases {
+        let mut schedule = unscheduled();
+        assert_eq!(
+            schedule.configure(amount, interval, None, max_occurrences, NOW).unwrap_err(),
+            SloomoError::InvalidRecurringDeposit.into()
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Unit tests for savings goals
+
+mod common;
+
+use sloomo_portfolio::{PerformanceSnapshot, Portfolio, SavingsGoal, SloomoError};
+
+const DAY: i64 = 24 * 60 * 60;
+const MONTH: i64 = 30 * DAY;
+
+fn portfolio(total_value: u64) -> Portfolio {
+    Portfolio { total_value, ..common::portfolio() }
+}
+
+fn goal(target_amount: u64, target_date: i64) -> Option<SavingsGoal> {
+    Some(SavingsGoal { target_amount, target_date, label: "House deposit".to_string() })
+}
+
+#[test]
+fn goals_are_validated() {
+    let mut portfolio = portfolio(0);
+
+    for invalid in [goal(0, MONTH), goal(1_000, 0)] {
+        let err = portfolio.set_goal(invalid, 0).unwrap_err();
+        assert_eq!(err, SloomoError::InvalidSavingsGoal.into());
+    }
+    let mut long_label = goal(1_000, MONTH);
+    long_label.as_mut().unwrap().label = "L".repeat(33);
+    assert!(portfolio.set_goal(long_label, 0).is_err());
+
+    portfolio.set_goal(goal(1_000, MONTH), 0).unwrap();
+    assert_eq!(portfolio.goal, goal(1_000, MONTH));
+    portfolio.set_goal(None, 0).unwrap();
+    assert_eq!(portfolio.goal, None);
+}
+
+#[test]
+fn progress_needs_a_goal_and_an_interval() {
+    let mut portfolio = portfolio(400);
+    let err = portfolio.goal_progress(0, MONTH).unwrap_err();
+    assert_eq!(err, SloomoError::SavingsGoalNotSet.into());
+
+    portfolio.set_goal(goal(1_000, 12 * MONTH), 0).unwrap();
+    let err = portfolio.goal_progress(0, 0).unwrap_err();
+    assert_eq!(err, SloomoError::InvalidContributionInterval.into());
+}
+
+#[test]
+fn contribution_splits_the_shortfall_without_history() {
+    let mut portfolio = portfolio(4_000_000);
+    portfolio.set_goal(goal(10_000_000, 12 * MONTH), 0).unwrap();
+
+    let progress = portfolio.goal_progress(0, MONTH).unwrap();
+    assert_eq!(progress.total_value, 4_000_000);
+    assert_eq!(progress.progress_bps, 4000);
+    assert_eq!(progress.periods_remaining, 12);
+    assert_eq!(progress.expected_growth_bps, 0);
+    assert_eq!(progress.required_contribution, 500_000);
+}
+
+#[test]
+fn performance_history_lowers_the_contribution() {
+    let mut portfolio = portfolio(4_000_000);
+    portfolio.set_goal(goal(10_000_000, 12 * MONTH), 0).unwrap();
+    let without_growth = portfolio.goal_progress(0, MONTH).unwrap();
+
+    // 1% growth a month over the last two months
+    portfolio.performance_history = (0..3)
+        .map(|i| PerformanceSnapshot {
+            timestamp: i * MONTH,
+            total_value: 4_000_000,
+            growth_rate: if i == 0 { 0 } else { 100 },
+        })
+        .collect();
+    let progress = portfolio.goal_progress(0, MONTH).unwrap();
+    assert_eq!(progress.expected_growth_bps, 100);
+    assert!(progress.projected_value > 4_000_000);
+    assert!(progress.required_contribution < without_growth.required_contribution);
+}
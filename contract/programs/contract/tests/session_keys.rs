@@ -0,0 +1,140 @@
+//! Tests for session key scopes, expiry and spend limits
+
+mod common;
+
+use common::key;
+use sloomo_portfolio::utils::{validate_authority, validate_swap_programs, JUPITER_PROGRAM_ID};
+use sloomo_portfolio::{
+    Portfolio, SessionKey, SloomoError, MAX_SESSION_DURATION, SESSION_SCOPE_ALL,
+    SESSION_SCOPE_ALLOCATION, SESSION_SCOPE_DEPOSIT, SESSION_SCOPE_REBALANCE, MAX_SWAP_PROGRAMS,
+};
+
+const NOW: i64 = 1_000_000;
+
+/// Portfolio owned by key 0
+fn portfolio() -> Portfolio {
+    Portfolio { owner: key(0), portfolio_id: key(0), ..common::portfolio() }
+}
+
+/// Session key 1 issued by the owner for deposits, expiring in an hour
+fn deposit_key(spend_limit: Option<u64>) -> SessionKey {
+    SessionKey {
+        portfolio: key(8),
+        authority: key(1),
+        issued_by: key(0),
+        bump: 255,
+        scopes: SESSION_SCOPE_DEPOSIT,
+        expires_at: NOW + 3_600,
+        spend_limit,
+        spent: 0,
+        created_at: NOW,
+    }
+}
+
+#[test]
+fn owner_needs_no_session_key() {
+    let portfolio = portfolio();
+    validate_authority(&portfolio, &key(0), None, SESSION_SCOPE_ALL, u64::MAX, NOW).unwrap();
+}
+
+#[test]
+fn other_signers_need_a_session_key() {
+    let portfolio = portfolio();
+    assert_eq!(
+        validate_authority(&portfolio, &key(1), None, SESSION_SCOPE_DEPOSIT, 0, NOW).unwrap_err(),
+        SloomoError::Unauthorized.into()
+    );
+}
+
+#[test]
+fn session_key_is_limited_to_its_scopes() {
+    let portfolio = portfolio();
+    let mut session_key = deposit_key(None);
+    validate_authority(&portfolio, &key(1), Some(&mut session_key), SESSION_SCOPE_DEPOSIT, 10, NOW)
+        .unwrap();
+
+    for scope in [SESSION_SCOPE_ALLOCATION, SESSION_SCOPE_REBALANCE] {
+        assert_eq!(
+            validate_authority(&portfolio, &key(1), Some(&mut session_key), scope, 0, NOW)
+                .unwrap_err(),
+            SloomoError::SessionScopeNotAllowed.into()
+        );
+    }
+}
+
+#[test]
+fn session_key_expires() {
+    let session_key = deposit_key(None);
+    session_key.authorize(&key(0), SESSION_SCOPE_DEPOSIT, NOW + 3_599).unwrap();
+    assert_eq!(
+        session_key.authorize(&key(0), SESSION_SCOPE_DEPOSIT, NOW + 3_600).unwrap_err(),
+        SloomoError::SessionKeyExpired.into()
+    );
+}
+
+#[test]
+fn session_key_dies_with_an_owner_change() {
+    let mut portfolio = portfolio();
+    portfolio.reassign_owner(key(2));
+    let mut session_key = deposit_key(None);
+
+    assert_eq!(
+        validate_authority(&portfolio, &key(1), Some(&mut session_key), SESSION_SCOPE_DEPOSIT, 0, NOW)
+            .unwrap_err(),
+        SloomoError::SessionKeyExpired.into()
+    );
+}
+
+#[test]
+fn spend_limit_caps_the_total_moved() {
+    let portfolio = portfolio();
+    let mut session_key = deposit_key(Some(1_000));
+    validate_authority(&portfolio, &key(1), Some(&mut session_key), SESSION_SCOPE_DEPOSIT, 600, NOW)
+        .unwrap();
+    validate_authority(&portfolio, &key(1), Some(&mut session_key), SESSION_SCOPE_DEPOSIT, 400, NOW)
+        .unwrap();
+    assert_eq!(session_key.spent, 1_000);
+
+    assert_eq!(
+        validate_authority(&portfolio, &key(1), Some(&mut session_key), SESSION_SCOPE_DEPOSIT, 1, NOW)
+            .unwrap_err(),
+        SloomoError::SessionSpendLimitExceeded.into()
+    );
+    assert_eq!(session_key.spent, 1_000);
+}
+
+#[test]
+fn grants_need_known_scopes_and_a_bounded_lifetime() {
+    SessionKey::validate_grant(SESSION_SCOPE_ALL, NOW + MAX_SESSION_DURATION, NOW).unwrap();
+
+    for scopes in [0, SESSION_SCOPE_ALL + 1, 0x80] {
+        assert_eq!(
+            SessionKey::validate_grant(scopes, NOW + 60, NOW).unwrap_err(),
+            SloomoError::InvalidSessionScope.into()
+        );
+    }
+    for expires_at in [NOW, NOW - 1, NOW + MAX_SESSION_DURATION + 1] {
+        assert_eq!(
+            SessionKey::validate_grant(SESSION_SCOPE_DEPOSIT, expires_at, NOW).unwrap_err(),
+            SloomoError::InvalidSessionExpiry.into()
+        );
+    }
+}
+
+#[test]
+fn swap_allowlist_is_bounded_unique_and_excludes_this_program() {
+    validate_swap_programs(&[]).unwrap();
+    validate_swap_programs(&[JUPITER_PROGRAM_ID, key(5)]).unwrap();
+
+    let too_many: Vec<_> = (0..=MAX_SWAP_PROGRAMS as u8).map(key).collect();
+    for programs in [
+        too_many,
+        vec![JUPITER_PROGRAM_ID, JUPITER_PROGRAM_ID],
+        vec![sloomo_portfolio::ID],
+    ] {
+        assert_eq!(
+            validate_swap_programs(&programs).unwrap_err(),
+            SloomoError::InvalidSwapProgram.into()
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Tests for Token-2022 transfer-fee handling on deposits
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeConfig};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
+use sloomo_portfolio::utils::{received_after_fee, transfer_fee};
+
+/// Fee schedule entry taking effect at `epoch`
+fn fee(epoch: u64, basis_points: u16, maximum_fee: u64) -> TransferFee {
+    TransferFee {
+        epoch: epoch.into(),
+        maximum_fee: maximum_fee.into(),
+        transfer_fee_basis_points: basis_points.into(),
+    }
+}
+
+fn mint_data(fees: Option<(TransferFee, TransferFee)>) -> Vec<u8> {
+    let extensions: &[ExtensionType] = match fees {
+        Some(_) => &[ExtensionType::TransferFeeConfig],
+        None => &[],
+    };
+    let len = ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap();
+    let mut data = vec![0u8; len];
+
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    if let Some((older, newer)) = fees {
+        let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        config.older_transfer_fee = older;
+        config.newer_transfer_fee = newer;
+    }
+    state.base = Mint {
+        decimals: 6,
+        is_initialized: true,
+        ..Mint::default()
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    data
+}
+
+/// Owned storage for one mint `AccountInfo`
+struct TestMint {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestMint {
+    fn new(owner: Pubkey, fees: Option<(TransferFee, TransferFee)>) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: 1,
+            data: mint_data(fees),
+        }
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            false,
+            false,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+#[test]
+fn spl_token_mint_charges_no_fee() {
+    let mut mint = TestMint::new(TOKEN_PROGRAM_ID, None);
+    assert_eq!(transfer_fee(&mint.info(), 1_000_000, 0).unwrap(), 0);
+    assert_eq!(received_after_fee(&mint.info(), 1_000_000, 0).unwrap(), 1_000_000);
+}
+
+#[test]
+fn token_2022_mint_without_the_extension_charges_no_fee() {
+    let mut mint = TestMint::new(TOKEN_2022_PROGRAM_ID, None);
+    assert_eq!(transfer_fee(&mint.info(), 1_000_000, 0).unwrap(), 0);
+}
+
+#[test]
+fn deposit_is_credited_net_of_the_transfer_fee() {
+    // 1% fee capped at 5_000
+    let mut mint = TestMint::new(
+        TOKEN_2022_PROGRAM_ID,
+        Some((fee(0, 100, 5_000), fee(0, 100, 5_000))),
+    );
+    assert_eq!(transfer_fee(&mint.info(), 100_000, 0).unwrap(), 1_000);
+    assert_eq!(received_after_fee(&mint.info(), 100_000, 0).unwrap(), 99_000);
+
+    // The cap applies to large transfers
+    assert_eq!(received_after_fee(&mint.info(), 10_000_000, 0).unwrap(), 9_995_000);
+}
+
+#[test]
+fn fee_follows_the_transfer_epoch() {
+    // 1% until epoch 10, then 2%
+    let mut mint = TestMint::new(
+        TOKEN_2022_PROGRAM_ID,
+        Some((fee(0, 100, u64::MAX), fee(10, 200, u64::MAX))),
+    );
+    assert_eq!(transfer_fee(&mint.info(), 100_000, 9).unwrap(), 1_000);
+    assert_eq!(transfer_fee(&mint.info(), 100_000, 10).unwrap(), 2_000);
+}
@@ -0,0 +1,211 @@
+//! Tests for PDA validation of portfolio vaults passed as remaining accounts
+
+mod common;
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::system_program;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState};
+use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
+use common::mint;
+use sloomo_portfolio::utils::{
+    read_vault_balance, read_vault_balances, validate_swap_accounts, vault_address,
+};
+use sloomo_portfolio::SloomoError;
+
+fn token_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0u8; SplAccount::LEN];
+    SplAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+/// Token account of `owner` that approved `delegate` for its whole balance
+fn delegated_token_data(mint: Pubkey, owner: Pubkey, delegate: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = token_data(mint, owner, amount);
+    let mut account = SplAccount::unpack(&data).unwrap();
+    account.delegate = COption::Some(delegate);
+    account.delegated_amount = amount;
+    account.pack_into_slice(&mut data);
+    data
+}
+
+/// Owned storage for one `AccountInfo`
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    fn vault(portfolio: &Pubkey, mint: Pubkey, amount: u64) -> Self {
+        Self {
+            key: vault_address(portfolio, &mint),
+            owner: TOKEN_PROGRAM_ID,
+            lamports: 1,
+            data: token_data(mint, *portfolio, amount),
+        }
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            false,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+fn error(code: SloomoError) -> Error {
+    code.into()
+}
+
+#[test]
+fn vault_balance_is_read_from_the_pda() {
+    let portfolio = Pubkey::new_unique();
+    let mut vault = TestAccount::vault(&portfolio, mint(0), 42);
+    assert_eq!(read_vault_balance(&portfolio, &mint(0), &vault.info()).unwrap(), 42);
+}
+
+#[test]
+fn uncreated_vault_counts_as_empty() {
+    let portfolio = Pubkey::new_unique();
+    let mut vault = TestAccount {
+        key: vault_address(&portfolio, &mint(0)),
+        owner: system_program::ID,
+        lamports: 0,
+        data: Vec::new(),
+    };
+    assert_eq!(read_vault_balance(&portfolio, &mint(0), &vault.info()).unwrap(), 0);
+}
+
+#[test]
+fn owner_wallet_account_is_rejected() {
+    // A token account holding the right mint, but not at the vault PDA
+    let portfolio = Pubkey::new_unique();
+    let mut wallet = TestAccount::vault(&portfolio, mint(0), 42);
+    wallet.key = Pubkey::new_unique();
+    assert_eq!(
+        read_vault_balance(&portfolio, &mint(0), &wallet.info()).unwrap_err(),
+        error(SloomoError::InvalidVault)
+    );
+}
+
+#[test]
+fn vault_of_another_mint_is_rejected() {
+    let portfolio = Pubkey::new_unique();
+    let mut vault = TestAccount::vault(&portfolio, mint(1), 42);
+    assert_eq!(
+        read_vault_balance(&portfolio, &mint(0), &vault.info()).unwrap_err(),
+        error(SloomoError::InvalidVault)
+    );
+}
+
+#[test]
+fn vault_not_owned_by_the_token_program_is_rejected() {
+    let portfolio = Pubkey::new_unique();
+    let mut vault = TestAccount::vault(&portfolio, mint(0), 42);
+    vault.owner = Pubkey::new_unique();
+    assert_eq!(
+        read_vault_balance(&portfolio, &mint(0), &vault.info()).unwrap_err(),
+        error(SloomoError::InvalidVault)
+    );
+}
+
+#[test]
+fn token_2022_vault_is_read() {
+    let portfolio = Pubkey::new_unique();
+    let mut vault = TestAccount::vault(&portfolio, mint(0), 42);
+    vault.owner = TOKEN_2022_PROGRAM_ID;
+    assert_eq!(read_vault_balance(&portfolio, &mint(0), &vault.info()).unwrap(), 42);
+}
+
+#[test]
+fn every_mint_needs_a_vault() {
+    let portfolio = Pubkey::new_unique();
+    let mut first = TestAccount::vault(&portfolio, mint(0), 10);
+    let mut second = TestAccount::vault(&portfolio, mint(1), 20);
+
+    let vaults = [first.info(), second.info()];
+    assert_eq!(
+        read_vault_balances(&portfolio, &[mint(0), mint(1)], &vaults).unwrap(),
+        vec![(mint(0), 10), (mint(1), 20)]
+    );
+    assert_eq!(
+        read_vault_balances(&portfolio, &[mint(0), mint(1), mint(2)], &vaults).unwrap_err(),
+        error(SloomoError::MissingVaultAccount)
+    );
+    // Vaults must follow the order of the mints
+    assert_eq!(
+        read_vault_balances(&portfolio, &[mint(1), mint(0)], &vaults).unwrap_err(),
+        error(SloomoError::InvalidVault)
+    );
+}
+
+#[test]
+fn leg_swap_accounts_may_only_hold_the_leg_vaults() {
+    let portfolio = Pubkey::new_unique();
+    let mut source = TestAccount::vault(&portfolio, mint(0), 10);
+    let mut destination = TestAccount::vault(&portfolio, mint(1), 0);
+    let mut third = TestAccount::vault(&portfolio, mint(2), 30);
+    // Pool accounts and the mints themselves are the swap program's business
+    let mut pool = TestAccount::vault(&Pubkey::new_unique(), mint(0), 99);
+    pool.key = Pubkey::new_unique();
+    let mut token_mint = TestAccount {
+        key: mint(0),
+        owner: TOKEN_PROGRAM_ID,
+        lamports: 1,
+        data: vec![0u8; 82],
+    };
+    let leg_vaults = [source.key, destination.key];
+
+    validate_swap_accounts(
+        &portfolio,
+        &leg_vaults,
+        &[source.info(), destination.info(), pool.info(), token_mint.info()],
+    )
+    .unwrap();
+
+    // A session key routing a third vault through the swap
+    assert_eq!(
+        validate_swap_accounts(&portfolio, &leg_vaults, &[source.info(), third.info()])
+            .unwrap_err(),
+        error(SloomoError::UnauthorizedSwapAccount)
+    );
+}
+
+#[test]
+fn owner_account_delegated_to_the_portfolio_cannot_be_swapped() {
+    // The recurring deposit source approves the portfolio PDA as delegate
+    let portfolio = Pubkey::new_unique();
+    let mut source = TestAccount::vault(&portfolio, mint(0), 10);
+    let mut owner_account = TestAccount {
+        key: Pubkey::new_unique(),
+        owner: TOKEN_PROGRAM_ID,
+        lamports: 1,
+        data: delegated_token_data(mint(0), Pubkey::new_unique(), portfolio, 500),
+    };
+    assert_eq!(
+        validate_swap_accounts(&portfolio, &[source.key], &[source.info(), owner_account.info()])
+            .unwrap_err(),
+        error(SloomoError::UnauthorizedSwapAccount)
+    );
+}
@@ -0,0 +1,105 @@
+//! Unit tests for the read-only summary and drift views
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{allocation, band, mint, targets};
+use sloomo_portfolio::{AssetClass, ClassMembership, PerformanceSnapshot, Portfolio};
+
+/// Three allocations targeting 30% / 30% / 40%
+fn portfolio_with(amounts: [u64; 3]) -> Portfolio {
+    Portfolio {
+        owner: Pubkey::new_from_array([0xee; 32]),
+        ..common::portfolio_with(vec![
+            allocation(0, amounts[0], 3000),
+            allocation(1, amounts[1], 3000),
+            allocation(2, amounts[2], 4000),
+        ])
+    }
+}
+
+#[test]
+fn summary_reports_shares_and_targets() {
+    let mut portfolio = portfolio_with([300, 300, 400]);
+    portfolio.performance_history.push(PerformanceSnapshot {
+        timestamp: 0,
+        total_value: 1_000,
+        growth_rate: 125,
+    });
+
+    let summary = portfolio.summary(0).unwrap();
+    assert_eq!(summary.owner, portfolio.owner);
+    assert_eq!(summary.allocated_value, 1_000);
+    assert!(!summary.needs_rebalancing);
+    assert_eq!(summary.latest_growth_bps, 125);
+
+    let shares: Vec<(u64, u16)> = summary
+        .allocations
+        .iter()
+        .map(|a| (a.current_percentage, a.target_percentage))
+        .collect();
+    assert_eq!(shares, vec![(3000, 3000), (3000, 3000), (4000, 4000)]);
+}
+
+#[test]
+fn drift_is_measured_against_the_threshold_or_band() {
+    // 40% / 20% / 40%: the first two drift by 10% each
+    let mut portfolio = portfolio_with([400, 200, 400]);
+    let report = portfolio.drift_report(0).unwrap();
+    assert!(report.needs_rebalancing);
+    assert_eq!(report.max_drift_bps, 1000);
+    let drifts: Vec<(u64, bool)> = report.allocations.iter().map(|a| (a.drift_bps, a.within_band)).collect();
+    assert_eq!(drifts, vec![(1000, false), (1000, false), (0, true)]);
+
+    // Wide bands absorb the same drift
+    for allocation in &mut portfolio.allocations[..2] {
+        allocation.band = band(1500, 4500);
+    }
+    let report = portfolio.drift_report(0).unwrap();
+    assert!(!report.needs_rebalancing);
+    assert!(report.allocations.iter().all(|a| a.within_band));
+    assert_eq!(report.max_drift_bps, 1000);
+}
+
+#[test]
+fn drift_reports_asset_classes() {
+    let mut portfolio = portfolio_with([450, 150, 400]);
+    let classes = vec![
+        AssetClass { name: "Equity".to_string(), target_percentage: 6000, band: None },
+        AssetClass { name: "Stable".to_string(), target_percentage: 4000, band: None },
+    ];
+    let memberships = [
+        ClassMembership { mint: mint(0), asset_class: 0, weight: 5000 },
+        ClassMembership { mint: mint(1), asset_class: 0, weight: 5000 },
+        ClassMembership { mint: mint(2), asset_class: 1, weight: 10000 },
+    ];
+    portfolio.set_asset_classes(classes, &memberships).unwrap();
+
+    let report = portfolio.drift_report(0).unwrap();
+    let classes: Vec<(&str, u64, bool)> = report
+        .asset_classes
+        .iter()
+        .map(|c| (c.name.as_str(), c.current_percentage, c.within_band))
+        .collect();
+    assert_eq!(classes, vec![("Equity", 6000, true), ("Stable", 4000, true)]);
+    // Members still drift within the class
+    assert_eq!(report.max_drift_bps, 1500);
+}
+
+#[test]
+fn views_follow_the_glide_path() {
+    let mut portfolio = portfolio_with([300, 300, 400]);
+    portfolio
+        .set_glide_path(&targets(&[3000, 3000]), &targets(&[1000, 5000]), 10_000, 5000, 0)
+        .unwrap();
+
+    // Halfway down the path the stored targets have not stepped yet
+    assert_eq!(portfolio.allocations[0].target_percentage, 3000);
+    let summary = portfolio.summary(5_000).unwrap();
+    let glided: Vec<u16> = summary.allocations.iter().map(|a| a.target_percentage).collect();
+    assert_eq!(glided, vec![2000, 4000, 4000]);
+
+    let report = portfolio.drift_report(5_000).unwrap();
+    assert_eq!(report.max_drift_bps, 1000);
+    assert!(report.needs_rebalancing);
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MockSwapError {
+    #[msg("Invalid price")]
+    InvalidPrice,
+    #[msg("Invalid fee")]
+    InvalidFee,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Slippage tolerance exceeded")]
+    SlippageExceeded,
+    #[msg("Insufficient pool liquidity")]
+    InsufficientLiquidity,
+    #[msg("Swap failed (forced by pool mode)")]
+    ForcedFailure,
+    #[msg("Reentry target program missing")]
+    MissingReentryProgram,
+    #[msg("Reentry data too long")]
+    ReentryDataTooLong,
+    #[msg("Math overflow")]
+    MathOverflow,
+}
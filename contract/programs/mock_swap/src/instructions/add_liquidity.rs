@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::Pool;
+use crate::error::MockSwapError;
+
+/// Account structure for adding output liquidity
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    /// Pool receiving liquidity
+    #[account(has_one = authority)]
+    pub pool: Account<'info, Pool>,
+
+    /// Administrator's output token account (source)
+    #[account(mut, token::mint = pool.output_mint)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Pool output vault (destination)
+    #[account(
+        mut,
+        seeds = [b"pool_vault", pool.key().as_ref(), pool.output_mint.as_ref()],
+        bump,
+    )]
+    pub output_vault: Account<'info, TokenAccount>,
+
+    /// Pool administrator
+    pub authority: Signer<'info>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deposit output tokens the pool can pay out
+pub fn handler(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0, MockSwapError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.output_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer(cpi_ctx, amount)
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, SwapMode, MAX_REENTRY_DATA};
+use crate::error::MockSwapError;
+
+/// Account structure for pool configuration
+#[derive(Accounts)]
+pub struct ConfigurePool<'info> {
+    /// Pool to be configured
+    #[account(mut, has_one = authority)]
+    pub pool: Account<'info, Pool>,
+
+    /// Pool administrator
+    pub authority: Signer<'info>,
+}
+
+/// Pool configuration parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConfigurePoolParams {
+    /// Price numerator
+    pub price_numerator: u64,
+    /// Price denominator
+    pub price_denominator: u64,
+    /// Swap fee (basis points)
+    pub fee_bps: u16,
+    /// Swap behaviour
+    pub mode: SwapMode,
+    /// Output withheld in ShortOutput mode (basis points)
+    pub short_output_bps: u16,
+    /// Program invoked in Reentrancy mode
+    pub reentry_program: Pubkey,
+    /// Instruction data sent to the reentry program
+    pub reentry_data: Vec<u8>,
+}
+
+/// Change price, fee and adversarial mode
+pub fn handler(ctx: Context<ConfigurePool>, params: ConfigurePoolParams) -> Result<()> {
+    require!(
+        params.price_numerator > 0 && params.price_denominator > 0,
+        MockSwapError::InvalidPrice
+    );
+    require!(
+        params.fee_bps <= 10000 && params.short_output_bps <= 10000,
+        MockSwapError::InvalidFee
+    );
+    require!(
+        params.reentry_data.len() <= MAX_REENTRY_DATA,
+        MockSwapError::ReentryDataTooLong
+    );
+
+    let pool = &mut ctx.accounts.pool;
+    pool.price_numerator = params.price_numerator;
+    pool.price_denominator = params.price_denominator;
+    pool.fee_bps = params.fee_bps;
+    pool.mode = params.mode;
+    pool.short_output_bps = params.short_output_bps;
+    pool.reentry_program = params.reentry_program;
+    pool.reentry_data = params.reentry_data;
+
+    msg!("Mock pool configured: mode {:?}", pool.mode);
+    Ok(())
+}
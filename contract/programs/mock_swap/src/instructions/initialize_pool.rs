@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{Pool, SwapMode};
+use crate::error::MockSwapError;
+
+/// Account structure for pool initialization
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    /// Pool account to be initialized
+    #[account(
+        init,
+        payer = authority,
+        space = Pool::SIZE,
+        seeds = [b"pool", input_mint.key().as_ref(), output_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Vault receiving swapper input
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_vault", pool.key().as_ref(), input_mint.key().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = pool,
+    )]
+    pub input_vault: Account<'info, TokenAccount>,
+
+    /// Vault paying swapper output
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_vault", pool.key().as_ref(), output_mint.key().as_ref()],
+        bump,
+        token::mint = output_mint,
+        token::authority = pool,
+    )]
+    pub output_vault: Account<'info, TokenAccount>,
+
+    /// Input mint
+    pub input_mint: Account<'info, Mint>,
+
+    /// Output mint
+    pub output_mint: Account<'info, Mint>,
+
+    /// Pool administrator (fee payer)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Initialize a pool in Normal mode
+pub fn handler(
+    ctx: Context<InitializePool>,
+    price_numerator: u64,
+    price_denominator: u64,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(price_numerator > 0 && price_denominator > 0, MockSwapError::InvalidPrice);
+    require!(fee_bps <= 10000, MockSwapError::InvalidFee);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.authority = ctx.accounts.authority.key();
+    pool.input_mint = ctx.accounts.input_mint.key();
+    pool.output_mint = ctx.accounts.output_mint.key();
+    pool.price_numerator = price_numerator;
+    pool.price_denominator = price_denominator;
+    pool.fee_bps = fee_bps;
+    pool.mode = SwapMode::Normal;
+    pool.short_output_bps = 0;
+    pool.reentry_program = Pubkey::default();
+    pool.reentry_data = Vec::new();
+    pool.bump = ctx.bumps.pool;
+
+    msg!(
+        "Mock pool initialized: {} -> {} at {}/{} (fee {} bps)",
+        pool.input_mint,
+        pool.output_mint,
+        price_numerator,
+        price_denominator,
+        fee_bps
+    );
+    Ok(())
+}
@@ -0,0 +1,12 @@
+#![allow(ambiguous_glob_reexports)]
+
+pub mod initialize_pool;
+pub mod configure_pool;
+pub mod add_liquidity;
+pub mod swap;
+
+// Re-export everything from each module
+pub use initialize_pool::*;
+pub use configure_pool::*;
+pub use add_liquidity::*;
+pub use swap::*;
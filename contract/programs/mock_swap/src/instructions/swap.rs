@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke};
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::state::{Pool, SwapMode};
+use crate::error::MockSwapError;
+
+/// Account structure for swapping through a pool
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    /// Pool to swap through
+    #[account(
+        seeds = [b"pool", pool.input_mint.as_ref(), pool.output_mint.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Owner of the swapper's token accounts (may be a PDA signing via CPI)
+    pub user_authority: Signer<'info>,
+
+    /// Swapper's input token account (source)
+    #[account(mut, token::mint = pool.input_mint)]
+    pub user_source: Account<'info, TokenAccount>,
+
+    /// Swapper's output token account (destination)
+    #[account(mut, token::mint = pool.output_mint)]
+    pub user_destination: Account<'info, TokenAccount>,
+
+    /// Pool input vault
+    #[account(
+        mut,
+        seeds = [b"pool_vault", pool.key().as_ref(), pool.input_mint.as_ref()],
+        bump,
+    )]
+    pub input_vault: Account<'info, TokenAccount>,
+
+    /// Pool output vault
+    #[account(
+        mut,
+        seeds = [b"pool_vault", pool.key().as_ref(), pool.output_mint.as_ref()],
+        bump,
+    )]
+    pub output_vault: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Swap `amount_in` input tokens for output tokens
+///
+/// In Reentrancy mode the remaining accounts are `[reentry_program, ...accounts]`
+/// and are forwarded to the reentry program before any tokens move.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    require!(amount_in > 0, MockSwapError::InvalidAmount);
+
+    let pool = &ctx.accounts.pool;
+    match pool.mode {
+        SwapMode::Fail => return err!(MockSwapError::ForcedFailure),
+        SwapMode::Reentrancy => reenter(pool, ctx.remaining_accounts)?,
+        SwapMode::Normal | SwapMode::ShortOutput => {}
+    }
+
+    let quoted = pool.quote(amount_in)?;
+    // ShortOutput mode deliberately skips the slippage check
+    if pool.mode != SwapMode::ShortOutput {
+        require!(quoted >= minimum_amount_out, MockSwapError::SlippageExceeded);
+    }
+    let amount_out = pool.delivered(quoted);
+    require!(
+        ctx.accounts.output_vault.amount >= amount_out,
+        MockSwapError::InsufficientLiquidity
+    );
+
+    // Input: swapper -> pool
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_source.to_account_info(),
+        to: ctx.accounts.input_vault.to_account_info(),
+        authority: ctx.accounts.user_authority.to_account_info(),
+    };
+    transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount_in,
+    )?;
+
+    // Output: pool -> swapper
+    let input_mint = pool.input_mint;
+    let output_mint = pool.output_mint;
+    let seeds = &[
+        b"pool".as_ref(),
+        input_mint.as_ref(),
+        output_mint.as_ref(),
+        &[pool.bump],
+    ];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.output_vault.to_account_info(),
+        to: ctx.accounts.user_destination.to_account_info(),
+        authority: ctx.accounts.pool.to_account_info(),
+    };
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&seeds[..]],
+        ),
+        amount_out,
+    )?;
+
+    emit!(MockSwapExecuted {
+        pool: ctx.accounts.pool.key(),
+        amount_in,
+        quoted_amount_out: quoted,
+        amount_out,
+    });
+
+    Ok(())
+}
+
+/// Call back into the configured program with the forwarded accounts
+fn reenter<'info>(pool: &Pool, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+    let (program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(MockSwapError::MissingReentryProgram)?;
+    require_keys_eq!(program.key(), pool.reentry_program, MockSwapError::MissingReentryProgram);
+
+    let instruction = Instruction {
+        program_id: pool.reentry_program,
+        accounts: accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.key(),
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: pool.reentry_data.clone(),
+    };
+
+    msg!("Mock swap re-entering {}", pool.reentry_program);
+    invoke(&instruction, remaining_accounts)?;
+    Ok(())
+}
+
+/// Mock swap execution event
+#[event]
+pub struct MockSwapExecuted {
+    /// Pool account
+    pub pool: Pubkey,
+    /// Input amount received
+    pub amount_in: u64,
+    /// Output amount quoted after fees
+    pub quoted_amount_out: u64,
+    /// Output amount actually delivered
+    pub amount_out: u64,
+}
@@ -0,0 +1,57 @@
+// Anchor 0.31's #[program] expansion still calls the deprecated AccountInfo::realloc
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+// Module declarations
+mod error;
+mod state;
+mod instructions;
+
+// Public exports
+pub use error::*;
+pub use state::*;
+pub use instructions::*;
+
+declare_id!("B5wRy73ZxiVyYygK4H1x9s9rgMgDRxHSW33DZUJDr5ef");
+
+/// Mock swap aggregator used to exercise Sloomo rebalancing without network access
+#[program]
+pub mod mock_swap {
+    use super::*;
+
+    /// Initialize a single-direction pool
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        price_numerator: u64,
+        price_denominator: u64,
+        fee_bps: u16,
+    ) -> Result<()> {
+        instructions::initialize_pool::handler(ctx, price_numerator, price_denominator, fee_bps)
+    }
+
+    /// Configure price, fee and adversarial mode
+    pub fn configure_pool(
+        ctx: Context<ConfigurePool>,
+        params: ConfigurePoolParams,
+    ) -> Result<()> {
+        instructions::configure_pool::handler(ctx, params)
+    }
+
+    /// Add output liquidity
+    pub fn add_liquidity(
+        ctx: Context<AddLiquidity>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity::handler(ctx, amount)
+    }
+
+    /// Swap input tokens for output tokens
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        instructions::swap::handler(ctx, amount_in, minimum_amount_out)
+    }
+}
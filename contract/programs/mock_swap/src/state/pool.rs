@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::error::MockSwapError;
+
+/// Single-direction liquidity pool (input mint -> output mint)
+#[account]
+pub struct Pool {
+    /// Pool administrator (can configure price, fee and mode)
+    pub authority: Pubkey,
+    /// Mint received from the swapper
+    pub input_mint: Pubkey,
+    /// Mint paid out to the swapper
+    pub output_mint: Pubkey,
+    /// Price numerator (output = input * numerator / denominator)
+    pub price_numerator: u64,
+    /// Price denominator
+    pub price_denominator: u64,
+    /// Swap fee (basis points, taken from the output)
+    pub fee_bps: u16,
+    /// Behaviour of the next swaps
+    pub mode: SwapMode,
+    /// Output withheld in ShortOutput mode (basis points of the quote)
+    pub short_output_bps: u16,
+    /// Program invoked in Reentrancy mode
+    pub reentry_program: Pubkey,
+    /// Instruction data sent to the reentry program
+    pub reentry_data: Vec<u8>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Pool {
+    /// Account size calculation
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // input_mint
+        32 + // output_mint
+        8 + // price_numerator
+        8 + // price_denominator
+        2 + // fee_bps
+        1 + // mode
+        2 + // short_output_bps
+        32 + // reentry_program
+        4 + MAX_REENTRY_DATA + // reentry_data
+        1; // bump
+
+    /// Quote the output for `amount_in` after fees (before mode adjustments)
+    pub fn quote(&self, amount_in: u64) -> Result<u64> {
+        let gross = amount_in as u128 * self.price_numerator as u128 / self.price_denominator as u128;
+        let net = gross * (10000 - self.fee_bps as u128) / 10000;
+        u64::try_from(net).map_err(|_| MockSwapError::MathOverflow.into())
+    }
+
+    /// Amount actually delivered for a quoted output in the current mode
+    pub fn delivered(&self, quoted: u64) -> u64 {
+        match self.mode {
+            SwapMode::ShortOutput => {
+                (quoted as u128 * (10000 - self.short_output_bps as u128) / 10000) as u64
+            }
+            _ => quoted,
+        }
+    }
+}
+
+/// Pool behaviour used to simulate aggregator failure cases
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapMode {
+    /// Honest swap at the configured price and fee
+    Normal,
+    /// Delivers less than quoted and ignores `minimum_amount_out`
+    ShortOutput,
+    /// Every swap fails
+    Fail,
+    /// Calls back into `reentry_program` before moving any tokens
+    Reentrancy,
+}
+
+// Constant definitions
+pub const MAX_REENTRY_DATA: usize = 256;
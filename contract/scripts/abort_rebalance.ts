@@ -0,0 +1,94 @@
+/**
+ * リバランスセッション中断スクリプト
+ * キーパーが停止してポートフォリオがロックされたままの場合に使用
+ * Usage: yarn portfolio:abort-rebalance [owner_pubkey]
+ */
+
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda } from "./portfolio_pda";
+
+async function abortRebalance(ownerArg?: string) {
+  try {
+    console.log("=== リバランスセッション中断 ===");
+
+    // プロバイダー設定
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const program = anchor.workspace.SloomoPortfolio as anchor.Program<SloomoPortfolio>;
+    const authority = provider.wallet;
+    // オーナー以外は期限切れのセッションのみ中断できる
+    const owner = ownerArg ? new PublicKey(ownerArg) : authority.publicKey;
+
+    const portfolioPda = await findPortfolioPda(program, owner);
+    const [sessionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), portfolioPda.toBuffer()],
+      program.programId
+    );
+
+    console.log("Portfolio PDA:", portfolioPda.toString());
+    console.log("Session PDA:", sessionPda.toString());
+
+    let session;
+    try {
+      session = await program.account.rebalanceSession.fetch(sessionPda);
+    } catch (error) {
+      console.log("✅ 進行中のリバランスセッションはありません");
+      return;
+    }
+
+    const executed = session.legs.filter((leg) => leg.executed).length;
+    const deadline = session.deadline.toNumber();
+    console.log(`実行済みレッグ: ${executed} / ${session.legs.length}`);
+    console.log("期限:", new Date(deadline * 1000).toLocaleString());
+
+    if (!owner.equals(authority.publicKey) && Date.now() / 1000 <= deadline) {
+      console.log("⚠️  期限前のセッションはオーナーのみ中断できます");
+      return;
+    }
+
+    // 中断時は実際のボールト残高で配分を更新する（ベース資産 + スナップショットの各ミント）
+    const vaultPda = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+        program.programId
+      )[0];
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const vaultAccounts = session.snapshot.slice(1).map((balance) => ({
+      pubkey: vaultPda(balance.mint),
+      isSigner: false,
+      isWritable: false,
+    }));
+
+    const tx = await program.methods
+      .abortRebalance()
+      .accounts({
+        portfolio: portfolioPda,
+        session: sessionPda,
+        owner,
+        authority: authority.publicKey,
+        baseVault: vaultPda(portfolio.baseMint),
+      } as any)
+      .remainingAccounts(vaultAccounts)
+      .rpc();
+
+    console.log("✅ リバランスセッションを中断しました（ポートフォリオのロック解除）");
+    console.log("トランザクション:", tx);
+  } catch (error) {
+    console.error("❌ リバランス中断エラー:");
+    console.error(error);
+
+    if (error.message.includes("RebalanceSessionActive")) {
+      console.log("💡 ヒント: セッションの期限がまだ切れていません");
+    }
+  }
+}
+
+// スクリプト実行
+if (require.main === module) {
+  abortRebalance(process.argv[2]).catch(console.error);
+}
+
+export { abortRebalance };
@@ -6,6 +6,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda } from "./portfolio_pda";
 
 async function addOrUpdateAllocation(symbol?: string, percentage?: number, mintAddress?: string) {
   try {
@@ -21,10 +22,7 @@ async function addOrUpdateAllocation(symbol?: string, percentage?: number, mintA
     console.log("User:", user.publicKey.toString());
 
     // Get Portfolio PDA
-    const [portfolioPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
-      program.programId
-    );
+    const portfolioPda = await findPortfolioPda(program, user.publicKey);
 
     console.log("Portfolio PDA:", portfolioPda.toString());
 
@@ -121,16 +119,23 @@ async function addOrUpdateAllocation(symbol?: string, percentage?: number, mintA
 
     console.log("\nSending allocation add/edit transaction...");
 
+    // Keep the existing band while it still contains the new target
+    const band = existingAllocation?.band;
+    const keepBand =
+      band && band.minPercentage <= targetPercentageBps && targetPercentageBps <= band.maxPercentage;
+
     // Execute allocation add/edit
     const tx = await program.methods
       .addOrUpdateAllocation(
         allocationMint,
         allocationSymbol,
-        targetPercentageBps
+        targetPercentageBps,
+        keepBand ? band : null
       )
       .accounts({
         portfolio: portfolioPda,
-        owner: user.publicKey,
+        authority: user.publicKey,
+        sessionKey: null,
       } as any)
       .rpc();
 
@@ -6,6 +6,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda } from "./portfolio_pda";
 
 async function checkPortfolio() {
   try {
@@ -21,10 +22,7 @@ async function checkPortfolio() {
     console.log("ユーザー:", user.publicKey.toString());
 
     // Portfolio PDA取得
-    const [portfolioPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
-      program.programId
-    );
+    const portfolioPda = await findPortfolioPda(program, user.publicKey);
 
     console.log("Portfolio PDA:", portfolioPda.toString());
 
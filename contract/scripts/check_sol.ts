@@ -7,6 +7,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { getAssociatedTokenAddress, NATIVE_MINT } from "@solana/spl-token";
 import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda } from "./portfolio_pda";
 
 async function checkSol() {
   try {
@@ -22,10 +23,7 @@ async function checkSol() {
     console.log("ユーザー:", user.publicKey.toString());
 
     // Portfolio PDA取得
-    const [portfolioPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
-      program.programId
-    );
+    const portfolioPda = await findPortfolioPda(program, user.publicKey);
 
     console.log("Portfolio PDA:", portfolioPda.toString());
 
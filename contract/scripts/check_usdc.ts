@@ -7,6 +7,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { getAssociatedTokenAddress } from "@solana/spl-token";
 import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda } from "./portfolio_pda";
 
 async function checkUsdc() {
   try {
@@ -22,10 +23,7 @@ async function checkUsdc() {
     console.log("User:", user.publicKey.toString());
 
     // Get Portfolio PDA
-    const [portfolioPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
-      program.programId
-    );
+    const portfolioPda = await findPortfolioPda(program, user.publicKey);
 
     console.log("Portfolio PDA:", portfolioPda.toString());
 
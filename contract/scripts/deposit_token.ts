@@ -8,16 +8,15 @@
  */
 
 import * as anchor from "@coral-xyz/anchor";
-import { PublicKey, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { 
   getAssociatedTokenAddress, 
   TOKEN_PROGRAM_ID, 
   ASSOCIATED_TOKEN_PROGRAM_ID,
-  createAssociatedTokenAccountInstruction,
-  createSyncNativeInstruction,
   NATIVE_MINT
 } from "@solana/spl-token";
 import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda } from "./portfolio_pda";
 
 async function depositToken(amount?: number, tokenType: 'USDC' | 'SOL' = 'USDC') {
   try {
@@ -33,10 +32,7 @@ async function depositToken(amount?: number, tokenType: 'USDC' | 'SOL' = 'USDC')
     console.log("ユーザー:", user.publicKey.toString());
 
     // Portfolio PDA取得
-    const [portfolioPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
-      program.programId
-    );
+    const portfolioPda = await findPortfolioPda(program, user.publicKey);
 
     console.log("Portfolio PDA:", portfolioPda.toString());
 
@@ -146,6 +142,7 @@ async function depositUsdc(program: any, user: any, portfolioPda: PublicKey, amo
         userUsdcAccount: userUsdcAccount,
         portfolioUsdcVault: portfolioUsdcVault,
         usdcMint: usdcMint,
+        baseMint: portfolioData.baseMint,
         owner: user.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -230,126 +227,39 @@ async function depositSol(program: any, user: any, portfolioPda: PublicKey, amou
     // SOLをlamportsに変換
     const depositAmountLamports = Math.floor(depositAmount * LAMPORTS_PER_SOL);
 
-    // ユーザーのwSOLトークンアカウント取得
-    const userWsolAccount = await getAssociatedTokenAddress(
-      wsolMint,
-      user.publicKey
-    );
-
-    // ポートフォリオのwSOLボルト PDA取得
-    const [portfolioWsolVault, vaultBump] = await PublicKey.findProgramAddress(
+    // ポートフォリオのwSOLボルト PDA取得（プログラム側でラップ）
+    const [portfolioWsolVault] = await PublicKey.findProgramAddress(
       [Buffer.from("vault"), portfolioPda.toBuffer(), wsolMint.toBuffer()],
       program.programId
     );
 
     console.log("wSOL Mint:", wsolMint.toString());
-    console.log("User wSOL Account:", userWsolAccount.toString());
     console.log("Portfolio wSOL Vault:", portfolioWsolVault.toString());
 
-    // wSOLトークンアカウントの存在確認
-    const wsolAccountInfo = await program.provider.connection.getAccountInfo(userWsolAccount);
-    const needsWsolAccount = !wsolAccountInfo;
-
     console.log("\n=== 投資情報 ===");
     console.log("投資金額:", depositAmount, "SOL");
     console.log("投資金額 (lamports):", depositAmountLamports);
     console.log("投資前ポートフォリオ総価値:", portfolioData.totalValue.toString(), "lamports");
-    console.log("wSOLアカウント作成必要:", needsWsolAccount);
 
     console.log("\n投資トランザクション送信中...");
 
-    if (needsWsolAccount) {
-      console.log("📝 wSOLトークンアカウントを作成中...");
-      
-      // wSOLアカウント作成
-      const createWsolAccountIx = createAssociatedTokenAccountInstruction(
-        user.publicKey,
-        userWsolAccount,
-        user.publicKey,
-        wsolMint
-      );
-
-      // SOLをwSOLアカウントに送金
-      const transferIx = SystemProgram.transfer({
-        fromPubkey: user.publicKey,
-        toPubkey: userWsolAccount,
-        lamports: depositAmountLamports,
-      });
-
-      // wSOLアカウントをSync（ラップ）
-      const syncNativeIx = createSyncNativeInstruction(userWsolAccount);
-
-      // SOL投資実行（wSOLとして既存のdeposit_usdcメソッドを使用）
-      const depositIx = await program.methods
-        .depositUsdc(new anchor.BN(depositAmountLamports))
-        .accounts({
-          portfolio: portfolioPda,
-          userUsdcAccount: userWsolAccount,
-          portfolioUsdcVault: portfolioWsolVault,
-          usdcMint: wsolMint,
-          owner: user.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-        } as any)
-        .instruction();
-
-      // 複数の命令を1つのトランザクションにまとめる
-      const transaction = new Transaction()
-        .add(createWsolAccountIx)
-        .add(transferIx)
-        .add(syncNativeIx)
-        .add(depositIx);
-
-      const tx = await program.provider.sendAndConfirm(transaction);
-
-      console.log("✅ SOL投資完了（wSOLアカウント作成＋SOLラップ含む）!");
-      console.log("トランザクション:", tx);
-      console.log("Explorer:", `https://explorer.solana.com/tx/${tx}?cluster=devnet`);
-
-    } else {
-      console.log("💎 既存のwSOLアカウントを使用...");
-      
-      // 既存のwSOLアカウントがある場合は、SOLをwSOLアカウントに送金してからSync
-      
-      // SOLをwSOLアカウントに送金
-      const transferIx = SystemProgram.transfer({
-        fromPubkey: user.publicKey,
-        toPubkey: userWsolAccount,
-        lamports: depositAmountLamports,
-      });
-
-      // wSOLアカウントをSync（ラップ）
-      const syncNativeIx = createSyncNativeInstruction(userWsolAccount);
-
-      // SOL投資実行
-      const depositIx = await program.methods
-        .depositUsdc(new anchor.BN(depositAmountLamports))
-        .accounts({
-          portfolio: portfolioPda,
-          userUsdcAccount: userWsolAccount,
-          portfolioUsdcVault: portfolioWsolVault,
-          usdcMint: wsolMint,
-          owner: user.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-        } as any)
-        .instruction();
-
-      // 複数の命令を1つのトランザクションにまとめる
-      const transaction = new Transaction()
-        .add(transferIx)
-        .add(syncNativeIx)
-        .add(depositIx);
-
-      const tx = await program.provider.sendAndConfirm(transaction);
-
-      console.log("✅ SOL投資完了!");
-      console.log("トランザクション:", tx);
-      console.log("Explorer:", `https://explorer.solana.com/tx/${tx}?cluster=devnet`);
+    // SOLを直接ボルトへ送金し、sync_nativeでwSOLとして計上
+    const tx = await program.methods
+      .depositSol(new anchor.BN(depositAmountLamports))
+      .accounts({
+        portfolio: portfolioPda,
+        portfolioWsolVault,
+        wsolMint,
+        baseMint: portfolioData.baseMint,
+        owner: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
 
-    }
+    console.log("✅ SOL投資完了!");
+    console.log("トランザクション:", tx);
+    console.log("Explorer:", `https://explorer.solana.com/tx/${tx}?cluster=devnet`);
 
     // 投資後の状態確認
     const afterData = await program.account.portfolio.fetch(portfolioPda);
@@ -377,7 +287,7 @@ async function depositSol(program: any, user: any, portfolioPda: PublicKey, amou
 
   } catch (solError) {
     console.log("❌ SOL投資エラー");
-    console.log("💡 ヒント: wSOLトークンアカウントの作成が必要な場合があります");
+    console.log("💡 ヒント: SOLがポートフォリオのベース資産またはアロケーションに含まれている必要があります");
     console.error("詳細:", solError.message);
     throw solError;
   }
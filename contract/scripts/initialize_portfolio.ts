@@ -4,8 +4,9 @@
  */
 
 import * as anchor from "@coral-xyz/anchor";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
 import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda, portfolioPdaFor } from "./portfolio_pda";
 
 async function initializePortfolio() {
   try {
@@ -21,14 +22,12 @@ async function initializePortfolio() {
     console.log("User:", user.publicKey.toString());
     console.log("Program ID:", program.programId.toString());
 
-    // Generate Portfolio PDA
-    const [portfolioPda, bump] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
-      program.programId
-    );
+    // Generate Portfolio PDA from a fresh portfolio ID
+    const portfolioId = Keypair.generate().publicKey;
+    const portfolioPda = portfolioPdaFor(program.programId, portfolioId);
 
+    console.log("Portfolio ID:", portfolioId.toString());
     console.log("Portfolio PDA:", portfolioPda.toString());
-    console.log("Bump:", bump);
 
     // Initial allocation settings (e.g., 60:40 split between SOL and USDC)
     const initialAllocations = [
@@ -36,11 +35,13 @@ async function initializePortfolio() {
         mint: new PublicKey("So11111111111111111111111111111111111111112"), // WSOL
         symbol: "SOL",
         targetPercentage: 6000, // 60%
+        band: null,
       },
       {
         mint: new PublicKey("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"), // devnet USDC
         symbol: "USDC", 
         targetPercentage: 4000, // 40%
+        band: null,
       },
     ];
 
@@ -52,7 +53,9 @@ async function initializePortfolio() {
 
     // Check existing portfolio
     try {
-      const existingPortfolio = await program.account.portfolio.fetch(portfolioPda);
+      const existingPortfolio = await program.account.portfolio.fetch(
+        await findPortfolioPda(program, user.publicKey)
+      );
       console.log("⚠️  Portfolio is already initialized");
       console.log("Existing portfolio information:");
       console.log("  Owner:", existingPortfolio.owner.toString());
@@ -67,7 +70,7 @@ async function initializePortfolio() {
     // Execute portfolio initialization
     console.log("Sending initialization transaction...");
     const tx = await program.methods
-      .initializePortfolio({ initialAllocations })
+      .initializePortfolio(portfolioId, { initialAllocations })
       .accounts({
         portfolio: portfolioPda,
         owner: user.publicKey,
@@ -5,8 +5,9 @@
 
 import * as anchor from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID, NATIVE_MINT } from "@solana/spl-token";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+import { findPortfolioPda } from "./portfolio_pda";
 
 async function jupiterRebalance(slippageBps?: number) {
   try {
@@ -22,10 +23,7 @@ async function jupiterRebalance(slippageBps?: number) {
     console.log("ユーザー:", user.publicKey.toString());
 
     // Portfolio PDA取得
-    const [portfolioPda] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
-      program.programId
-    );
+    const portfolioPda = await findPortfolioPda(program, user.publicKey);
 
     console.log("Portfolio PDA:", portfolioPda.toString());
 
@@ -47,21 +45,29 @@ async function jupiterRebalance(slippageBps?: number) {
       return;
     }
 
-    // wSOL設定
-    const wsolMint = NATIVE_MINT; // So11111111111111111111111111111111111111112
-    let wsolTokenAccount;
-    
-    try {
-      wsolTokenAccount = await getAssociatedTokenAddress(
-        wsolMint,
-        user.publicKey
-      );
-      console.log("wSOL Token Account:", wsolTokenAccount.toString());
-    } catch (error) {
-      console.log("❌ wSOL トークンアカウントの取得に失敗しました");
-      console.log("まず SOL をデポジットしてください: yarn portfolio:deposit [amount] SOL");
-      return;
-    }
+    // ベース資産設定（初期化時に選択）とポートフォリオ保有ボルト
+    const baseMint: PublicKey = portfolioData.baseMint;
+    const vaultFor = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+        program.programId
+      )[0];
+    const baseVault = vaultFor(baseMint);
+    console.log("Base Mint:", baseMint.toString());
+    console.log("Base Vault:", baseVault.toString());
+
+    // ベース以外の全アロケーションのボルト（アロケーション順）、続いて同じ順のミント
+    const rebalanceMints = portfolioData.allocations
+      .filter((allocation) => !allocation.mint.equals(baseMint))
+      .map((allocation) => allocation.mint);
+    const vaultAccounts = [
+      ...rebalanceMints.map((mint) => ({
+        pubkey: vaultFor(mint),
+        isSigner: false,
+        isWritable: true,
+      })),
+      ...rebalanceMints.map((mint) => ({ pubkey: mint, isSigner: false, isWritable: false })),
+    ];
 
     // スリッページ設定
     const slippage = slippageBps || 50; // デフォルト0.5%
@@ -127,23 +133,24 @@ async function jupiterRebalance(slippageBps?: number) {
 
     // ユーザー確認（本番環境では重要）
     console.log("\n⚠️  重要: これは実際の資産移動を伴う操作です");
-    console.log("devnet環境での実行のため、実際のスワップはクライアントサイドで別途実行が必要です");
+    console.log("この命令はポートフォリオのボルト残高から計画を算出します（スワップは実行しません）");
 
-    // SOLリバランス実行
-    console.log("\nSOLリバランストランザクション送信中...");
+    // リバランス実行
+    console.log("\nリバランストランザクション送信中...");
     const tx = await program.methods
-      .solJupiterRebalance(targetAllocations, slippage)
+      .rebalance(targetAllocations, slippage)
       .accounts({
         portfolio: portfolioPda,
         owner: user.publicKey,
-        wsolTokenAccount: wsolTokenAccount,
-        wsolMint: wsolMint,
+        baseVault: baseVault,
+        baseMint: baseMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       } as any)
+      .remainingAccounts(vaultAccounts)
       .rpc();
 
-    console.log("✅ SOLベースJupiterリバランス完了!");
+    console.log("✅ Jupiterリバランス完了!");
     console.log("トランザクション:", tx);
     console.log("Explorer:", `https://explorer.solana.com/tx/${tx}?cluster=devnet`);
 
@@ -175,7 +182,7 @@ async function jupiterRebalance(slippageBps?: number) {
     }
 
     console.log("\n⚠️  重要な注意事項:");
-    console.log("このSOLリバランスはオンチェーンでの計算とログ出力のみです");
+    console.log("このリバランスはオンチェーンでの計算とログ出力のみです");
     console.log("実際のJupiterスワップを実行するには、コントラクト内での");
     console.log("Jupiter Rust API統合が必要です");
 
@@ -185,7 +192,7 @@ async function jupiterRebalance(slippageBps?: number) {
     console.log("📈 利回り更新: yarn portfolio:update-yields");
 
   } catch (error) {
-    console.error("❌ SOLベースJupiterリバランスエラー:");
+    console.error("❌ Jupiterリバランスエラー:");
     console.error(error);
     
     if (error.message.includes("NoRebalanceNeeded")) {
@@ -194,6 +201,7 @@ async function jupiterRebalance(slippageBps?: number) {
       console.log("💡 ヒント: 配分の合計が100%になっていません");
     } else if (error.message.includes("RebalanceInProgress")) {
       console.log("💡 ヒント: 既にリバランスが実行中です");
+      console.log("   キーパーが停止した場合は期限後に yarn portfolio:abort-rebalance で解除できます");
     } else if (error.message.includes("RebalanceTooFrequent")) {
       console.log("💡 ヒント: リバランス実行間隔が短すぎます");
     }
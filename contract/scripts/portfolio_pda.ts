@@ -0,0 +1,43 @@
+/**
+ * ポートフォリオPDAの検索（スクリプト共通）
+ *
+ * PDA は初期化時に選んだ portfolio ID から導出されるため、所有者から検索する。
+ * 複数のポートフォリオを持つ場合は PORTFOLIO_ID 環境変数で指定する。
+ */
+
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { SloomoPortfolio } from "../target/types/sloomo_portfolio";
+
+/** portfolio ID から導出される PDA */
+export function portfolioPdaFor(programId: PublicKey, portfolioId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("portfolio"), portfolioId.toBuffer()],
+    programId
+  )[0];
+}
+
+/** 所有者のポートフォリオPDA */
+export async function findPortfolioPda(
+  program: anchor.Program<SloomoPortfolio>,
+  owner: PublicKey
+): Promise<PublicKey> {
+  if (process.env.PORTFOLIO_ID) {
+    return portfolioPdaFor(program.programId, new PublicKey(process.env.PORTFOLIO_ID));
+  }
+
+  try {
+    // owner はディスクリミネータ直後のフィールド
+    const owned = await program.account.portfolio.all([
+      { memcmp: { offset: 8, bytes: owner.toBase58() } },
+    ]);
+    if (owned.length > 0) {
+      return owned[0].publicKey;
+    }
+  } catch (error) {
+    // 未移行（旧レイアウト）のアカウントはデコードできない
+  }
+
+  // portfolio ID 導入前のポートフォリオは所有者の鍵がシード
+  return portfolioPdaFor(program.programId, owner);
+}
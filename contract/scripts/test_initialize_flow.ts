@@ -43,9 +43,10 @@ describe("Full Initialize Flow Test", () => {
   it("Should initialize portfolio and switch to rebalance mode", async () => {
     console.log("🚀 Starting full initialize flow test...");
     
-    // Generate portfolio PDA
+    // Generate portfolio PDA from a fresh portfolio ID
+    const portfolioId = Keypair.generate().publicKey;
     const [portfolioPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
       program.programId
     );
 
@@ -105,7 +106,7 @@ describe("Full Initialize Flow Test", () => {
     // Initialize portfolio with SOL deposit
     console.log("🏛️ Initializing portfolio with SOL deposit...");
     const tx = await program.methods
-      .initializePortfolio({
+      .initializePortfolio(portfolioId, {
         initialAllocations,
         initialSolAmount: new anchor.BN(initialSolAmount),
         enableJupiterSwap: true,
@@ -113,9 +114,9 @@ describe("Full Initialize Flow Test", () => {
       .accounts({
         portfolio: portfolioPda,
         owner: user.publicKey,
-        userWsolAccount: userWsolAccount,
-        portfolioWsolVault: portfolioWsolVault,
-        wsolMint: NATIVE_MINT,
+        userBaseAccount: userWsolAccount,
+        portfolioBaseVault: portfolioWsolVault,
+        baseMint: NATIVE_MINT,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
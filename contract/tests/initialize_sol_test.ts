@@ -25,6 +25,7 @@ describe("Initialize Portfolio with SOL", () => {
 
   const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
   
+  const portfolioId = Keypair.generate().publicKey;
   let portfolioPda: PublicKey;
   let userWsolAccount: PublicKey;
   let portfolioWsolVault: PublicKey;
@@ -48,7 +49,7 @@ describe("Initialize Portfolio with SOL", () => {
   beforeEach(async () => {
     // Generate portfolio PDA
     [portfolioPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
       program.programId
     );
 
@@ -93,7 +94,7 @@ describe("Initialize Portfolio with SOL", () => {
 
       // Initialize portfolio with SOL deposit
       const tx = await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations,
           initialSolAmount: new anchor.BN(initialSolAmount),
           enableJupiterSwap: true,
@@ -101,9 +102,9 @@ describe("Initialize Portfolio with SOL", () => {
         .accounts({
           portfolio: portfolioPda,
           owner: user.publicKey,
-          userWsolAccount: userWsolAccount,
-          portfolioWsolVault: portfolioWsolVault,
-          wsolMint: NATIVE_MINT,
+          userBaseAccount: userWsolAccount,
+          portfolioBaseVault: portfolioWsolVault,
+          baseMint: NATIVE_MINT,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -150,8 +151,9 @@ describe("Initialize Portfolio with SOL", () => {
       await new Promise(resolve => setTimeout(resolve, 1000));
 
       // Generate PDAs for new user
+      const newPortfolioId = Keypair.generate().publicKey;
       const [newPortfolioPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("portfolio"), newUser.publicKey.toBuffer()],
+        [Buffer.from("portfolio"), newPortfolioId.toBuffer()],
         program.programId
       );
 
@@ -186,7 +188,7 @@ describe("Initialize Portfolio with SOL", () => {
 
       // Initialize portfolio without Jupiter swap
       const tx = await program.methods
-        .initializePortfolio({
+        .initializePortfolio(newPortfolioId, {
           initialAllocations,
           initialSolAmount: new anchor.BN(initialSolAmount),
           enableJupiterSwap: false,
@@ -194,9 +196,9 @@ describe("Initialize Portfolio with SOL", () => {
         .accounts({
           portfolio: newPortfolioPda,
           owner: newUser.publicKey,
-          userWsolAccount: newUserWsolAccount,
-          portfolioWsolVault: newPortfolioWsolVault,
-          wsolMint: NATIVE_MINT,
+          userBaseAccount: newUserWsolAccount,
+          portfolioBaseVault: newPortfolioWsolVault,
+          baseMint: NATIVE_MINT,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -0,0 +1,167 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// 配分ごとに許容バンド（例: 目標30%、バンド25〜35%）を持ち、
+// バンドを外れた資産だけがリバランスの対象になる
+describe("Allocation Band Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let user: Keypair;
+  let baseMint: PublicKey;
+  let equityMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let portfolioPda: PublicKey;
+
+  const band = (minPercentage: number, maxPercentage: number) => ({
+    minPercentage,
+    maxPercentage,
+  });
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const initialize = (owner: Keypair, equityBand: { minPercentage: number; maxPercentage: number }) => {
+    const [portfolio] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    const [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolio.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+    return program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 7000, band: band(6800, 7200) },
+          { mint: equityMint, symbol: "EQUITY", targetPercentage: 3000, band: equityBand },
+        ],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio,
+        owner: owner.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+  };
+
+  const updateAllocation = (
+    targetPercentage: number,
+    allocationBand: { minPercentage: number; maxPercentage: number } | null
+  ) =>
+    program.methods
+      .addOrUpdateAllocation(equityMint, "EQUITY", targetPercentage, allocationBand)
+      .accounts({
+        portfolio: portfolioPda,
+        authority: user.publicKey,
+        sessionKey: null,
+      } as any)
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    equityMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("目標を含まないバンドでは初期化できない", async () => {
+    await expectError(initialize(user, band(3500, 4000)), "InvalidAllocationBand");
+  });
+
+  it("配分ごとのバンドを指定して初期化できる", async () => {
+    await initialize(user, band(2500, 3500));
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const equity = portfolio.allocations.find((a) => a.mint.equals(equityMint));
+    expect(equity.band).to.deep.equal(band(2500, 3500));
+    expect(portfolio.bandRestore).to.have.property("edge");
+  });
+
+  it("目標とバンドをまとめて更新できる", async () => {
+    await updateAllocation(2000, band(1000, 3000));
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const equity = portfolio.allocations.find((a) => a.mint.equals(equityMint));
+    expect(equity.targetPercentage).to.equal(2000);
+    expect(equity.band).to.deep.equal(band(1000, 3000));
+  });
+
+  it("100%を超えるバンドは拒否される", async () => {
+    await expectError(updateAllocation(3000, band(2500, 10001)), "InvalidAllocationBand");
+  });
+
+  it("バンドを外すとデフォルトの5%閾値に戻る", async () => {
+    await updateAllocation(3000, null);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const equity = portfolio.allocations.find((a) => a.mint.equals(equityMint));
+    expect(equity.band).to.be.null;
+  });
+
+  it("オーナーはバンド外の資産を目標まで戻す設定に切り替えられる", async () => {
+    await program.methods
+      .setBandRestore({ target: {} })
+      .accounts({ portfolio: portfolioPda, owner: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.bandRestore).to.have.property("target");
+  });
+
+  it("オーナー以外は戻し方を変更できない", async () => {
+    const stranger = Keypair.generate();
+    await expectError(
+      program.methods
+        .setBandRestore({ edge: {} })
+        .accounts({ portfolio: portfolioPda, owner: stranger.publicKey } as any)
+        .signers([stranger])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+  });
+});
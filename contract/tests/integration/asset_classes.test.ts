@@ -0,0 +1,179 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// 配分を資産クラス（例: 株式60%、ステーブル40%）にまとめ、
+// クラス目標 × クラス内ウェイトが各ミントの実効目標になる
+describe("Asset Class Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let user: Keypair;
+  let baseMint: PublicKey;
+  let equityMintA: PublicKey;
+  let equityMintB: PublicKey;
+  let userBaseAccount: PublicKey;
+  let portfolioPda: PublicKey;
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const classes = (equityTarget: number, stableTarget: number) => [
+    { name: "EQUITIES", targetPercentage: equityTarget, band: null },
+    { name: "STABLES", targetPercentage: stableTarget, band: null },
+  ];
+
+  const memberships = (weightA: number, weightB: number) => [
+    { mint: equityMintA, assetClass: 0, weight: weightA },
+    { mint: equityMintB, assetClass: 0, weight: weightB },
+    { mint: baseMint, assetClass: 1, weight: 10000 },
+  ];
+
+  const setAssetClasses = (
+    assetClasses: ReturnType<typeof classes>,
+    members: ReturnType<typeof memberships>
+  ) =>
+    program.methods
+      .setAssetClasses(assetClasses, members)
+      .accounts({
+        portfolio: portfolioPda,
+        authority: user.publicKey,
+        sessionKey: null,
+      } as any)
+      .signers([user])
+      .rpc();
+
+  const targets = async () => {
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const target = (mint: PublicKey) =>
+      portfolio.allocations.find((a) => a.mint.equals(mint)).targetPercentage;
+    return [target(equityMintA), target(equityMintB), target(baseMint)];
+  };
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    equityMintA = await createMint(provider.connection, user, user.publicKey, null, 6);
+    equityMintB = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    const [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 4000, band: null },
+          { mint: equityMintA, symbol: "EQ-A", targetPercentage: 3000, band: null },
+          { mint: equityMintB, symbol: "EQ-B", targetPercentage: 3000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("クラス内ウェイトの合計が100%でなければ拒否される", async () => {
+    await expectError(setAssetClasses(classes(6000, 4000), memberships(6000, 3000)), "InvalidAssetClass");
+  });
+
+  it("クラス目標の合計が100%を超えると拒否される", async () => {
+    await expectError(
+      setAssetClasses(classes(7000, 4000), memberships(5000, 5000)),
+      "AllocationOverflow"
+    );
+  });
+
+  it("資産クラスを設定すると実効目標が計算される", async () => {
+    await setAssetClasses(classes(6000, 4000), memberships(7000, 3000));
+
+    expect(await targets()).to.deep.equal([4200, 1800, 4000]);
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.assetClasses.map((c) => c.name)).to.deep.equal(["EQUITIES", "STABLES"]);
+    const equity = portfolio.allocations.find((a) => a.mint.equals(equityMintA));
+    expect(equity.assetClass).to.deep.equal({ assetClass: 0, weight: 7000 });
+  });
+
+  it("クラスに属する配分の目標は直接変更できない", async () => {
+    await expectError(
+      program.methods
+        .addOrUpdateAllocation(equityMintA, "EQ-A", 5000, null)
+        .accounts({
+          portfolio: portfolioPda,
+          authority: user.publicKey,
+          sessionKey: null,
+        } as any)
+        .signers([user])
+        .rpc(),
+      "AllocationInAssetClass"
+    );
+  });
+
+  it("クラス目標の変更はメンバー全体に反映される", async () => {
+    await setAssetClasses(classes(5000, 5000), memberships(7000, 3000));
+
+    expect(await targets()).to.deep.equal([3500, 1500, 5000]);
+  });
+
+  it("資産クラスを外すとフラットな配分に戻る", async () => {
+    await program.methods
+      .setAssetClasses([], [])
+      .accounts({
+        portfolio: portfolioPda,
+        authority: user.publicKey,
+        sessionKey: null,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.assetClasses).to.be.empty;
+    expect(portfolio.allocations.every((a) => a.assetClass === null)).to.be.true;
+    expect(await targets()).to.deep.equal([3500, 1500, 5000]);
+  });
+});
@@ -0,0 +1,304 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { MockSwap } from "../../target/types/mock_swap";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  AccountMeta,
+} from "@solana/web3.js";
+import {
+  createMint,
+  createAccount,
+  createAssociatedTokenAccount,
+  mintTo,
+  approve,
+  getAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// ベース資産での引き出し：不足分はアロケーションを売却して賄い、満たせなければ全体が失敗する
+describe("Base Asset Withdrawal Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const mockSwap = anchor.workspace.MockSwap as Program<MockSwap>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let user: Keypair;
+  let portfolioPda: PublicKey;
+  let baseMint: PublicKey;
+  let assetMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let userAssetAccount: PublicKey;
+  let poolPda: PublicKey;
+
+  const vaultOf = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const balanceOf = async (account: PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  const withdrawToBase = (
+    amount: number,
+    swaps: any[] = [],
+    swapProgram: PublicKey = mockSwap.programId,
+    swapAccounts: AccountMeta[] = []
+  ) =>
+    program.methods
+      .withdrawToBase(new anchor.BN(amount), { proportional: {} }, 50, swaps)
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        baseVault: vaultOf(baseMint),
+        userBaseAccount,
+        baseMint,
+        swapProgram,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts([
+        { pubkey: vaultOf(assetMint), isSigner: false, isWritable: true },
+        { pubkey: assetMint, isSigner: false, isWritable: false },
+        ...swapAccounts,
+      ])
+      .signers([user])
+      .rpc();
+
+  const poolVaultOf = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), poolPda.toBuffer(), mint.toBuffer()],
+      mockSwap.programId
+    )[0];
+
+  // 資産ボルトからベースボルトへのモックスワップ（ポートフォリオPDAが署名する）
+  const sellAsset = async (amountIn: number, userSource: PublicKey = vaultOf(assetMint)) => {
+    const ix = await mockSwap.methods
+      .swap(new anchor.BN(amountIn), new anchor.BN(0))
+      .accounts({
+        pool: poolPda,
+        userAuthority: portfolioPda,
+        userSource,
+        userDestination: vaultOf(baseMint),
+        inputVault: poolVaultOf(assetMint),
+        outputVault: poolVaultOf(baseMint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .instruction();
+    return {
+      swap: {
+        fromMint: assetMint,
+        amountIn: new anchor.BN(amountIn),
+        accountCount: ix.keys.length,
+        data: ix.data,
+      },
+      accounts: ix.keys.map((key) => ({ ...key, isSigner: false })),
+    };
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      10 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    assetMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    userAssetAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      assetMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+    await mintTo(provider.connection, user, assetMint, userAssetAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 5000, band: null },
+          { mint: assetMint, symbol: "ASSET", targetPercentage: 5000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(400_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: vaultOf(baseMint),
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .depositToken(new anchor.BN(200_000_000))
+      .accounts({
+        portfolio: portfolioPda,
+        userTokenAccount: userAssetAccount,
+        portfolioVault: vaultOf(assetMint),
+        mint: assetMint,
+        baseMint,
+        authority: user.publicKey,
+        sessionKey: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    // 売却はローカルネットに配置したモックスワップ経由
+    await program.methods
+      .setSwapPrograms([mockSwap.programId])
+      .accounts({ portfolio: portfolioPda, owner: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+    // 1:1、手数料 0.25% のプール
+    [poolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), assetMint.toBuffer(), baseMint.toBuffer()],
+      mockSwap.programId
+    );
+    await mockSwap.methods
+      .initializePool(new anchor.BN(1), new anchor.BN(1), 25)
+      .accounts({
+        pool: poolPda,
+        inputVault: poolVaultOf(assetMint),
+        outputVault: poolVaultOf(baseMint),
+        inputMint: assetMint,
+        outputMint: baseMint,
+        authority: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+    await mockSwap.methods
+      .addLiquidity(new anchor.BN(200_000_000))
+      .accounts({
+        pool: poolPda,
+        authorityTokenAccount: userBaseAccount,
+        outputVault: poolVaultOf(baseMint),
+        authority: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("許可リストにないスワッププログラムは拒否される", async () => {
+    await expectError(withdrawToBase(100_000_000, [], TOKEN_PROGRAM_ID), "InvalidSwapProgram");
+  });
+
+  it("ベースボルトで賄える額はスワップなしで支払われる", async () => {
+    const before = await balanceOf(userBaseAccount);
+
+    await withdrawToBase(100_000_000);
+
+    expect((await balanceOf(userBaseAccount)) - before).to.equal(100_000_000);
+    expect(await balanceOf(vaultOf(baseMint))).to.equal(300_000_000);
+    expect(await balanceOf(vaultOf(assetMint))).to.equal(200_000_000);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.totalValue.toNumber()).to.equal(500_000_000);
+  });
+
+  it("売却で不足分を満たせない場合は何も引き出されない", async () => {
+    const before = await balanceOf(userBaseAccount);
+
+    await expectError(withdrawToBase(400_000_000), "InsufficientLiquidity");
+
+    expect(await balanceOf(userBaseAccount)).to.equal(before);
+    expect(await balanceOf(vaultOf(baseMint))).to.equal(300_000_000);
+  });
+
+  it("保有資産の合計を超える引き出しは拒否される", async () => {
+    await expectError(withdrawToBase(600_000_000), "InsufficientLiquidity");
+  });
+
+  it("計画を超える売却や計画外のミントの売却は拒否される", async () => {
+    const swap = (fromMint: PublicKey, amountIn: number) => ({
+      fromMint,
+      amountIn: new anchor.BN(amountIn),
+      accountCount: 0,
+      data: Buffer.from([]),
+    });
+
+    // 不足分 100,000,000 をスリッページ込みで賄う売却枠を超える
+    await expectError(
+      withdrawToBase(400_000_000, [swap(assetMint, 200_000_000)]),
+      "LiquidationExceedsPlan"
+    );
+    await expectError(
+      withdrawToBase(400_000_000, [swap(baseMint, 1)]),
+      "InvalidTokenMint"
+    );
+  });
+
+  it("ポートフォリオに委任されたトークンアカウントをスワップに渡すと拒否される", async () => {
+    const delegated = await createAccount(
+      provider.connection,
+      user,
+      assetMint,
+      user.publicKey,
+      Keypair.generate()
+    );
+    await mintTo(provider.connection, user, assetMint, delegated, user.publicKey, 100_500_000);
+    await approve(provider.connection, user, delegated, portfolioPda, user, 100_500_000);
+
+    const { swap, accounts } = await sellAsset(100_500_000, delegated);
+    await expectError(
+      withdrawToBase(400_000_000, [swap], mockSwap.programId, accounts),
+      "UnauthorizedSwapAccount"
+    );
+    expect(await balanceOf(delegated)).to.equal(100_500_000);
+  });
+
+  it("不足分はモックスワップでの売却で賄われる", async () => {
+    const before = await balanceOf(userBaseAccount);
+    const { swap, accounts } = await sellAsset(100_500_000);
+
+    await withdrawToBase(400_000_000, [swap], mockSwap.programId, accounts);
+
+    // 100,500,000 の売却で手数料 0.25% 控除後 100,248,750 を受け取る
+    expect((await balanceOf(userBaseAccount)) - before).to.equal(400_000_000);
+    expect(await balanceOf(vaultOf(baseMint))).to.equal(248_750);
+    expect(await balanceOf(vaultOf(assetMint))).to.equal(99_500_000);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.totalValue.toNumber()).to.equal(99_748_750);
+    const asset = portfolio.allocations.find((a) => a.mint.equals(assetMint));
+    expect(asset.currentAmount.toNumber()).to.equal(99_500_000);
+  });
+});
@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// 入金ごとに取得原価（平均法）を記帳し、get_pnl は呼び出し側の価格で
+// 含み損益と実現損益を return data で返す
+describe("Cost Basis and P&L Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  const INITIAL_AMOUNT = 100_000_000;
+  const DEPOSIT_AMOUNT = 20_000_000;
+  const PRICE_SCALE = 1_000_000_000;
+
+  let user: Keypair;
+  let baseMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let portfolioPda: PublicKey;
+  let baseVault: PublicKey;
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      const logs = error.simulationResponse?.logs ?? error.logs ?? [];
+      expect([error.toString(), ...logs].join("\n")).to.include(code);
+    }
+  };
+
+  const getPnl = (prices: { mint: PublicKey; price: anchor.BN }[]) =>
+    program.methods
+      .getPnl(prices)
+      .accounts({ portfolio: portfolioPda } as any)
+      .view();
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(INITIAL_AMOUNT),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("初回入金はベース資産の取得原価になる", async () => {
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.allocations[0].costBasis.toNumber()).to.equal(INITIAL_AMOUNT);
+    expect(portfolio.allocations[0].realizedPnl.toNumber()).to.equal(0);
+
+    // ベース資産は価格を渡さなくても 1単位 = 1ベース単位で評価され、損益は0
+    const report = await getPnl([]);
+    expect(report.costBasis.toNumber()).to.equal(INITIAL_AMOUNT);
+    expect(report.value.toNumber()).to.equal(INITIAL_AMOUNT);
+    expect(report.unrealizedPnl.toNumber()).to.equal(0);
+    expect(report.allocations[0].price.toNumber()).to.equal(PRICE_SCALE);
+    expect(report.allocations[0].averageCost.toNumber()).to.equal(PRICE_SCALE);
+  });
+
+  it("入金額が取得原価に加算される", async () => {
+    await program.methods
+      .depositToken(new anchor.BN(DEPOSIT_AMOUNT))
+      .accounts({
+        portfolio: portfolioPda,
+        userTokenAccount: userBaseAccount,
+        portfolioVault: baseVault,
+        mint: baseMint,
+        baseMint,
+        authority: user.publicKey,
+        sessionKey: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.allocations[0].currentAmount.toNumber()).to.equal(
+      INITIAL_AMOUNT + DEPOSIT_AMOUNT
+    );
+    expect(portfolio.allocations[0].costBasis.toNumber()).to.equal(
+      INITIAL_AMOUNT + DEPOSIT_AMOUNT
+    );
+  });
+
+  it("渡した価格で含み損益を計算する", async () => {
+    // 1単位 = 1.1 ベース単位
+    const report = await getPnl([
+      { mint: baseMint, price: new anchor.BN(PRICE_SCALE + PRICE_SCALE / 10) },
+    ]);
+
+    const held = INITIAL_AMOUNT + DEPOSIT_AMOUNT;
+    expect(report.allocations[0].value.toNumber()).to.equal(held + held / 10);
+    expect(report.allocations[0].unrealizedPnl.toNumber()).to.equal(held / 10);
+    expect(report.unrealizedPnl.toNumber()).to.equal(held / 10);
+    expect(report.realizedPnl.toNumber()).to.equal(0);
+  });
+
+  it("同じミントの価格が重複すると失敗する", async () => {
+    const price = { mint: baseMint, price: new anchor.BN(PRICE_SCALE) };
+    await expectError(getPnl([price, price]), "InvalidTokenMint");
+  });
+});
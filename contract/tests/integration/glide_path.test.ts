@@ -0,0 +1,183 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// 目標日に向けて配分を開始配分から終了配分へ線形に移す（例: 株式80% → 30%）
+// ドリフト判定とリバランス計画は現在時刻で補間した目標を使う
+describe("Glide Path Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  const YEAR = 365 * 24 * 60 * 60;
+
+  let user: Keypair;
+  let baseMint: PublicKey;
+  let equityMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let portfolioPda: PublicKey;
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const now = () => Math.floor(Date.now() / 1000);
+
+  const targets = (equity: number, base: number) => [
+    { mint: equityMint, targetPercentage: equity },
+    { mint: baseMint, targetPercentage: base },
+  ];
+
+  const setGlidePath = (
+    start: ReturnType<typeof targets>,
+    end: ReturnType<typeof targets>,
+    targetDate: number,
+    stepBps = 500
+  ) =>
+    program.methods
+      .setGlidePath(start, end, new anchor.BN(targetDate), stepBps)
+      .accounts({
+        portfolio: portfolioPda,
+        authority: user.publicKey,
+        sessionKey: null,
+      } as any)
+      .signers([user])
+      .rpc();
+
+  const advance = () =>
+    program.methods
+      .advanceGlidePath()
+      .accounts({ portfolio: portfolioPda, cranker: provider.publicKey } as any)
+      .rpc();
+
+  const storedTargets = async () => {
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const target = (mint: PublicKey) =>
+      portfolio.allocations.find((a) => a.mint.equals(mint)).targetPercentage;
+    return [target(equityMint), target(baseMint)];
+  };
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    equityMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    const [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 5000, band: null },
+          { mint: equityMint, symbol: "EQUITY", targetPercentage: 5000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("過去の目標日は拒否される", async () => {
+    await expectError(setGlidePath(targets(8000, 2000), targets(3000, 7000), now() - 60), "InvalidGlidePath");
+  });
+
+  it("開始配分と終了配分のミントが一致しなければ拒否される", async () => {
+    await expectError(
+      setGlidePath(targets(8000, 2000), [{ mint: equityMint, targetPercentage: 3000 }], now() + YEAR),
+      "InvalidGlidePath"
+    );
+  });
+
+  it("グライドパスを設定すると開始配分が適用される", async () => {
+    const targetDate = now() + YEAR;
+    await setGlidePath(targets(8000, 2000), targets(3000, 7000), targetDate);
+
+    expect(await storedTargets()).to.deep.equal([8000, 2000]);
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.glidePath.targetDate.toNumber()).to.equal(targetDate);
+    expect(portfolio.glidePath.stepBps).to.equal(500);
+    expect(portfolio.glidePath.legs).to.have.length(2);
+  });
+
+  it("グライドパス上の配分の目標は直接変更できない", async () => {
+    await expectError(
+      program.methods
+        .addOrUpdateAllocation(equityMint, "EQUITY", 6000, null)
+        .accounts({
+          portfolio: portfolioPda,
+          authority: user.publicKey,
+          sessionKey: null,
+        } as any)
+        .signers([user])
+        .rpc(),
+      "AllocationOnGlidePath"
+    );
+  });
+
+  it("補間した目標の移動がステップ以内なら保存済みの目標は変わらない", async () => {
+    await advance();
+
+    expect(await storedTargets()).to.deep.equal([8000, 2000]);
+  });
+
+  it("目標日を過ぎると誰でも終了配分まで進められる", async () => {
+    await setGlidePath(targets(8000, 2000), targets(3000, 7000), now() + 2);
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    await advance();
+
+    expect(await storedTargets()).to.deep.equal([3000, 7000]);
+  });
+
+  it("空の開始・終了配分でグライドパスを外すと現在の目標が残る", async () => {
+    await setGlidePath([], [], 0);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.glidePath).to.be.null;
+    expect(await storedTargets()).to.deep.equal([3000, 7000]);
+  });
+});
@@ -0,0 +1,196 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// ガーディアンによる復旧：閾値の承認後、タイムロック中はオーナーが拒否できる
+describe("Guardian Recovery Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  const TIMELOCK = 24 * 60 * 60;
+
+  let owner: Keypair;
+  let guardians: Keypair[];
+  let newOwner: Keypair;
+  let stranger: Keypair;
+  let portfolioPda: PublicKey;
+  let guardianSetPda: PublicKey;
+
+  const airdrop = async (key: PublicKey) => {
+    const signature = await provider.connection.requestAirdrop(key, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const setGuardians = (keys: PublicKey[], threshold: number, timelock = TIMELOCK) =>
+    program.methods
+      .setGuardians(keys, threshold, new anchor.BN(timelock))
+      .accounts({
+        portfolio: portfolioPda,
+        guardianSet: guardianSetPda,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([owner])
+      .rpc();
+
+  const initiate = (guardian: Keypair, proposed: PublicKey) =>
+    program.methods
+      .initiateRecovery(proposed)
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, guardian: guardian.publicKey } as any)
+      .signers([guardian])
+      .rpc();
+
+  const approve = (guardian: Keypair) =>
+    program.methods
+      .approveRecovery()
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, guardian: guardian.publicKey } as any)
+      .signers([guardian])
+      .rpc();
+
+  const veto = (signer: Keypair) =>
+    program.methods
+      .vetoRecovery()
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, owner: signer.publicKey } as any)
+      .signers([signer])
+      .rpc();
+
+  const complete = (signer: Keypair) =>
+    program.methods
+      .completeRecovery()
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, executor: signer.publicKey } as any)
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    owner = Keypair.generate();
+    guardians = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    newOwner = Keypair.generate();
+    stranger = Keypair.generate();
+    for (const key of [owner, newOwner, stranger, ...guardians]) {
+      await airdrop(key.publicKey);
+    }
+
+    const baseMint = await createMint(provider.connection, owner, owner.publicKey, null, 6);
+    const userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      owner,
+      baseMint,
+      owner.publicKey
+    );
+    await mintTo(provider.connection, owner, baseMint, userBaseAccount, owner.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [guardianSetPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("guardians"), portfolioPda.toBuffer()],
+      program.programId
+    );
+    const [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [{ mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null }],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: owner.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+  });
+
+  it("不正なガーディアン設定は拒否される", async () => {
+    const keys = guardians.map((g) => g.publicKey);
+    await expectError(setGuardians(keys, 4), "InvalidGuardianSet");
+    await expectError(setGuardians(keys, 2, TIMELOCK - 1), "InvalidGuardianSet");
+    await expectError(setGuardians([owner.publicKey, ...keys], 2), "InvalidGuardianSet");
+
+    await setGuardians(keys, 2);
+    const guardianSet = await program.account.guardianSet.fetch(guardianSetPda);
+    expect(guardianSet.threshold).to.equal(2);
+    expect(guardianSet.guardians.length).to.equal(3);
+  });
+
+  it("ガーディアン以外は復旧を開始できない", async () => {
+    await expectError(initiate(stranger, newOwner.publicKey), "NotAGuardian");
+  });
+
+  it("閾値未満の復旧は完了できない", async () => {
+    await initiate(guardians[0], newOwner.publicKey);
+    await expectError(approve(guardians[0]), "RecoveryAlreadyApproved");
+    await expectError(complete(newOwner), "RecoveryNotApproved");
+  });
+
+  it("タイムロック中は完了できず、オーナーが拒否できる", async () => {
+    await approve(guardians[1]);
+    const guardianSet = await program.account.guardianSet.fetch(guardianSetPda);
+    const recovery = guardianSet.recovery;
+    expect(recovery.approvedAt.toNumber() + TIMELOCK).to.be.greaterThan(Date.now() / 1000);
+
+    await expectError(complete(newOwner), "RecoveryTimelockActive");
+    await expectError(veto(stranger), "ConstraintHasOne");
+
+    await veto(owner);
+    expect((await program.account.guardianSet.fetch(guardianSetPda)).recovery).to.be.null;
+    await expectError(complete(newOwner), "NoPendingRecovery");
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.owner.equals(owner.publicKey)).to.be.true;
+  });
+
+  it("オーナー移転で進行中の復旧は破棄される", async () => {
+    const successor = Keypair.generate();
+    await initiate(guardians[0], newOwner.publicKey);
+    await approve(guardians[1]);
+
+    await program.methods
+      .proposeOwnerTransfer(successor.publicKey)
+      .accounts({ portfolio: portfolioPda, owner: owner.publicKey } as any)
+      .signers([owner])
+      .rpc();
+    await program.methods
+      .acceptOwnerTransfer()
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, newOwner: successor.publicKey } as any)
+      .signers([successor])
+      .rpc();
+
+    // 旧オーナーに対して合意された復旧は新オーナーには適用されない
+    expect((await program.account.guardianSet.fetch(guardianSetPda)).recovery).to.be.null;
+    await expectError(complete(newOwner), "NoPendingRecovery");
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.owner.equals(successor.publicKey)).to.be.true;
+  });
+});
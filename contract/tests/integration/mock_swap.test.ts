@@ -0,0 +1,205 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { MockSwap } from "../../target/types/mock_swap";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// オフライン（localnet）でリバランスを検証するためのモックアグリゲーター
+describe("Mock Swap Aggregator Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const mockSwap = anchor.workspace.MockSwap as Program<MockSwap>;
+  const provider = anchor.getProvider();
+
+  let user: Keypair;
+  let inputMint: PublicKey;
+  let outputMint: PublicKey;
+  let userInputAccount: PublicKey;
+  let userOutputAccount: PublicKey;
+  let poolPda: PublicKey;
+  let inputVault: PublicKey;
+  let outputVault: PublicKey;
+
+  const normalConfig = {
+    priceNumerator: new anchor.BN(2),
+    priceDenominator: new anchor.BN(1),
+    feeBps: 30,
+    mode: { normal: {} },
+    shortOutputBps: 0,
+    reentryProgram: PublicKey.default,
+    reentryData: Buffer.alloc(0),
+  };
+
+  const configure = (overrides: object) =>
+    mockSwap.methods
+      .configurePool({ ...normalConfig, ...overrides } as any)
+      .accounts({ pool: poolPda, authority: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+  const swap = (amountIn: number, minimumAmountOut: number, remainingAccounts = []) =>
+    mockSwap.methods
+      .swap(new anchor.BN(amountIn), new anchor.BN(minimumAmountOut))
+      .accounts({
+        pool: poolPda,
+        userAuthority: user.publicKey,
+        userSource: userInputAccount,
+        userDestination: userOutputAccount,
+        inputVault,
+        outputVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .signers([user])
+      .rpc();
+
+  const outputBalance = async () =>
+    Number((await getAccount(provider.connection, userOutputAccount)).amount);
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    inputMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    outputMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userInputAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      inputMint,
+      user.publicKey
+    );
+    userOutputAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      outputMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, inputMint, userInputAccount, user.publicKey, 1_000_000_000);
+    await mintTo(provider.connection, user, outputMint, userOutputAccount, user.publicKey, 10_000_000_000);
+
+    [poolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), inputMint.toBuffer(), outputMint.toBuffer()],
+      mockSwap.programId
+    );
+    [inputVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), poolPda.toBuffer(), inputMint.toBuffer()],
+      mockSwap.programId
+    );
+    [outputVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), poolPda.toBuffer(), outputMint.toBuffer()],
+      mockSwap.programId
+    );
+
+    // 価格 2.0、手数料 0.3% のプールを作成
+    await mockSwap.methods
+      .initializePool(new anchor.BN(2), new anchor.BN(1), 30)
+      .accounts({
+        pool: poolPda,
+        inputVault,
+        outputVault,
+        inputMint,
+        outputMint,
+        authority: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    await mockSwap.methods
+      .addLiquidity(new anchor.BN(5_000_000_000))
+      .accounts({
+        pool: poolPda,
+        authorityTokenAccount: userOutputAccount,
+        outputVault,
+        authority: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  afterEach(async () => {
+    await configure({});
+  });
+
+  it("設定した価格と手数料でスワップできる", async () => {
+    const before = await outputBalance();
+    await swap(1_000_000, 1_994_000);
+    // 1,000,000 * 2 * (1 - 0.003) = 1,994,000
+    expect((await outputBalance()) - before).to.equal(1_994_000);
+  });
+
+  it("最小受取量を下回る場合はスリッページエラーになる", async () => {
+    try {
+      await swap(1_000_000, 1_994_001);
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include("SlippageExceeded");
+    }
+  });
+
+  it("ShortOutputモードでは見積もりより少なく受け取り、最小受取量を無視する", async () => {
+    await configure({ mode: { shortOutput: {} }, shortOutputBps: 1000 });
+
+    const before = await outputBalance();
+    await swap(1_000_000, 1_994_000);
+    // 見積もりの90%のみ受け取る（呼び出し側で残高差分の検証が必要）
+    expect((await outputBalance()) - before).to.equal(1_794_600);
+  });
+
+  it("Failモードではスワップが失敗する", async () => {
+    await configure({ mode: { fail: {} } });
+
+    try {
+      await swap(1_000_000, 0);
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include("ForcedFailure");
+    }
+  });
+
+  it("Reentrancyモードでは設定したプログラムを呼び出してからスワップする", async () => {
+    const [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), Keypair.generate().publicKey.toBuffer()],
+      program.programId
+    );
+
+    // 再入先として sloomo の add_or_update_allocation を呼び出す
+    const reentryIx = await program.methods
+      .addOrUpdateAllocation(Keypair.generate().publicKey, "REENTRY", 1000, null)
+      .accounts({ portfolio: portfolioPda, authority: user.publicKey, sessionKey: null } as any)
+      .instruction();
+    await configure({
+      mode: { reentrancy: {} },
+      reentryProgram: program.programId,
+      reentryData: reentryIx.data,
+    });
+
+    // ポートフォリオ未作成のため再入呼び出しが失敗し、スワップ全体がロールバックされる
+    const before = await outputBalance();
+    try {
+      await swap(1_000_000, 0, [
+        { pubkey: program.programId, isSigner: false, isWritable: false },
+        ...reentryIx.keys,
+      ]);
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include("AccountNotInitialized");
+    }
+    expect(await outputBalance()).to.equal(before);
+  });
+});
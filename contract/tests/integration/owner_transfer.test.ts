@@ -0,0 +1,179 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// 二段階のオーナー移転：PDAとボルトはそのままで、オーナーだけが切り替わる
+describe("Owner Transfer Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let oldOwner: Keypair;
+  let newOwner: Keypair;
+  let stranger: Keypair;
+  let portfolioPda: PublicKey;
+  let baseMint: PublicKey;
+  let baseVault: PublicKey;
+
+  const airdrop = async (key: PublicKey) => {
+    const signature = await provider.connection.requestAirdrop(key, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const propose = (owner: Keypair, proposed: PublicKey) =>
+    program.methods
+      .proposeOwnerTransfer(proposed)
+      .accounts({ portfolio: portfolioPda, owner: owner.publicKey } as any)
+      .signers([owner])
+      .rpc();
+
+  const accept = (signer: Keypair) =>
+    program.methods
+      .acceptOwnerTransfer()
+      .accounts({
+        portfolio: portfolioPda,
+        guardianSet: PublicKey.findProgramAddressSync(
+          [Buffer.from("guardians"), portfolioPda.toBuffer()],
+          program.programId
+        )[0],
+        newOwner: signer.publicKey,
+      } as any)
+      .signers([signer])
+      .rpc();
+
+  const setRouting = (owner: Keypair, enabled: boolean) =>
+    program.methods
+      .setDepositRouting(enabled)
+      .accounts({ portfolio: portfolioPda, owner: owner.publicKey } as any)
+      .signers([owner])
+      .rpc();
+
+  const vaultOf = (portfolio: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolio.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    )[0];
+
+  const initialize = (id: PublicKey, portfolio: PublicKey) =>
+    program.methods
+      .initializePortfolio(id, {
+        initialAllocations: [{ mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null }],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio,
+        owner: oldOwner.publicKey,
+        userBaseAccount: getAssociatedTokenAddressSync(baseMint, oldOwner.publicKey),
+        portfolioBaseVault: vaultOf(portfolio),
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([oldOwner])
+      .rpc();
+
+  before(async () => {
+    oldOwner = Keypair.generate();
+    newOwner = Keypair.generate();
+    stranger = Keypair.generate();
+    await airdrop(oldOwner.publicKey);
+    await airdrop(newOwner.publicKey);
+    await airdrop(stranger.publicKey);
+
+    baseMint = await createMint(provider.connection, oldOwner, oldOwner.publicKey, null, 6);
+    const userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      oldOwner,
+      baseMint,
+      oldOwner.publicKey
+    );
+    await mintTo(provider.connection, oldOwner, baseMint, userBaseAccount, oldOwner.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    baseVault = vaultOf(portfolioPda);
+
+    await initialize(portfolioId, portfolioPda);
+  });
+
+  it("提案されていない鍵は移転を受け入れられない", async () => {
+    await expectError(accept(newOwner), "NoPendingOwnerTransfer");
+
+    await propose(oldOwner, newOwner.publicKey);
+    await expectError(accept(stranger), "NoPendingOwnerTransfer");
+  });
+
+  it("取り消した提案は受け入れられない", async () => {
+    await program.methods
+      .cancelOwnerTransfer()
+      .accounts({ portfolio: portfolioPda, owner: oldOwner.publicKey } as any)
+      .signers([oldOwner])
+      .rpc();
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.pendingOwner).to.be.null;
+    await expectError(accept(newOwner), "NoPendingOwnerTransfer");
+  });
+
+  it("現在のオーナー自身への移転は拒否される", async () => {
+    await expectError(propose(oldOwner, oldOwner.publicKey), "InvalidNewOwner");
+  });
+
+  it("受け入れ後は新オーナーだけが操作でき、PDAとボルトは変わらない", async () => {
+    await propose(oldOwner, newOwner.publicKey);
+    await accept(newOwner);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.owner.equals(newOwner.publicKey)).to.be.true;
+    expect(portfolio.portfolioId.equals(portfolioId)).to.be.true;
+    expect(portfolio.pendingOwner).to.be.null;
+
+    const vault = await getAccount(provider.connection, baseVault);
+    expect(vault.owner.equals(portfolioPda)).to.be.true;
+    expect(Number(vault.amount)).to.equal(100_000_000);
+
+    await setRouting(newOwner, true);
+    await expectError(setRouting(oldOwner, false), "ConstraintHasOne");
+  });
+
+  it("移転した旧オーナーは新しいIDで別のポートフォリオを作れる", async () => {
+    const otherId = Keypair.generate().publicKey;
+    const [otherPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), otherId.toBuffer()],
+      program.programId
+    );
+    await initialize(otherId, otherPda);
+
+    const other = await program.account.portfolio.fetch(otherPda);
+    expect(other.owner.equals(oldOwner.publicKey)).to.be.true;
+    expect(other.portfolioId.equals(otherId)).to.be.true;
+
+    // 移転済みのポートフォリオはそのまま
+    const transferred = await program.account.portfolio.fetch(portfolioPda);
+    expect(transferred.owner.equals(newOwner.publicKey)).to.be.true;
+  });
+});
@@ -0,0 +1,158 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// スワップせずに全アロケーションの一定割合をそのまま引き出せる
+describe("Proportional Withdrawal Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let user: Keypair;
+  let portfolioPda: PublicKey;
+  let baseMint: PublicKey;
+  let assetMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let userAssetAccount: PublicKey;
+
+  const vaultOf = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const balanceOf = async (account: PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  // ベース資産が先頭、以降はアロケーション順に [ボルト, オーナーのATA, ミント]
+  const withdrawProportional = (bps: number) =>
+    program.methods
+      .withdrawProportional(bps)
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      } as any)
+      .remainingAccounts(
+        [
+          [baseMint, userBaseAccount],
+          [assetMint, userAssetAccount],
+        ].flatMap(([mint, ownerAccount]) => [
+          { pubkey: vaultOf(mint), isSigner: false, isWritable: true },
+          { pubkey: ownerAccount, isSigner: false, isWritable: true },
+          { pubkey: mint, isSigner: false, isWritable: false },
+        ])
+      )
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      10 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    assetMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    userAssetAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      assetMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+    await mintTo(provider.connection, user, assetMint, userAssetAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 5000, band: null },
+          { mint: assetMint, symbol: "ASSET", targetPercentage: 5000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(400_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: vaultOf(baseMint),
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .depositToken(new anchor.BN(200_000_000))
+      .accounts({
+        portfolio: portfolioPda,
+        userTokenAccount: userAssetAccount,
+        portfolioVault: vaultOf(assetMint),
+        mint: assetMint,
+        baseMint,
+        authority: user.publicKey,
+        sessionKey: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("25%の引き出しで各ボルトの25%がオーナーに送られる", async () => {
+    const baseBefore = await balanceOf(userBaseAccount);
+    const assetBefore = await balanceOf(userAssetAccount);
+
+    await withdrawProportional(2500);
+
+    expect((await balanceOf(userBaseAccount)) - baseBefore).to.equal(100_000_000);
+    expect((await balanceOf(userAssetAccount)) - assetBefore).to.equal(50_000_000);
+    expect(await balanceOf(vaultOf(baseMint))).to.equal(300_000_000);
+    expect(await balanceOf(vaultOf(assetMint))).to.equal(150_000_000);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const amountOf = (mint: PublicKey) =>
+      portfolio.allocations.find((a) => a.mint.equals(mint)).currentAmount.toNumber();
+    expect(amountOf(baseMint)).to.equal(300_000_000);
+    expect(amountOf(assetMint)).to.equal(150_000_000);
+  });
+
+  it("0 bpsと10000 bps超の引き出しは拒否される", async () => {
+    for (const bps of [0, 10001]) {
+      try {
+        await withdrawProportional(bps);
+        expect.fail("エラーが発生するべき");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidWithdrawalShare");
+      }
+    }
+  });
+});
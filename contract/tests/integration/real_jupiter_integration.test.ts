@@ -17,6 +17,7 @@ describe("Real Jupiter Integration Tests", () => {
 
   const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
   const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
 
   let user: Keypair;
   let portfolioPda: PublicKey;
@@ -42,7 +43,7 @@ describe("Real Jupiter Integration Tests", () => {
 
     // Portfolio PDAを生成
     [portfolioPda, portfolioBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
       program.programId
     );
 
@@ -153,7 +154,7 @@ describe("Real Jupiter Integration Tests", () => {
       ];
 
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations,
         })
         .accounts({
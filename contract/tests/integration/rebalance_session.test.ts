@@ -0,0 +1,380 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { MockSwap } from "../../target/types/mock_swap";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  MINT_SIZE,
+  MintLayout,
+  TOKEN_PROGRAM_ID,
+  createTransferInstruction,
+} from "@solana/spl-token";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { expect } from "chai";
+
+// モックスワップを取引先とした二段階リバランス（begin → execute → commit）
+// 初期化直後は頻度制限があるため、bankrun で時計を1日進めて実行する
+describe("Rebalance Session Tests", () => {
+  const DAY = 24 * 60 * 60;
+  const INITIAL_AMOUNT = 100_000_000; // ベース資産（6桁）
+  const ASSET_SUPPLY = 100_000_000_000; // 資産（9桁）
+  const POOL_LIQUIDITY = 60_000_000_000;
+  // 既定の手数料 0.25% + スリッページ 0.5%
+  const LEG_COST_BPS = 75;
+
+  const owner = Keypair.generate();
+  const baseMint = Keypair.generate().publicKey;
+  const assetMint = Keypair.generate().publicKey;
+  const ownerBaseAccount = Keypair.generate().publicKey;
+  const ownerAssetAccount = Keypair.generate().publicKey;
+  const portfolioId = Keypair.generate().publicKey;
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<SloomoPortfolio>;
+  let mockSwap: Program<MockSwap>;
+  let portfolioPda: PublicKey;
+  let sessionPda: PublicKey;
+  let recordPda: PublicKey;
+  let poolPda: PublicKey;
+  let poolInputVault: PublicKey;
+  let poolOutputVault: PublicKey;
+
+  const vaultOf = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const mintAccount = (decimals: number, supply: number) => {
+    const data = Buffer.alloc(MINT_SIZE);
+    MintLayout.encode(
+      {
+        mintAuthorityOption: 0,
+        mintAuthority: PublicKey.default,
+        supply: BigInt(supply),
+        decimals,
+        isInitialized: true,
+        freezeAuthorityOption: 0,
+        freezeAuthority: PublicKey.default,
+      },
+      data
+    );
+    return { lamports: LAMPORTS_PER_SOL, data, owner: TOKEN_PROGRAM_ID, executable: false };
+  };
+
+  const tokenAccount = (mint: PublicKey, amount: number) => {
+    const data = Buffer.alloc(ACCOUNT_SIZE);
+    AccountLayout.encode(
+      {
+        mint,
+        owner: owner.publicKey,
+        amount: BigInt(amount),
+        delegateOption: 0,
+        delegate: PublicKey.default,
+        state: 1,
+        isNativeOption: 0,
+        isNative: BigInt(0),
+        delegatedAmount: BigInt(0),
+        closeAuthorityOption: 0,
+        closeAuthority: PublicKey.default,
+      },
+      data
+    );
+    return { lamports: LAMPORTS_PER_SOL, data, owner: TOKEN_PROGRAM_ID, executable: false };
+  };
+
+  const balanceOf = async (account: PublicKey) =>
+    Number(AccountLayout.decode((await context.banksClient.getAccount(account)).data).amount);
+
+  const configurePool = (mode: object, shortOutputBps = 0) =>
+    mockSwap.methods
+      .configurePool({
+        priceNumerator: new anchor.BN(1000),
+        priceDenominator: new anchor.BN(1),
+        feeBps: 25,
+        mode,
+        shortOutputBps,
+        reentryProgram: PublicKey.default,
+        reentryData: Buffer.alloc(0),
+      } as any)
+      .accounts({ pool: poolPda, authority: owner.publicKey } as any)
+      .signers([owner])
+      .rpc();
+
+  // ベースボルトから資産ボルトへ、ポートフォリオPDAを売り手としてスワップ
+  const executeLeg = async (amountIn: anchor.BN, minimumAmountOut: anchor.BN) => {
+    const swapIx = await mockSwap.methods
+      .swap(amountIn, minimumAmountOut)
+      .accounts({
+        pool: poolPda,
+        userAuthority: portfolioPda,
+        userSource: vaultOf(baseMint),
+        userDestination: vaultOf(assetMint),
+        inputVault: poolInputVault,
+        outputVault: poolOutputVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .instruction();
+
+    return program.methods
+      .executeRebalanceLeg(0, swapIx.data)
+      .accounts({
+        portfolio: portfolioPda,
+        session: sessionPda,
+        authority: owner.publicKey,
+        sessionKey: null,
+        sourceVault: vaultOf(baseMint),
+        destinationVault: vaultOf(assetMint),
+        fromMint: baseMint,
+        toMint: assetMint,
+        swapProgram: mockSwap.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts([
+        { pubkey: vaultOf(baseMint), isSigner: false, isWritable: true },
+        { pubkey: vaultOf(assetMint), isSigner: false, isWritable: true },
+        ...swapIx.keys.map((key) => ({ ...key, isSigner: false })),
+      ])
+      .signers([owner])
+      .rpc();
+  };
+
+  before(async () => {
+    context = await startAnchor("", [], [
+      {
+        address: owner.publicKey,
+        info: { lamports: 10 * LAMPORTS_PER_SOL, data: Buffer.alloc(0), owner: SystemProgram.programId, executable: false },
+      },
+      { address: baseMint, info: mintAccount(6, INITIAL_AMOUNT) },
+      { address: assetMint, info: mintAccount(9, ASSET_SUPPLY) },
+      { address: ownerBaseAccount, info: tokenAccount(baseMint, INITIAL_AMOUNT) },
+      { address: ownerAssetAccount, info: tokenAccount(assetMint, ASSET_SUPPLY) },
+    ]);
+    provider = new BankrunProvider(context);
+    program = new Program<SloomoPortfolio>(require("../../target/idl/sloomo_portfolio.json"), provider);
+    mockSwap = new Program<MockSwap>(require("../../target/idl/mock_swap.json"), provider);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [sessionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rebalance_session"), portfolioPda.toBuffer()],
+      program.programId
+    );
+    [recordPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("rebalance_record"),
+        portfolioPda.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    [poolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), baseMint.toBuffer(), assetMint.toBuffer()],
+      mockSwap.programId
+    );
+    [poolInputVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), poolPda.toBuffer(), baseMint.toBuffer()],
+      mockSwap.programId
+    );
+    [poolOutputVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), poolPda.toBuffer(), assetMint.toBuffer()],
+      mockSwap.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 5000, band: null },
+          { mint: assetMint, symbol: "ASSET", targetPercentage: 5000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(INITIAL_AMOUNT),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: owner.publicKey,
+        userBaseAccount: ownerBaseAccount,
+        portfolioBaseVault: vaultOf(baseMint),
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .setSwapPrograms([mockSwap.programId])
+      .accounts({ portfolio: portfolioPda, owner: owner.publicKey } as any)
+      .signers([owner])
+      .rpc();
+
+    // 1ベース単位（6桁）= 1資産単位（9桁）、手数料 0.25%
+    await mockSwap.methods
+      .initializePool(new anchor.BN(1000), new anchor.BN(1), 25)
+      .accounts({
+        pool: poolPda,
+        inputVault: poolInputVault,
+        outputVault: poolOutputVault,
+        inputMint: baseMint,
+        outputMint: assetMint,
+        authority: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+    await mockSwap.methods
+      .addLiquidity(new anchor.BN(POOL_LIQUIDITY))
+      .accounts({
+        pool: poolPda,
+        authorityTokenAccount: ownerAssetAccount,
+        outputVault: poolOutputVault,
+        authority: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+
+    // 頻度制限（1日1回）を越える
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(DAY)
+      )
+    );
+  });
+
+  it("計画レッグは売却額を売却側、最小受取量を購入側の単位で持つ", async () => {
+    await program.methods
+      .beginRebalance(
+        [
+          { mint: baseMint, targetPercentage: 5000 },
+          { mint: assetMint, targetPercentage: 5000 },
+        ],
+        50
+      )
+      .accounts({
+        portfolio: portfolioPda,
+        session: sessionPda,
+        authority: owner.publicKey,
+        sessionKey: null,
+        baseVault: vaultOf(baseMint),
+        baseMint,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts(
+        [vaultOf(assetMint), assetMint].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      )
+      .signers([owner])
+      .rpc();
+
+    const session = await program.account.rebalanceSession.fetch(sessionPda);
+    expect(session.legs).to.have.length(1);
+    const [leg] = session.legs;
+    expect(leg.fromMint.equals(baseMint)).to.be.true;
+    expect(leg.toMint.equals(assetMint)).to.be.true;
+
+    // 売却額はベース資産（6桁）、最小受取量は資産（9桁）の単位
+    const amount = leg.amount.toNumber();
+    const minimum = (amount - Math.floor((amount * LEG_COST_BPS) / 10000)) * 1000;
+    expect(leg.minAmountOut.toNumber()).to.equal(minimum);
+  });
+
+  it("最小受取量を下回るスワップではレッグを実行できない", async () => {
+    const { legs } = await program.account.rebalanceSession.fetch(sessionPda);
+
+    // 見積もりの1%を受け渡さないプール（手数料込みで最小受取量を下回る）
+    await configurePool({ shortOutput: {} }, 100);
+    await expectError(executeLeg(legs[0].amount, new anchor.BN(0)), "SlippageExceeded");
+    await configurePool({ normal: {} });
+
+    const session = await program.account.rebalanceSession.fetch(sessionPda);
+    expect(session.legs[0].executed).to.be.false;
+  });
+
+  it("モックスワップ経由でレッグを実行し、結果をボルト残高から記録する", async () => {
+    const { legs } = await program.account.rebalanceSession.fetch(sessionPda);
+    const amount = legs[0].amount.toNumber();
+
+    await executeLeg(legs[0].amount, legs[0].minAmountOut);
+
+    const [leg] = (await program.account.rebalanceSession.fetch(sessionPda)).legs;
+    expect(leg.executed).to.be.true;
+    expect(leg.spent.toNumber()).to.equal(amount);
+    // 1000倍、手数料 0.25% 控除
+    expect(leg.received.toNumber()).to.equal(Math.floor((amount * 1000 * 9975) / 10000));
+    expect(leg.received.gte(leg.minAmountOut)).to.be.true;
+
+    expect(await balanceOf(vaultOf(baseMint))).to.equal(INITIAL_AMOUNT - amount);
+    expect(await balanceOf(vaultOf(assetMint))).to.equal(leg.received.toNumber());
+  });
+
+  it("ボルトへのダストの送金があってもコミットできる", async () => {
+    const [leg] = (await program.account.rebalanceSession.fetch(sessionPda)).legs;
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        createTransferInstruction(ownerAssetAccount, vaultOf(assetMint), owner.publicKey, 1)
+      ),
+      [owner]
+    );
+
+    await program.methods
+      .commitRebalance()
+      .accounts({
+        portfolio: portfolioPda,
+        session: sessionPda,
+        record: recordPda,
+        owner: owner.publicKey,
+        authority: owner.publicKey,
+        sessionKey: null,
+        baseVault: vaultOf(baseMint),
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .remainingAccounts([{ pubkey: vaultOf(assetMint), isSigner: false, isWritable: false }])
+      .signers([owner])
+      .rpc();
+
+    expect(await context.banksClient.getAccount(sessionPda)).to.be.null;
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.isRebalancing).to.be.false;
+    expect(portfolio.rebalanceCount.toNumber()).to.equal(1);
+    const asset = portfolio.allocations.find((a) => a.mint.equals(assetMint));
+    expect(asset.currentAmount.toNumber()).to.equal(leg.received.toNumber() + 1);
+    // 合計はベース資産の小数桁（6桁）で計上
+    const baseHeld = INITIAL_AMOUNT - leg.spent.toNumber();
+    expect(portfolio.totalValue.toNumber()).to.equal(
+      baseHeld + Math.floor((leg.received.toNumber() + 1) / 1000)
+    );
+
+    const record = await program.account.rebalanceRecord.fetch(recordPda);
+    expect(record.legs).to.have.length(1);
+    expect(record.totalValueAfter.toNumber()).to.equal(portfolio.totalValue.toNumber());
+  });
+});
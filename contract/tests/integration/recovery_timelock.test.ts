@@ -0,0 +1,189 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  MINT_SIZE,
+  MintLayout,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { expect } from "chai";
+
+// タイムロック経過後の復旧完了：ローカルバリデータでは時計を進められないため bankrun で実行する
+describe("Guardian Recovery Completion Tests", () => {
+  const TIMELOCK = 24 * 60 * 60;
+  const INITIAL_AMOUNT = 100_000_000;
+
+  const owner = Keypair.generate();
+  const guardians = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const newOwner = Keypair.generate();
+  const baseMint = Keypair.generate().publicKey;
+  const userBaseAccount = Keypair.generate().publicKey;
+  const portfolioId = Keypair.generate().publicKey;
+
+  let context: ProgramTestContext;
+  let program: Program<SloomoPortfolio>;
+  let portfolioPda: PublicKey;
+  let guardianSetPda: PublicKey;
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  // 作成済みのミントとオーナーのトークンアカウントを直接配置する
+  const mintAccount = () => {
+    const data = Buffer.alloc(MINT_SIZE);
+    MintLayout.encode(
+      {
+        mintAuthorityOption: 0,
+        mintAuthority: PublicKey.default,
+        supply: BigInt(INITIAL_AMOUNT),
+        decimals: 6,
+        isInitialized: true,
+        freezeAuthorityOption: 0,
+        freezeAuthority: PublicKey.default,
+      },
+      data
+    );
+    return { lamports: LAMPORTS_PER_SOL, data, owner: TOKEN_PROGRAM_ID, executable: false };
+  };
+
+  const tokenAccount = () => {
+    const data = Buffer.alloc(ACCOUNT_SIZE);
+    AccountLayout.encode(
+      {
+        mint: baseMint,
+        owner: owner.publicKey,
+        amount: BigInt(INITIAL_AMOUNT),
+        delegateOption: 0,
+        delegate: PublicKey.default,
+        state: 1,
+        isNativeOption: 0,
+        isNative: BigInt(0),
+        delegatedAmount: BigInt(0),
+        closeAuthorityOption: 0,
+        closeAuthority: PublicKey.default,
+      },
+      data
+    );
+    return { lamports: LAMPORTS_PER_SOL, data, owner: TOKEN_PROGRAM_ID, executable: false };
+  };
+
+  const complete = (signer: Keypair) =>
+    program.methods
+      .completeRecovery()
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, executor: signer.publicKey } as any)
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    context = await startAnchor("", [], [
+      {
+        address: owner.publicKey,
+        info: { lamports: 10 * LAMPORTS_PER_SOL, data: Buffer.alloc(0), owner: SystemProgram.programId, executable: false },
+      },
+      { address: baseMint, info: mintAccount() },
+      { address: userBaseAccount, info: tokenAccount() },
+    ]);
+    const provider = new BankrunProvider(context);
+    program = new Program<SloomoPortfolio>(
+      require("../../target/idl/sloomo_portfolio.json"),
+      provider
+    );
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [guardianSetPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("guardians"), portfolioPda.toBuffer()],
+      program.programId
+    );
+    const [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [{ mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null }],
+        initialSolAmount: new anchor.BN(INITIAL_AMOUNT),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: owner.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .setGuardians(guardians.map((g) => g.publicKey), 2, new anchor.BN(TIMELOCK))
+      .accounts({
+        portfolio: portfolioPda,
+        guardianSet: guardianSetPda,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([owner])
+      .rpc();
+  });
+
+  it("タイムロック経過後に復旧を完了すると新オーナーに切り替わる", async () => {
+    await program.methods
+      .initiateRecovery(newOwner.publicKey)
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, guardian: guardians[0].publicKey } as any)
+      .signers([guardians[0]])
+      .rpc();
+    await program.methods
+      .approveRecovery()
+      .accounts({ portfolio: portfolioPda, guardianSet: guardianSetPda, guardian: guardians[1].publicKey } as any)
+      .signers([guardians[1]])
+      .rpc();
+    await expectError(complete(guardians[2]), "RecoveryTimelockActive");
+
+    // 承認時刻からタイムロック分だけ時計を進める
+    const { recovery } = await program.account.guardianSet.fetch(guardianSetPda);
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(recovery.approvedAt.toNumber() + TIMELOCK)
+      )
+    );
+
+    await complete(newOwner);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.owner.equals(newOwner.publicKey)).to.be.true;
+    expect(portfolio.portfolioId.equals(portfolioId)).to.be.true;
+    expect((await program.account.guardianSet.fetch(guardianSetPda)).recovery).to.be.null;
+
+    // 旧オーナーはもう操作できない
+    await expectError(
+      program.methods
+        .setDepositRouting(true)
+        .accounts({ portfolio: portfolioPda, owner: owner.publicKey } as any)
+        .signers([owner])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+  });
+});
@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  approve,
+  getAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+const HOUR = 60 * 60;
+
+// 定期入金：オーナーがポートフォリオPDAに委任し、期日が来たら誰でも実行できる
+describe("Recurring Deposit Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let owner: Keypair;
+  let cranker: Keypair;
+  let portfolioPda: PublicKey;
+  let recurringDepositPda: PublicKey;
+  let baseMint: PublicKey;
+  let baseVault: PublicKey;
+  let userBaseAccount: PublicKey;
+
+  const airdrop = async (key: PublicKey) => {
+    const signature = await provider.connection.requestAirdrop(key, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const balanceOf = async (account: PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  const schedule = (
+    amount: number,
+    interval: number,
+    startAt: number | null = null,
+    maxOccurrences: number | null = null
+  ) =>
+    program.methods
+      .createRecurringDeposit(
+        new anchor.BN(amount),
+        new anchor.BN(interval),
+        startAt === null ? null : new anchor.BN(startAt),
+        maxOccurrences
+      )
+      .accounts({
+        portfolio: portfolioPda,
+        recurringDeposit: recurringDepositPda,
+        source: userBaseAccount,
+        baseMint,
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([owner])
+      .rpc();
+
+  const crank = () =>
+    program.methods
+      .crankRecurringDeposit()
+      .accounts({
+        portfolio: portfolioPda,
+        recurringDeposit: recurringDepositPda,
+        source: userBaseAccount,
+        baseVault,
+        baseMint,
+        cranker: cranker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([cranker])
+      .rpc();
+
+  const cancel = (signer: Keypair) =>
+    program.methods
+      .cancelRecurringDeposit()
+      .accounts({
+        portfolio: portfolioPda,
+        recurringDeposit: recurringDepositPda,
+        owner: signer.publicKey,
+      } as any)
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    owner = Keypair.generate();
+    cranker = Keypair.generate();
+    await airdrop(owner.publicKey);
+    await airdrop(cranker.publicKey);
+
+    baseMint = await createMint(provider.connection, owner, owner.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      owner,
+      baseMint,
+      owner.publicKey
+    );
+    await mintTo(provider.connection, owner, baseMint, userBaseAccount, owner.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [recurringDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recurring_deposit"), portfolioPda.toBuffer()],
+      program.programId
+    );
+    [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [{ mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null }],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: owner.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+  });
+
+  it("不正なスケジュールは作成できない", async () => {
+    await expectError(schedule(0, HOUR), "InvalidRecurringDeposit");
+    await expectError(schedule(50_000_000, HOUR - 1), "InvalidRecurringDeposit");
+    await expectError(schedule(50_000_000, HOUR, null, 0), "InvalidRecurringDeposit");
+  });
+
+  it("委任が承認されていない場合は実行できない", async () => {
+    await schedule(50_000_000, HOUR, null, 2);
+    await expectError(crank(), "RecurringDepositNotApproved");
+  });
+
+  it("期日が来た入金は誰でも実行でき、次の期日まで再実行できない", async () => {
+    await approve(provider.connection, owner, userBaseAccount, portfolioPda, owner, 100_000_000);
+    const before = await balanceOf(userBaseAccount);
+
+    await crank();
+
+    expect(before - (await balanceOf(userBaseAccount))).to.equal(50_000_000);
+    expect(await balanceOf(baseVault)).to.equal(150_000_000);
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.totalValue.toNumber()).to.equal(150_000_000);
+
+    const recurringDeposit = await program.account.recurringDeposit.fetch(recurringDepositPda);
+    expect(recurringDeposit.executed).to.equal(1);
+    await expectError(crank(), "RecurringDepositNotDue");
+  });
+
+  it("開始時刻前は実行できない", async () => {
+    await schedule(50_000_000, HOUR, Math.floor(Date.now() / 1000) + HOUR);
+    await expectError(crank(), "RecurringDepositNotDue");
+  });
+
+  it("オーナーだけが取り消せ、取り消し後は実行できない", async () => {
+    await expectError(cancel(cranker), "ConstraintHasOne");
+
+    await cancel(owner);
+    await expectError(crank(), "AccountNotInitialized");
+  });
+});
@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// 目標金額・目標日・ラベルを持つ貯蓄目標を設定し、
+// 読み取り専用の get_goal_progress が進捗と必要な定期積立額を return data で返す
+describe("Savings Goal Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  const MONTH = 30 * 24 * 60 * 60;
+  const INITIAL_AMOUNT = 100_000_000;
+
+  let user: Keypair;
+  let baseMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let portfolioPda: PublicKey;
+
+  // 送信エラーはメッセージ、シミュレーションエラーはログにエラーコードが含まれる
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      const logs = error.simulationResponse?.logs ?? error.logs ?? [];
+      expect([error.toString(), ...logs].join("\n")).to.include(code);
+    }
+  };
+
+  const now = () => Math.floor(Date.now() / 1000);
+
+  const setGoal = (goal: { targetAmount: anchor.BN; targetDate: anchor.BN; label: string } | null) =>
+    program.methods
+      .setSavingsGoal(goal)
+      .accounts({ portfolio: portfolioPda, owner: user.publicKey } as any)
+      .signers([user])
+      .rpc();
+
+  const goalProgress = (interval = MONTH) =>
+    program.methods
+      .getGoalProgress(new anchor.BN(interval))
+      .accounts({ portfolio: portfolioPda } as any)
+      .view();
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    const [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(INITIAL_AMOUNT),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("目標がなければ進捗は取得できない", async () => {
+    await expectError(goalProgress(), "SavingsGoalNotSet");
+  });
+
+  it("過去の目標日は拒否される", async () => {
+    await expectError(
+      setGoal({
+        targetAmount: new anchor.BN(500_000_000),
+        targetDate: new anchor.BN(now() - 60),
+        label: "House",
+      }),
+      "InvalidSavingsGoal"
+    );
+  });
+
+  it("オーナーは貯蓄目標を設定できる", async () => {
+    await setGoal({
+      targetAmount: new anchor.BN(500_000_000),
+      targetDate: new anchor.BN(now() + 12 * MONTH),
+      label: "House",
+    });
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.goal.label).to.equal("House");
+    expect(portfolio.goal.targetAmount.toNumber()).to.equal(500_000_000);
+  });
+
+  it("進捗と毎月の必要積立額をシミュレーションで取得できる", async () => {
+    const progress = await goalProgress();
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(progress.totalValue.toNumber()).to.equal(portfolio.totalValue.toNumber());
+    expect(progress.progressBps).to.equal(
+      Math.floor((portfolio.totalValue.toNumber() * 10000) / 500_000_000)
+    );
+    expect(progress.periodsRemaining.toNumber()).to.equal(12);
+    // 成長実績がないため不足額を残り期間で割った額
+    expect(progress.expectedGrowthBps).to.equal(0);
+    expect(progress.requiredContribution.toNumber()).to.equal(
+      Math.ceil((500_000_000 - portfolio.totalValue.toNumber()) / 12)
+    );
+  });
+
+  it("積立間隔は正の値でなければならない", async () => {
+    await expectError(goalProgress(0), "InvalidContributionInterval");
+  });
+
+  it("オーナー以外は目標を変更できない", async () => {
+    const stranger = Keypair.generate();
+    await expectError(
+      program.methods
+        .setSavingsGoal(null)
+        .accounts({ portfolio: portfolioPda, owner: stranger.publicKey } as any)
+        .signers([stranger])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+  });
+
+  it("目標を外すと進捗は取得できなくなる", async () => {
+    await setGoal(null);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.goal).to.be.null;
+    await expectError(goalProgress(), "SavingsGoalNotSet");
+  });
+});
@@ -0,0 +1,217 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  approve,
+  getAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+const SESSION_SCOPE_DEPOSIT = 1 << 0;
+const SESSION_SCOPE_ALLOCATION = 1 << 1;
+
+// セッションキー：オーナーが発行した期限付きの鍵が、許可された範囲の命令だけに署名できる
+describe("Session Key Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let owner: Keypair;
+  let device: Keypair;
+  let portfolioPda: PublicKey;
+  let sessionKeyPda: PublicKey;
+  let baseMint: PublicKey;
+  let userBaseAccount: PublicKey;
+
+  const vaultOf = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const airdrop = async (key: PublicKey) => {
+    const signature = await provider.connection.requestAirdrop(key, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  const createSessionKey = (scopes: number, expiresIn: number, spendLimit: number | null) =>
+    program.methods
+      .createSessionKey(
+        device.publicKey,
+        scopes,
+        new anchor.BN(Math.floor(Date.now() / 1000) + expiresIn),
+        spendLimit === null ? null : new anchor.BN(spendLimit)
+      )
+      .accounts({
+        portfolio: portfolioPda,
+        sessionKey: sessionKeyPda,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([owner])
+      .rpc();
+
+  // セッションキーが所有者のトークンアカウントの委任先として入金する
+  const depositAsDevice = (amount: number) =>
+    program.methods
+      .depositToken(new anchor.BN(amount))
+      .accounts({
+        portfolio: portfolioPda,
+        userTokenAccount: userBaseAccount,
+        portfolioVault: vaultOf(baseMint),
+        mint: baseMint,
+        baseMint,
+        authority: device.publicKey,
+        sessionKey: sessionKeyPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([device])
+      .rpc();
+
+  const updateAllocationAsDevice = () =>
+    program.methods
+      .addOrUpdateAllocation(Keypair.generate().publicKey, "NEW", 1000, null)
+      .accounts({ portfolio: portfolioPda, authority: device.publicKey, sessionKey: sessionKeyPda } as any)
+      .signers([device])
+      .rpc();
+
+  before(async () => {
+    owner = Keypair.generate();
+    device = Keypair.generate();
+    await airdrop(owner.publicKey);
+    await airdrop(device.publicKey);
+
+    baseMint = await createMint(provider.connection, owner, owner.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      owner,
+      baseMint,
+      owner.publicKey
+    );
+    await mintTo(provider.connection, owner, baseMint, userBaseAccount, owner.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [sessionKeyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("session_key"), portfolioPda.toBuffer(), device.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [{ mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null }],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: owner.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: vaultOf(baseMint),
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([owner])
+      .rpc();
+
+    // 入金用にセッションキーを委任先として承認しておく
+    await approve(
+      provider.connection,
+      owner,
+      userBaseAccount,
+      device.publicKey,
+      owner,
+      500_000_000
+    );
+  });
+
+  it("セッションキーが未発行の鍵は署名できない", async () => {
+    await expectError(depositAsDevice(1_000_000), "AccountNotInitialized");
+  });
+
+  it("不正な範囲や期限のセッションキーは発行できない", async () => {
+    await expectError(createSessionKey(0, 3_600, null), "InvalidSessionScope");
+    await expectError(createSessionKey(1 << 3, 3_600, null), "InvalidSessionScope");
+    await expectError(
+      createSessionKey(SESSION_SCOPE_DEPOSIT, 8 * 24 * 60 * 60, null),
+      "InvalidSessionExpiry"
+    );
+  });
+
+  it("入金範囲のセッションキーは上限まで入金できる", async () => {
+    await createSessionKey(SESSION_SCOPE_DEPOSIT, 3_600, 50_000_000);
+
+    await depositAsDevice(30_000_000);
+    expect(Number((await getAccount(provider.connection, vaultOf(baseMint))).amount)).to.equal(
+      130_000_000
+    );
+    const sessionKey = await program.account.sessionKey.fetch(sessionKeyPda);
+    expect(sessionKey.spent.toNumber()).to.equal(30_000_000);
+
+    await expectError(depositAsDevice(30_000_000), "SessionSpendLimitExceeded");
+  });
+
+  it("許可されていない範囲の命令には署名できない", async () => {
+    await expectError(updateAllocationAsDevice(), "SessionScopeNotAllowed");
+
+    await createSessionKey(SESSION_SCOPE_DEPOSIT | SESSION_SCOPE_ALLOCATION, 3_600, null);
+    await updateAllocationAsDevice();
+  });
+
+  it("引き出しとオーナー変更はセッションキーでは実行できない", async () => {
+    await expectError(
+      program.methods
+        .proposeOwnerTransfer(device.publicKey)
+        .accounts({ portfolio: portfolioPda, owner: device.publicKey } as any)
+        .signers([device])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+    await expectError(
+      program.methods
+        .withdrawToBase(new anchor.BN(1_000_000), { proportional: {} }, 50, [])
+        .accounts({
+          portfolio: portfolioPda,
+          owner: device.publicKey,
+          baseVault: vaultOf(baseMint),
+          userBaseAccount,
+          baseMint,
+          swapProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([device])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+  });
+
+  it("取り消したセッションキーは使えない", async () => {
+    await program.methods
+      .revokeSessionKey()
+      .accounts({ portfolio: portfolioPda, sessionKey: sessionKeyPda, owner: owner.publicKey } as any)
+      .signers([owner])
+      .rpc();
+
+    await expectError(depositAsDevice(1_000_000), "AccountNotInitialized");
+  });
+});
@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// ユーザーはwSOLアカウントを用意せずにSOLを入出金できる
+describe("Native SOL Deposit Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let user: Keypair;
+  let portfolioPda: PublicKey;
+  let wsolVault: PublicKey;
+  let baseMint: PublicKey;
+  let unwrapAccount: PublicKey;
+
+  const depositSol = (lamports: number) =>
+    program.methods
+      .depositSol(new anchor.BN(lamports))
+      .accounts({
+        portfolio: portfolioPda,
+        portfolioWsolVault: wsolVault,
+        wsolMint: NATIVE_MINT,
+        baseMint,
+        owner: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+
+  const withdrawSol = (lamports: number) =>
+    program.methods
+      .withdrawSol(new anchor.BN(lamports))
+      .accounts({
+        portfolio: portfolioPda,
+        portfolioWsolVault: wsolVault,
+        unwrapAccount,
+        wsolMint: NATIVE_MINT,
+        baseMint,
+        owner: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+
+  const vaultBalance = async () =>
+    Number((await getAccount(provider.connection, wsolVault)).amount);
+
+  const solAllocation = async () =>
+    (await program.account.portfolio.fetch(portfolioPda)).allocations.find((a) =>
+      a.mint.equals(NATIVE_MINT)
+    );
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      10 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    // ベース資産はテスト用ミント、SOLはアロケーションとして保有
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    const userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [wsolVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    [unwrapAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("unwrap"), portfolioPda.toBuffer()],
+      program.programId
+    );
+    const [baseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), baseMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: NATIVE_MINT, symbol: "SOL", targetPercentage: 5000, band: null },
+          { mint: baseMint, symbol: "BASE", targetPercentage: 5000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  const totalValue = async () =>
+    (await program.account.portfolio.fetch(portfolioPda)).totalValue.toNumber();
+
+  it("SOLを入金するとwSOLボルトにラップされる", async () => {
+    const valueBefore = await totalValue();
+    await depositSol(LAMPORTS_PER_SOL);
+
+    expect(await vaultBalance()).to.equal(LAMPORTS_PER_SOL);
+    expect((await solAllocation()).currentAmount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    // 総価値はベース資産（6桁）の単位で加算される
+    expect(await totalValue()).to.equal(valueBefore + 1_000_000);
+  });
+
+  it("SOLを出金するとアンラップされてオーナーに届く", async () => {
+    const before = await provider.connection.getBalance(user.publicKey);
+    const valueBefore = await totalValue();
+    await withdrawSol(LAMPORTS_PER_SOL / 2);
+
+    expect(await vaultBalance()).to.equal(LAMPORTS_PER_SOL / 2);
+    expect((await solAllocation()).currentAmount.toNumber()).to.equal(LAMPORTS_PER_SOL / 2);
+    expect(await totalValue()).to.equal(valueBefore - 500_000);
+    // 一時アカウントの家賃は返却され、手数料のみ差し引かれる
+    const received = (await provider.connection.getBalance(user.publicKey)) - before;
+    expect(received).to.be.greaterThan(LAMPORTS_PER_SOL / 2 - 10_000);
+    expect(await provider.connection.getAccountInfo(unwrapAccount)).to.equal(null);
+  });
+
+  it("ボルト残高を超える出金は拒否される", async () => {
+    try {
+      await withdrawSol(LAMPORTS_PER_SOL);
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include("InsufficientBalance");
+    }
+  });
+
+  it("0 lamportsの入金は拒否される", async () => {
+    try {
+      await depositSol(0);
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidAmount");
+    }
+  });
+});
@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAccount,
+  getMintLen,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// 送金手数料付きのToken-2022ミントは、ボルトが実際に受け取った額で記帳される
+describe("Token-2022 Deposit Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  const FEE_BASIS_POINTS = 100; // 1%
+  const MAX_FEE = BigInt(1_000_000_000);
+  const INITIAL_AMOUNT = 100_000_000;
+
+  let user: Keypair;
+  let portfolioPda: PublicKey;
+  let feeMint: PublicKey;
+  let userFeeAccount: PublicKey;
+  let feeVault: PublicKey;
+
+  const netOf = (amount: number) => amount - (amount * FEE_BASIS_POINTS) / 10_000;
+
+  const createTransferFeeMint = async () => {
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const transaction = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: user.publicKey,
+        newAccountPubkey: mintKeypair.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mintKeypair.publicKey,
+        user.publicKey,
+        user.publicKey,
+        FEE_BASIS_POINTS,
+        MAX_FEE,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mintKeypair.publicKey,
+        6,
+        user.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await sendAndConfirmTransaction(provider.connection, transaction, [user, mintKeypair]);
+    return mintKeypair.publicKey;
+  };
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      10 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    feeMint = await createTransferFeeMint();
+    userFeeAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      feeMint,
+      user.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      user,
+      feeMint,
+      userFeeAccount,
+      user.publicKey,
+      1_000_000_000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), feeMint.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("Token-2022ミントをベース資産として初期化すると手数料控除後の額が記帳される", async () => {
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [{ mint: feeMint, symbol: "FEE", targetPercentage: 10000, band: null }],
+        initialSolAmount: new anchor.BN(INITIAL_AMOUNT),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount: userFeeAccount,
+        portfolioBaseVault: feeVault,
+        baseMint: feeMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    const vault = await getAccount(provider.connection, feeVault, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(vault.amount)).to.equal(netOf(INITIAL_AMOUNT));
+    expect(portfolio.totalValue.toNumber()).to.equal(netOf(INITIAL_AMOUNT));
+    expect(portfolio.allocations[0].currentAmount.toNumber()).to.equal(netOf(INITIAL_AMOUNT));
+  });
+
+  it("deposit_tokenは送金手数料を差し引いた額をアロケーションとtotal_valueに加算する", async () => {
+    const amount = 10_000_000;
+    const before = await program.account.portfolio.fetch(portfolioPda);
+
+    await program.methods
+      .depositToken(new anchor.BN(amount))
+      .accounts({
+        portfolio: portfolioPda,
+        userTokenAccount: userFeeAccount,
+        portfolioVault: feeVault,
+        mint: feeMint,
+        baseMint: feeMint,
+        authority: user.publicKey,
+        sessionKey: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+
+    const after = await program.account.portfolio.fetch(portfolioPda);
+    const vault = await getAccount(provider.connection, feeVault, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(vault.amount)).to.equal(netOf(INITIAL_AMOUNT) + netOf(amount));
+    expect(after.totalValue.sub(before.totalValue).toNumber()).to.equal(netOf(amount));
+    expect(
+      after.allocations[0].currentAmount.sub(before.allocations[0].currentAmount).toNumber()
+    ).to.equal(netOf(amount));
+  });
+});
@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// ベース資産以外のアロケーション済みトークンもdecimalsを考慮して入金できる
+describe("Token Deposit Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let user: Keypair;
+  let portfolioPda: PublicKey;
+  let baseMint: PublicKey; // 6 decimals
+  let assetMint: PublicKey; // 9 decimals
+  let otherMint: PublicKey; // アロケーションなし
+
+  const vaultOf = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const fundedAccount = async (mint: PublicKey, amount: number) => {
+    const account = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      mint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, mint, account, user.publicKey, amount);
+    return account;
+  };
+
+  const depositToken = (mint: PublicKey, userTokenAccount: PublicKey, amount: number) =>
+    program.methods
+      .depositToken(new anchor.BN(amount))
+      .accounts({
+        portfolio: portfolioPda,
+        userTokenAccount,
+        portfolioVault: vaultOf(mint),
+        mint,
+        baseMint,
+        authority: user.publicKey,
+        sessionKey: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([user])
+      .rpc();
+
+  const fetchPortfolio = () => program.account.portfolio.fetch(portfolioPda);
+
+  let userBaseAccount: PublicKey;
+  let userAssetAccount: PublicKey;
+  let userOtherAccount: PublicKey;
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      10 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    assetMint = await createMint(provider.connection, user, user.publicKey, null, 9);
+    otherMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await fundedAccount(baseMint, 1_000_000_000);
+    userAssetAccount = await fundedAccount(assetMint, 10_000_000_000);
+    userOtherAccount = await fundedAccount(otherMint, 1_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: assetMint, symbol: "ASSET", targetPercentage: 5000, band: null },
+          { mint: baseMint, symbol: "BASE", targetPercentage: 5000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: vaultOf(baseMint),
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("アロケーション済みトークンを入金するとアロケーションとtotal_valueに反映される", async () => {
+    const before = await fetchPortfolio();
+    await depositToken(assetMint, userAssetAccount, 2_000_000_000); // 2.0 ASSET
+
+    const after = await fetchPortfolio();
+    const allocation = after.allocations.find((a) => a.mint.equals(assetMint));
+    expect(allocation.currentAmount.toNumber()).to.equal(2_000_000_000);
+    // total_valueはベース資産の6桁に換算される
+    expect(after.totalValue.sub(before.totalValue).toNumber()).to.equal(2_000_000);
+    expect(Number((await getAccount(provider.connection, vaultOf(assetMint))).amount)).to.equal(
+      2_000_000_000
+    );
+  });
+
+  it("ベース資産も同じ命令で入金できる", async () => {
+    const before = await fetchPortfolio();
+    await depositToken(baseMint, userBaseAccount, 5_000_000);
+
+    const after = await fetchPortfolio();
+    expect(after.totalValue.sub(before.totalValue).toNumber()).to.equal(5_000_000);
+  });
+
+  it("アロケーションにないトークンの入金は拒否される", async () => {
+    try {
+      await depositToken(otherMint, userOtherAccount, 1_000);
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidTokenMint");
+    }
+  });
+
+  it("残高を超える入金は拒否される", async () => {
+    try {
+      await depositToken(assetMint, userAssetAccount, 100_000_000_000);
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include("InsufficientBalance");
+    }
+  });
+
+  describe("deposit_usdc（非推奨）", () => {
+    const depositUsdc = (mint: PublicKey, userUsdcAccount: PublicKey, amount: number) =>
+      program.methods
+        .depositUsdc(new anchor.BN(amount))
+        .accounts({
+          portfolio: portfolioPda,
+          userUsdcAccount,
+          portfolioUsdcVault: vaultOf(mint),
+          usdcMint: mint,
+          baseMint,
+          owner: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([user])
+        .rpc();
+
+    it("ベース資産は入金できる", async () => {
+      const before = await fetchPortfolio();
+      await depositUsdc(baseMint, userBaseAccount, 1_000_000);
+
+      const after = await fetchPortfolio();
+      expect(after.totalValue.sub(before.totalValue).toNumber()).to.equal(1_000_000);
+    });
+
+    it("ベース資産以外のミントは拒否される", async () => {
+      try {
+        await depositUsdc(otherMint, userOtherAccount, 1_000);
+        expect.fail("エラーが発生するべき");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidBaseMint");
+      }
+    });
+  });
+});
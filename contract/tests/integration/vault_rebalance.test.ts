@@ -0,0 +1,233 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// リバランスはオーナーのウォレットではなくポートフォリオのボルトから残高を読む
+describe("Vault Rebalance Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  let user: Keypair;
+  let baseMint: PublicKey;
+  let otherMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let portfolioPda: PublicKey;
+  let baseVault: PublicKey;
+  let otherVault: PublicKey;
+
+  const vaultFor = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const targets = () => [
+    { mint: baseMint, targetPercentage: 5000 },
+    { mint: otherMint, targetPercentage: 5000 },
+  ];
+
+  const rebalance = (vault: PublicKey, remainingAccounts: PublicKey[]) =>
+    program.methods
+      .rebalance(targets(), 50)
+      .accounts({
+        portfolio: portfolioPda,
+        authority: user.publicKey,
+        sessionKey: null,
+        baseVault: vault,
+        baseMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .remainingAccounts(
+        remainingAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+      )
+      .signers([user])
+      .rpc();
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      expect(error.toString()).to.include(code);
+    }
+  };
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    otherMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    baseVault = vaultFor(baseMint);
+    otherVault = vaultFor(otherMint);
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 5000, band: null },
+          { mint: otherMint, symbol: "OTHER", targetPercentage: 5000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(100_000_000),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("オーナーのウォレットをベースボルトとして渡すと拒否される", async () => {
+    await expectError(rebalance(userBaseAccount, [otherVault]), "ConstraintSeeds");
+  });
+
+  it("アロケーションのボルトが不足していると拒否される", async () => {
+    await expectError(rebalance(baseVault, []), "MissingVaultAccount");
+  });
+
+  it("ボルト以外のアカウントを渡すと拒否される", async () => {
+    await expectError(rebalance(baseVault, [userBaseAccount, otherMint]), "InvalidVault");
+  });
+
+  it("ボルトに続くミントアカウントが不足していると拒否される", async () => {
+    // 合計額はミントの小数点桁数でベース資産の単位に換算するため必要
+    await expectError(rebalance(baseVault, [otherVault]), "MissingMintAccount");
+    await expectError(rebalance(baseVault, [otherVault, baseMint]), "InvalidTokenMint");
+  });
+
+  it("正しいボルトを渡すとボルト検証を通過する", async () => {
+    // 初期化直後のため頻度制限で止まる（ボルト検証はその前に完了している）
+    await expectError(rebalance(baseVault, [otherVault, otherMint]), "RebalanceTooFrequent");
+  });
+
+  describe("二段階リバランス", () => {
+    let sessionPda: PublicKey;
+    let recordPda: PublicKey;
+
+    before(() => {
+      [sessionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("rebalance_session"), portfolioPda.toBuffer()],
+        program.programId
+      );
+      // コミットごとのリバランス記録（ポートフォリオのカウンタで番号付け）
+      [recordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("rebalance_record"),
+          portfolioPda.toBuffer(),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+    });
+
+    it("begin_rebalanceにも頻度制限が適用される", async () => {
+      await expectError(
+        program.methods
+          .beginRebalance(targets(), 50)
+          .accounts({
+            portfolio: portfolioPda,
+            session: sessionPda,
+            authority: user.publicKey,
+            sessionKey: null,
+            baseVault,
+            baseMint,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .remainingAccounts(
+            [otherVault, otherMint].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+          )
+          .signers([user])
+          .rpc(),
+        "RebalanceTooFrequent"
+      );
+    });
+
+    it("コミット前はリバランス記録がなく、閉じることもできない", async () => {
+      const portfolio = await program.account.portfolio.fetch(portfolioPda);
+      expect(portfolio.rebalanceCount.toNumber()).to.equal(0);
+      expect(await provider.connection.getAccountInfo(recordPda)).to.be.null;
+
+      await expectError(
+        program.methods
+          .closeRebalanceRecord()
+          .accounts({ portfolio: portfolioPda, record: recordPda, owner: user.publicKey } as any)
+          .signers([user])
+          .rpc(),
+        "AccountNotInitialized"
+      );
+    });
+
+    it("セッションがない場合はコミットも中断もできない", async () => {
+      await expectError(
+        program.methods
+          .commitRebalance()
+          .accounts({
+            portfolio: portfolioPda,
+            session: sessionPda,
+            record: recordPda,
+            owner: user.publicKey,
+            authority: user.publicKey,
+            sessionKey: null,
+            baseVault,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([user])
+          .rpc(),
+        "AccountNotInitialized"
+      );
+      await expectError(
+        program.methods
+          .abortRebalance()
+          .accounts({
+            portfolio: portfolioPda,
+            session: sessionPda,
+            owner: user.publicKey,
+            authority: user.publicKey,
+            sessionKey: null,
+            baseVault,
+          } as any)
+          .signers([user])
+          .rpc(),
+        "AccountNotInitialized"
+      );
+    });
+
+    it("失敗したbegin_rebalanceはポートフォリオをロックしない", async () => {
+      const portfolio = await program.account.portfolio.fetch(portfolioPda);
+      expect(portfolio.isRebalancing).to.equal(false);
+    });
+  });
+});
@@ -0,0 +1,196 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SloomoPortfolio } from "../../target/types/sloomo_portfolio";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// get_summary / get_drift / preview_rebalance は状態を書き換えず、
+// 結果を return data で返すためシミュレーションだけで取得できる
+describe("Read-only View Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.SloomoPortfolio as Program<SloomoPortfolio>;
+  const provider = anchor.getProvider();
+  const portfolioId = Keypair.generate().publicKey;
+
+  const INITIAL_AMOUNT = 100_000_000;
+
+  let user: Keypair;
+  let baseMint: PublicKey;
+  let otherMint: PublicKey;
+  let userBaseAccount: PublicKey;
+  let portfolioPda: PublicKey;
+  let baseVault: PublicKey;
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail("エラーが発生するべき");
+    } catch (error) {
+      const logs = error.simulationResponse?.logs ?? error.logs ?? [];
+      expect([error.toString(), ...logs].join("\n")).to.include(code);
+    }
+  };
+
+  const vaultFor = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), portfolioPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  // accounts: 対象ミントのボールト、続いて同じ順のミントアカウント
+  const preview = (
+    targets: { mint: PublicKey; targetPercentage: number }[],
+    accounts: PublicKey[]
+  ) =>
+    program.methods
+      .previewRebalance(targets, null)
+      .accounts({ portfolio: portfolioPda, baseVault, baseMint } as any)
+      .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .view();
+
+  before(async () => {
+    user = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      user.publicKey,
+      5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+
+    baseMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    otherMint = await createMint(provider.connection, user, user.publicKey, null, 6);
+    userBaseAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      user,
+      baseMint,
+      user.publicKey
+    );
+    await mintTo(provider.connection, user, baseMint, userBaseAccount, user.publicKey, 1_000_000_000);
+
+    [portfolioPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
+      program.programId
+    );
+    baseVault = vaultFor(baseMint);
+
+    await program.methods
+      .initializePortfolio(portfolioId, {
+        initialAllocations: [
+          { mint: baseMint, symbol: "BASE", targetPercentage: 10000, band: null },
+        ],
+        initialSolAmount: new anchor.BN(INITIAL_AMOUNT),
+        enableJupiterSwap: false,
+      })
+      .accounts({
+        portfolio: portfolioPda,
+        owner: user.publicKey,
+        userBaseAccount,
+        portfolioBaseVault: baseVault,
+        baseMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([user])
+      .rpc();
+  });
+
+  it("サマリーは保有額・比率・目標を返す", async () => {
+    const summary = await program.methods
+      .getSummary()
+      .accounts({ portfolio: portfolioPda } as any)
+      .view();
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(summary.owner.toString()).to.equal(user.publicKey.toString());
+    expect(summary.baseMint.toString()).to.equal(baseMint.toString());
+    expect(summary.totalValue.toNumber()).to.equal(portfolio.totalValue.toNumber());
+    expect(summary.needsRebalancing).to.be.false;
+    expect(summary.allocations).to.have.length(1);
+    expect(summary.allocations[0].symbol).to.equal("BASE");
+    expect(summary.allocations[0].targetPercentage).to.equal(10000);
+  });
+
+  it("ドリフトレポートは目標との乖離を返す", async () => {
+    const report = await program.methods
+      .getDrift()
+      .accounts({ portfolio: portfolioPda } as any)
+      .view();
+
+    expect(report.needsRebalancing).to.be.false;
+    expect(report.maxDriftBps.toNumber()).to.equal(0);
+    expect(report.allocations[0].withinBand).to.be.true;
+    expect(report.assetClasses).to.be.empty;
+  });
+
+  it("目標どおりならプレビューにスワップは含まれない", async () => {
+    const result = await preview([{ mint: baseMint, targetPercentage: 10000 }], []);
+
+    expect(result.totalValue.toNumber()).to.equal(INITIAL_AMOUNT);
+    expect(result.legs).to.be.empty;
+    expect(result.estimatedCost.toNumber()).to.equal(0);
+  });
+
+  it("新しい目標へのスワップ区間を状態を変えずにプレビューできる", async () => {
+    // 未作成のボールトは残高 0 として扱われる
+    const result = await preview(
+      [
+        { mint: baseMint, targetPercentage: 5000 },
+        { mint: otherMint, targetPercentage: 5000 },
+      ],
+      [vaultFor(otherMint), otherMint]
+    );
+
+    expect(result.legs).to.have.length(1);
+    expect(result.legs[0].fromMint.toString()).to.equal(baseMint.toString());
+    expect(result.legs[0].toMint.toString()).to.equal(otherMint.toString());
+    // 既定の 5% しきい値の端（45%）まで取引する
+    expect(result.legs[0].amount.toNumber()).to.equal(45_000_000);
+
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(portfolio.allocations).to.have.length(1);
+  });
+
+  it("ドライランは乖離・手数料内訳・実行可否を返す", async () => {
+    const result = await preview(
+      [
+        { mint: baseMint, targetPercentage: 5000 },
+        { mint: otherMint, targetPercentage: 5000 },
+      ],
+      [vaultFor(otherMint), otherMint]
+    );
+
+    expect(result.needsRebalancing).to.be.true;
+    expect(result.maxDriftBps.toNumber()).to.equal(5000);
+
+    // 手数料 0.25% + 既定スリッページ 0.5%
+    expect(result.estimatedFees.toNumber()).to.equal(112_500);
+    expect(result.estimatedSlippage.toNumber()).to.equal(225_000);
+    expect(result.estimatedCost.toNumber()).to.equal(337_500);
+    expect(result.legs[0].estimatedFee.toNumber()).to.equal(112_500);
+
+    // 初期化直後は1日経つまで rebalance は実行できない
+    const portfolio = await program.account.portfolio.fetch(portfolioPda);
+    expect(result.canExecute).to.be.false;
+    expect(result.nextRebalanceAt.toNumber()).to.equal(portfolio.lastRebalance.toNumber() + 86400);
+  });
+
+  it("ボールトが足りないプレビューは拒否される", async () => {
+    await expectError(
+      preview([{ mint: otherMint, targetPercentage: 10000 }], []),
+      "MissingVaultAccount"
+    );
+  });
+
+  it("別のアカウントをボールトとして渡すと拒否される", async () => {
+    await expectError(
+      preview([{ mint: otherMint, targetPercentage: 10000 }], [userBaseAccount, otherMint]),
+      "InvalidVault"
+    );
+  });
+});
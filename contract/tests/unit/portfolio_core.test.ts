@@ -11,6 +11,7 @@ describe("Portfolio Core Unit Tests", () => {
   const provider = anchor.getProvider();
 
   let user: Keypair;
+  let portfolioId: PublicKey;
   let portfolioPda: PublicKey;
   let portfolioBump: number;
 
@@ -26,8 +27,9 @@ describe("Portfolio Core Unit Tests", () => {
     await provider.connection.confirmTransaction(signature, "confirmed");
 
     // Portfolio PDAを生成
+    portfolioId = Keypair.generate().publicKey;
     [portfolioPda, portfolioBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("portfolio"), user.publicKey.toBuffer()],
+      [Buffer.from("portfolio"), portfolioId.toBuffer()],
       program.programId
     );
   });
@@ -48,7 +50,7 @@ describe("Portfolio Core Unit Tests", () => {
       ];
 
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations,
         })
         .accounts({
@@ -86,7 +88,7 @@ describe("Portfolio Core Unit Tests", () => {
 
       try {
         await program.methods
-          .initializePortfolio({
+          .initializePortfolio(portfolioId, {
             initialAllocations: invalidAllocations,
           })
           .accounts({
@@ -105,7 +107,7 @@ describe("Portfolio Core Unit Tests", () => {
 
     it("空の配分でポートフォリオを初期化できる", async () => {
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations: [],
         })
         .accounts({
@@ -128,7 +130,7 @@ describe("Portfolio Core Unit Tests", () => {
       }));
 
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations: maxAllocations,
         })
         .accounts({
@@ -154,7 +156,7 @@ describe("Portfolio Core Unit Tests", () => {
 
       // 1回目の初期化
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations,
         })
         .accounts({
@@ -168,7 +170,7 @@ describe("Portfolio Core Unit Tests", () => {
       // 2回目の初期化（失敗するべき）
       try {
         await program.methods
-          .initializePortfolio({
+          .initializePortfolio(portfolioId, {
             initialAllocations,
           })
           .accounts({
@@ -203,7 +205,7 @@ describe("Portfolio Core Unit Tests", () => {
       ];
 
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations,
         })
         .accounts({
@@ -279,7 +281,7 @@ describe("Portfolio Core Unit Tests", () => {
       ];
 
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations,
         })
         .accounts({
@@ -370,7 +372,7 @@ describe("Portfolio Core Unit Tests", () => {
       ];
 
       await program.methods
-        .initializePortfolio({
+        .initializePortfolio(portfolioId, {
           initialAllocations,
         })
         .accounts({